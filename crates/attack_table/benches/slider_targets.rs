@@ -0,0 +1,72 @@
+#[cfg(target_arch = "x86_64")]
+use chess_kit_attack_table::PextAttackTable;
+use chess_kit_attack_table::{AttackTable, DefaultAttackTable};
+use chess_kit_primitives::{Bitboard, Square};
+use chess_kit_rng::Rng;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const OCCUPANCIES: usize = 1024;
+
+/// occupancies returns pseudo-random occupancies paired with every square
+fn occupancies() -> Vec<(Square, Bitboard)> {
+    let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+    (0..OCCUPANCIES)
+        .map(|idx| {
+            (
+                Square::from_idx(idx % Square::TOTAL),
+                Bitboard::new(rng.next_u64()),
+            )
+        })
+        .collect()
+}
+
+fn slider_targets<AT: AttackTable>(c: &mut Criterion, name: &str) {
+    let occupancies = occupancies();
+    let mut group = c.benchmark_group(format!("slider_targets/{name}"));
+    group.throughput(Throughput::Elements(OCCUPANCIES as u64));
+
+    group.bench_function("rook", |b| {
+        b.iter(|| {
+            for &(square, occupancy) in &occupancies {
+                black_box(AT::rook_targets(black_box(square), black_box(occupancy)));
+            }
+        });
+    });
+
+    group.bench_function("bishop", |b| {
+        b.iter(|| {
+            for &(square, occupancy) in &occupancies {
+                black_box(AT::bishop_targets(black_box(square), black_box(occupancy)));
+            }
+        });
+    });
+
+    group.bench_function("queen", |b| {
+        b.iter(|| {
+            for &(square, occupancy) in &occupancies {
+                black_box(AT::queen_targets(black_box(square), black_box(occupancy)));
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn magic_benches(c: &mut Criterion) {
+    slider_targets::<DefaultAttackTable>(c, "magic");
+}
+
+// note: the pext table is only benchmarked on cpus that support BMI2
+#[cfg(target_arch = "x86_64")]
+fn pext_benches(c: &mut Criterion) {
+    if PextAttackTable::is_supported() {
+        slider_targets::<PextAttackTable>(c, "pext");
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+criterion_group!(benches, magic_benches, pext_benches);
+#[cfg(not(target_arch = "x86_64"))]
+criterion_group!(benches, magic_benches);
+criterion_main!(benches);
@@ -0,0 +1,225 @@
+use crate::magics::{BISHOP_MAGIC_NUMS, ROOK_MAGIC_NUMS};
+use crate::sliding_pieces::{bishop_attack_board, bishop_mask, rook_attack_board, rook_mask};
+use chess_kit_primitives::{Bitboard, Square};
+use chess_kit_rng::Rng;
+
+/// MIN_INDEX_BITS is the minimum number of bits a candidate magic must move
+/// into the top byte of the product with the mask to be worth verifying
+const MIN_INDEX_BITS: u32 = 6;
+
+/// `Slider` enumerates the sliding pieces that are indexed with magic numbers
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Slider {
+    Rook,
+    Bishop,
+}
+
+impl Slider {
+    /// magic_nums returns the magic numbers the attack table is built with
+    ///
+    /// @return: magic number of every square for the slider
+    pub const fn magic_nums(self) -> &'static [u64; Square::TOTAL] {
+        match self {
+            Self::Rook => &ROOK_MAGIC_NUMS,
+            Self::Bishop => &BISHOP_MAGIC_NUMS,
+        }
+    }
+
+    /// mask returns the relevant occupancy mask of the slider on a square
+    ///
+    /// @param: square - square that the slider is on
+    /// @return: squares whose occupancy can block the slider
+    const fn mask(self, square: Square) -> Bitboard {
+        match self {
+            Self::Rook => rook_mask(square),
+            Self::Bishop => bishop_mask(square),
+        }
+    }
+
+    /// attacks returns the squares the slider on a square attacks
+    ///
+    /// @param: square - square that the slider is on
+    /// @param: occupancy - occupancy of the board
+    /// @return: attack board for the given square and occupancy
+    const fn attacks(self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let square_bitboard = Bitboard::square(square);
+        match self {
+            Self::Rook => rook_attack_board(
+                square_bitboard,
+                Bitboard::file(square.file()),
+                Bitboard::rank(square.rank()),
+                occupancy,
+            ),
+            Self::Bishop => bishop_attack_board(
+                square_bitboard,
+                Bitboard::diagonal(square),
+                Bitboard::anti_diagonal(square),
+                occupancy,
+            ),
+        }
+    }
+}
+
+/// `Blockers` is every blocker board of a slider on a square along with the
+/// attack board it produces
+///
+/// @type
+struct Blockers {
+    mask: u64,              // relevant occupancy mask of the square
+    shift: u32,             // shift of the product down to a table index
+    occupancies: Vec<u64>,  // every subset of the mask
+    attacks: Vec<Bitboard>, // attack board of each subset
+    indexed: Vec<Bitboard>, // scratch table of the attack board at each index
+}
+
+impl Blockers {
+    /// new enumerates the blocker boards of a slider on a square
+    ///
+    /// note: this uses the Carry-Rippler method to iterate through all the
+    ///       possible blocker boards for the mask
+    ///
+    /// @param: slider - slider to enumerate the blocker boards of
+    /// @param: square - square that the slider is on
+    /// @return: blocker boards and attack boards of the slider
+    fn new(slider: Slider, square: Square) -> Self {
+        let mask = slider.mask(square).const_unwrap();
+        let bits = mask.count_ones();
+        let mut occupancies = Vec::with_capacity(1 << bits);
+        let mut attacks = Vec::with_capacity(1 << bits);
+
+        let mut n: u64 = 0;
+        loop {
+            occupancies.push(n);
+            attacks.push(slider.attacks(square, Bitboard::new(n)));
+
+            n = n.wrapping_sub(mask) & mask;
+            if n == 0 {
+                break;
+            }
+        }
+
+        Self {
+            mask,
+            shift: 64 - bits,
+            occupancies,
+            attacks,
+            indexed: vec![Bitboard::empty(); 1 << bits],
+        }
+    }
+
+    /// is_collision_free checks that a magic maps every blocker board to an
+    /// index that holds its attack board
+    ///
+    /// note: blocker boards may share an index when they produce the same
+    ///       attack board, since the lookup still returns the right targets
+    ///
+    /// @param: magic - magic number to check
+    /// @return: true if no two blocker boards with different attack boards
+    ///          share an index, false otherwise
+    fn is_collision_free(&mut self, magic: u64) -> bool {
+        self.indexed.fill(Bitboard::empty());
+
+        // attack boards are never empty, so an empty entry is an unused index
+        for (&occupancy, &attacks) in self.occupancies.iter().zip(&self.attacks) {
+            let idx = (occupancy.wrapping_mul(magic) >> self.shift) as usize;
+            if self.indexed[idx].is_empty() {
+                self.indexed[idx] = attacks;
+            } else if self.indexed[idx] != attacks {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// sparse returns a pseudo-random number with few bits set, which makes for
+/// better magic candidates
+///
+/// @param: rng - generator of the candidates
+/// @return: pseudo-random number with about an eighth of its bits set
+/// @side-effects: advances the generator
+fn sparse(rng: &mut Rng) -> u64 {
+    rng.next_u64() & rng.next_u64() & rng.next_u64()
+}
+
+/// is_collision_free checks that a magic number maps every blocker board of a
+/// slider on a square to an index holding the right attack board
+///
+/// @param: slider - slider the magic indexes the attacks of
+/// @param: square - square that the slider is on
+/// @param: magic - magic number to check
+/// @return: true if the magic indexes every attack board, false otherwise
+pub fn is_collision_free(slider: Slider, square: Square, magic: u64) -> bool {
+    Blockers::new(slider, square).is_collision_free(magic)
+}
+
+/// find_magics searches for a magic number of a slider for every square
+///
+/// note: the search is deterministic, so the same seed always finds the same
+///       magic numbers
+///
+/// @param: slider - slider to find the magic numbers of
+/// @param: seed - seed of the candidate generator
+/// @return: collision-free magic number of every square
+pub fn find_magics(slider: Slider, seed: u64) -> [u64; Square::TOTAL] {
+    let mut rng = Rng::new(seed);
+    let mut magics = [0; Square::TOTAL];
+
+    for (sq, magic) in magics.iter_mut().enumerate() {
+        let mut blockers = Blockers::new(slider, Square::from_idx(sq));
+        *magic = loop {
+            let candidate = sparse(&mut rng);
+
+            // skip candidates that cannot spread the mask over the index bits
+            if (blockers.mask.wrapping_mul(candidate) >> 56).count_ones() < MIN_INDEX_BITS {
+                continue;
+            }
+            if blockers.is_collision_free(candidate) {
+                break candidate;
+            }
+        };
+    }
+
+    magics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn magic_numbers_are_collision_free() {
+        for slider in [Slider::Rook, Slider::Bishop] {
+            for (sq, &magic) in slider.magic_nums().iter().enumerate() {
+                let square = Square::from_idx(sq);
+                assert!(
+                    is_collision_free(slider, square, magic),
+                    "{slider:?} magic {magic:#018x} collides on {square}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_magics_that_collide() {
+        assert!(!is_collision_free(Slider::Rook, Square::A1, 0));
+        assert!(!is_collision_free(Slider::Bishop, Square::D4, 1));
+    }
+
+    #[test]
+    fn finds_the_same_collision_free_magics_for_a_seed() {
+        let magics = find_magics(Slider::Bishop, 42);
+
+        assert_eq!(magics, find_magics(Slider::Bishop, 42));
+        for (sq, &magic) in magics.iter().enumerate() {
+            assert!(is_collision_free(
+                Slider::Bishop,
+                Square::from_idx(sq),
+                magic
+            ));
+        }
+    }
+}
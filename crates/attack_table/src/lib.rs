@@ -1,9 +1,15 @@
+mod finder;
 mod magics;
 mod moving_pieces;
+#[cfg(all(feature = "pext", target_arch = "x86_64"))]
+mod pext;
 mod sliding_pieces;
 mod table;
 
+pub use finder::{Slider, find_magics, is_collision_free};
 pub(crate) use moving_pieces::{NOT_A_FILE, NOT_H_FILE};
+#[cfg(all(feature = "pext", target_arch = "x86_64"))]
+pub use pext::PextAttackTable;
 pub use table::DefaultAttackTable;
 
 use chess_kit_primitives::{Bitboard, Side, Square};
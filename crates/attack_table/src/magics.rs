@@ -5,6 +5,11 @@ use chess_kit_primitives::{Bitboard, Square};
 const ROOK_TABLE_SIZE: usize = 102_400; // total permutations of all rook boards
 const BISHOP_TABLE_SIZE: usize = 5_248; // total permutations of all bishop boards
 
+/// ROOK_MAGIC_NUMS are the magic numbers indexing the rook attack boards
+///
+/// note: these were found by a magic search and can be regenerated with the
+///       `magics` example. a magic that maps two blocker boards with different
+///       attack boards to the same index fails the build of the rook table
 #[rustfmt::skip]
 #[allow(clippy::unreadable_literal)]
 pub const ROOK_MAGIC_NUMS: [u64; Square::TOTAL] = [
@@ -26,7 +31,11 @@ pub const ROOK_MAGIC_NUMS: [u64; Square::TOTAL] = [
     5188428314494240769u64, 1157988067282792450u64, 1152939243166828548u64, 4611967569673330817u64,
 ];
 
-/** Bishop magic numbers. Don't touch them. Changing these numbers breaks the program. */
+/// BISHOP_MAGIC_NUMS are the magic numbers indexing the bishop attack boards
+///
+/// note: these were found by a magic search and can be regenerated with the
+///       `magics` example. a magic that maps two blocker boards with different
+///       attack boards to the same index fails the build of the bishop table
 #[rustfmt::skip]
 #[allow(clippy::unreadable_literal)]
 pub const BISHOP_MAGIC_NUMS: [u64; Square::TOTAL] = [
@@ -180,7 +189,15 @@ const fn new_rook_square_magics(offset: &mut u64, square: Square, table: &mut [B
 
         // get the respective attack board for the given square and blocker
         // board
-        table[index] = rook_attack_board(square_bitboard, file, rank, occupancy);
+        let attacks = rook_attack_board(square_bitboard, file, rank, occupancy);
+
+        // blocker boards may only share an index if they share an attack
+        // board, and attack boards are never empty
+        assert!(
+            table[index].is_empty() || table[index].const_unwrap() == attacks.const_unwrap(),
+            "rook magic maps different attack boards to the same index"
+        );
+        table[index] = attacks;
 
         next += 1;
         n = n.wrapping_sub(mask) & mask;
@@ -239,7 +256,15 @@ const fn new_bishop_square_magics(
 
         // get the respective attack board for the given square and blocker
         // board
-        table[index] = bishop_attack_board(square_bitboard, diagonal, anti_diagonal, occupancy);
+        let attacks = bishop_attack_board(square_bitboard, diagonal, anti_diagonal, occupancy);
+
+        // blocker boards may only share an index if they share an attack
+        // board, and attack boards are never empty
+        assert!(
+            table[index].is_empty() || table[index].const_unwrap() == attacks.const_unwrap(),
+            "bishop magic maps different attack boards to the same index"
+        );
+        table[index] = attacks;
 
         next += 1;
         n = n.wrapping_sub(mask) & mask;
@@ -0,0 +1,297 @@
+use crate::sliding_pieces::{bishop_attack_board, bishop_mask, rook_attack_board, rook_mask};
+use crate::{AttackTable, DefaultAttackTable, PawnDirections};
+use chess_kit_primitives::{Bitboard, Side, Square};
+
+const ROOK_TABLE_SIZE: usize = 102_400; // total permutations of all rook boards
+const BISHOP_TABLE_SIZE: usize = 5_248; // total permutations of all bishop boards
+
+#[allow(long_running_const_eval)]
+static ROOK_PEXT_TABLE: PextTable<ROOK_TABLE_SIZE> = PextTable::<ROOK_TABLE_SIZE>::new();
+static BISHOP_PEXT_TABLE: PextTable<BISHOP_TABLE_SIZE> = PextTable::<BISHOP_TABLE_SIZE>::new();
+
+/// `PextAttackTable` is an implementation of the `AttackTable` trait that
+/// indexes the slider attacks with the BMI2 `pext` instruction instead of magic
+/// numbers
+///
+/// note: lookups execute `pext` without checking for BMI2, so the table must
+///       only be used once `is_supported` returned true, e.g. by choosing it
+///       over `DefaultAttackTable` when an engine is constructed. building with
+///       `-C target-cpu=native` on a cpu that has BMI2 also inlines the lookups
+///       into their callers
+///
+/// @type
+pub struct PextAttackTable;
+
+impl PextAttackTable {
+    /// is_supported returns true if the running cpu supports BMI2
+    ///
+    /// @return: true if the table can be used on the running cpu
+    #[inline]
+    pub fn is_supported() -> bool {
+        std::arch::is_x86_feature_detected!("bmi2")
+    }
+}
+
+impl AttackTable for PextAttackTable {
+    /// king_targets returns the squares that the king targets from the given
+    /// square
+    ///
+    /// @impl: PieceTargetsTable::king_targets
+    #[inline]
+    fn king_targets(sq: Square) -> Bitboard {
+        DefaultAttackTable::king_targets(sq)
+    }
+
+    /// knight_targets returns the squares that the knight targets from the
+    /// given square
+    ///
+    /// @impl: PieceTargetsTable::knight_targets
+    #[inline]
+    fn knight_targets(sq: Square) -> Bitboard {
+        DefaultAttackTable::knight_targets(sq)
+    }
+
+    /// pawn_targets returns the squares that the pawn targets from the given
+    /// square for the given side
+    ///
+    /// @impl: PieceTargetsTable::pawn_targets
+    #[inline]
+    fn pawn_targets<SideT: Side>(sq: Square) -> Bitboard {
+        DefaultAttackTable::pawn_targets::<SideT>(sq)
+    }
+
+    /// pawn_pushes returns the squares that the pawn pushes to from the given
+    /// square
+    ///
+    /// @impl: PieceTargetsTable::pawn_pushes
+    #[inline]
+    fn pawn_pushes<SideT: Side>(sq: Square) -> Bitboard {
+        DefaultAttackTable::pawn_pushes::<SideT>(sq)
+    }
+
+    /// all_pawn_targets returns the squares that the all the pawns on the given
+    /// squares target from the given side in the given direction
+    ///
+    /// @impl: PieceTargetsTable::all_pawn_targets
+    #[inline]
+    fn all_pawn_targets<SideT: Side>(squares: Bitboard, direction: PawnDirections) -> Bitboard {
+        DefaultAttackTable::all_pawn_targets::<SideT>(squares, direction)
+    }
+
+    /// empty_rook_targets returns the squares that the rook targets from the given
+    /// square on an empty board
+    ///
+    /// @impl: PieceTargetsTable::empty_rook_targets
+    #[inline]
+    fn empty_rook_targets(square: Square) -> Bitboard {
+        DefaultAttackTable::empty_rook_targets(square)
+    }
+
+    /// rook_targets returns the attacks for the given square and bitboard.
+    ///
+    /// @impl: PieceTargetsTable::rook_targets
+    #[inline]
+    fn rook_targets(square: Square, bitboard: Bitboard) -> Bitboard {
+        debug_assert!(Self::is_supported(), "the cpu does not support bmi2");
+        // SAFETY: the table is only used on cpus that support bmi2
+        unsafe { ROOK_PEXT_TABLE.targets(square, bitboard) }
+    }
+
+    /// empty_bishop_targets returns the squares that the bishop targets from the given
+    /// square on an empty board
+    ///
+    /// @impl: PieceTargetsTable::empty_bishop_targets
+    #[inline]
+    fn empty_bishop_targets(square: Square) -> Bitboard {
+        DefaultAttackTable::empty_bishop_targets(square)
+    }
+
+    /// bishop_targets returns the attacks for the given square and bitboard.
+    ///
+    /// @impl: PieceTargetsTable::bishop_targets
+    #[inline]
+    fn bishop_targets(square: Square, bitboard: Bitboard) -> Bitboard {
+        debug_assert!(Self::is_supported(), "the cpu does not support bmi2");
+        // SAFETY: the table is only used on cpus that support bmi2
+        unsafe { BISHOP_PEXT_TABLE.targets(square, bitboard) }
+    }
+
+    /// queen_targets returns the attacks for the given square and bitboard.
+    ///
+    /// @impl: PieceTargetsTable::queen_targets
+    #[inline]
+    fn queen_targets(square: Square, bitboard: Bitboard) -> Bitboard {
+        Self::rook_targets(square, bitboard) | Self::bishop_targets(square, bitboard)
+    }
+}
+
+/// `PextTable` is a table of the attack boards of a slider for every square,
+/// indexed by the relevant occupancy extracted with `pext`
+///
+/// @type
+pub(crate) struct PextTable<const SIZE: usize> {
+    table: [Bitboard; SIZE],         // attack boards of every square
+    masks: [u64; Square::TOTAL],     // relevant occupancy mask of each square
+    offsets: [usize; Square::TOTAL], // offset of the attack boards of each square
+}
+
+impl PextTable<ROOK_TABLE_SIZE> {
+    /// new creates and initializes a new rook pext table
+    ///
+    /// note: the Carry-Rippler method visits the subsets of a mask in the same
+    ///       order as the values `pext` extracts from them, so the n-th subset
+    ///       is stored at index n without needing `pext` at compile time
+    ///
+    /// @return: new rook pext table
+    pub(crate) const fn new() -> Self {
+        let mut pext_table = Self {
+            table: [Bitboard::empty(); ROOK_TABLE_SIZE],
+            masks: [0; Square::TOTAL],
+            offsets: [0; Square::TOTAL],
+        };
+
+        let mut offset = 0;
+        let mut sq = 0;
+        while sq < Square::TOTAL {
+            let square = Square::from_idx(sq);
+            let mask = rook_mask(square).const_unwrap();
+            let square_bitboard = Bitboard::square(square);
+            let file = Bitboard::file(square.file());
+            let rank = Bitboard::rank(square.rank());
+
+            pext_table.masks[sq] = mask;
+            pext_table.offsets[sq] = offset;
+
+            // index the attack boards of every blocker board for the square
+            let mut n: u64 = 0;
+            loop {
+                let occupancy = Bitboard::new(n);
+                pext_table.table[offset] =
+                    rook_attack_board(square_bitboard, file, rank, occupancy);
+                offset += 1;
+
+                n = n.wrapping_sub(mask) & mask;
+                if n == 0 {
+                    break;
+                }
+            }
+
+            sq += 1;
+        }
+
+        pext_table
+    }
+}
+
+impl PextTable<BISHOP_TABLE_SIZE> {
+    /// new creates and initializes a new bishop pext table
+    ///
+    /// note: this function is copy-pasted from the rook table to improve CTFE
+    ///       performance by avoiding branching in the hot path
+    ///
+    /// @return: new bishop pext table
+    pub(crate) const fn new() -> Self {
+        let mut pext_table = Self {
+            table: [Bitboard::empty(); BISHOP_TABLE_SIZE],
+            masks: [0; Square::TOTAL],
+            offsets: [0; Square::TOTAL],
+        };
+
+        let mut offset = 0;
+        let mut sq = 0;
+        while sq < Square::TOTAL {
+            let square = Square::from_idx(sq);
+            let mask = bishop_mask(square).const_unwrap();
+            let square_bitboard = Bitboard::square(square);
+            let diagonal = Bitboard::diagonal(square);
+            let anti_diagonal = Bitboard::anti_diagonal(square);
+
+            pext_table.masks[sq] = mask;
+            pext_table.offsets[sq] = offset;
+
+            // index the attack boards of every blocker board for the square
+            let mut n: u64 = 0;
+            loop {
+                let occupancy = Bitboard::new(n);
+                pext_table.table[offset] =
+                    bishop_attack_board(square_bitboard, diagonal, anti_diagonal, occupancy);
+                offset += 1;
+
+                n = n.wrapping_sub(mask) & mask;
+                if n == 0 {
+                    break;
+                }
+            }
+
+            sq += 1;
+        }
+
+        pext_table
+    }
+}
+
+impl<const SIZE: usize> PextTable<SIZE> {
+    /// targets returns the attack board for the given square and occupancy
+    ///
+    /// @param: square - square that the slider is on
+    /// @param: occupancy - occupancy of the board
+    /// @return: attack board for the given square and occupancy
+    /// @requires: the running cpu supports BMI2
+    #[target_feature(enable = "bmi2")]
+    #[inline]
+    pub(crate) fn targets(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let idx = std::arch::x86_64::_pext_u64(occupancy.const_unwrap(), self.masks[square.idx()]);
+        self.table[self.offsets[square.idx()] + idx as usize]
+    }
+
+    /// targets_with_software_pext returns the attack board for the given
+    /// square and occupancy without the bmi2 instruction
+    ///
+    /// @param: square - square that the slider is on
+    /// @param: occupancy - occupancy of the board
+    /// @return: attack board for the given square and occupancy
+    #[cfg(test)]
+    fn targets_with_software_pext(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let mut mask = self.masks[square.idx()];
+        let occupancy = occupancy.const_unwrap();
+        let mut idx = 0;
+        let mut bit = 0;
+        while mask != 0 {
+            if occupancy & mask & mask.wrapping_neg() != 0 {
+                idx |= 1 << bit;
+            }
+            mask &= mask - 1;
+            bit += 1;
+        }
+        self.table[self.offsets[square.idx()] + idx]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_rng::Rng;
+
+    #[test]
+    fn pext_tables_are_indexed_by_the_extracted_occupancy() {
+        let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+        for sq in 0..Square::TOTAL {
+            let square = Square::from_idx(sq);
+            for _ in 0..256 {
+                let occupancy =
+                    Bitboard::new(rng.next_u64() & !Bitboard::square(square).const_unwrap());
+
+                assert_eq!(
+                    ROOK_PEXT_TABLE.targets_with_software_pext(square, occupancy),
+                    DefaultAttackTable::rook_targets(square, occupancy),
+                    "rook on {square} with occupancy:\n{occupancy}"
+                );
+                assert_eq!(
+                    BISHOP_PEXT_TABLE.targets_with_software_pext(square, occupancy),
+                    DefaultAttackTable::bishop_targets(square, occupancy),
+                    "bishop on {square} with occupancy:\n{occupancy}"
+                );
+            }
+        }
+    }
+}
@@ -9,6 +9,9 @@ pub(crate) type MagicTable = [Magic; Square::TOTAL];
 
 /// `DefaultAttackTable` is the default implementation of the `AttackTable` trait
 ///
+/// note: every table is built by const evaluation, so the tables are embedded
+///       in the binary and need no initialization at startup
+///
 /// @type
 pub struct DefaultAttackTable;
 
@@ -0,0 +1,107 @@
+#![cfg(all(feature = "pext", target_arch = "x86_64"))]
+
+use chess_kit_attack_table::{AttackTable, DefaultAttackTable, PextAttackTable};
+use chess_kit_primitives::{Bitboard, Square};
+use chess_kit_rng::Rng;
+
+/// is_supported returns true if the running cpu can execute the pext lookups
+///
+/// @return: true if the pext table can be compared with the magics
+/// @side-effects: reports the skipped comparison on cpus without BMI2
+fn is_supported() -> bool {
+    let supported = PextAttackTable::is_supported();
+    if !supported {
+        eprintln!("skipped: the cpu does not support bmi2");
+    }
+    supported
+}
+
+/// assert_agrees_on_every_subset checks that both attack tables agree for every
+/// occupancy subset of the empty-board targets of a square
+///
+/// @param: square - square that the slider is on
+/// @param: lines - empty-board targets of the slider on the square
+/// @param: targets - slider lookup of each attack table, magics then pext
+/// @return: void
+/// @side-effects: panics on the first occupancy the tables disagree on
+fn assert_agrees_on_every_subset(
+    square: Square,
+    lines: Bitboard,
+    targets: [fn(Square, Bitboard) -> Bitboard; 2],
+) {
+    let mask = lines.const_unwrap();
+    let mut n: u64 = 0;
+    loop {
+        let occupancy = Bitboard::new(n);
+        assert_eq!(
+            targets[1](square, occupancy),
+            targets[0](square, occupancy),
+            "slider on {square} with occupancy:\n{occupancy}"
+        );
+
+        n = n.wrapping_sub(mask) & mask;
+        if n == 0 {
+            break;
+        }
+    }
+}
+
+#[test]
+fn pext_and_magic_rook_targets_agree() {
+    if !is_supported() {
+        return;
+    }
+
+    for sq in 0..Square::TOTAL {
+        let square = Square::from_idx(sq);
+        assert_agrees_on_every_subset(
+            square,
+            DefaultAttackTable::empty_rook_targets(square),
+            [
+                DefaultAttackTable::rook_targets,
+                PextAttackTable::rook_targets,
+            ],
+        );
+    }
+}
+
+#[test]
+fn pext_and_magic_bishop_targets_agree() {
+    if !is_supported() {
+        return;
+    }
+
+    for sq in 0..Square::TOTAL {
+        let square = Square::from_idx(sq);
+        assert_agrees_on_every_subset(
+            square,
+            DefaultAttackTable::empty_bishop_targets(square),
+            [
+                DefaultAttackTable::bishop_targets,
+                PextAttackTable::bishop_targets,
+            ],
+        );
+    }
+}
+
+#[test]
+fn pext_and_magic_queen_targets_agree_on_full_boards() {
+    if !is_supported() {
+        return;
+    }
+
+    // occupancies outside the lines of the slider must not change its targets
+    let mut rng = Rng::new(0x2545_F491_4F6C_DD1D);
+    for sq in 0..Square::TOTAL {
+        let square = Square::from_idx(sq);
+        for _ in 0..1024 {
+            let occupancy = Bitboard::new(rng.next_u64());
+
+            assert_eq!(
+                PextAttackTable::queen_targets(square, occupancy),
+                DefaultAttackTable::queen_targets(square, occupancy),
+                "queen on {square} with occupancy:\n{occupancy}"
+            );
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use chess_kit_collections::LargeArray;
+use chess_kit_rng::Rng;
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+const MIB: usize = 1024 * 1024;
+
+// large enough to be filled in parallel and to miss the TLB on random accesses
+const ARRAY_MIB: usize = 256;
+
+// number of random accesses per iteration
+const ACCESSES: usize = 4096;
+
+// number of accesses between prefetching an element and reading it
+const PREFETCH_DISTANCE: usize = 8;
+
+/// `Line` is a cache-line sized element, like a transposition table bucket
+#[derive(Clone, Copy, Default)]
+#[repr(align(64))]
+struct Line([u64; 8]);
+
+const LINES: usize = ARRAY_MIB * MIB / size_of::<Line>();
+
+/// random_indices returns a fixed sequence of pseudo-random line indices
+fn random_indices() -> Vec<usize> {
+    let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+    (0..ACCESSES).map(|_| rng.below(LINES)).collect()
+}
+
+fn fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_array/fill");
+    group.throughput(Throughput::Bytes((ARRAY_MIB * MIB) as u64));
+    group.sample_size(10);
+
+    let mut vec = vec![Line::default(); LINES];
+    group.bench_function("vec", |b| {
+        b.iter(|| vec.fill(black_box(Line::default())));
+    });
+    drop(vec);
+
+    let mut array = LargeArray::new(LINES, Line::default());
+    group.bench_function("large_array", |b| {
+        b.iter(|| array.fill(black_box(Line::default())));
+    });
+
+    group.finish();
+}
+
+fn random_access(c: &mut Criterion) {
+    let indices = random_indices();
+    let mut group = c.benchmark_group("large_array/random_access");
+    group.throughput(Throughput::Elements(ACCESSES as u64));
+
+    let vec = vec![Line([1; 8]); LINES];
+    group.bench_function("vec", |b| {
+        b.iter(|| {
+            indices
+                .iter()
+                .fold(0_u64, |sum, &index| sum.wrapping_add(vec[index].0[0]))
+        });
+    });
+    drop(vec);
+
+    let array = LargeArray::new(LINES, Line([1; 8]));
+    group.bench_function("large_array", |b| {
+        b.iter(|| {
+            indices
+                .iter()
+                .fold(0_u64, |sum, &index| sum.wrapping_add(array[index].0[0]))
+        });
+    });
+
+    group.bench_function("large_array_prefetched", |b| {
+        b.iter(|| {
+            let mut sum = 0_u64;
+            for (position, &index) in indices.iter().enumerate() {
+                if let Some(&ahead) = indices.get(position + PREFETCH_DISTANCE) {
+                    array.prefetch(ahead);
+                }
+                sum = sum.wrapping_add(array[index].0[0]);
+            }
+            black_box(sum)
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, fill, random_access);
+criterion_main!(benches);
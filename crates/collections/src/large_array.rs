@@ -0,0 +1,223 @@
+use std::alloc::{self, Layout};
+use std::mem::MaybeUninit;
+use std::num::NonZero;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+use std::{slice, thread};
+
+/// HUGE_PAGE_SIZE is the size of a transparent huge page on linux
+#[cfg(all(feature = "huge-pages", target_os = "linux"))]
+const HUGE_PAGE_SIZE: usize = 2 * 1024 * 1024;
+
+/// PARALLEL_FILL_BYTES is the size from which an array is filled by as many
+/// threads as the machine runs in parallel
+const PARALLEL_FILL_BYTES: usize = 64 * 1024 * 1024;
+
+/// `LargeArray` is a fixed-length, heap-allocated array for tables that span
+/// many megabytes, such as the buckets of a hash table
+///
+/// note: with the `huge-pages` feature on linux, arrays of at least 2 MB are
+///       aligned to 2 MB and advised to be backed by transparent huge pages,
+///       which saves TLB misses on random accesses
+///
+/// note: large arrays are filled by several threads, which also spreads the
+///       page faults of the first touch of a new allocation across them
+///
+/// @type
+pub struct LargeArray<T: Copy + Send> {
+    ptr: NonNull<T>, // start of the allocation, dangling if nothing is allocated
+    len: usize,      // number of elements in the array
+}
+
+// SAFETY: the array owns its elements like a `Vec` does
+unsafe impl<T: Copy + Send> Send for LargeArray<T> {}
+// SAFETY: shared references to the array only hand out shared references to
+//         its elements
+unsafe impl<T: Copy + Send + Sync> Sync for LargeArray<T> {}
+
+impl<T: Copy + Send> LargeArray<T> {
+    /// new allocates an array with every element set to a value
+    ///
+    /// @param: len - number of elements in the array
+    /// @param: value - value of every element
+    /// @return: new array
+    pub fn new(len: usize, value: T) -> Self {
+        let layout = Self::layout(len);
+        if layout.size() == 0 {
+            return Self {
+                ptr: NonNull::dangling(),
+                len,
+            };
+        }
+
+        // SAFETY: the layout has a non-zero size
+        let Some(ptr) = NonNull::new(unsafe { alloc::alloc(layout) }.cast::<T>()) else {
+            alloc::handle_alloc_error(layout);
+        };
+
+        #[cfg(all(feature = "huge-pages", target_os = "linux"))]
+        if layout.align() == HUGE_PAGE_SIZE {
+            // note: the advice is only a hint to the kernel, so a failure
+            //       leaves the array backed by regular pages
+            // SAFETY: the range is exactly the allocation
+            unsafe {
+                libc::madvise(ptr.as_ptr().cast(), layout.size(), libc::MADV_HUGEPAGE);
+            }
+        }
+
+        // SAFETY: the allocation holds `len` elements of `T`, which may be
+        //         uninitialized until they are filled
+        let elements =
+            unsafe { slice::from_raw_parts_mut(ptr.as_ptr().cast::<MaybeUninit<T>>(), len) };
+        fill(elements, MaybeUninit::new(value));
+
+        Self { ptr, len }
+    }
+
+    /// fill sets every element of the array to a value
+    ///
+    /// note: this shadows `slice::fill` in order to fill large arrays with
+    ///       several threads
+    ///
+    /// @param: value - value of every element
+    /// @return: void
+    /// @side-effects: overwrites every element
+    pub fn fill(&mut self, value: T) {
+        fill(self, value);
+    }
+
+    /// prefetch hints the cpu to load an element into the cache ahead of an
+    /// access to it
+    ///
+    /// note: this is a no-op on architectures without a stable prefetch
+    ///       intrinsic, and for indices out of bounds
+    ///
+    /// @param: index - index of the element to load
+    /// @return: void
+    #[inline]
+    pub fn prefetch(&self, index: usize) {
+        if index >= self.len {
+            return;
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        // SAFETY: the element is in bounds of the allocation, and prefetching
+        //         never faults
+        unsafe {
+            use std::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+            _mm_prefetch::<_MM_HINT_T0>(self.ptr.as_ptr().add(index).cast());
+        }
+    }
+
+    /// layout returns the layout of the allocation for an array
+    ///
+    /// @param: len - number of elements in the array
+    /// @return: layout of the allocation
+    fn layout(len: usize) -> Layout {
+        let layout = Layout::array::<T>(len).expect("array size must not overflow");
+
+        #[cfg(all(feature = "huge-pages", target_os = "linux"))]
+        if layout.size() >= HUGE_PAGE_SIZE {
+            return layout
+                .align_to(HUGE_PAGE_SIZE)
+                .expect("huge page alignment must be valid");
+        }
+
+        layout
+    }
+}
+
+impl<T: Copy + Send> Drop for LargeArray<T> {
+    fn drop(&mut self) {
+        let layout = Self::layout(self.len);
+        if layout.size() != 0 {
+            // SAFETY: the array was allocated with the same layout, and its
+            //         elements need no drop since they are `Copy`
+            unsafe { alloc::dealloc(self.ptr.as_ptr().cast(), layout) };
+        }
+    }
+}
+
+impl<T: Copy + Send> Deref for LargeArray<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &[T] {
+        // SAFETY: the allocation holds `len` initialized elements
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T: Copy + Send> DerefMut for LargeArray<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        // SAFETY: the allocation holds `len` initialized elements
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+/// fill sets every element of a slice to a value, splitting large slices
+/// between threads
+///
+/// @param: elements - slice to fill
+/// @param: value - value of every element
+/// @return: void
+/// @side-effects: overwrites every element
+fn fill<T: Copy + Send>(elements: &mut [T], value: T) {
+    let threads = if size_of_val(elements) < PARALLEL_FILL_BYTES {
+        1
+    } else {
+        thread::available_parallelism().map_or(1, NonZero::get)
+    };
+    if threads == 1 {
+        elements.fill(value);
+        return;
+    }
+
+    let chunk_len = elements.len().div_ceil(threads);
+    thread::scope(|scope| {
+        for chunk in elements.chunks_mut(chunk_len) {
+            scope.spawn(move || chunk.fill(value));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_arrays_do_not_allocate() {
+        let array = LargeArray::new(0, 7_u64);
+
+        assert!(array.is_empty());
+        array.prefetch(0);
+    }
+
+    #[test]
+    fn new_and_fill_set_every_element() {
+        let mut array = LargeArray::new(1000, 7_u64);
+        assert!(array.iter().all(|&element| element == 7));
+
+        array[10] = 3;
+        array.prefetch(10);
+        assert_eq!(array[10], 3);
+
+        array.fill(9);
+        assert!(array.iter().all(|&element| element == 9));
+    }
+
+    #[test]
+    fn large_arrays_are_filled_in_parallel() {
+        let len = PARALLEL_FILL_BYTES / size_of::<u64>() + 3;
+        let mut array = LargeArray::new(len, 1_u64);
+        assert_eq!(array.len(), len);
+        assert_eq!(array[len - 1], 1);
+
+        array.fill(2);
+        assert!(array.iter().all(|&element| element == 2));
+
+        #[cfg(all(feature = "huge-pages", target_os = "linux"))]
+        assert_eq!(array.as_ptr() as usize % HUGE_PAGE_SIZE, 0);
+    }
+}
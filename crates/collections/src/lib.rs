@@ -1,7 +1,9 @@
 mod fixed_array;
+mod large_array;
 mod map;
 mod stack;
 
 pub use fixed_array::{FixedArray, FixedArrayIntoIter};
+pub use large_array::LargeArray;
 pub use map::{EvictionPolicy, HashFn, HashKey, Map, Value, ValuePriority};
-pub use stack::{Copyable, Stack};
+pub use stack::{Copyable, GrowableStack, Stack};
@@ -17,19 +17,18 @@ pub(crate) struct Bucket<
     const N: usize = DEFAULT_CAPACITY,
 > {
     entries: [Option<Entry<T>>; N],
-    _policy: PhantomData<P>,
+    _policy: PhantomData<fn() -> P>, // policies are markers, so buckets stay `Send`
 }
 
 impl<T: Value, P: EvictionPolicy<T>, const N: usize> Clone for Bucket<T, P, N> {
     #[inline]
     fn clone(&self) -> Self {
-        Self {
-            entries: self.entries,
-            _policy: PhantomData,
-        }
+        *self
     }
 }
 
+impl<T: Value, P: EvictionPolicy<T>, const N: usize> Copy for Bucket<T, P, N> {}
+
 impl<T: Value, P: EvictionPolicy<T>, const N: usize> Bucket<T, P, N> {
     #[inline]
     pub fn new() -> Self {
@@ -89,13 +88,6 @@ impl<T: Value, P: EvictionPolicy<T>, const N: usize> Bucket<T, P, N> {
         SetResult::Evicted
     }
 
-    #[inline]
-    pub fn clear(&mut self) {
-        for entry in self.entries.iter_mut() {
-            *entry = None;
-        }
-    }
-
     #[inline]
     pub const fn size_of_mem() -> usize {
         std::mem::size_of::<Option<Entry<T>>>() * N
@@ -1,6 +1,7 @@
 mod bucket;
 mod entry;
 
+use crate::LargeArray;
 use bucket::{Bucket, SetResult};
 use entry::Entry;
 use std::marker::PhantomData;
@@ -32,8 +33,10 @@ pub trait HashFn<K> {
 /// Value is a trait that defines the contract for a type that can be used as a
 /// value in a map
 ///
+/// note: values are `Send` so that large maps can be cleared by several threads
+///
 /// @trait
-pub trait Value: Copy + Default {
+pub trait Value: Copy + Default + Send {
     /// priority returns the priority of the value
     ///
     /// @return: the priority of the value
@@ -72,7 +75,7 @@ pub struct Map<K, V: Value, Hasher: HashFn<K>, Policy: EvictionPolicy<V> = Value
     /// amount of memory allocated in MBs
     memory_size: usize,
     /// buckets of the map
-    buckets: Vec<Bucket<V, Policy>>,
+    buckets: LargeArray<Bucket<V, Policy>>,
 
     _key: PhantomData<K>,
     _hasher: PhantomData<Hasher>,
@@ -86,11 +89,7 @@ impl<K, V: Value, Hasher: HashFn<K>, Policy: EvictionPolicy<V>> Map<K, V, Hasher
     /// @return: a new map
     pub fn new(memory_size: usize) -> Self {
         let (bucket_count, capacity) = Self::calculate_sizes(memory_size);
-        let buckets = if bucket_count == 0 {
-            Vec::new()
-        } else {
-            vec![Bucket::<V, Policy>::new(); bucket_count]
-        };
+        let buckets = LargeArray::new(bucket_count, Bucket::<V, Policy>::new());
 
         Self {
             len: 0,
@@ -184,9 +183,7 @@ impl<K, V: Value, Hasher: HashFn<K>, Policy: EvictionPolicy<V>> Map<K, V, Hasher
     /// @side-effects: clears the map
     #[inline]
     pub fn clear(&mut self) {
-        for entry in self.buckets.iter_mut() {
-            entry.clear();
-        }
+        self.buckets.fill(Bucket::new());
         self.len = 0;
     }
 
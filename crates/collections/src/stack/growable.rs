@@ -0,0 +1,233 @@
+use super::{Copyable, DEFAULT_CAPACITY, StackIter};
+
+/// GrowableStack is a stack of copyable items that grows its capacity when it
+/// runs out of slots
+///
+/// note: unlike `Stack`, the items live on the heap so that histories of
+///       arbitrary length (e.g. very long games) can be stored. spare slots are
+///       kept initialized and reused across pushes, so `push_next` derives new
+///       items in place exactly like `Stack` does
+///
+/// @type
+pub struct GrowableStack<T: Copyable> {
+    current: usize, // number of active items
+    items: Vec<T>,  // active items followed by initialized spare slots
+}
+
+impl<T: Copyable> GrowableStack<T> {
+    /// new creates a new stack with the default initial capacity
+    ///
+    /// @return: new stack
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// with_capacity creates a new stack with the given initial capacity
+    ///
+    /// @param: capacity - number of slots to initialize up front
+    /// @return: new stack
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            current: 0,
+            items: vec![T::default(); capacity.max(1)],
+        }
+    }
+
+    /// push adds a new item to the stack
+    ///
+    /// @param: item - item to add to the stack
+    /// @return: void
+    /// @side-effects: modifies the stack, increments the current index, and
+    ///                grows the stack if it is full
+    #[inline]
+    pub fn push(&mut self, item: T) {
+        if self.current == self.items.len() {
+            self.grow();
+        }
+
+        self.items[self.current] = item;
+        self.current += 1;
+    }
+
+    /// push_next adds a new item to the stack by deriving it from the copy of
+    /// the current item
+    ///
+    /// @return: mutable reference to the newly pushed item
+    /// @side-effects: modifies the stack, increments the current index, and
+    ///                grows the stack if it is full
+    /// @requires: the stack is non-empty
+    #[inline]
+    pub fn push_next(&mut self) -> &mut T {
+        debug_assert!(self.current > 0, "cannot clone from an empty stack");
+        if self.current == self.items.len() {
+            self.grow();
+        }
+
+        let current = self.current;
+        let (active, spare) = self.items.split_at_mut(current);
+        let src_item = &active[current - 1];
+        let dst_item = &mut spare[0];
+        self.current = current + 1;
+        dst_item.copy_from(src_item);
+        dst_item
+    }
+
+    /// pop removes the last item from the stack
+    ///
+    /// @return: void
+    /// @side-effects: modifies the stack, decrements the current index
+    #[inline]
+    pub fn pop(&mut self) {
+        if self.current == 0 {
+            return;
+        }
+
+        self.current -= 1;
+    }
+
+    /// top returns an immutable reference to the top item
+    ///
+    /// @return: reference to the current item
+    /// @requires: the stack is non-empty
+    #[inline]
+    pub fn top(&self) -> &T {
+        assert!(self.current > 0, "stack is empty");
+        &self.items[self.current - 1]
+    }
+
+    /// top_mut returns a mutable reference to the top item
+    ///
+    /// @return: mutable reference to the current item
+    /// @requires: the stack is non-empty
+    #[inline]
+    pub fn top_mut(&mut self) -> &mut T {
+        assert!(self.current > 0, "stack is empty");
+        &mut self.items[self.current - 1]
+    }
+
+    /// size returns the number of items in the stack
+    ///
+    /// @return: the number of items in the stack
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.current
+    }
+
+    /// capacity returns the number of items the stack can hold before growing
+    ///
+    /// @return: the number of initialized slots in the stack
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.items.len()
+    }
+
+    /// is_empty returns true if the stack is empty
+    ///
+    /// @return: true if the stack is empty
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.current == 0
+    }
+
+    /// clear resets the stack to an empty state
+    ///
+    /// note: the grown capacity is kept, so a cleared stack does not have to
+    ///       grow again to hold a history of the same length
+    ///
+    /// @return: void
+    /// @side-effects: sets the current index to 0
+    #[inline]
+    pub fn clear(&mut self) {
+        self.current = 0;
+    }
+
+    /// as_slice returns the active stack items in insertion order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.items[..self.current]
+    }
+
+    /// as_mut_slice returns the active stack items in insertion order.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        &mut self.items[..self.current]
+    }
+
+    /// iter returns an iterator over the active stack items
+    ///
+    /// @return: double-ended iterator over the items in insertion order
+    #[inline]
+    pub fn iter(&self) -> StackIter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// grow doubles the number of initialized slots in the stack
+    ///
+    /// @return: void
+    /// @side-effects: reallocates the items of the stack
+    #[cold]
+    #[inline(never)]
+    fn grow(&mut self) {
+        let capacity = self.items.len() * 2;
+        self.items.resize(capacity, T::default());
+    }
+}
+
+impl<T: Copyable> Default for GrowableStack<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+    struct TestItem(u16);
+
+    impl Copyable for TestItem {
+        fn copy_from(&mut self, other: &Self) {
+            *self = *other;
+        }
+    }
+
+    #[test]
+    fn push_next_grows_past_the_initial_capacity() {
+        let mut stack = GrowableStack::<TestItem>::with_capacity(2);
+        stack.push(TestItem(0));
+
+        for _ in 0..999 {
+            stack.push_next().0 += 1;
+        }
+
+        assert_eq!(stack.size(), 1000);
+        assert!(stack.capacity() >= 1000);
+        assert_eq!(stack.top(), &TestItem(999));
+        assert!(
+            stack
+                .iter()
+                .enumerate()
+                .all(|(index, item)| item.0 as usize == index)
+        );
+    }
+
+    #[test]
+    fn pop_and_clear_keep_the_grown_capacity() {
+        let mut stack = GrowableStack::<TestItem>::with_capacity(1);
+        for value in 0..5 {
+            stack.push(TestItem(value));
+        }
+        let capacity = stack.capacity();
+
+        stack.pop();
+        assert_eq!(stack.top(), &TestItem(3));
+
+        stack.clear();
+        assert!(stack.is_empty());
+        assert_eq!(stack.capacity(), capacity);
+    }
+}
@@ -1,13 +1,18 @@
+mod growable;
+
+pub use growable::GrowableStack;
+
 use std::slice;
 
 /// Copyable is a trait that defines the behavior of a copyable item
 ///
 /// note: this trait is different from `Copy`, with the explicit intention of
-///       defining custom copy behaviour without sacrificing support from the
-///       standard `Copy` behaviour
+///       defining custom copy behaviour. items are only required to be `Clone`,
+///       so that they may share heap data (e.g. an evaluation network) and
+///       skip updating it when the slot they are copied into already does
 ///
 /// @trait
-pub trait Copyable: Default + Copy + Clone {
+pub trait Copyable: Default + Clone {
     /// copy_from copies the contents of another Copyable item into this one
     ///
     /// @param: other - the item to copy from
@@ -39,7 +44,7 @@ impl<T: Copyable, const N: usize> Stack<T, N> {
     pub fn new() -> Self {
         Self {
             current: 0,
-            items: [T::default(); N],
+            items: std::array::from_fn(|_| T::default()),
         }
     }
 
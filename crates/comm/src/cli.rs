@@ -1,25 +1,50 @@
 //! Human-oriented interactive command-line adapter.
 
 use std::fmt::Display;
+use std::fs;
 use std::io::{self, BufRead, Write};
 use std::str::FromStr;
 
-use chess_kit_engine::{Engine, PositionProvider, PositionSnapshot, SearchOutcome};
-use chess_kit_primitives::{Move, Pieces, SearchDepth, Sides, Square, call_as};
+use chess_kit_engine::{
+    Engine, EngineError, PositionBase, PositionProvider, PositionSnapshot, SearchOutcome,
+};
+use chess_kit_primitives::{Depth, Move, Pieces, SearchDepth, Sides, Square, call_as};
 
+use crate::pgn::GameRecord;
 use crate::uci::UciMove;
 
+/// HELP lists the commands of an interactive game
+const HELP: &str = "Commands:\n\
+  <move>       Play a move in UCI notation, such as e2e4 or e7e8q\n\
+  undo         Take back your last move and the engine's reply\n\
+  new          Start a new game from the standard starting position\n\
+  fen <FEN>    Start a new game from a FEN position\n\
+  moves        List the legal moves\n\
+  hint         Suggest a move without playing it\n\
+  flip         Flip the board\n\
+  depth <N>    Set the search depth of engine moves\n\
+  eval         Print the evaluation breakdown of the position\n\
+  pgn          Print the game so far\n\
+  save <FILE>  Save the game so far as PGN\n\
+  help         Print this list\n\
+  quit, exit   Stop";
+
+/// PLAYER is the name of the human player in game records
+const PLAYER: &str = "Player";
+
 /// `InteractiveGame` is a human-friendly command-line facade over [`Engine`].
 ///
-/// It owns prompts, notation conversion, board display, and game-over
-/// messaging. Position state, legality, move application, and search remain in
-/// the engine
+/// It owns prompts, notation conversion, board display, game records, and
+/// game-over messaging. Position state, legality, move application, and search
+/// remain in the engine
 ///
 /// @marker: EngineT - protocol-neutral engine implementation
 /// @type
 pub struct InteractiveGame<EngineT> {
     engine: EngineT,           // engine used to play the game
     search_depth: SearchDepth, // fixed positive depth used for engine replies
+    flipped: bool,             // whether the board is displayed from black's side
+    record: GameRecord,        // moves played since the game started
 }
 
 impl<EngineT> InteractiveGame<EngineT>
@@ -31,10 +56,13 @@ where
     /// @param: engine - protocol-neutral engine session
     /// @param: search_depth - fixed search depth used for engine replies
     /// @return: interactive game adapter
-    pub const fn new(engine: EngineT, search_depth: SearchDepth) -> Self {
+    pub fn new(engine: EngineT, search_depth: SearchDepth) -> Self {
+        let record = GameRecord::new(PLAYER, engine.name(), None);
         Self {
             engine,
             search_depth,
+            flipped: false,
+            record,
         }
     }
 
@@ -52,6 +80,20 @@ where
         &mut self.engine
     }
 
+    /// record returns the record of the current game
+    ///
+    /// @return: moves played since the game started
+    pub const fn record(&self) -> &GameRecord {
+        &self.record
+    }
+
+    /// search_depth returns the depth used for engine replies
+    ///
+    /// @return: fixed positive search depth
+    pub const fn search_depth(&self) -> SearchDepth {
+        self.search_depth
+    }
+
     /// into_inner consumes the adapter and returns the wrapped engine
     ///
     /// @return: wrapped engine
@@ -74,7 +116,7 @@ where
     ///
     /// @marker: ReaderT - buffered input stream type
     /// @marker: WriterT - output stream type
-    /// @param: reader - stream containing player moves
+    /// @param: reader - stream containing player moves and commands
     /// @param: writer - stream receiving prompts, moves, and board displays
     /// @return: Ok when the session exits, or an I/O error
     /// @side-effects: reads input, writes output, and modifies engine game state
@@ -87,14 +129,17 @@ where
         ReaderT: BufRead,
         WriterT: Write,
     {
-        self.engine.new_game().map_err(engine_error)?;
+        self.start(None).map_err(engine_error)?;
 
         writeln!(
             writer,
             "You are playing White. Enter moves in UCI notation (for example, e2e4)."
         )?;
-        writeln!(writer, "Enter `quit` or `exit` to stop.\n")?;
-        write_position(&mut writer, &self.engine.position())?;
+        writeln!(
+            writer,
+            "Enter `help` to list the other commands, or `quit` to stop.\n"
+        )?;
+        self.write_position(&mut writer)?;
 
         let mut line = String::new();
         loop {
@@ -108,55 +153,281 @@ where
             }
 
             let input = line.trim();
-            if matches!(input, "quit" | "exit") {
-                break;
+            let (command, argument) = input
+                .split_once(char::is_whitespace)
+                .map_or((input, ""), |(command, argument)| {
+                    (command, argument.trim())
+                });
+            match command {
+                "quit" | "exit" => break,
+                "help" => writeln!(writer, "{HELP}")?,
+                "eval" => match self.engine.eval() {
+                    Ok(trace) => writeln!(writer, "{trace}")?,
+                    Err(error) => writeln!(writer, "Evaluation unavailable: {error}")?,
+                },
+                "undo" => self.undo(&mut writer)?,
+                "new" => {
+                    self.start(None).map_err(engine_error)?;
+                    self.write_position(&mut writer)?;
+                }
+                "fen" => self.load(argument, &mut writer)?,
+                "moves" => self.write_legal_moves(&mut writer)?,
+                "hint" => self.hint(&mut writer)?,
+                "flip" => {
+                    self.flipped = !self.flipped;
+                    self.write_position(&mut writer)?;
+                }
+                "depth" => self.set_depth(argument, &mut writer)?,
+                "pgn" => write!(writer, "{}", self.record)?,
+                "save" => self.save(argument, &mut writer)?,
+                _ => self.play_turn(input, &mut writer)?,
             }
+        }
 
-            let player_move = match UciMove::from_str(input) {
-                Ok(mv) => match Move::try_from(&mv) {
-                    Ok(mv) => mv,
-                    Err(_) => {
-                        writeln!(writer, "Invalid move: the null move cannot be played.")?;
-                        continue;
-                    }
-                },
-                Err(error) => {
-                    writeln!(writer, "Invalid move: {error}")?;
-                    continue;
+        Ok(())
+    }
+
+    /// play_turn plays the player's move and the engine's reply
+    ///
+    /// @param: input - player move in UCI notation
+    /// @param: writer - output stream receiving moves and board displays
+    /// @return: Ok after the turn, or an I/O error
+    /// @side-effects: plays moves in the engine and records them
+    fn play_turn(&mut self, input: &str, writer: &mut impl Write) -> io::Result<()> {
+        if self.engine.status().is_over() {
+            return writeln!(
+                writer,
+                "The game is over. Enter `undo`, `new` or `fen <FEN>` to continue."
+            );
+        }
+
+        let player_move = match UciMove::from_str(input) {
+            Ok(mv) => match Move::try_from(&mv) {
+                Ok(mv) => mv,
+                Err(_) => {
+                    return writeln!(writer, "Invalid move: the null move cannot be played.");
                 }
-            };
+            },
+            Err(error) => return writeln!(writer, "Invalid move: {error}"),
+        };
 
-            if let Err(error) = self.engine.play(player_move) {
-                writeln!(writer, "Invalid move: {error}")?;
-                continue;
-            }
+        if let Err(error) = self.play(player_move, false) {
+            return writeln!(writer, "Invalid move: {error}");
+        }
 
-            let outcome = self
+        let status = self.engine.status();
+        if status.is_over() {
+            self.write_position(writer)?;
+            return writeln!(writer, "Game over: {status}.");
+        }
+
+        let outcome = self
+            .engine
+            .search(self.search_depth)
+            .map_err(engine_error)?;
+        let Some(engine_move) = outcome.best_move else {
+            self.write_position(writer)?;
+            return writeln!(writer, "Game over: the engine has no legal moves.");
+        };
+
+        writeln!(
+            writer,
+            "Engine plays: {}{}",
+            UciMove::from(engine_move),
+            format_search_info(&outcome)
+        )?;
+        self.play(engine_move, true).map_err(engine_error)?;
+        self.write_position(writer)?;
+
+        let status = self.engine.status();
+        if status.is_over() {
+            writeln!(writer, "Game over: {status}.")?;
+        }
+        Ok(())
+    }
+
+    /// play plays a move in the engine and records it
+    ///
+    /// @param: mv - move to play
+    /// @param: by_engine - whether the engine chose the move
+    /// @return: Ok on success, or the engine error of an illegal move
+    /// @side-effects: plays the move in the engine and appends it to the record
+    fn play(&mut self, mv: Move, by_engine: bool) -> Result<(), EngineError> {
+        let position = self.engine.position();
+        let legal_moves = self.engine.legal_moves();
+        self.engine.play(mv)?;
+        self.record.push(
+            &position,
+            &legal_moves,
+            mv,
+            self.engine.in_check(),
+            self.engine.status(),
+            by_engine,
+        );
+        Ok(())
+    }
+
+    /// start starts a new game
+    ///
+    /// note: the player plays the side to move of the starting position
+    ///
+    /// @param: fen - starting position, or None for the standard one
+    /// @return: Ok on success, or the engine error of an invalid position
+    /// @side-effects: replaces the engine position and the game record
+    fn start(&mut self, fen: Option<String>) -> Result<(), EngineError> {
+        match &fen {
+            Some(fen) => self
                 .engine
-                .search(self.search_depth)
-                .map_err(engine_error)?;
-            let Some(engine_move) = outcome.best_move else {
-                write_position(&mut writer, &self.engine.position())?;
-                writeln!(writer, "Game over: the engine has no legal moves.")?;
+                .set_position(PositionBase::Fen(fen.clone()), &[])?,
+            None => self.engine.new_game()?,
+        }
+
+        let engine = self.engine.name().to_owned();
+        self.record = match self.engine.position().side_to_move() {
+            Sides::White => GameRecord::new(PLAYER, engine, fen),
+            Sides::Black => GameRecord::new(engine, PLAYER, fen),
+        };
+        Ok(())
+    }
+
+    /// load starts a new game from a FEN position
+    ///
+    /// @param: fen - FEN of the starting position
+    /// @param: writer - output stream receiving the new position
+    /// @return: Ok after loading or reporting an invalid position, or an I/O
+    ///          error
+    /// @side-effects: replaces the engine position and the game record on success
+    fn load(&mut self, fen: &str, writer: &mut impl Write) -> io::Result<()> {
+        if fen.is_empty() {
+            return writeln!(writer, "Usage: fen <FEN>");
+        }
+        if let Err(error) = self.start(Some(fen.to_owned())) {
+            return writeln!(writer, "Could not load the position: {error}");
+        }
+
+        let side = match self.engine.position().side_to_move() {
+            Sides::White => "White",
+            Sides::Black => "Black",
+        };
+        writeln!(writer, "Loaded the position. You are playing {side}.")?;
+        self.write_position(writer)?;
+        let status = self.engine.status();
+        if status.is_over() {
+            writeln!(writer, "Game over: {status}.")?;
+        }
+        Ok(())
+    }
+
+    /// undo takes back the player's last move and the engine's reply to it
+    ///
+    /// @param: writer - output stream receiving the restored position
+    /// @return: Ok after taking back the moves, or an I/O error
+    /// @side-effects: undoes moves in the engine and removes them from the record
+    fn undo(&mut self, writer: &mut impl Write) -> io::Result<()> {
+        if self.record.moves().is_empty() {
+            return writeln!(writer, "Nothing to undo.");
+        }
+
+        while let Some(recorded) = self.record.moves().last() {
+            let by_engine = recorded.by_engine;
+            self.engine.undo().map_err(engine_error)?;
+            self.record.pop();
+            if !by_engine {
                 break;
-            };
+            }
+        }
+        self.write_position(writer)
+    }
+
+    /// write_legal_moves lists the legal moves in UCI notation
+    ///
+    /// @param: writer - output stream receiving the moves
+    /// @return: Ok after writing, or an I/O error
+    fn write_legal_moves(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut moves = self
+            .engine
+            .legal_moves()
+            .into_iter()
+            .map(|mv| UciMove::from(mv).to_string())
+            .collect::<Vec<_>>();
+        if moves.is_empty() {
+            return writeln!(writer, "No legal moves.");
+        }
 
-            writeln!(
+        moves.sort_unstable();
+        writeln!(writer, "Legal moves ({}): {}", moves.len(), moves.join(" "))
+    }
+
+    /// hint suggests the engine's move in the current position
+    ///
+    /// @param: writer - output stream receiving the suggestion
+    /// @return: Ok after writing, or an I/O error
+    /// @side-effects: may modify engine search state
+    fn hint(&mut self, writer: &mut impl Write) -> io::Result<()> {
+        if self.engine.status().is_over() {
+            return writeln!(writer, "The game is over.");
+        }
+
+        let outcome = self
+            .engine
+            .search(self.search_depth)
+            .map_err(engine_error)?;
+        match outcome.best_move {
+            Some(mv) => writeln!(
                 writer,
-                "Engine plays: {}{}",
-                UciMove::from(engine_move),
+                "Hint: {}{}",
+                UciMove::from(mv),
                 format_search_info(&outcome)
-            )?;
-            self.engine.play(engine_move).map_err(engine_error)?;
-            write_position(&mut writer, &self.engine.position())?;
+            ),
+            None => writeln!(writer, "No legal moves."),
+        }
+    }
 
-            if !self.engine.has_legal_moves() {
-                writeln!(writer, "Game over: you have no legal moves.")?;
-                break;
-            }
+    /// set_depth sets the search depth of engine moves
+    ///
+    /// @param: depth - new depth in plies
+    /// @param: writer - output stream receiving the confirmation
+    /// @return: Ok after writing, or an I/O error
+    /// @side-effects: replaces the search depth when the depth is valid
+    fn set_depth(&mut self, depth: &str, writer: &mut impl Write) -> io::Result<()> {
+        let Some(depth) = depth
+            .parse::<Depth>()
+            .ok()
+            .and_then(|depth| SearchDepth::new(depth).ok())
+        else {
+            return writeln!(
+                writer,
+                "Invalid depth: expected a positive number of plies."
+            );
+        };
+
+        self.search_depth = depth;
+        writeln!(writer, "Search depth set to {depth} plies.")
+    }
+
+    /// save writes the game so far to a PGN file
+    ///
+    /// @param: path - path of the file to write
+    /// @param: writer - output stream receiving the confirmation
+    /// @return: Ok after saving or reporting the failure, or an I/O error
+    /// @side-effects: creates or replaces the file
+    fn save(&self, path: &str, writer: &mut impl Write) -> io::Result<()> {
+        if path.is_empty() {
+            return writeln!(writer, "Usage: save <FILE>");
         }
 
-        Ok(())
+        match fs::write(path, self.record.to_string()) {
+            Ok(()) => writeln!(writer, "Saved the game to {path}."),
+            Err(error) => writeln!(writer, "Could not save the game: {error}"),
+        }
+    }
+
+    /// write_position renders the current position in the board orientation
+    ///
+    /// @param: writer - output stream receiving the rendered board
+    /// @return: Ok after writing, or an I/O error
+    fn write_position(&self, writer: &mut impl Write) -> io::Result<()> {
+        write_position(writer, &self.engine.position(), self.flipped)
     }
 }
 
@@ -175,13 +446,21 @@ fn format_search_info(outcome: &SearchOutcome) -> String {
 ///
 /// @param: writer - output stream receiving the rendered board
 /// @param: position - position snapshot to render
+/// @param: flipped - whether to render the board from black's side
 /// @return: Ok after writing, or an I/O error
 /// @side-effects: writes the board to the output stream
-fn write_position(writer: &mut impl Write, position: &PositionSnapshot) -> io::Result<()> {
+fn write_position(
+    writer: &mut impl Write,
+    position: &PositionSnapshot,
+    flipped: bool,
+) -> io::Result<()> {
+    let orient = |index: u8| if flipped { index } else { 7 - index };
     writeln!(writer)?;
-    for rank in (0..8).rev() {
+    for row in 0..8 {
+        let rank = orient(row);
         write!(writer, "{}", rank + 1)?;
-        for file in 0..8 {
+        for column in 0..8 {
+            let file = 7 - orient(column);
             let square = Square::from_idx((rank * 8 + file) as usize);
             let symbol = position
                 .piece_at(square)
@@ -190,7 +469,11 @@ fn write_position(writer: &mut impl Write, position: &PositionSnapshot) -> io::R
         }
         writeln!(writer)?;
     }
-    writeln!(writer, "  A B C D E F G H")?;
+    if flipped {
+        writeln!(writer, "  H G F E D C B A")?;
+    } else {
+        writeln!(writer, "  A B C D E F G H")?;
+    }
     writeln!(
         writer,
         "{} to move",
@@ -227,8 +510,8 @@ mod tests {
     use std::io::Cursor;
     use std::time::Duration;
 
-    use chess_kit_engine::{EngineError, PositionBase};
-    use chess_kit_primitives::White;
+    use chess_kit_engine::{DrawReason, EvalTrace, GameStatus};
+    use chess_kit_primitives::{Black, White};
 
     use super::*;
 
@@ -238,6 +521,7 @@ mod tests {
         searches: usize,
         search_depths: Vec<SearchDepth>,
         legal_after_engine: bool,
+        black_to_move: bool,
     }
 
     impl Engine for TestEngine {
@@ -255,7 +539,14 @@ mod tests {
             Ok(())
         }
 
-        fn set_position(&mut self, _base: PositionBase, moves: &[Move]) -> Result<(), EngineError> {
+        fn set_position(&mut self, base: PositionBase, moves: &[Move]) -> Result<(), EngineError> {
+            if let PositionBase::Fen(fen) = base {
+                if !fen.contains('/') {
+                    return Err(EngineError::new("invalid FEN"));
+                }
+                self.black_to_move = fen.contains(" b ");
+                self.legal_after_engine = true;
+            }
             self.moves = moves.to_vec();
             Ok(())
         }
@@ -268,6 +559,13 @@ mod tests {
             Ok(())
         }
 
+        fn undo(&mut self) -> Result<Move, EngineError> {
+            self.legal_after_engine = true;
+            self.moves
+                .pop()
+                .ok_or_else(|| EngineError::new("no move to undo"))
+        }
+
         fn search(&mut self, depth: SearchDepth) -> Result<SearchOutcome, EngineError> {
             self.searches += 1;
             self.search_depths.push(depth);
@@ -283,6 +581,7 @@ mod tests {
                 depth,
                 score: 10,
                 nodes: 20,
+                hashfull: 0,
                 elapsed: Duration::default(),
             })
         }
@@ -290,11 +589,45 @@ mod tests {
         fn has_legal_moves(&self) -> bool {
             self.legal_after_engine
         }
+
+        fn legal_moves(&self) -> Vec<Move> {
+            vec![
+                Move::new(Square::E2, Square::E4),
+                Move::new(Square::D2, Square::D4),
+            ]
+        }
+
+        fn in_check(&self) -> bool {
+            false
+        }
+
+        fn status(&self) -> GameStatus {
+            if !self.legal_after_engine {
+                GameStatus::Checkmate {
+                    winner: Sides::Black,
+                }
+            } else if self.moves.last() == Some(&Move::new(Square::A2, Square::A3)) {
+                GameStatus::Draw(DrawReason::Repetition)
+            } else {
+                GameStatus::Ongoing
+            }
+        }
+
+        fn eval(&self) -> Result<EvalTrace, EngineError> {
+            let mut trace = EvalTrace::new(-35);
+            trace.phase = Some(0.25);
+            trace.push("Mobility", (10, 5), (40, 50));
+            Ok(trace)
+        }
     }
 
     impl PositionProvider for TestEngine {
         fn position(&self) -> PositionSnapshot {
-            PositionSnapshot::empty::<White>()
+            if self.black_to_move {
+                PositionSnapshot::empty::<Black>()
+            } else {
+                PositionSnapshot::empty::<White>()
+            }
         }
     }
 
@@ -318,7 +651,76 @@ mod tests {
             .map(|mv| mv.to_string())
             .collect::<Vec<_>>();
         assert_eq!(moves, ["e2e4", "e7e5"]);
-        assert!(output.contains("Game over: you have no legal moves."));
+        assert!(output.contains("Game over: checkmate, Black wins."));
         assert_eq!(game.engine().search_depths[0].get(), 7);
     }
+
+    #[test]
+    fn reports_draws_reached_by_the_player_move() {
+        let input = Cursor::new(b"a2a3\n");
+        let mut output = Vec::new();
+        let mut game = InteractiveGame::new(TestEngine::default(), SearchDepth::new(1).unwrap());
+
+        game.run_with_io(input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Game over: draw by threefold repetition."));
+        assert_eq!(game.engine().searches, 0);
+    }
+
+    #[test]
+    fn prints_the_evaluation_breakdown_on_request() {
+        let input = Cursor::new(b"eval\nquit\n");
+        let mut output = Vec::new();
+        let mut game = InteractiveGame::new(TestEngine::default(), SearchDepth::new(1).unwrap());
+
+        game.run_with_io(input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains(&game.engine().eval().unwrap().to_string()));
+        assert_eq!(game.engine().searches, 0);
+    }
+
+    #[test]
+    fn undo_takes_back_the_engine_reply_and_the_player_move() {
+        let input = Cursor::new(b"e2e4\nundo\nundo\nquit\n");
+        let mut output = Vec::new();
+        let mut game = InteractiveGame::new(TestEngine::default(), SearchDepth::new(2).unwrap());
+
+        game.run_with_io(input, &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(game.engine().moves.is_empty());
+        assert!(game.record().moves().is_empty());
+        assert!(output.contains("Nothing to undo."));
+    }
+
+    #[test]
+    fn sets_up_and_inspects_the_game_through_commands() {
+        let path = std::env::temp_dir().join(format!("chess-kit-cli-{}.pgn", std::process::id()));
+        let input = format!(
+            "depth 3\ndepth 0\nflip\nmoves\nhint\nfen nonsense\n\
+             fen 4k3/8/8/8/8/8/4P3/4K3 b - - 0 9\nd2d4\npgn\nsave {}\nquit\n",
+            path.display()
+        );
+        let mut output = Vec::new();
+        let mut game = InteractiveGame::new(TestEngine::default(), SearchDepth::new(1).unwrap());
+
+        game.run_with_io(Cursor::new(input), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(game.search_depth().get(), 3);
+        assert!(output.contains("Invalid depth: expected a positive number of plies."));
+        assert!(output.contains("  H G F E D C B A"));
+        assert!(output.contains("Legal moves (2): d2d4 e2e4"));
+        assert!(output.contains("Hint: e7e5 (depth 3, score 10 cp, 20 nodes)"));
+        assert!(output.contains("Could not load the position: invalid FEN"));
+        assert!(output.contains("Loaded the position. You are playing Black."));
+        assert!(output.contains("[White \"CLI Test\"]\n[Black \"Player\"]"));
+        assert!(output.contains("9... d2d4 *"));
+
+        let saved = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(saved.contains("[FEN \"4k3/8/8/8/8/8/4P3/4K3 b - - 0 9\"]"));
+    }
 }
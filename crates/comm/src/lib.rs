@@ -1,4 +1,5 @@
 //! Communication protocols for chess engines.
 
 pub mod cli;
+pub mod pgn;
 pub mod uci;
@@ -0,0 +1,342 @@
+//! Game records in Portable Game Notation.
+
+use std::fmt::{self, Display};
+
+use chess_kit_engine::{GameStatus, PositionSnapshot};
+use chess_kit_primitives::{Move, MoveType, Pieces, Sides};
+
+use crate::uci::UciMove;
+
+/// MAX_LINE_LENGTH is the longest line of movetext written to a PGN file
+const MAX_LINE_LENGTH: usize = 79;
+
+/// `RecordedMove` is a move of a game record with its notation
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedMove {
+    /// move played
+    pub mv: Move,
+    /// move in standard algebraic notation
+    pub san: String,
+    /// whether the engine rather than the player made the move
+    pub by_engine: bool,
+}
+
+/// `GameRecord` is the history of a game from its starting position
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GameRecord {
+    white: String,            // name of the white player
+    black: String,            // name of the black player
+    fen: Option<String>,      // starting position, or None for the standard one
+    first_turn: Sides,        // side to move in the starting position
+    first_fullmove: u32,      // fullmove number of the starting position
+    moves: Vec<RecordedMove>, // moves played from the starting position
+    status: GameStatus,       // status of the game after the last move
+}
+
+impl GameRecord {
+    /// new creates an empty record of a game
+    ///
+    /// @param: white - name of the white player
+    /// @param: black - name of the black player
+    /// @param: fen - starting position, or None for the standard one
+    /// @return: empty game record
+    pub fn new(white: impl Into<String>, black: impl Into<String>, fen: Option<String>) -> Self {
+        // note: the FEN of a record was accepted by the engine, so its turn and
+        //       fullmove segments are well formed when present
+        let segments = fen
+            .as_deref()
+            .map(|fen| fen.split_whitespace().collect::<Vec<_>>())
+            .unwrap_or_default();
+        let first_turn = match segments.get(1) {
+            Some(&"b") => Sides::Black,
+            _ => Sides::White,
+        };
+        let first_fullmove = segments
+            .get(5)
+            .and_then(|fullmove| fullmove.parse().ok())
+            .unwrap_or(1);
+
+        Self {
+            white: white.into(),
+            black: black.into(),
+            fen,
+            first_turn,
+            first_fullmove,
+            moves: Vec::new(),
+            status: GameStatus::Ongoing,
+        }
+    }
+
+    /// moves returns the moves played from the starting position
+    ///
+    /// @return: recorded moves in the order they were played
+    pub fn moves(&self) -> &[RecordedMove] {
+        &self.moves
+    }
+
+    /// push records a move
+    ///
+    /// @param: position - position before the move
+    /// @param: legal_moves - legal moves of the position before the move
+    /// @param: mv - move played
+    /// @param: in_check - whether the move gives check
+    /// @param: status - status of the game after the move
+    /// @param: by_engine - whether the engine made the move
+    /// @return: void
+    /// @side-effects: appends the move and replaces the game status
+    pub fn push(
+        &mut self,
+        position: &PositionSnapshot,
+        legal_moves: &[Move],
+        mv: Move,
+        in_check: bool,
+        status: GameStatus,
+        by_engine: bool,
+    ) {
+        let mut san = san(position, legal_moves, mv);
+        if matches!(status, GameStatus::Checkmate { .. }) {
+            san.push('#');
+        } else if in_check {
+            san.push('+');
+        }
+        self.moves.push(RecordedMove { mv, san, by_engine });
+        self.status = status;
+    }
+
+    /// pop removes the last recorded move
+    ///
+    /// @return: removed move, or None for an empty record
+    /// @side-effects: removes the last move and marks the game as ongoing
+    pub fn pop(&mut self) -> Option<RecordedMove> {
+        self.status = GameStatus::Ongoing;
+        self.moves.pop()
+    }
+
+    /// result returns the PGN result of the game
+    ///
+    /// @return: `1-0`, `0-1`, `1/2-1/2`, or `*` for an unfinished game
+    pub const fn result(&self) -> &'static str {
+        match self.status {
+            GameStatus::Ongoing => "*",
+            GameStatus::Checkmate {
+                winner: Sides::White,
+            } => "1-0",
+            GameStatus::Checkmate {
+                winner: Sides::Black,
+            } => "0-1",
+            GameStatus::Stalemate | GameStatus::Draw(_) => "1/2-1/2",
+        }
+    }
+}
+
+impl Display for GameRecord {
+    /// fmt writes the game record as a PGN game
+    ///
+    /// @param: f - formatter to write the game into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let result = self.result();
+        writeln!(f, "[Event \"chess-kit game\"]")?;
+        writeln!(f, "[Site \"?\"]")?;
+        writeln!(f, "[Date \"????.??.??\"]")?;
+        writeln!(f, "[Round \"-\"]")?;
+        writeln!(f, "[White \"{}\"]", self.white)?;
+        writeln!(f, "[Black \"{}\"]", self.black)?;
+        writeln!(f, "[Result \"{result}\"]")?;
+        if let Some(fen) = &self.fen {
+            writeln!(f, "[SetUp \"1\"]")?;
+            writeln!(f, "[FEN \"{fen}\"]")?;
+        }
+        writeln!(f)?;
+
+        let mut tokens = Vec::with_capacity(self.moves.len() * 2 + 1);
+        let mut fullmove = self.first_fullmove;
+        let mut turn = self.first_turn;
+        for (index, recorded) in self.moves.iter().enumerate() {
+            // move numbers stay on the line of the move they number
+            tokens.push(match turn {
+                Sides::White => format!("{fullmove}. {}", recorded.san),
+                Sides::Black if index == 0 => format!("{fullmove}... {}", recorded.san),
+                Sides::Black => recorded.san.clone(),
+            });
+            turn = match turn {
+                Sides::White => Sides::Black,
+                Sides::Black => {
+                    fullmove += 1;
+                    Sides::White
+                }
+            };
+        }
+        tokens.push(result.to_owned());
+
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length > 0 && line_length + 1 + token.len() > MAX_LINE_LENGTH {
+                writeln!(f)?;
+                line_length = 0;
+            }
+            if line_length > 0 {
+                write!(f, " ")?;
+                line_length += 1;
+            }
+            write!(f, "{token}")?;
+            line_length += token.len();
+        }
+        writeln!(f)
+    }
+}
+
+/// san formats a move in standard algebraic notation without a check suffix
+///
+/// @param: position - position before the move
+/// @param: legal_moves - legal moves of the position, used to disambiguate
+/// @param: mv - move to format
+/// @return: move in standard algebraic notation
+pub fn san(position: &PositionSnapshot, legal_moves: &[Move], mv: Move) -> String {
+    let (from, to) = (mv.from(), mv.to());
+    let Some((_, piece)) = position.piece_at(from) else {
+        return UciMove::from(mv).to_string();
+    };
+
+    if piece == Pieces::King && from.file().idx().abs_diff(to.file().idx()) == 2 {
+        return if to.file() > from.file() {
+            "O-O"
+        } else {
+            "O-O-O"
+        }
+        .to_owned();
+    }
+
+    let mut san = String::new();
+    if piece == Pieces::Pawn {
+        if from.file() != to.file() {
+            san.push_str(&format!("{}x", from.file()));
+        }
+        san.push_str(&to.to_string());
+        if mv.type_of() == MoveType::Promotion {
+            san.push_str(&format!("={}", mv.promoted_to()));
+        }
+        return san;
+    }
+
+    san.push_str(&piece.to_string());
+    let rivals = legal_moves
+        .iter()
+        .filter(|other| other.to() == to && other.from() != from)
+        .filter(|other| position.piece_at(other.from()).map(|(_, piece)| piece) == Some(piece))
+        .collect::<Vec<_>>();
+    if !rivals.is_empty() {
+        if rivals
+            .iter()
+            .all(|other| other.from().file() != from.file())
+        {
+            san.push_str(&from.file().to_string());
+        } else if rivals
+            .iter()
+            .all(|other| other.from().rank() != from.rank())
+        {
+            san.push_str(&from.rank().to_string());
+        } else {
+            san.push_str(&from.to_string());
+        }
+    }
+    if position.piece_at(to).is_some() {
+        san.push('x');
+    }
+    san.push_str(&to.to_string());
+    san
+}
+
+#[cfg(test)]
+mod tests {
+    use chess_kit_engine::{DefaultEngine, Engine, EngineConfig, PositionBase, PositionProvider};
+    use chess_kit_primitives::Square;
+
+    use super::*;
+
+    fn record(fen: &str, moves: &[Move]) -> GameRecord {
+        let mut engine = DefaultEngine::new(EngineConfig::new(1)).unwrap();
+        engine
+            .set_position(PositionBase::Fen(fen.to_owned()), &[])
+            .unwrap();
+        let mut record = GameRecord::new("Player", "Engine", Some(fen.to_owned()));
+        for mv in moves {
+            let position = engine.position();
+            let legal_moves = engine.legal_moves();
+            engine.play(*mv).unwrap();
+            record.push(
+                &position,
+                &legal_moves,
+                *mv,
+                engine.in_check(),
+                engine.status(),
+                false,
+            );
+        }
+        record
+    }
+
+    fn sans(record: &GameRecord) -> Vec<&str> {
+        record
+            .moves()
+            .iter()
+            .map(|recorded| recorded.san.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn formats_moves_in_standard_algebraic_notation() {
+        let game = record(
+            "r3k2r/1P6/8/3pP3/8/5N2/8/RN2K2R w KQkq d6 0 1",
+            &[
+                Move::new(Square::E5, Square::D6),
+                Move::new(Square::E8, Square::G8),
+                Move::new(Square::B1, Square::D2),
+                Move::new(Square::F8, Square::F3),
+                Move::new(Square::B7, Square::A8).with_promotion(Pieces::Queen),
+            ],
+        );
+
+        assert_eq!(sans(&game), ["exd6", "O-O", "Nbd2", "Rxf3", "bxa8=Q+"]);
+    }
+
+    #[test]
+    fn writes_games_with_their_starting_position_and_result() {
+        let fen = "6k1/5ppp/8/8/8/8/8/R5K1 b - - 0 30";
+        let game = record(
+            fen,
+            &[
+                Move::new(Square::G8, Square::H8),
+                Move::new(Square::A1, Square::A8),
+            ],
+        );
+
+        let pgn = game.to_string();
+        assert!(pgn.contains("[Result \"1-0\"]\n[SetUp \"1\"]\n"));
+        assert!(pgn.contains(&format!("[FEN \"{fen}\"]")));
+        assert!(pgn.ends_with("\n\n30... Kh8 31. Ra8# 1-0\n"));
+    }
+
+    #[test]
+    fn wraps_long_movetext() {
+        let shuffle = [
+            Move::new(Square::G1, Square::F3),
+            Move::new(Square::G8, Square::F6),
+            Move::new(Square::F3, Square::G1),
+            Move::new(Square::F6, Square::G8),
+        ];
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        let game = record(start, &shuffle.repeat(4));
+
+        let pgn = game.to_string();
+        let movetext = pgn.split("\n\n").nth(1).unwrap();
+        assert!(movetext.starts_with("1. Nf3 Nf6 2. Ng1 Ng8 3. Nf3"));
+        assert!(movetext.lines().count() > 1);
+        assert!(movetext.lines().all(|line| line.len() <= MAX_LINE_LENGTH));
+        assert!(movetext.ends_with("\n8. Ng1 Ng8 1/2-1/2\n"));
+    }
+}
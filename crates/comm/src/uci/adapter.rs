@@ -1,5 +1,6 @@
-use chess_kit_engine::{Engine, EngineError};
+use chess_kit_engine::{Engine, EngineError, EngineOption, EvalTrace};
 use chess_kit_primitives::{Move, SearchDepth};
+use std::path::Path;
 
 use super::{PositionCommand, SearchLimits, SearchResult, UciEngine};
 
@@ -90,13 +91,38 @@ where
             .search(limits.depth.unwrap_or(self.default_search_depth))?;
         Ok(SearchResult::from(outcome))
     }
+
+    /// @impl: UciEngine::eval
+    fn eval(&mut self) -> Result<Option<EvalTrace>, Self::Error> {
+        self.engine.eval().map(Some)
+    }
+
+    /// @impl: UciEngine::save_hash
+    fn save_hash(&mut self, path: &str) -> Result<(), Self::Error> {
+        self.engine.save_hash(Path::new(path))
+    }
+
+    /// @impl: UciEngine::load_hash
+    fn load_hash(&mut self, path: &str) -> Result<(), Self::Error> {
+        self.engine.load_hash(Path::new(path))
+    }
+
+    /// @impl: UciEngine::options
+    fn options(&self) -> &[EngineOption] {
+        self.engine.options()
+    }
+
+    /// @impl: UciEngine::set_option
+    fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+        self.engine.set_option(name, value.unwrap_or_default())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
 
-    use chess_kit_engine::{PositionBase, SearchOutcome};
+    use chess_kit_engine::{GameStatus, PositionBase, SearchOutcome};
     use chess_kit_primitives::{Pieces, Square};
 
     use super::*;
@@ -105,6 +131,7 @@ mod tests {
     #[derive(Default)]
     struct TestEngine {
         positions: Vec<(PositionBase, Vec<Move>)>,
+        options: Vec<(String, String)>,
     }
 
     impl Engine for TestEngine {
@@ -135,6 +162,7 @@ mod tests {
                 depth,
                 score: 15,
                 nodes: 23,
+                hashfull: 0,
                 elapsed: Duration::from_millis(4),
             })
         }
@@ -142,6 +170,23 @@ mod tests {
         fn has_legal_moves(&self) -> bool {
             true
         }
+
+        fn legal_moves(&self) -> Vec<Move> {
+            Vec::new()
+        }
+
+        fn in_check(&self) -> bool {
+            false
+        }
+
+        fn status(&self) -> GameStatus {
+            GameStatus::Ongoing
+        }
+
+        fn set_option(&mut self, name: &str, value: &str) -> Result<(), EngineError> {
+            self.options.push((name.to_owned(), value.to_owned()));
+            Ok(())
+        }
     }
 
     #[test]
@@ -171,4 +216,20 @@ mod tests {
         let default_result = adapter.search(&SearchLimits::default()).unwrap();
         assert_eq!(default_result.info.depth.map(SearchDepth::get), Some(4));
     }
+
+    #[test]
+    fn forwards_options_without_values_as_empty_strings() {
+        let mut adapter = UciAdapter::new(TestEngine::default(), SearchDepth::new(4).unwrap());
+
+        adapter.set_option("EvalFile", Some("net.nnue")).unwrap();
+        adapter.set_option("EvalFile", None).unwrap();
+
+        assert_eq!(
+            adapter.engine().options,
+            [
+                ("EvalFile".to_owned(), "net.nnue".to_owned()),
+                ("EvalFile".to_owned(), String::new()),
+            ]
+        );
+    }
 }
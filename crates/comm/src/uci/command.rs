@@ -11,12 +11,16 @@ pub enum Command {
     Uci,
     Debug(bool),
     IsReady,
+    SetOption { name: String, value: Option<String> },
     UciNewGame,
     Position(PositionCommand),
     Go(SearchLimits),
     Stop,
     PonderHit,
     Quit,
+    Eval,
+    SaveHash(String),
+    LoadHash(String),
     Unknown,
 }
 
@@ -40,13 +44,63 @@ impl FromStr for Command {
                 _ => Err(ParseError::InvalidArgument("debug")),
             },
             "isready" => Ok(Self::IsReady),
+            "setoption" => parse_set_option(tokens),
             "ucinewgame" => Ok(Self::UciNewGame),
             "position" => PositionCommand::from_tokens(tokens).map(Self::Position),
             "go" => SearchLimits::from_tokens(tokens).map(Self::Go),
             "stop" => Ok(Self::Stop),
             "ponderhit" => Ok(Self::PonderHit),
             "quit" => Ok(Self::Quit),
+            // note: `eval` is a non-standard debugging command that prints the
+            //       static evaluation breakdown of the current position
+            "eval" => Ok(Self::Eval),
+            // note: `savehash` and `loadhash` are non-standard commands that
+            //       save the hash to a file and resume from it later
+            "savehash" => parse_path(tokens).map(Self::SaveHash),
+            "loadhash" => parse_path(tokens).map(Self::LoadHash),
             _ => Ok(Self::Unknown),
         }
     }
 }
+
+/// parse_set_option parses the arguments following a UCI `setoption` command
+///
+/// note: option names and values may contain spaces, so the name spans every
+///       token up to the optional `value` delimiter and the value spans the rest
+///
+/// @param: tokens - iterator over the setoption command arguments
+/// @return: parsed setoption command, or a parse error
+fn parse_set_option<'a>(mut tokens: impl Iterator<Item = &'a str>) -> Result<Command, ParseError> {
+    if tokens.next() != Some("name") {
+        return Err(ParseError::MissingArgument("name"));
+    }
+
+    let name = tokens
+        .by_ref()
+        .take_while(|token| *token != "value")
+        .collect::<Vec<_>>()
+        .join(" ");
+    if name.is_empty() {
+        return Err(ParseError::MissingArgument("name"));
+    }
+
+    let value = tokens.collect::<Vec<_>>().join(" ");
+    Ok(Command::SetOption {
+        name,
+        value: (!value.is_empty()).then_some(value),
+    })
+}
+
+/// parse_path parses the file path following a hash persistence command
+///
+/// note: paths may contain spaces, so the path spans every remaining token
+///
+/// @param: tokens - iterator over the command arguments
+/// @return: parsed path, or a parse error
+fn parse_path<'a>(tokens: impl Iterator<Item = &'a str>) -> Result<String, ParseError> {
+    let path = tokens.collect::<Vec<_>>().join(" ");
+    if path.is_empty() {
+        return Err(ParseError::MissingArgument("path"));
+    }
+    Ok(path)
+}
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 use std::time::Duration;
 
-use chess_kit_engine::SearchOutcome;
+use chess_kit_engine::{EngineOption, EvalTrace, SearchOutcome};
 use chess_kit_primitives::SearchDepth;
 
 use super::{PositionCommand, SearchLimits, UciMove};
@@ -12,10 +12,16 @@ use super::{PositionCommand, SearchLimits, UciMove};
 /// @type
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct SearchInfo {
-    pub depth: Option<SearchDepth>, // completed positive search depth in plies
-    pub score_cp: Option<i32>,      // position score in centipawns
-    pub nodes: Option<u64>,         // number of nodes searched
-    pub elapsed: Option<Duration>,  // elapsed search time
+    /// completed positive search depth in plies
+    pub depth: Option<SearchDepth>,
+    /// position score in centipawns
+    pub score_cp: Option<i32>,
+    /// number of nodes searched
+    pub nodes: Option<u64>,
+    /// permille of the transposition table in use
+    pub hashfull: Option<u16>,
+    /// elapsed search time
+    pub elapsed: Option<Duration>,
 }
 
 /// `SearchResult` is a type that represents the result of a completed UCI search
@@ -23,9 +29,12 @@ pub struct SearchInfo {
 /// @type
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SearchResult {
-    pub best_move: Option<UciMove>, // best move found, or None when no legal move exists
-    pub ponder: Option<UciMove>,    // expected reply to search while pondering
-    pub info: SearchInfo,           // optional information describing the search
+    /// best move found, or None when no legal move exists
+    pub best_move: Option<UciMove>,
+    /// expected reply to search while pondering
+    pub ponder: Option<UciMove>,
+    /// optional information describing the search
+    pub info: SearchInfo,
 }
 
 impl SearchResult {
@@ -50,6 +59,7 @@ impl From<&SearchOutcome> for SearchInfo {
             depth: Some(outcome.depth),
             score_cp: Some(outcome.score),
             nodes: Some(outcome.nodes),
+            hashfull: Some(outcome.hashfull),
             elapsed: Some(outcome.elapsed),
         }
     }
@@ -122,10 +132,67 @@ pub trait UciEngine {
     /// @side-effects: may convert a ponder search into a normal search
     fn ponder_hit(&mut self) {}
 
+    /// eval breaks the static evaluation of the current position down into its
+    /// terms for the non-standard `eval` command
+    ///
+    /// note: engines without evaluation tracing may keep the default
+    ///       implementation, which ignores the command
+    ///
+    /// @return: evaluation breakdown if one is available, or the engine error
+    fn eval(&mut self) -> Result<Option<EvalTrace>, Self::Error> {
+        Ok(None)
+    }
+
+    /// save_hash saves the transposition table to a file for the non-standard
+    /// `savehash` command
+    ///
+    /// note: engines without hash persistence may keep the default
+    ///       implementation, which ignores the command
+    ///
+    /// @param: path - path of the file to write
+    /// @return: Ok on success, or the engine error
+    fn save_hash(&mut self, _path: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// load_hash replaces the transposition table with a saved one for the
+    /// non-standard `loadhash` command
+    ///
+    /// note: engines without hash persistence may keep the default
+    ///       implementation, which ignores the command
+    ///
+    /// @param: path - path of the file to read
+    /// @return: Ok on success, or the engine error
+    /// @side-effects: may replace the transposition table
+    fn load_hash(&mut self, _path: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
     /// set_debug enables or disables optional diagnostic output
     ///
     /// @param: enabled - whether diagnostic output should be enabled
     /// @return: void
     /// @side-effects: modifies the engine's diagnostic output mode
     fn set_debug(&mut self, _enabled: bool) {}
+
+    /// options returns the options advertised by the engine during UCI
+    /// initialization
+    ///
+    /// @return: options supported by the engine
+    fn options(&self) -> &[EngineOption] {
+        &[]
+    }
+
+    /// set_option updates an option advertised by the engine
+    ///
+    /// note: engines without options may keep the default implementation,
+    ///       which ignores every option as the protocol allows
+    ///
+    /// @param: name - name of the option
+    /// @param: value - new value of the option, or None for button options
+    /// @return: Ok on success, or the engine error
+    /// @side-effects: modifies the engine configuration
+    fn set_option(&mut self, _name: &str, _value: Option<&str>) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
@@ -1,6 +1,8 @@
 use std::fmt::Display;
 use std::io::{self, Write};
 
+use chess_kit_engine::EngineOptionKind;
+
 use super::{Command, PositionCommand, SearchInfo, SearchLimits, SearchResult, UciEngine};
 
 /// `CommandFlow` describes whether the protocol loop should continue after a
@@ -52,25 +54,50 @@ where
             Command::Uci => self.handle_uci()?,
             Command::Debug(enabled) => self.handle_debug(enabled),
             Command::IsReady => self.handle_is_ready()?,
+            Command::SetOption { name, value } => {
+                self.handle_set_option(&name, value.as_deref())?
+            }
             Command::UciNewGame => self.handle_uci_new_game()?,
             Command::Position(position) => self.handle_position(&position)?,
             Command::Go(limits) => self.handle_go(&limits)?,
             Command::Stop => self.handle_stop()?,
             Command::PonderHit => self.handle_ponder_hit(),
             Command::Quit => return Ok(self.handle_quit()),
+            Command::Eval => self.handle_eval()?,
+            Command::SaveHash(path) => self.handle_save_hash(&path)?,
+            Command::LoadHash(path) => self.handle_load_hash(&path)?,
             Command::Unknown => self.handle_unknown(),
         }
 
         Ok(CommandFlow::Continue)
     }
 
-    /// handle_uci writes the engine identity and completes UCI initialization
+    /// handle_uci writes the engine identity and options and completes UCI
+    /// initialization
     ///
     /// @return: Ok on success, or an I/O error
-    /// @side-effects: writes the engine identity to the output stream
+    /// @side-effects: writes the engine identity and options to the output stream
     fn handle_uci(&mut self) -> io::Result<()> {
         writeln!(self.writer, "id name {}", sanitize(self.engine.name()))?;
         writeln!(self.writer, "id author {}", sanitize(self.engine.author()))?;
+        for option in self.engine.options() {
+            match option.kind {
+                EngineOptionKind::String { default } => {
+                    // UCI represents an empty string default with `<empty>`
+                    let default = if default.is_empty() {
+                        "<empty>"
+                    } else {
+                        default
+                    };
+                    writeln!(
+                        self.writer,
+                        "option name {} type string default {}",
+                        sanitize(option.name),
+                        sanitize(default)
+                    )?;
+                }
+            }
+        }
         writeln!(self.writer, "uciok")
     }
 
@@ -91,6 +118,19 @@ where
         writeln!(self.writer, "readyok")
     }
 
+    /// handle_set_option updates an engine option
+    ///
+    /// @param: name - name of the option
+    /// @param: value - new value of the option, or None for button options
+    /// @return: Ok after handling the command, or an I/O error
+    /// @side-effects: modifies engine state and may write an error response
+    fn handle_set_option(&mut self, name: &str, value: Option<&str>) -> io::Result<()> {
+        if let Err(error) = self.engine.set_option(name, value) {
+            self.write_error(error)?;
+        }
+        Ok(())
+    }
+
     /// handle_uci_new_game resets engine state for a new game
     ///
     /// @return: Ok after handling the command, or an I/O error
@@ -156,6 +196,44 @@ where
         CommandFlow::Quit
     }
 
+    /// handle_eval writes the static evaluation breakdown of the current
+    /// position
+    ///
+    /// @return: Ok after handling the command, or an I/O error
+    /// @side-effects: writes the evaluation table or an error response
+    fn handle_eval(&mut self) -> io::Result<()> {
+        match self.engine.eval() {
+            Ok(Some(trace)) => writeln!(self.writer, "{trace}"),
+            Ok(None) => Ok(()),
+            Err(error) => self.write_error(error),
+        }
+    }
+
+    /// handle_save_hash saves the transposition table to a file
+    ///
+    /// @param: path - path of the file to write
+    /// @return: Ok after handling the command, or an I/O error
+    /// @side-effects: may write an error response
+    fn handle_save_hash(&mut self, path: &str) -> io::Result<()> {
+        if let Err(error) = self.engine.save_hash(path) {
+            self.write_error(error)?;
+        }
+        Ok(())
+    }
+
+    /// handle_load_hash replaces the transposition table with a saved one
+    ///
+    /// @param: path - path of the file to read
+    /// @return: Ok after handling the command, or an I/O error
+    /// @side-effects: may replace the transposition table and write an error
+    ///                response
+    fn handle_load_hash(&mut self, path: &str) -> io::Result<()> {
+        if let Err(error) = self.engine.load_hash(path) {
+            self.write_error(error)?;
+        }
+        Ok(())
+    }
+
     /// handle_unknown intentionally ignores commands outside the supported subset
     ///
     /// @return: void
@@ -202,6 +280,9 @@ where
         if let Some(nodes) = info.nodes {
             write!(self.writer, " nodes {nodes}")?;
         }
+        if let Some(hashfull) = info.hashfull {
+            write!(self.writer, " hashfull {hashfull}")?;
+        }
         if let Some(elapsed) = info.elapsed {
             write!(self.writer, " time {}", elapsed.as_millis())?;
         }
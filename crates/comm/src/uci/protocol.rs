@@ -80,6 +80,7 @@ mod tests {
     use std::convert::Infallible;
     use std::io::Cursor;
 
+    use chess_kit_engine::{EngineOption, EngineOptionKind, EvalTrace};
     use chess_kit_primitives::SearchDepth;
 
     use super::*;
@@ -92,8 +93,15 @@ mod tests {
         debug: Option<bool>,
         stops: usize,
         ponder_hits: usize,
+        options: Vec<(String, Option<String>)>,
+        hash_files: Vec<(&'static str, String)>,
     }
 
+    const TEST_OPTIONS: [EngineOption; 1] = [EngineOption {
+        name: "EvalFile",
+        kind: EngineOptionKind::String { default: "" },
+    }];
+
     impl UciEngine for TestEngine {
         type Error = Infallible;
 
@@ -121,6 +129,7 @@ mod tests {
                 depth: Some(SearchDepth::new(3).unwrap()),
                 score_cp: Some(12),
                 nodes: Some(42),
+                hashfull: Some(7),
                 elapsed: Some(std::time::Duration::from_millis(2)),
             };
             Ok(result)
@@ -138,6 +147,37 @@ mod tests {
         fn set_debug(&mut self, enabled: bool) {
             self.debug = Some(enabled);
         }
+
+        fn options(&self) -> &[EngineOption] {
+            &TEST_OPTIONS
+        }
+
+        fn set_option(&mut self, name: &str, value: Option<&str>) -> Result<(), Self::Error> {
+            self.options
+                .push((name.to_owned(), value.map(str::to_owned)));
+            Ok(())
+        }
+
+        fn eval(&mut self) -> Result<Option<EvalTrace>, Self::Error> {
+            Ok(Some(test_trace()))
+        }
+
+        fn save_hash(&mut self, path: &str) -> Result<(), Self::Error> {
+            self.hash_files.push(("save", path.to_owned()));
+            Ok(())
+        }
+
+        fn load_hash(&mut self, path: &str) -> Result<(), Self::Error> {
+            self.hash_files.push(("load", path.to_owned()));
+            Ok(())
+        }
+    }
+
+    fn test_trace() -> EvalTrace {
+        let mut trace = EvalTrace::new(12);
+        trace.phase = Some(1.0);
+        trace.push("Pawns", (800, 900), (788, 900));
+        trace
     }
 
     #[test]
@@ -157,9 +197,10 @@ mod tests {
             concat!(
                 "id name Test Engine\n",
                 "id author Test Author\n",
+                "option name EvalFile type string default <empty>\n",
                 "uciok\n",
                 "readyok\n",
-                "info depth 3 score cp 12 nodes 42 time 2\n",
+                "info depth 3 score cp 12 nodes 42 hashfull 7 time 2\n",
                 "bestmove e2e4\n",
             )
         );
@@ -181,4 +222,64 @@ mod tests {
         assert_eq!(engine.ponder_hits, 1);
         assert!(output.is_empty());
     }
+
+    #[test]
+    fn forwards_option_names_and_values_containing_spaces() {
+        let input = Cursor::new(
+            b"setoption name EvalFile value /nets/my net.nnue\nsetoption name Clear Hash\nsetoption\nquit\n",
+        );
+        let mut output = Vec::new();
+        let mut engine = TestEngine::default();
+
+        run_with_io(&mut engine, input, &mut output).unwrap();
+
+        assert_eq!(
+            engine.options,
+            [
+                ("EvalFile".to_owned(), Some("/nets/my net.nnue".to_owned())),
+                ("Clear Hash".to_owned(), None),
+            ]
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "info string error: missing value for name\n"
+        );
+    }
+
+    #[test]
+    fn writes_the_evaluation_breakdown_for_the_eval_command() {
+        let input = Cursor::new(b"eval\nquit\n");
+        let mut output = Vec::new();
+        let mut engine = TestEngine::default();
+
+        run_with_io(&mut engine, input, &mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            format!("{}\n", test_trace())
+        );
+    }
+
+    #[test]
+    fn forwards_hash_file_paths_containing_spaces() {
+        let input = Cursor::new(
+            b"savehash /tmp/my session.hash\nloadhash /tmp/my session.hash\nloadhash\nquit\n",
+        );
+        let mut output = Vec::new();
+        let mut engine = TestEngine::default();
+
+        run_with_io(&mut engine, input, &mut output).unwrap();
+
+        assert_eq!(
+            engine.hash_files,
+            [
+                ("save", "/tmp/my session.hash".to_owned()),
+                ("load", "/tmp/my session.hash".to_owned()),
+            ]
+        );
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "info string error: missing value for path\n"
+        );
+    }
 }
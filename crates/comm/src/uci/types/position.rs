@@ -9,8 +9,10 @@ use super::{ParseError, UciMove};
 /// @type
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct PositionCommand {
-    pub base: PositionBase,  // position from which the move history begins
-    pub moves: Vec<UciMove>, // ordered moves applied to the base position
+    /// position from which the move history begins
+    pub base: PositionBase,
+    /// ordered moves applied to the base position
+    pub moves: Vec<UciMove>,
 }
 
 impl PositionCommand {
@@ -15,15 +15,24 @@ use super::ParseError;
 /// @type
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct SearchLimits {
-    pub white_time: Option<Duration>,      // remaining white clock time
-    pub black_time: Option<Duration>,      // remaining black clock time
-    pub white_increment: Option<Duration>, // white increment per move
-    pub black_increment: Option<Duration>, // black increment per move
-    pub moves_to_go: Option<u32>,          // moves until the next time control
-    pub depth: Option<SearchDepth>,        // maximum positive search depth in plies
-    pub nodes: Option<u64>,                // maximum number of nodes to search
-    pub move_time: Option<Duration>,       // fixed time allocated to this move
-    pub infinite: bool,                    // whether search should continue until stopped
+    /// remaining white clock time
+    pub white_time: Option<Duration>,
+    /// remaining black clock time
+    pub black_time: Option<Duration>,
+    /// white increment per move
+    pub white_increment: Option<Duration>,
+    /// black increment per move
+    pub black_increment: Option<Duration>,
+    /// moves until the next time control
+    pub moves_to_go: Option<u32>,
+    /// maximum positive search depth in plies
+    pub depth: Option<SearchDepth>,
+    /// maximum number of nodes to search
+    pub nodes: Option<u64>,
+    /// fixed time allocated to this move
+    pub move_time: Option<Duration>,
+    /// whether search should continue until stopped
+    pub infinite: bool,
 }
 
 impl SearchLimits {
@@ -0,0 +1,87 @@
+use crate::DatagenError;
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_position::{Fen, Setup};
+use std::path::Path;
+
+/// load_book reads the openings of the book file at the given path
+///
+/// @param: path - path of the book file
+/// @return: openings of the book, or the datagen error
+pub fn load_book(path: impl AsRef<Path>) -> Result<Vec<Fen>, DatagenError> {
+    parse_book(&std::fs::read_to_string(path)?)
+}
+
+/// parse_book reads the openings of a book
+///
+/// Each line holds a FEN, with or without its move clocks, optionally followed
+/// by EPD operations which are ignored. Blank lines and lines starting with
+/// `#` are ignored
+///
+/// @param: contents - contents of a book file
+/// @return: openings of the book, or the datagen error
+pub fn parse_book(contents: &str) -> Result<Vec<Fen>, DatagenError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, contents)| {
+            let tokens = contents.split_whitespace().collect::<Vec<_>>();
+            if tokens.len() < 4 {
+                return Err(DatagenError::Book {
+                    line,
+                    reason: format!("expected a FEN, got {contents:?}"),
+                });
+            }
+            // the move clocks are optional, so they are only taken from the
+            // line when both of them are present
+            let has_clocks =
+                tokens.len() >= 6 && tokens[4..6].iter().all(|t| t.parse::<u32>().is_ok());
+            let fen = if has_clocks {
+                tokens[..6].join(" ")
+            } else {
+                format!("{} 0 1", tokens[..4].join(" "))
+            };
+            let fen = Fen::try_from(fen.as_str()).map_err(|error| DatagenError::Book {
+                line,
+                reason: error.to_string(),
+            })?;
+            Setup::from(fen.clone())
+                .validate::<DefaultAttackTable>()
+                .map_err(|error| DatagenError::Book {
+                    line,
+                    reason: error.to_string(),
+                })?;
+            Ok(fen)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_openings_with_or_without_clocks() {
+        let book = parse_book(
+            "# openings\n\n\
+             rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1\n\
+             rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq - id \"d4\";\n",
+        )
+        .unwrap();
+
+        assert_eq!(book.len(), 2);
+        assert_eq!(
+            book[1].to_string(),
+            "rnbqkbnr/pppppppp/8/8/3P4/8/PPP1PPPP/RNBQKBNR b KQkq - 0 1"
+        );
+        assert!(matches!(
+            parse_book("\n8/8/8 w"),
+            Err(DatagenError::Book { line: 2, .. })
+        ));
+        assert!(matches!(
+            parse_book("4k3/8/8/8/8/8/8/4R1K1 w - -"),
+            Err(DatagenError::Book { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,83 @@
+use chess_kit_position::Fen;
+
+/// Adjudication ends games whose outcome is settled before the rules end them
+///
+/// Scores are the search scores of both sides, in centipawns from white's
+/// point of view
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adjudication {
+    /// score at which a side is considered winning
+    pub win_score: i32,
+    /// consecutive winning plies that end the game
+    pub win_plies: usize,
+    /// score under which the game is considered drawn
+    pub draw_score: i32,
+    /// consecutive drawn plies that end the game
+    pub draw_plies: usize,
+    /// ply before which games are not adjudicated drawn
+    pub draw_min_ply: usize,
+}
+
+impl Default for Adjudication {
+    /// default returns the adjudication of self-play data generation
+    ///
+    /// @return: default adjudication
+    fn default() -> Self {
+        Self {
+            win_score: 1_500,
+            win_plies: 8,
+            draw_score: 10,
+            draw_plies: 12,
+            draw_min_ply: 80,
+        }
+    }
+}
+
+/// DatagenConfig is the settings of a self-play data generation run
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DatagenConfig {
+    /// number of games to play
+    pub games: usize,
+    /// number of games played at once
+    pub threads: usize,
+    /// node budget of every search
+    pub nodes: u64,
+    /// random moves played from the opening
+    pub random_plies: usize,
+    /// openings to start from, or empty for the start position
+    pub book: Vec<Fen>,
+    /// plies after which a game is drawn
+    pub max_plies: usize,
+    /// largest absolute score of a recorded position
+    pub max_score: i32,
+    /// rules ending settled games
+    pub adjudication: Adjudication,
+    /// seed of the opening randomization
+    pub seed: u64,
+    /// transposition table size of every engine
+    pub transposition_table_size_mb: usize,
+}
+
+impl Default for DatagenConfig {
+    /// default returns the settings of a small single-threaded run
+    ///
+    /// @return: default settings
+    fn default() -> Self {
+        Self {
+            games: 100,
+            threads: 1,
+            nodes: 5_000,
+            random_plies: 8,
+            book: Vec::new(),
+            max_plies: 400,
+            max_score: 3_000,
+            adjudication: Adjudication::default(),
+            seed: 0,
+            transposition_table_size_mb: 16,
+        }
+    }
+}
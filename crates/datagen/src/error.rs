@@ -0,0 +1,47 @@
+use chess_kit_engine::EngineError;
+use std::fmt::{self, Display};
+use std::io;
+
+/// DatagenError is returned when training data cannot be generated
+///
+/// @type
+#[derive(Debug)]
+pub enum DatagenError {
+    // a book or output file could not be read or written
+    Io(io::Error),
+    // the engine rejected a position, move or search
+    Engine(EngineError),
+    // a line of an opening book could not be parsed
+    Book { line: usize, reason: String },
+    // no game could be started from the openings
+    NoPlayableOpening,
+}
+
+impl Display for DatagenError {
+    /// fmt writes a human-readable description of the datagen error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to access file: {error}"),
+            Self::Engine(error) => write!(f, "engine error: {error}"),
+            Self::Book { line, reason } => write!(f, "book line {line}: {reason}"),
+            Self::NoPlayableOpening => write!(f, "no playable opening"),
+        }
+    }
+}
+
+impl std::error::Error for DatagenError {}
+
+impl From<io::Error> for DatagenError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<EngineError> for DatagenError {
+    fn from(error: EngineError) -> Self {
+        Self::Engine(error)
+    }
+}
@@ -0,0 +1,201 @@
+use crate::{DatagenConfig, DatagenError, GameResult, Rng, TrainingPosition};
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_engine::{DefaultEngine, Engine, GameStatus, PositionBase};
+use chess_kit_position::{
+    DefaultPosition, Fen, PositionAttacks, PositionMoves, PositionView, Setup,
+};
+use chess_kit_primitives::{Move, MoveType, Pieces, Sides};
+
+/// Game is a finished self-play game
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    /// quiet positions kept from the game
+    pub positions: Vec<TrainingPosition>,
+    /// result of the game
+    pub result: GameResult,
+    /// plies played, the opening included
+    pub plies: usize,
+}
+
+/// play_game plays a game of the engine against itself
+///
+/// The game starts from a random opening of the book, or from the start
+/// position, followed by random legal moves. Every later move is the best
+/// move of a node-limited search, and the positions where the side to move is
+/// not in check, the best move is not a capture or promotion and the score is
+/// not decisive are kept
+///
+/// @param: engine - engine playing both sides
+/// @param: config - settings of the game
+/// @param: rng - generator picking the opening
+/// @return: finished game, None if the opening ended the game, or the
+///          datagen error
+/// @side-effects: replaces the engine's game
+pub fn play_game(
+    engine: &mut DefaultEngine,
+    config: &DatagenConfig,
+    rng: &mut Rng,
+) -> Result<Option<Game>, DatagenError> {
+    engine.new_game()?;
+    // the engine's position is mirrored to inspect the game between searches
+    let mut position = if config.book.is_empty() {
+        DefaultPosition::<DefaultAttackTable>::default()
+    } else {
+        let fen = config.book[rng.below(config.book.len())].clone();
+        engine.set_position(PositionBase::Fen(fen.to_string()), &[])?;
+        DefaultPosition::try_from(Setup::from(fen)).expect("the engine accepted the opening")
+    };
+
+    let mut plies = 0;
+    for _ in 0..config.random_plies {
+        let moves = engine.legal_moves();
+        if moves.is_empty() {
+            return Ok(None);
+        }
+        play(engine, &mut position, moves[rng.below(moves.len())])?;
+        plies += 1;
+    }
+    if engine.status().is_over() {
+        return Ok(None);
+    }
+
+    let adjudication = config.adjudication;
+    let mut samples = Vec::new();
+    let mut winning = (0, 0); // sign and length of the current streak of wins
+    let mut drawn = 0; // length of the current streak of drawn scores
+    let result = loop {
+        match engine.status() {
+            GameStatus::Ongoing => {}
+            GameStatus::Checkmate { winner } => break GameResult::win(winner),
+            GameStatus::Stalemate | GameStatus::Draw(_) => break GameResult::Draw,
+        }
+        if plies >= config.max_plies {
+            break GameResult::Draw;
+        }
+
+        let outcome = engine.search_nodes(config.nodes)?;
+        let mv = outcome
+            .best_move
+            .ok_or_else(|| DatagenError::Engine("search found no move".into()))?;
+        let score = match position.turn() {
+            Sides::White => outcome.score,
+            Sides::Black => -outcome.score,
+        };
+
+        let tactical = position.piece_at(mv.to()) != Pieces::None
+            || matches!(mv.type_of(), MoveType::Promotion | MoveType::EnPassant);
+        let in_check = position.checkers().not_empty();
+        if !in_check && !tactical && score.abs() < config.max_score {
+            samples.push((Fen::from(&position), score));
+        }
+
+        if score.abs() >= adjudication.win_score {
+            let sign = score.signum();
+            winning = (sign, if winning.0 == sign { winning.1 + 1 } else { 1 });
+        } else {
+            winning = (0, 0);
+        }
+        if winning.1 >= adjudication.win_plies {
+            break if winning.0 > 0 {
+                GameResult::WhiteWin
+            } else {
+                GameResult::BlackWin
+            };
+        }
+        if plies >= adjudication.draw_min_ply && score.abs() <= adjudication.draw_score {
+            drawn += 1;
+        } else {
+            drawn = 0;
+        }
+        if drawn >= adjudication.draw_plies {
+            break GameResult::Draw;
+        }
+
+        play(engine, &mut position, mv)?;
+        plies += 1;
+    };
+
+    let positions = samples
+        .into_iter()
+        .map(|(fen, score)| TrainingPosition { fen, score, result })
+        .collect();
+    Ok(Some(Game {
+        positions,
+        result,
+        plies,
+    }))
+}
+
+/// play plays a legal move in the engine and its mirrored position
+///
+/// @param: engine - engine playing the game
+/// @param: position - mirror of the engine's position
+/// @param: mv - legal move generated in the position
+/// @return: Ok on success, or the datagen error
+/// @side-effects: plays the move in the engine and the position
+fn play(
+    engine: &mut DefaultEngine,
+    position: &mut DefaultPosition<DefaultAttackTable>,
+    mv: Move,
+) -> Result<(), DatagenError> {
+    engine.play(mv)?;
+    let _ = position.play_unchecked(mv);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_engine::EngineConfig;
+
+    fn config() -> DatagenConfig {
+        DatagenConfig {
+            nodes: 200,
+            max_plies: 60,
+            transposition_table_size_mb: 1,
+            ..DatagenConfig::default()
+        }
+    }
+
+    fn engine() -> DefaultEngine {
+        DefaultEngine::new(EngineConfig::new(1)).unwrap()
+    }
+
+    #[test]
+    fn keeps_the_quiet_positions_of_a_game() {
+        let config = config();
+        let game = play_game(&mut engine(), &config, &mut Rng::new(1))
+            .unwrap()
+            .unwrap();
+
+        assert!(game.plies > config.random_plies && game.plies <= config.max_plies);
+        assert!(!game.positions.is_empty());
+        for sample in &game.positions {
+            let position =
+                DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(sample.fen.clone()))
+                    .unwrap();
+            assert!(position.checkers().is_empty());
+            assert!(sample.score.abs() < config.max_score);
+            assert_eq!(sample.result, game.result);
+        }
+    }
+
+    #[test]
+    fn ends_decided_games() {
+        // a king and queen ending is won, while bare kings are drawn before
+        // the game starts
+        let mut config = config();
+        config.random_plies = 0;
+        config.book = vec![Fen::try_from("7k/8/6K1/8/8/8/8/1Q6 w - - 0 1").unwrap()];
+        let game = play_game(&mut engine(), &config, &mut Rng::new(1))
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.result, GameResult::WhiteWin);
+
+        config.book = vec![Fen::try_from("7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap()];
+        let game = play_game(&mut engine(), &config, &mut Rng::new(1)).unwrap();
+        assert!(game.is_none());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{DatagenConfig, DatagenError, Game, GameResult, Rng, play_game};
+use chess_kit_engine::{DefaultEngine, EngineConfig};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+
+/// MAX_OPENING_ATTEMPTS is the number of openings tried in a row before giving
+/// up on starting a game
+const MAX_OPENING_ATTEMPTS: usize = 100;
+
+/// DatagenStats is the summary of a data generation run
+///
+/// @type
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DatagenStats {
+    /// number of games played
+    pub games: usize,
+    /// number of positions kept
+    pub positions: usize,
+    /// number of games won by white
+    pub white_wins: usize,
+    /// number of drawn games
+    pub draws: usize,
+    /// number of games won by black
+    pub black_wins: usize,
+}
+
+/// generate plays the games of a data generation run on several threads and
+/// hands every finished game to a callback on the calling thread
+///
+/// note: every thread has its own engine and a generator seeded from the
+///       run's seed, so games are reproducible per thread but their order
+///       depends on scheduling
+///
+/// @param: config - settings of the run
+/// @param: record - callback receiving every finished game, which stops the
+///                  run by returning an error
+/// @return: summary of the run, or the first datagen error
+pub fn generate<F>(config: &DatagenConfig, mut record: F) -> Result<DatagenStats, DatagenError>
+where
+    F: FnMut(&Game) -> Result<(), DatagenError>,
+{
+    let started = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::scope(|scope| {
+        for worker in 0..config.threads.max(1) {
+            let sender = sender.clone();
+            let started = &started;
+            scope.spawn(move || {
+                if let Err(error) = play_games(config, worker, started, &sender) {
+                    let _ = sender.send(Err(error));
+                }
+            });
+        }
+        drop(sender);
+
+        // note: returning early drops the receiver, which stops the workers
+        //       after their current game
+        let mut stats = DatagenStats::default();
+        for game in receiver {
+            let game = game?;
+            record(&game)?;
+            stats.games += 1;
+            stats.positions += game.positions.len();
+            match game.result {
+                GameResult::WhiteWin => stats.white_wins += 1,
+                GameResult::Draw => stats.draws += 1,
+                GameResult::BlackWin => stats.black_wins += 1,
+            }
+        }
+        Ok(stats)
+    })
+}
+
+/// play_games plays games on a worker thread until the run has started all of
+/// its games
+///
+/// @param: config - settings of the run
+/// @param: worker - index of the worker thread
+/// @param: started - number of games started by every worker
+/// @param: sender - channel to send the finished games to
+/// @return: Ok once the games are played or the run is stopped, or the
+///          datagen error
+/// @side-effects: increments the number of started games
+fn play_games(
+    config: &DatagenConfig,
+    worker: usize,
+    started: &AtomicUsize,
+    sender: &Sender<Result<Game, DatagenError>>,
+) -> Result<(), DatagenError> {
+    let mut engine = DefaultEngine::new(EngineConfig::new(config.transposition_table_size_mb))?;
+    let mut rng = Rng::new(config.seed ^ (worker as u64).wrapping_mul(0x2545_F491_4F6C_DD1D));
+
+    while started.fetch_add(1, Ordering::Relaxed) < config.games {
+        let game = (0..MAX_OPENING_ATTEMPTS)
+            .find_map(|_| play_game(&mut engine, config, &mut rng).transpose())
+            .ok_or(DatagenError::NoPlayableOpening)??;
+        if sender.send(Ok(game)).is_err() {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_position::Fen;
+
+    #[test]
+    fn plays_every_game_of_the_run() {
+        let config = DatagenConfig {
+            games: 3,
+            threads: 2,
+            nodes: 100,
+            max_plies: 40,
+            transposition_table_size_mb: 1,
+            ..DatagenConfig::default()
+        };
+        let mut positions = 0;
+        let stats = generate(&config, |game| {
+            positions += game.positions.len();
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(stats.games, 3);
+        assert_eq!(stats.positions, positions);
+        assert_eq!(stats.white_wins + stats.draws + stats.black_wins, 3);
+    }
+
+    #[test]
+    fn stops_on_the_first_error() {
+        let config = DatagenConfig {
+            games: 2,
+            random_plies: 0,
+            book: vec![Fen::try_from("7k/8/8/8/8/8/8/K7 w - - 0 1").unwrap()],
+            ..DatagenConfig::default()
+        };
+        assert!(matches!(
+            generate(&config, |_| Ok(())),
+            Err(DatagenError::NoPlayableOpening)
+        ));
+
+        let config = DatagenConfig {
+            games: 4,
+            nodes: 50,
+            max_plies: 20,
+            transposition_table_size_mb: 1,
+            ..DatagenConfig::default()
+        };
+        let mut games = 0;
+        let stopped = generate(&config, |_| {
+            games += 1;
+            Err(DatagenError::NoPlayableOpening)
+        });
+        assert!(stopped.is_err());
+        assert_eq!(games, 1);
+    }
+}
@@ -0,0 +1,23 @@
+//! Self-play training data generation.
+//!
+//! [`generate`] plays games of the default engine against itself on several
+//! threads, starting from randomized openings and searching every move with a
+//! fixed node budget. Games are adjudicated once the scores settle, and the
+//! quiet positions of every game are kept with their search score and the
+//! game's result, to be written with [`TrainingPosition::write_text`] or
+//! [`TrainingPosition::write_binary`].
+
+pub mod book;
+pub mod config;
+pub mod error;
+pub mod game;
+pub mod generator;
+pub mod record;
+
+pub use book::{load_book, parse_book};
+pub use chess_kit_rng::Rng;
+pub use config::{Adjudication, DatagenConfig};
+pub use error::DatagenError;
+pub use game::{Game, play_game};
+pub use generator::{DatagenStats, generate};
+pub use record::{GameResult, RECORD_SIZE, TrainingPosition};
@@ -0,0 +1,152 @@
+use chess_kit_position::{Fen, PackedPosition, Setup};
+use chess_kit_primitives::Sides;
+use std::io::{self, Write};
+
+/// RECORD_SIZE is the number of bytes of a position in the binary format
+pub const RECORD_SIZE: usize = 32;
+
+/// GameResult is the outcome of a self-play game
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameResult {
+    WhiteWin,
+    Draw,
+    BlackWin,
+}
+
+impl GameResult {
+    /// score returns the result from white's point of view
+    ///
+    /// @return: 1 for a white win, 0.5 for a draw and 0 for a black win
+    pub const fn score(self) -> f64 {
+        match self {
+            Self::WhiteWin => 1.0,
+            Self::Draw => 0.5,
+            Self::BlackWin => 0.0,
+        }
+    }
+
+    /// win returns the result of a game won by a side
+    ///
+    /// @param: side - winning side
+    /// @return: result of the game
+    pub const fn win(side: Sides) -> Self {
+        match side {
+            Sides::White => Self::WhiteWin,
+            Sides::Black => Self::BlackWin,
+        }
+    }
+}
+
+/// TrainingPosition is a quiet position of a self-play game with its search
+/// score and the game's result
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrainingPosition {
+    /// position taken from the game
+    pub fen: Fen,
+    /// search score in centipawns from white's point of view
+    pub score: i32,
+    /// result of the game the position was taken from
+    pub result: GameResult,
+}
+
+impl TrainingPosition {
+    /// write_text writes the position as a line of text, with the FEN, score
+    /// and result separated by `|`, e.g. `<FEN> | 35 | 1.0`
+    ///
+    /// note: the lines can be loaded as a tuning dataset, which takes the
+    ///       result from the last token of each line
+    ///
+    /// @param: writer - destination of the line
+    /// @return: Ok on success, or the io error
+    pub fn write_text<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writeln!(
+            writer,
+            "{} | {} | {:.1}",
+            self.fen,
+            self.score,
+            self.result.score()
+        )
+    }
+
+    /// write_binary writes the position as a record of [`RECORD_SIZE`] bytes
+    ///
+    /// The record holds, in order and little-endian:
+    /// - the position as a [`PackedPosition`]
+    /// - the score as 16 bits
+    /// - the result as 2 for a white win, 1 for a draw and 0 for a black win
+    ///
+    /// @param: writer - destination of the record
+    /// @return: Ok on success, or the io error, which is invalid data for
+    ///          positions that cannot be packed
+    pub fn write_binary<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut record = [0u8; RECORD_SIZE];
+        let packed = PackedPosition::try_from(&Setup::from(self.fen.clone()))
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        record[..PackedPosition::SIZE].copy_from_slice(packed.as_bytes());
+
+        let score = self.score.clamp(i32::from(i16::MIN), i32::from(i16::MAX)) as i16;
+        record[29..31].copy_from_slice(&score.to_le_bytes());
+        record[31] = match self.result {
+            GameResult::WhiteWin => 2,
+            GameResult::Draw => 1,
+            GameResult::BlackWin => 0,
+        };
+        writer.write_all(&record)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(fen: &str, score: i32, result: GameResult) -> TrainingPosition {
+        TrainingPosition {
+            fen: Fen::try_from(fen).unwrap(),
+            score,
+            result,
+        }
+    }
+
+    #[test]
+    fn writes_lines_of_text() {
+        let mut text = Vec::new();
+        position("4k3/8/8/8/8/8/4P3/4K3 b - - 3 40", -25, GameResult::Draw)
+            .write_text(&mut text)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(text).unwrap(),
+            "4k3/8/8/8/8/8/4P3/4K3 b - - 3 40 | -25 | 0.5\n"
+        );
+    }
+
+    #[test]
+    fn writes_fixed_size_records() {
+        let mut record = Vec::new();
+        position(
+            "4k3/8/8/8/8/8/4P3/4K3 b K - 3 300",
+            40_000,
+            GameResult::WhiteWin,
+        )
+        .write_binary(&mut record)
+        .unwrap();
+
+        assert_eq!(record.len(), RECORD_SIZE);
+        assert_eq!(
+            u64::from_le_bytes(record[..8].try_into().unwrap()),
+            (1 << 4) | (1 << 12) | (1 << 60)
+        );
+        // white king on e1, white pawn on e2 and black king on e8
+        assert_eq!(record[8..11], [0x16, 0x0E, 0x00]);
+        assert_eq!(record[24] & 1, 1);
+        assert_eq!(record[25], 64);
+        assert_eq!(record[26], 3);
+        assert_eq!(u16::from_le_bytes([record[27], record[28]]), 300);
+        assert_eq!(i16::from_le_bytes([record[29], record[30]]), i16::MAX);
+        assert_eq!(record[31], 2);
+    }
+}
@@ -1,19 +1,85 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
-use chess_kit_attack_table::DefaultAttackTable;
-use chess_kit_eval::{Accumulator, DefaultAccumulator, EvalState, PSQTEvalState};
+#[cfg(all(feature = "pext", target_arch = "x86_64"))]
+use chess_kit_attack_table::PextAttackTable;
+use chess_kit_attack_table::{AttackTable, DefaultAttackTable};
+use chess_kit_eval::endgame::EndgameEvalState;
+use chess_kit_eval::nnue::Network;
+use chess_kit_eval::{Accumulator, DefaultAccumulator, EvalState};
 use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
-use chess_kit_position::{DefaultPosition, Fen, PositionMoves, PositionSnapshot, Setup};
-use chess_kit_primitives::{Move, MoveList, MoveType, SearchDepth};
-use chess_kit_search::{Negamax, SearchNode, iterative_deepening};
-use chess_kit_transposition::{DefaultTranspositionTable, TranspositionTable};
+use chess_kit_position::{
+    DefaultPosition, Fen, PositionAttacks, PositionMoves, PositionSnapshot, PositionView, Setup,
+};
+use chess_kit_primitives::{Clock, Depth, Move, MoveList, MoveType, SearchDepth};
+use chess_kit_search::{Negamax, SearchNode, iterative_deepening, iterative_deepening_nodes};
+use chess_kit_syzygy::Tablebases;
+use chess_kit_tablegen::Tablebase;
+use chess_kit_transposition::{DefaultTranspositionTable, TranspositionError, TranspositionTable};
 
-use crate::{Engine, EngineConfig, EngineError, PositionBase, PositionProvider, SearchOutcome};
+use crate::eval::EngineEvalState;
+use crate::tablebases::EngineTablebases;
+use crate::{
+    DrawReason, Engine, EngineConfig, EngineError, EngineOption, EngineOptionKind, EvalTrace,
+    GameStatus, PositionBase, PositionProvider, SearchOutcome,
+};
 
-type EnginePosition = DefaultPosition<DefaultAttackTable>;
-type EngineMoveGenerator = DefaultMoveGenerator<DefaultAttackTable>;
-type EngineAccumulator = DefaultAccumulator<PSQTEvalState>;
+type EnginePosition<AT> = DefaultPosition<AT>;
+type EngineMoveGenerator<AT> = DefaultMoveGenerator<AT>;
+type EngineAccumulator = DefaultAccumulator<EndgameEvalState<EngineEvalState>>;
 type EngineTranspositionTable = DefaultTranspositionTable<SearchNode>;
+type EngineSearch = Negamax<EngineTablebases>;
+
+/// EVAL_FILE is the option selecting the network file used for evaluation
+///
+/// note: an empty value unloads the network and falls back to the PSQT
+///       evaluation
+const EVAL_FILE: &str = "EvalFile";
+
+/// SYZYGY_PATH is the option selecting the directories of the Syzygy
+/// tablebases probed during search
+///
+/// note: an empty value disables tablebase probing
+const SYZYGY_PATH: &str = "SyzygyPath";
+
+/// TABLEBASE_PATH is the option selecting the directory of the `.cktb`
+/// distance-to-mate tables probed during search
+///
+/// note: an empty value disables probing of these tables
+const TABLEBASE_PATH: &str = "TablebasePath";
+
+/// OPTIONS are the runtime options accepted by the default engine
+const OPTIONS: [EngineOption; 3] = [
+    EngineOption {
+        name: EVAL_FILE,
+        kind: EngineOptionKind::String { default: "" },
+    },
+    EngineOption {
+        name: SYZYGY_PATH,
+        kind: EngineOptionKind::String { default: "" },
+    },
+    EngineOption {
+        name: TABLEBASE_PATH,
+        kind: EngineOptionKind::String { default: "" },
+    },
+];
+
+/// NAME is the engine name advertised to presentation adapters
+const NAME: &str = concat!("chess-kit ", env!("CARGO_PKG_VERSION"));
+
+/// AUTHOR is the engine author advertised to presentation adapters
+const AUTHOR: &str = "chess-kit contributors";
+
+/// NODE_SEARCH_MAX_DEPTH is the deepest iteration of a node-limited search,
+/// which bounds the search of positions with very few legal moves
+const NODE_SEARCH_MAX_DEPTH: Depth = 64;
+
+/// FIFTY_MOVE_HALFMOVES is the halfmove clock at which the game is drawn by the
+/// fifty-move rule
+const FIFTY_MOVE_HALFMOVES: Clock = 100;
 
 /// `DefaultEngine` is the composed, protocol-agnostic chess engine session
 ///
@@ -21,31 +87,39 @@ type EngineTranspositionTable = DefaultTranspositionTable<SearchNode>;
 /// search algorithm. Presentation layers should call this API rather than
 /// wiring toolkit crates themselves
 ///
+/// note: the attack table is chosen once when the engine is constructed. with
+///       the `pext` feature, cpus that support BMI2 look slider attacks up with
+///       `PextAttackTable` and other cpus fall back to the magics
+///
 /// @type
 pub struct DefaultEngine {
-    position: EnginePosition,
-    move_generator: EngineMoveGenerator,
-    accumulator: EngineAccumulator,
-    transposition_table: EngineTranspositionTable,
-    search: Negamax,
+    session: Box<dyn Session>, // session on the attack table of the cpu
 }
 
+/// `Session` is an engine session on any attack table
+///
+/// @trait
+trait Session: Engine + PositionProvider {}
+
+impl<EngineT: Engine + PositionProvider> Session for EngineT {}
+
 impl DefaultEngine {
     /// new creates an engine at the standard starting position
     ///
     /// @param: configuration - construction-time engine settings
     /// @return: initialized engine, or an engine error
     pub fn new(configuration: EngineConfig) -> Result<Self, EngineError> {
-        let (position, accumulator) = Self::build_position(PositionBase::StartPos, &[])?;
+        #[cfg(all(feature = "pext", target_arch = "x86_64"))]
+        if PextAttackTable::is_supported() {
+            let session = EngineSession::<PextAttackTable>::new(configuration)?;
+            return Ok(Self {
+                session: Box::new(session),
+            });
+        }
 
+        let session = EngineSession::<DefaultAttackTable>::new(configuration)?;
         Ok(Self {
-            position,
-            move_generator: EngineMoveGenerator::new(),
-            accumulator,
-            transposition_table: EngineTranspositionTable::new(
-                configuration.transposition_table_size_mb,
-            ),
-            search: Negamax::new(),
+            session: Box::new(session),
         })
     }
 
@@ -53,21 +127,156 @@ impl DefaultEngine {
     ///
     /// @return: engine name
     pub fn name(&self) -> &'static str {
-        concat!("chess-kit ", env!("CARGO_PKG_VERSION"))
+        NAME
     }
 
     /// author returns the engine author advertised to presentation adapters
     ///
     /// @return: engine author
     pub fn author(&self) -> &'static str {
-        "chess-kit contributors"
+        AUTHOR
     }
 
     /// legal_moves returns the legal moves available in the current position
     ///
     /// @return: legal move list for the side to move
     pub fn legal_moves(&self) -> Vec<Move> {
-        self.primitive_legal_moves().as_slice().to_vec()
+        self.session.legal_moves()
+    }
+}
+
+impl Engine for DefaultEngine {
+    /// @impl: Engine::name
+    fn name(&self) -> &str {
+        self.name()
+    }
+
+    /// @impl: Engine::author
+    fn author(&self) -> &str {
+        self.author()
+    }
+
+    /// @impl: Engine::new_game
+    fn new_game(&mut self) -> Result<(), EngineError> {
+        self.session.new_game()
+    }
+
+    /// @impl: Engine::set_position
+    fn set_position(&mut self, base: PositionBase, moves: &[Move]) -> Result<(), EngineError> {
+        self.session.set_position(base, moves)
+    }
+
+    /// @impl: Engine::play
+    fn play(&mut self, mv: Move) -> Result<(), EngineError> {
+        self.session.play(mv)
+    }
+
+    /// @impl: Engine::undo
+    fn undo(&mut self) -> Result<Move, EngineError> {
+        self.session.undo()
+    }
+
+    /// @impl: Engine::search
+    fn search(&mut self, depth: SearchDepth) -> Result<SearchOutcome, EngineError> {
+        self.session.search(depth)
+    }
+
+    /// @impl: Engine::search_nodes
+    fn search_nodes(&mut self, nodes: u64) -> Result<SearchOutcome, EngineError> {
+        self.session.search_nodes(nodes)
+    }
+
+    /// @impl: Engine::has_legal_moves
+    fn has_legal_moves(&self) -> bool {
+        self.session.has_legal_moves()
+    }
+
+    /// @impl: Engine::legal_moves
+    fn legal_moves(&self) -> Vec<Move> {
+        self.legal_moves()
+    }
+
+    /// @impl: Engine::in_check
+    fn in_check(&self) -> bool {
+        self.session.in_check()
+    }
+
+    /// @impl: Engine::status
+    fn status(&self) -> GameStatus {
+        self.session.status()
+    }
+
+    /// @impl: Engine::eval
+    fn eval(&self) -> Result<EvalTrace, EngineError> {
+        self.session.eval()
+    }
+
+    /// @impl: Engine::save_hash
+    fn save_hash(&self, path: &Path) -> Result<(), EngineError> {
+        self.session.save_hash(path)
+    }
+
+    /// @impl: Engine::load_hash
+    fn load_hash(&mut self, path: &Path) -> Result<(), EngineError> {
+        self.session.load_hash(path)
+    }
+
+    /// @impl: Engine::options
+    fn options(&self) -> &[EngineOption] {
+        self.session.options()
+    }
+
+    /// @impl: Engine::set_option
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), EngineError> {
+        self.session.set_option(name, value)
+    }
+}
+
+impl PositionProvider for DefaultEngine {
+    /// @impl: PositionProvider::position
+    fn position(&self) -> PositionSnapshot {
+        self.session.position()
+    }
+}
+
+/// `EngineSession` is an engine session whose positions look up attacks in the
+/// given attack table
+///
+/// It owns the live position, evaluation accumulator, transposition table, and
+/// search algorithm
+///
+/// @type
+struct EngineSession<AT: AttackTable> {
+    base: PositionBase, // root position of the current game
+    history: Vec<Move>, // moves played from the root position
+    position: EnginePosition<AT>,
+    move_generator: EngineMoveGenerator<AT>,
+    accumulator: EngineAccumulator,
+    network: Option<Arc<Network>>, // network evaluated with, or None for PSQT
+    transposition_table: EngineTranspositionTable,
+    search: EngineSearch,
+}
+
+impl<AT: AttackTable> EngineSession<AT> {
+    /// new creates a session at the standard starting position
+    ///
+    /// @param: configuration - construction-time engine settings
+    /// @return: initialized session, or an engine error
+    fn new(configuration: EngineConfig) -> Result<Self, EngineError> {
+        let (position, accumulator) = Self::build_position(PositionBase::StartPos, &[], None)?;
+
+        Ok(Self {
+            base: PositionBase::StartPos,
+            history: Vec::new(),
+            position,
+            move_generator: EngineMoveGenerator::new(),
+            accumulator,
+            network: None,
+            transposition_table: EngineTranspositionTable::new(
+                configuration.transposition_table_size_mb,
+            ),
+            search: EngineSearch::with_tablebases(None),
+        })
     }
 
     /// primitive_legal_moves returns the movegen representation used internally
@@ -87,19 +296,23 @@ impl DefaultEngine {
     ///
     /// @param: base - root position before applying moves
     /// @param: moves - ordered moves to apply
+    /// @param: network - network to evaluate with, or None for PSQT
     /// @return: replacement position and accumulator, or an engine error
     fn build_position(
         base: PositionBase,
         moves: &[Move],
-    ) -> Result<(EnginePosition, EngineAccumulator), EngineError> {
+        network: Option<&Arc<Network>>,
+    ) -> Result<(EnginePosition<AT>, EngineAccumulator), EngineError> {
         let fen = match &base {
             PositionBase::StartPos => Fen::default(),
             PositionBase::Fen(fen) => Fen::try_from(fen.as_str())
                 .map_err(|error| EngineError::new(format!("invalid FEN: {error}")))?,
         };
 
-        let mut position = EnginePosition::from(Setup::from(fen));
-        let eval = PSQTEvalState::from_position(&position);
+        let mut position = EnginePosition::try_from(Setup::from(fen))
+            .map_err(|error| EngineError::new(format!("invalid position: {error}")))?;
+        let eval =
+            EndgameEvalState::new(EngineEvalState::with_network(network, &position), &position);
         let mut accumulator = EngineAccumulator::new();
         accumulator.push(eval);
         let move_generator = EngineMoveGenerator::new();
@@ -113,30 +326,114 @@ impl DefaultEngine {
 
         Ok((position, accumulator))
     }
+
+    /// set_eval_file selects the network used for evaluation and re-evaluates
+    /// the current position with it
+    ///
+    /// note: the network belongs to this engine only, so engines of other
+    ///       sessions or threads keep their own evaluation
+    ///
+    /// @param: path - path of the network file, or an empty path to unload the
+    ///                current network
+    /// @return: Ok on success, or an engine error
+    /// @side-effects: replaces the network of the engine and resets the
+    ///                accumulator
+    fn set_eval_file(&mut self, path: &str) -> Result<(), EngineError> {
+        self.network = if path.is_empty() || path == "<empty>" {
+            None
+        } else {
+            let network = Network::load(path)
+                .map_err(|error| EngineError::new(format!("invalid {EVAL_FILE}: {error}")))?;
+            Some(Arc::new(network))
+        };
+
+        // search only pushes and pops states above the current position, so
+        // the states of earlier positions can be discarded
+        self.accumulator.reset();
+        let eval = EngineEvalState::with_network(self.network.as_ref(), &self.position);
+        self.accumulator
+            .push(EndgameEvalState::new(eval, &self.position));
+        Ok(())
+    }
+
+    /// set_syzygy_path selects the tablebases probed during search
+    ///
+    /// @param: path - directories of the table files, separated by `:` (`;` on
+    ///                windows), or an empty path to disable probing
+    /// @return: Ok on success, or an engine error
+    /// @side-effects: replaces the tablebases of the search
+    fn set_syzygy_path(&mut self, path: &str) -> Result<(), EngineError> {
+        let syzygy = if path.is_empty() || path == "<empty>" {
+            None
+        } else {
+            let syzygy = Tablebases::open(path)
+                .map_err(|error| EngineError::new(format!("invalid {SYZYGY_PATH}: {error}")))?;
+            Some(Arc::new(syzygy))
+        };
+        let tables = self
+            .search
+            .tablebases()
+            .and_then(|current| current.tables().cloned());
+        self.search
+            .set_tablebases(EngineTablebases::compose(syzygy, tables));
+        Ok(())
+    }
+
+    /// set_tablebase_path selects the distance-to-mate tables probed during
+    /// search
+    ///
+    /// @param: path - directory of the `.cktb` table files, or an empty path to
+    ///                disable probing them
+    /// @return: Ok on success, or an engine error
+    /// @side-effects: replaces the tablebases of the search
+    fn set_tablebase_path(&mut self, path: &str) -> Result<(), EngineError> {
+        let tables = if path.is_empty() || path == "<empty>" {
+            None
+        } else {
+            let tables = Tablebase::open(path)
+                .map_err(|error| EngineError::new(format!("invalid {TABLEBASE_PATH}: {error}")))?;
+            Some(Arc::new(tables))
+        };
+        let syzygy = self
+            .search
+            .tablebases()
+            .and_then(|current| current.syzygy().cloned());
+        self.search
+            .set_tablebases(EngineTablebases::compose(syzygy, tables));
+        Ok(())
+    }
 }
 
-impl Engine for DefaultEngine {
+impl<AT: AttackTable> Engine for EngineSession<AT> {
     /// @impl: Engine::name
     fn name(&self) -> &str {
-        self.name()
+        NAME
     }
 
     /// @impl: Engine::author
     fn author(&self) -> &str {
-        self.author()
+        AUTHOR
     }
 
     /// @impl: Engine::new_game
     fn new_game(&mut self) -> Result<(), EngineError> {
-        (self.position, self.accumulator) = Self::build_position(PositionBase::StartPos, &[])?;
+        (self.position, self.accumulator) =
+            Self::build_position(PositionBase::StartPos, &[], self.network.as_ref())?;
+        self.base = PositionBase::StartPos;
+        self.history.clear();
         self.transposition_table.clear();
-        self.search = Negamax::new();
+        // tablebases are an option of the engine rather than state of a game
+        let tablebases = self.search.tablebases().cloned();
+        self.search = EngineSearch::with_tablebases(tablebases);
         Ok(())
     }
 
     /// @impl: Engine::set_position
     fn set_position(&mut self, base: PositionBase, moves: &[Move]) -> Result<(), EngineError> {
-        (self.position, self.accumulator) = Self::build_position(base, moves)?;
+        (self.position, self.accumulator) =
+            Self::build_position(base.clone(), moves, self.network.as_ref())?;
+        self.base = base;
+        self.history = moves.to_vec();
         Ok(())
     }
 
@@ -146,9 +443,23 @@ impl Engine for DefaultEngine {
         let eval = self.accumulator.push_next();
         let delta = self.position.play_unchecked(mv);
         eval.apply(delta);
+        self.history.push(mv);
         Ok(())
     }
 
+    /// @impl: Engine::undo
+    fn undo(&mut self) -> Result<Move, EngineError> {
+        let Some((&mv, moves)) = self.history.split_last() else {
+            return Err(EngineError::new("no move to undo"));
+        };
+        // note: replaying the remaining moves rebuilds the accumulator, whose
+        //       earlier states may have been discarded by a network change
+        (self.position, self.accumulator) =
+            Self::build_position(self.base.clone(), moves, self.network.as_ref())?;
+        self.history.pop();
+        Ok(mv)
+    }
+
     /// @impl: Engine::search
     fn search(&mut self, depth: SearchDepth) -> Result<SearchOutcome, EngineError> {
         let started = Instant::now();
@@ -161,16 +472,121 @@ impl Engine for DefaultEngine {
             depth.get(),
         );
 
-        Ok(SearchOutcome::from((result, depth, started.elapsed())))
+        Ok(SearchOutcome::from((
+            result,
+            depth,
+            self.transposition_table.hashfull(),
+            started.elapsed(),
+        )))
+    }
+
+    /// @impl: Engine::search_nodes
+    fn search_nodes(&mut self, nodes: u64) -> Result<SearchOutcome, EngineError> {
+        let started = Instant::now();
+        let (result, depth) = iterative_deepening_nodes(
+            &mut self.search,
+            &mut self.position,
+            &self.move_generator,
+            &mut self.transposition_table,
+            &mut self.accumulator,
+            NODE_SEARCH_MAX_DEPTH,
+            nodes,
+        );
+        let depth = SearchDepth::new(depth).expect("a node-limited search completes an iteration");
+
+        Ok(SearchOutcome::from((
+            result,
+            depth,
+            self.transposition_table.hashfull(),
+            started.elapsed(),
+        )))
     }
 
     /// @impl: Engine::has_legal_moves
     fn has_legal_moves(&self) -> bool {
         !self.primitive_legal_moves().as_slice().is_empty()
     }
+
+    /// @impl: Engine::legal_moves
+    fn legal_moves(&self) -> Vec<Move> {
+        self.primitive_legal_moves().as_slice().to_vec()
+    }
+
+    /// @impl: Engine::in_check
+    fn in_check(&self) -> bool {
+        self.position.checkers().not_empty()
+    }
+
+    /// @impl: Engine::status
+    fn status(&self) -> GameStatus {
+        if !self.has_legal_moves() {
+            return if !self.in_check() {
+                GameStatus::Stalemate
+            } else {
+                GameStatus::Checkmate {
+                    winner: self.position.turn().other(),
+                }
+            };
+        }
+
+        let draw_state = self.position.draw_state();
+        if self.position.halfmoves() >= FIFTY_MOVE_HALFMOVES {
+            GameStatus::Draw(DrawReason::FiftyMoves)
+        } else if draw_state.is_threefold_repetition() {
+            GameStatus::Draw(DrawReason::Repetition)
+        } else if draw_state.is_material_draw() {
+            GameStatus::Draw(DrawReason::InsufficientMaterial)
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+
+    /// @impl: Engine::eval
+    fn eval(&self) -> Result<EvalTrace, EngineError> {
+        // note: the final score matches the one used by the search, which
+        //       refines the evaluation with endgame knowledge
+        Ok(self.accumulator.latest().trace(&self.position))
+    }
+
+    /// @impl: Engine::save_hash
+    fn save_hash(&self, path: &Path) -> Result<(), EngineError> {
+        let save = || -> Result<(), TranspositionError> {
+            let mut writer = BufWriter::new(File::create(path)?);
+            self.transposition_table.save(&mut writer)?;
+            Ok(writer.flush()?)
+        };
+        save().map_err(|error| EngineError::new(format!("unable to save hash: {error}")))
+    }
+
+    /// @impl: Engine::load_hash
+    fn load_hash(&mut self, path: &Path) -> Result<(), EngineError> {
+        let mut load = || -> Result<(), TranspositionError> {
+            let mut reader = BufReader::new(File::open(path)?);
+            self.transposition_table.load(&mut reader)
+        };
+        load().map_err(|error| EngineError::new(format!("unable to load hash: {error}")))
+    }
+
+    /// @impl: Engine::options
+    fn options(&self) -> &[EngineOption] {
+        &OPTIONS
+    }
+
+    /// @impl: Engine::set_option
+    fn set_option(&mut self, name: &str, value: &str) -> Result<(), EngineError> {
+        if name.eq_ignore_ascii_case(EVAL_FILE) {
+            self.set_eval_file(value)
+        } else if name.eq_ignore_ascii_case(SYZYGY_PATH) {
+            self.set_syzygy_path(value)
+        } else if name.eq_ignore_ascii_case(TABLEBASE_PATH) {
+            self.set_tablebase_path(value)
+        } else {
+            Err(EngineError::new(format!("unknown option: {name}")))
+        }
+    }
 }
 
-impl PositionProvider for DefaultEngine {
+impl<AT: AttackTable> PositionProvider for EngineSession<AT> {
     /// @impl: PositionProvider::position
     fn position(&self) -> PositionSnapshot {
         PositionSnapshot::from(&self.position)
@@ -183,9 +599,9 @@ impl PositionProvider for DefaultEngine {
 /// @param: position - position to search from
 /// @param: requested_move - protocol-neutral move requested by a caller
 /// @return: matching legal move, or an engine error
-fn find_legal_move(
-    move_generator: &EngineMoveGenerator,
-    position: &EnginePosition,
+fn find_legal_move<AT: AttackTable>(
+    move_generator: &EngineMoveGenerator<AT>,
+    position: &EnginePosition<AT>,
     requested_move: Move,
 ) -> Result<Move, EngineError> {
     let mut legal_moves = MoveList::new();
@@ -223,15 +639,18 @@ fn requested_move_matches(legal_move: Move, requested_move: Move) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use chess_kit_position::PositionView;
+    use chess_kit_eval::nnue::Network;
     use chess_kit_primitives::{Pieces, Sides, Square};
+    use chess_kit_tablegen::Dtm;
 
     use super::*;
 
+    const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
     const TEST_TRANSPOSITION_TABLE_SIZE_MB: usize = 1;
 
-    fn engine() -> DefaultEngine {
-        DefaultEngine::new(EngineConfig::new(TEST_TRANSPOSITION_TABLE_SIZE_MB)).unwrap()
+    fn engine() -> EngineSession<DefaultAttackTable> {
+        EngineSession::new(EngineConfig::new(TEST_TRANSPOSITION_TABLE_SIZE_MB)).unwrap()
     }
 
     #[test]
@@ -252,6 +671,34 @@ mod tests {
         assert_eq!(engine.position.turn(), Sides::White);
     }
 
+    #[test]
+    fn replays_and_searches_games_of_arbitrary_length() {
+        let cycle = [
+            Move::new(Square::G1, Square::F3),
+            Move::new(Square::G8, Square::F6),
+            Move::new(Square::F3, Square::G1),
+            Move::new(Square::F6, Square::G8),
+        ];
+        let game = cycle.repeat(150);
+        assert_eq!(game.len(), 600);
+
+        let mut engine = engine();
+        engine.set_position(PositionBase::StartPos, &game).unwrap();
+        for mv in cycle {
+            engine.play(mv).unwrap();
+        }
+
+        assert_eq!(engine.position.halfmoves(), 604);
+        assert!(engine.position.draw_state().is_threefold_repetition());
+        assert!(
+            engine
+                .search(SearchDepth::new(2).unwrap())
+                .unwrap()
+                .best_move
+                .is_some()
+        );
+    }
+
     #[test]
     fn search_returns_a_legal_move() {
         let mut engine = engine();
@@ -262,6 +709,18 @@ mod tests {
         assert!(outcome.nodes > 1);
     }
 
+    #[test]
+    fn search_nodes_deepens_until_the_budget_is_spent() {
+        let mut engine = engine();
+        let shallow = engine.search_nodes(1).unwrap();
+        assert!(shallow.best_move.is_some());
+        assert_eq!(shallow.depth.get(), 1);
+
+        let deeper = engine.search_nodes(5_000).unwrap();
+        assert!(deeper.depth > shallow.depth);
+        assert!(deeper.nodes >= 5_000);
+    }
+
     #[test]
     fn play_rejects_illegal_moves_without_changing_turn() {
         let mut engine = engine();
@@ -288,6 +747,24 @@ mod tests {
         assert_eq!(engine.position.piece_at(Square::F1), Pieces::Rook);
     }
 
+    #[test]
+    fn set_position_rejects_illegal_setups_without_changing_position() {
+        let mut engine = engine();
+        engine
+            .set_position(PositionBase::StartPos, &[Move::new(Square::E2, Square::E4)])
+            .unwrap();
+
+        let error = engine
+            .set_position(
+                PositionBase::Fen("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1".to_owned()),
+                &[],
+            )
+            .unwrap_err();
+
+        assert!(error.to_string().contains("invalid position"));
+        assert_eq!(engine.position.piece_at(Square::E4), Pieces::Pawn);
+    }
+
     #[test]
     fn search_depth_rejects_non_positive_values() {
         assert!(SearchDepth::new(0).is_err());
@@ -300,6 +777,246 @@ mod tests {
         assert!(engine.has_legal_moves());
     }
 
+    #[test]
+    fn undo_takes_back_played_and_set_moves() {
+        let mut engine = engine();
+        engine
+            .set_position(PositionBase::StartPos, &[Move::new(Square::E2, Square::E4)])
+            .unwrap();
+        engine.play(Move::new(Square::E7, Square::E5)).unwrap();
+
+        assert_eq!(engine.undo().unwrap(), Move::new(Square::E7, Square::E5));
+        assert_eq!(engine.position.piece_at(Square::E7), Pieces::Pawn);
+        assert_eq!(engine.undo().unwrap(), Move::new(Square::E2, Square::E4));
+        assert_eq!(Fen::from(&engine.position).to_string(), START_POSITION);
+        assert!(engine.undo().is_err());
+    }
+
+    #[test]
+    fn status_reports_mates_stalemates_and_draws() {
+        let status = |fen: &str, moves: &[Move]| {
+            let mut engine = engine();
+            engine
+                .set_position(PositionBase::Fen(fen.to_owned()), moves)
+                .unwrap();
+            engine.status()
+        };
+
+        assert_eq!(status(START_POSITION, &[]), GameStatus::Ongoing);
+        assert_eq!(
+            status("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1", &[]),
+            GameStatus::Checkmate {
+                winner: Sides::White
+            }
+        );
+        assert_eq!(
+            status("k7/8/1QK5/8/8/8/8/8 b - - 0 1", &[]),
+            GameStatus::Stalemate
+        );
+        assert_eq!(
+            status("k7/8/1K6/8/8/8/8/7R w - - 100 80", &[]),
+            GameStatus::Draw(DrawReason::FiftyMoves)
+        );
+        assert_eq!(
+            status("k7/8/1K6/8/8/8/8/7N w - - 0 1", &[]),
+            GameStatus::Draw(DrawReason::InsufficientMaterial)
+        );
+        let shuffle = [
+            Move::new(Square::G1, Square::F3),
+            Move::new(Square::G8, Square::F6),
+            Move::new(Square::F3, Square::G1),
+            Move::new(Square::F6, Square::G8),
+        ];
+        assert_eq!(
+            status(START_POSITION, &[shuffle, shuffle].concat()),
+            GameStatus::Draw(DrawReason::Repetition)
+        );
+        // a checkmate delivered on the hundredth halfmove decides the game
+        assert_eq!(
+            status(
+                "k7/7Q/1K6/8/8/8/8/8 w - - 99 80",
+                &[Move::new(Square::H7, Square::B7)]
+            ),
+            GameStatus::Checkmate {
+                winner: Sides::White
+            }
+        );
+    }
+
+    #[test]
+    fn eval_file_selects_and_unloads_the_evaluation_network() {
+        // a network with no weights scores every position as its output bias
+        let mut network = vec![0; Network::FILE_SIZE];
+        network[Network::FILE_SIZE - 2..].copy_from_slice(&16320_i16.to_le_bytes());
+        let path = std::env::temp_dir().join(format!("chess-kit-{}.nnue", std::process::id()));
+        std::fs::write(&path, network).unwrap();
+
+        let mut engine = engine();
+        assert_eq!(engine.options()[0].name, "EvalFile");
+
+        engine
+            .set_option("evalfile", path.to_str().unwrap())
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            engine.accumulator.latest().inner(),
+            EngineEvalState::Nnue(_)
+        ));
+        assert_eq!(engine.accumulator.latest_mut().score(), 400);
+        assert_eq!(engine.eval().unwrap(), EvalTrace::new(400));
+
+        // the network belongs to the engine that loaded it
+        let other = self::engine();
+        assert!(matches!(
+            other.accumulator.latest().inner(),
+            EngineEvalState::Psqt(_)
+        ));
+
+        engine.set_option("EvalFile", "").unwrap();
+        assert!(matches!(
+            engine.accumulator.latest().inner(),
+            EngineEvalState::Psqt(_)
+        ));
+        let trace = engine.eval().unwrap();
+        assert!(trace.term("Pawns").is_some());
+        assert_eq!(trace.score, engine.accumulator.latest_mut().score());
+    }
+
+    #[test]
+    fn set_option_rejects_unknown_options_and_unreadable_networks() {
+        let mut engine = engine();
+
+        assert!(engine.set_option("Hash", "16").is_err());
+        assert!(
+            engine
+                .set_option("EvalFile", "/nonexistent/chess-kit.nnue")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn syzygy_path_selects_and_disables_the_tablebases() {
+        let mut engine = engine();
+        assert_eq!(engine.options()[1].name, "SyzygyPath");
+
+        assert!(
+            engine
+                .set_option("SyzygyPath", "/nonexistent/syzygy")
+                .is_err()
+        );
+        assert!(engine.search.tablebases().is_none());
+
+        let directory = std::env::temp_dir();
+        engine
+            .set_option("syzygypath", directory.to_str().unwrap())
+            .unwrap();
+        assert!(engine.search.tablebases().is_some());
+
+        engine.new_game().unwrap();
+        assert!(engine.search.tablebases().is_some());
+
+        engine.set_option("SyzygyPath", "<empty>").unwrap();
+        assert!(engine.search.tablebases().is_none());
+    }
+
+    #[test]
+    fn tablebase_path_probes_generated_tables_alongside_syzygy() {
+        let mut engine = engine();
+        assert_eq!(engine.options()[2].name, "TablebasePath");
+        assert!(
+            engine
+                .set_option("TablebasePath", "/nonexistent/tables")
+                .is_err()
+        );
+
+        let directory =
+            std::env::temp_dir().join(format!("chess-kit-engine-tables-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut tables = Tablebase::default();
+        chess_kit_tablegen::generate(&mut tables, "KQvK".try_into().unwrap()).unwrap();
+        tables.save(&directory).unwrap();
+        engine
+            .set_option("tablebasepath", directory.to_str().unwrap())
+            .unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+
+        // the syzygy tables are selected and cleared without the other tables
+        let syzygy = std::env::temp_dir();
+        engine
+            .set_option("SyzygyPath", syzygy.to_str().unwrap())
+            .unwrap();
+        engine.set_option("SyzygyPath", "").unwrap();
+        let tablebases = engine.search.tablebases().unwrap();
+        assert!(tablebases.syzygy().is_none() && tablebases.tables().is_some());
+
+        // only the moves that mate the fastest are searched at the root
+        let fen = "k7/8/1K6/8/8/8/8/6Q1 w - - 0 1";
+        engine
+            .set_position(PositionBase::Fen(fen.to_string()), &[])
+            .unwrap();
+        let Some(Dtm::Win(plies)) = tables.probe(&engine.position) else {
+            panic!("position should be won");
+        };
+        let outcome = engine.search(SearchDepth::new(1).unwrap()).unwrap();
+        engine
+            .set_position(
+                PositionBase::Fen(fen.to_string()),
+                &[outcome.best_move.unwrap()],
+            )
+            .unwrap();
+        assert_eq!(tables.probe(&engine.position), Some(Dtm::Loss(plies - 1)));
+
+        engine.set_option("TablebasePath", "<empty>").unwrap();
+        assert!(engine.search.tablebases().is_none());
+    }
+
+    #[test]
+    fn saved_hash_resumes_in_another_session() {
+        let path = std::env::temp_dir().join(format!("chess-kit-{}.hash", std::process::id()));
+        let mut saved = engine();
+        saved.search(SearchDepth::new(3).unwrap()).unwrap();
+        saved.save_hash(&path).unwrap();
+
+        let mut resumed = engine();
+        resumed.load_hash(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let key = saved.position.key();
+        assert_eq!(
+            resumed.transposition_table.probe(key),
+            saved.transposition_table.probe(key)
+        );
+        assert!(resumed.transposition_table.probe(key).is_some());
+
+        assert!(
+            resumed
+                .load_hash(Path::new("/nonexistent/chess-kit.hash"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn default_engine_plays_on_the_attack_table_of_the_cpu() {
+        let mut engine =
+            DefaultEngine::new(EngineConfig::new(TEST_TRANSPOSITION_TABLE_SIZE_MB)).unwrap();
+        engine
+            .set_position(PositionBase::StartPos, &[Move::new(Square::E2, Square::E4)])
+            .unwrap();
+
+        assert_eq!(engine.legal_moves().len(), 20);
+        assert_eq!(engine.status(), GameStatus::Ongoing);
+        assert!(
+            engine
+                .search(SearchDepth::new(2).unwrap())
+                .unwrap()
+                .best_move
+                .is_some()
+        );
+        assert_eq!(
+            PositionProvider::position(&engine).piece_at(Square::E4),
+            Some((Sides::White, Pieces::Pawn))
+        );
+    }
+
     #[test]
     fn position_exposes_a_protocol_neutral_snapshot() {
         let engine = engine();
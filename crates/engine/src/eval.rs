@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use chess_kit_collections::Copyable;
+use chess_kit_eval::nnue::Network;
+use chess_kit_eval::{
+    EvalState, EvalTrace, NnueEvalState, PSQTEvalState, Score, StandaloneEvalState,
+};
+use chess_kit_position::PositionView;
+use chess_kit_primitives::MoveDelta;
+
+/// `EngineEvalState` is the evaluation state used by the engine, which
+/// evaluates with the engine's network when one is loaded and falls back to the
+/// PSQT evaluation otherwise
+///
+/// note: the states are copied in place on every move, so the network variant
+///       is kept inline rather than boxed despite being much larger
+///
+/// @type
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub(crate) enum EngineEvalState {
+    Psqt(PSQTEvalState),
+    Nnue(NnueEvalState),
+}
+
+impl EngineEvalState {
+    /// with_network initializes the evaluation state of a position, evaluating
+    /// with a network if one is given
+    ///
+    /// @param: network - network of the engine, if one is loaded
+    /// @param: position - position to initialize the state from
+    /// @return: evaluation state of the position
+    pub(crate) fn with_network<P: PositionView>(
+        network: Option<&Arc<Network>>,
+        position: &P,
+    ) -> Self {
+        match network {
+            Some(network) => Self::Nnue(NnueEvalState::with_network(network.clone(), position)),
+            None => Self::Psqt(PSQTEvalState::from_position(position)),
+        }
+    }
+}
+
+impl EvalState for EngineEvalState {
+    /// apply updates the wrapped evaluation state with the piece changes of
+    /// one move
+    ///
+    /// @impl: EvalState::apply
+    #[inline]
+    fn apply(&mut self, delta: MoveDelta) {
+        match self {
+            Self::Psqt(state) => state.apply(delta),
+            Self::Nnue(state) => state.apply(delta),
+        }
+    }
+
+    /// score returns the evaluation score of the wrapped evaluation state
+    ///
+    /// @impl: EvalState::score
+    #[inline]
+    fn score(&mut self) -> Score {
+        match self {
+            Self::Psqt(state) => state.score(),
+            Self::Nnue(state) => state.score(),
+        }
+    }
+
+    /// trace breaks the score of the wrapped evaluation state down into its
+    /// terms
+    ///
+    /// @impl: EvalState::trace
+    fn trace<P: PositionView>(&self, position: &P) -> EvalTrace {
+        match self {
+            Self::Psqt(state) => state.trace(position),
+            Self::Nnue(state) => state.trace(position),
+        }
+    }
+}
+
+impl Copyable for EngineEvalState {
+    /// copy_from copies the contents of another EngineEvalState into this one
+    ///
+    /// @impl: Copyable::copy_from
+    #[inline]
+    fn copy_from(&mut self, other: &Self) {
+        match (self, other) {
+            (Self::Psqt(state), Self::Psqt(other)) => state.copy_from(other),
+            (Self::Nnue(state), Self::Nnue(other)) => state.copy_from(other),
+            (state, other) => state.clone_from(other),
+        }
+    }
+}
+
+impl Default for EngineEvalState {
+    #[inline]
+    fn default() -> Self {
+        Self::Psqt(PSQTEvalState::default())
+    }
+}
@@ -6,14 +6,21 @@
 
 mod engine;
 mod error;
+mod eval;
+mod tablebases;
 mod types;
 
+pub use chess_kit_eval::{EvalTerm, EvalTrace};
 pub use chess_kit_position::PositionSnapshot;
 pub use engine::DefaultEngine;
 pub use error::EngineError;
-pub use types::{EngineConfig, PositionBase, SearchOutcome};
+pub use types::{
+    DrawReason, EngineConfig, EngineOption, EngineOptionKind, GameStatus, PositionBase,
+    SearchOutcome,
+};
 
 use chess_kit_primitives::{Move, SearchDepth};
+use std::path::Path;
 
 /// `Engine` is the protocol-agnostic session surface used by presentation
 /// adapters
@@ -54,6 +61,14 @@ pub trait Engine {
     /// @side-effects: updates the current position when the move is legal
     fn play(&mut self, mv: Move) -> Result<(), EngineError>;
 
+    /// undo takes back the last move played since the position was set
+    ///
+    /// @return: move taken back, or the engine error
+    /// @side-effects: restores the position before the move on success
+    fn undo(&mut self) -> Result<Move, EngineError> {
+        Err(EngineError::new("undoing moves is not supported"))
+    }
+
     /// search searches the current position to the requested depth
     ///
     /// @param: depth - maximum search depth in plies
@@ -61,10 +76,85 @@ pub trait Engine {
     /// @side-effects: may modify engine search state
     fn search(&mut self, depth: SearchDepth) -> Result<SearchOutcome, EngineError>;
 
+    /// search_nodes searches the current position ever deeper until a node
+    /// budget is spent
+    ///
+    /// @param: nodes - number of nodes after which no deeper search starts
+    /// @return: completed search outcome, or the engine error
+    /// @side-effects: may modify engine search state
+    fn search_nodes(&mut self, _nodes: u64) -> Result<SearchOutcome, EngineError> {
+        Err(EngineError::new("node-limited search is not supported"))
+    }
+
     /// has_legal_moves reports whether the side to move has any legal reply
     ///
     /// @return: true when at least one legal move exists
     fn has_legal_moves(&self) -> bool;
+
+    /// legal_moves returns the legal moves of the side to move
+    ///
+    /// @return: legal moves in the current position
+    fn legal_moves(&self) -> Vec<Move>;
+
+    /// in_check reports whether the side to move is in check
+    ///
+    /// @return: true when the king of the side to move is attacked
+    fn in_check(&self) -> bool;
+
+    /// status reports whether the game at the current position is ongoing,
+    /// decided or drawn
+    ///
+    /// note: checkmate and stalemate take precedence over the draw rules
+    ///
+    /// @return: status of the game at the current position
+    fn status(&self) -> GameStatus;
+
+    /// eval breaks the static evaluation of the current position down into its
+    /// terms
+    ///
+    /// @return: evaluation breakdown from white's perspective, or the engine
+    ///          error
+    fn eval(&self) -> Result<EvalTrace, EngineError> {
+        Err(EngineError::new("evaluation tracing is not supported"))
+    }
+
+    /// save_hash saves the transposition table to a file, so that a later
+    /// session can resume from it
+    ///
+    /// @param: path - path of the file to write
+    /// @return: Ok on success, or the engine error
+    fn save_hash(&self, _path: &Path) -> Result<(), EngineError> {
+        Err(EngineError::new("saving the hash is not supported"))
+    }
+
+    /// load_hash replaces the transposition table with one saved by save_hash
+    ///
+    /// note: files saved with another hash size or by an incompatible build
+    ///       are rejected
+    ///
+    /// @param: path - path of the file to read
+    /// @return: Ok on success, or the engine error
+    /// @side-effects: replaces the transposition table on success
+    fn load_hash(&mut self, _path: &Path) -> Result<(), EngineError> {
+        Err(EngineError::new("loading the hash is not supported"))
+    }
+
+    /// options returns the runtime options accepted by set_option
+    ///
+    /// @return: options supported by the engine
+    fn options(&self) -> &[EngineOption] {
+        &[]
+    }
+
+    /// set_option updates a runtime option of the engine
+    ///
+    /// @param: name - case-insensitive name of the option
+    /// @param: value - new value of the option
+    /// @return: Ok on success, or the engine error
+    /// @side-effects: modifies the engine configuration on success
+    fn set_option(&mut self, name: &str, _value: &str) -> Result<(), EngineError> {
+        Err(EngineError::new(format!("unknown option: {name}")))
+    }
 }
 
 /// `PositionProvider` exposes an owned view of an engine's current position
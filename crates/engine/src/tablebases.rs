@@ -0,0 +1,120 @@
+use std::sync::Arc;
+
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
+use chess_kit_primitives::Move;
+use chess_kit_syzygy::Tablebases;
+use chess_kit_tablebase::{EndgameTablebase, Wdl};
+use chess_kit_tablegen::Tablebase;
+
+/// `EngineTablebases` is the set of endgame tablebases probed by the engine's
+/// search, which probes the distance-to-mate tables generated by chess-kit
+/// first and falls back to the Syzygy tables
+///
+/// note: the distance-to-mate tables know the fastest mate of a position,
+///       which the Syzygy tables cannot tell apart from slower ones
+///
+/// @type
+pub(crate) struct EngineTablebases {
+    syzygy: Option<Arc<Tablebases>>, // tables selected with the SyzygyPath option
+    tables: Option<Arc<Tablebase>>,  // tables selected with the TablebasePath option
+}
+
+impl EngineTablebases {
+    /// compose combines the tablebases selected by the engine's options
+    ///
+    /// @param: syzygy - Syzygy tables, if any are selected
+    /// @param: tables - distance-to-mate tables, if any are selected
+    /// @return: combined tablebases, or None to disable probing when no
+    ///          tables are selected
+    pub(crate) fn compose(
+        syzygy: Option<Arc<Tablebases>>,
+        tables: Option<Arc<Tablebase>>,
+    ) -> Option<Arc<Self>> {
+        (syzygy.is_some() || tables.is_some()).then(|| Arc::new(Self { syzygy, tables }))
+    }
+
+    /// syzygy returns the Syzygy tables
+    ///
+    /// @return: Syzygy tables, if any are selected
+    #[inline]
+    pub(crate) fn syzygy(&self) -> Option<&Arc<Tablebases>> {
+        self.syzygy.as_ref()
+    }
+
+    /// tables returns the distance-to-mate tables
+    ///
+    /// @return: distance-to-mate tables, if any are selected
+    #[inline]
+    pub(crate) fn tables(&self) -> Option<&Arc<Tablebase>> {
+        self.tables.as_ref()
+    }
+}
+
+impl EndgameTablebase for EngineTablebases {
+    /// covers checks whether either set of tables may probe a position
+    ///
+    /// @impl: EndgameTablebase::covers
+    #[inline]
+    fn covers<PositionT: PositionView>(&self, position: &PositionT) -> bool {
+        self.tables
+            .as_ref()
+            .is_some_and(|tables| tables.covers(position))
+            || self
+                .syzygy
+                .as_ref()
+                .is_some_and(|syzygy| syzygy.covers(position))
+    }
+
+    /// probe_wdl probes the outcome of a position in the first set of tables
+    /// covering it
+    ///
+    /// @impl: EndgameTablebase::probe_wdl
+    fn probe_wdl<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Wdl>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        if let Some(tables) = &self.tables
+            && tables.covers(position)
+            && let Some(wdl) = tables.probe_wdl(position, move_generator)
+        {
+            return Some(wdl);
+        }
+        match &self.syzygy {
+            Some(syzygy) if syzygy.covers(position) => syzygy.probe_wdl(position, move_generator),
+            _ => None,
+        }
+    }
+
+    /// best_root_moves returns the best moves of a position according to the
+    /// first set of tables covering it
+    ///
+    /// @impl: EndgameTablebase::best_root_moves
+    fn best_root_moves<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Vec<Move>>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        if let Some(tables) = &self.tables
+            && tables.covers(position)
+            && let Some(moves) = tables.best_root_moves(position, move_generator)
+        {
+            return Some(moves);
+        }
+        match &self.syzygy {
+            Some(syzygy) if syzygy.covers(position) => {
+                syzygy.best_root_moves(position, move_generator)
+            }
+            _ => None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use std::fmt::{self, Display};
 use std::time::Duration;
 
-use chess_kit_primitives::{Move, SearchDepth};
+use chess_kit_primitives::{Move, SearchDepth, Sides};
 use chess_kit_search::SearchResult;
 
 /// `EngineConfig` contains construction-time settings for a composed engine session
@@ -8,7 +9,8 @@ use chess_kit_search::SearchResult;
 /// @type
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct EngineConfig {
-    pub transposition_table_size_mb: usize, // transposition table allocation in megabytes
+    /// transposition table allocation in megabytes
+    pub transposition_table_size_mb: usize,
 }
 
 impl EngineConfig {
@@ -23,6 +25,26 @@ impl EngineConfig {
     }
 }
 
+/// `EngineOption` describes a runtime option that an engine accepts through
+/// `Engine::set_option`
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EngineOption {
+    /// case-insensitive option name
+    pub name: &'static str,
+    /// value type and default value of the option
+    pub kind: EngineOptionKind,
+}
+
+/// `EngineOptionKind` is the value type and default value of an engine option
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EngineOptionKind {
+    String { default: &'static str },
+}
+
 /// `PositionBase` is the root position from which a move history is applied
 ///
 /// @type
@@ -37,22 +59,97 @@ pub enum PositionBase {
 /// @type
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SearchOutcome {
-    pub best_move: Option<Move>, // best move found, or None when no legal move exists
-    pub depth: SearchDepth,      // completed positive search depth in plies
-    pub score: i32,              // position score in centipawns
-    pub nodes: u64,              // number of nodes searched
-    pub elapsed: Duration,       // elapsed search time
+    /// best move found, or None when no legal move exists
+    pub best_move: Option<Move>,
+    /// completed positive search depth in plies
+    pub depth: SearchDepth,
+    /// position score in centipawns
+    pub score: i32,
+    /// number of nodes searched
+    pub nodes: u64,
+    /// permille of the transposition table written by the search
+    pub hashfull: u16,
+    /// elapsed search time
+    pub elapsed: Duration,
 }
 
-impl From<(SearchResult, SearchDepth, Duration)> for SearchOutcome {
+impl From<(SearchResult, SearchDepth, u16, Duration)> for SearchOutcome {
     /// from enriches an internal search result with engine-boundary metadata.
-    fn from((result, depth, elapsed): (SearchResult, SearchDepth, Duration)) -> Self {
+    fn from(
+        (result, depth, hashfull, elapsed): (SearchResult, SearchDepth, u16, Duration),
+    ) -> Self {
         Self {
             best_move: result.best_move,
             depth,
             score: result.score,
             nodes: result.nodes,
+            hashfull,
             elapsed,
         }
     }
 }
+
+/// `GameStatus` is the state of the game at the current position
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+    Ongoing,                     // the side to move has a legal move and no draw applies
+    Checkmate { winner: Sides }, // the side to move is checkmated
+    Stalemate,                   // the side to move has no legal move but is not in check
+    Draw(DrawReason),            // the game is drawn by rule
+}
+
+impl GameStatus {
+    /// is_over reports whether the game has ended
+    ///
+    /// @return: true unless the game is ongoing
+    pub const fn is_over(self) -> bool {
+        !matches!(self, Self::Ongoing)
+    }
+}
+
+impl Display for GameStatus {
+    /// fmt writes a human-readable description of the game status
+    ///
+    /// @param: f - formatter to write the status into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Ongoing => write!(f, "ongoing"),
+            Self::Checkmate {
+                winner: Sides::White,
+            } => write!(f, "checkmate, White wins"),
+            Self::Checkmate {
+                winner: Sides::Black,
+            } => write!(f, "checkmate, Black wins"),
+            Self::Stalemate => write!(f, "stalemate"),
+            Self::Draw(reason) => write!(f, "draw by {reason}"),
+        }
+    }
+}
+
+/// `DrawReason` is the rule by which a game is drawn
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DrawReason {
+    FiftyMoves,           // 100 halfmoves passed without a capture or pawn move
+    Repetition,           // the position occurred for the third time
+    InsufficientMaterial, // neither side has the material to checkmate
+}
+
+impl Display for DrawReason {
+    /// fmt writes a human-readable description of the draw rule
+    ///
+    /// @param: f - formatter to write the rule into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Self::FiftyMoves => "the fifty-move rule",
+            Self::Repetition => "threefold repetition",
+            Self::InsufficientMaterial => "insufficient material",
+        };
+        write!(f, "{reason}")
+    }
+}
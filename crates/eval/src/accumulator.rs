@@ -1,12 +1,16 @@
-use chess_kit_collections::Stack;
+use chess_kit_collections::GrowableStack;
 
 use crate::{Accumulator, EvalState};
 
 /// DefaultAccumulator is the default implementation of the Accumulator trait
 ///
+/// note: the states are kept in a growable stack, since the accumulator holds
+///       a state for every move of the game on top of the states pushed by
+///       the search
+///
 /// @type
 pub struct DefaultAccumulator<EvalStateT: EvalState> {
-    stack: Stack<EvalStateT>,
+    stack: GrowableStack<EvalStateT>,
 }
 
 impl<EvalStateT: EvalState> Accumulator<EvalStateT> for DefaultAccumulator<EvalStateT> {
@@ -16,7 +20,7 @@ impl<EvalStateT: EvalState> Accumulator<EvalStateT> for DefaultAccumulator<EvalS
     #[inline]
     fn new() -> Self {
         Self {
-            stack: Stack::new(),
+            stack: GrowableStack::new(),
         }
     }
 
@@ -0,0 +1,157 @@
+use super::{EndgameView, KNOWN_WIN, king_distance, kpk};
+use crate::Score;
+use chess_kit_primitives::{Pieces, Rank, Sides, Square};
+
+/// PAWN_VALUE is the endgame value of a pawn
+const PAWN_VALUE: Score = 100;
+
+/// ROOK_VALUE is the endgame value of a rook
+const ROOK_VALUE: Score = 500;
+
+/// kpk evaluates king and pawn versus king with the KPK bitbase
+///
+/// @param: view - piece placement of the position
+/// @param: strong - side with the pawn
+/// @return: evaluation from the strong side's perspective
+pub(super) fn kpk(view: &EndgameView, strong: Sides) -> Score {
+    let weak = strong.other();
+    let strong_king = view.square(strong, Pieces::King, strong);
+    let pawn = view.square(strong, Pieces::Pawn, strong);
+    let weak_king = view.square(weak, Pieces::King, strong);
+
+    if !kpk::probe(strong_king, pawn, weak_king, view.turn() == strong) {
+        return 0;
+    }
+
+    // prefer advancing the pawn once the win is certain
+    KNOWN_WIN + PAWN_VALUE + pawn.rank().idx() as Score * 10
+}
+
+/// kbnk evaluates king, bishop and knight versus king, which is won by driving
+/// the defending king into a corner of the bishop's color
+///
+/// @param: view - piece placement of the position
+/// @param: strong - side with the bishop and knight
+/// @return: evaluation from the strong side's perspective
+pub(super) fn kbnk(view: &EndgameView, strong: Sides) -> Score {
+    let weak = strong.other();
+    let strong_king = view.square(strong, Pieces::King, Sides::White);
+    let weak_king = view.square(weak, Pieces::King, Sides::White);
+    let bishop = view.square(strong, Pieces::Bishop, Sides::White);
+
+    // only the corners of the bishop's color can be mated in
+    let corners = if bishop.is_white() {
+        [Square::A8, Square::H1]
+    } else {
+        [Square::A1, Square::H8]
+    };
+    let corner_distance = corners
+        .iter()
+        .map(|corner| {
+            corner.file().idx().abs_diff(weak_king.file().idx())
+                + corner.rank().idx().abs_diff(weak_king.rank().idx())
+        })
+        .min()
+        .unwrap_or_default() as Score;
+    let king_distance = king_distance(strong_king, weak_king) as Score;
+
+    KNOWN_WIN + 20 * (14 - corner_distance) + 10 * (7 - king_distance)
+}
+
+/// krkp evaluates king and rook versus king and pawn, which is usually won
+/// unless the pawn is far advanced and supported by its king
+///
+/// @param: view - piece placement of the position
+/// @param: strong - side with the rook
+/// @return: evaluation from the strong side's perspective
+pub(super) fn krkp(view: &EndgameView, strong: Sides) -> Score {
+    let weak = strong.other();
+    let strong_king = view.square(strong, Pieces::King, strong);
+    let weak_king = view.square(weak, Pieces::King, strong);
+    let rook = view.square(strong, Pieces::Rook, strong);
+    let pawn = view.square(weak, Pieces::Pawn, strong);
+
+    // the pawn advances towards the strong side's first rank
+    let queening = Square::new(pawn.file(), Rank::R1);
+    let stop = Square::from_idx(pawn.idx() - 8);
+    let strong_to_move = u8::from(view.turn() == strong);
+    let weak_to_move = u8::from(view.turn() == weak);
+
+    let pawn_distance = king_distance(strong_king, pawn) as Score;
+    if strong_king.file() == pawn.file() && strong_king.rank() < pawn.rank() {
+        // the strong king blocks the pawn
+        ROOK_VALUE - pawn_distance
+    } else if king_distance(weak_king, pawn) >= 3 + weak_to_move
+        && king_distance(weak_king, rook) >= 3
+    {
+        // the pawn is too far from its king to be defended
+        ROOK_VALUE - pawn_distance
+    } else if weak_king.rank() <= Rank::R3
+        && king_distance(weak_king, pawn) == 1
+        && strong_king.rank() >= Rank::R4
+        && king_distance(strong_king, pawn) > 2 + strong_to_move
+    {
+        // the pawn is far advanced and supported while the strong king is too
+        // far away to help, which is often a draw
+        80 - 8 * pawn_distance
+    } else {
+        200 - 8
+            * (king_distance(strong_king, stop) as Score
+                - king_distance(weak_king, stop) as Score
+                - king_distance(pawn, queening) as Score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endgame::{KNOWN_WIN, evaluate};
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, Setup};
+
+    fn evaluate_fen(fen: &str) -> i32 {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        evaluate(&position, 0)
+    }
+
+    #[test]
+    fn evaluates_kpk_with_the_bitbase() {
+        // the defending king is outside the square of the pawn
+        assert!(evaluate_fen("7k/8/P7/8/8/8/8/7K w - - 0 1") > KNOWN_WIN);
+        // rook pawns are drawn once the defending king reaches the corner
+        assert_eq!(evaluate_fen("k7/8/8/8/P7/8/8/7K w - - 0 1"), 0);
+        // the defending king captures the undefended pawn
+        assert_eq!(evaluate_fen("8/8/8/4k3/4P3/8/8/3K4 b - - 0 1"), 0);
+        // a king on the sixth rank in front of its pawn wins either way
+        assert!(evaluate_fen("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1") > KNOWN_WIN);
+        assert!(evaluate_fen("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1") > KNOWN_WIN);
+        // the same holds for black, whose wins are negative
+        assert!(evaluate_fen("8/8/8/8/4p3/4k3/8/4K3 w - - 0 1") < -KNOWN_WIN);
+        assert_eq!(evaluate_fen("8/8/8/8/8/8/p7/K6k w - - 0 1"), 0);
+    }
+
+    #[test]
+    fn drives_the_defending_king_to_the_bishop_corner_in_kbnk() {
+        // a light-squared bishop mates on a8 and h1, never on a1 or h8
+        let right_corner = evaluate_fen("k7/8/1K6/8/8/8/8/5BN1 b - - 0 1");
+        let wrong_corner = evaluate_fen("8/8/8/8/8/1K6/8/k4BN1 b - - 0 1");
+        let center = evaluate_fen("8/8/8/3k4/8/1K6/8/5BN1 b - - 0 1");
+
+        assert!(right_corner > center);
+        assert!(center >= wrong_corner);
+        assert!(wrong_corner > KNOWN_WIN);
+    }
+
+    #[test]
+    fn distinguishes_won_and_drawish_krkp_positions() {
+        // the rook side's king blocks the pawn
+        let blocked = evaluate_fen("8/8/8/8/3k4/8/3p4/3KR3 b - - 0 1");
+        // the pawn is about to promote with its king next to it
+        let advanced = evaluate_fen("K7/8/8/8/8/8/2kp4/4R3 w - - 0 1");
+
+        assert!(blocked > 400);
+        assert!(advanced < 100);
+    }
+}
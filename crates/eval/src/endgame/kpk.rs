@@ -0,0 +1,455 @@
+use super::{king_distance, mirror_file};
+use chess_kit_primitives::{File, Rank, Sides, Square};
+use std::sync::LazyLock;
+
+/// POSITIONS is the number of positions indexed by the bitbase
+///
+/// note: the pawn is restricted to files A-D and ranks 2-7, since every other
+///       position is a mirror image of one of these
+const POSITIONS: usize = Sides::TOTAL * Square::TOTAL * Square::TOTAL * 4 * 6;
+
+/// BITBASE is the KPK bitbase, with one bit per position that is set when the
+/// side with the pawn wins
+///
+/// note: the bitbase is generated by retrograde analysis the first time it is
+///       probed, which takes a few milliseconds
+static BITBASE: LazyLock<Box<[u64]>> = LazyLock::new(generate);
+
+/// Classification flags of a position during the retrograde analysis
+///
+/// note: the flags are bits so that the classifications of all successors of a
+///       position can be combined with a bitwise or
+const INVALID: u8 = 0;
+const UNKNOWN: u8 = 1;
+const DRAW: u8 = 2;
+const WIN: u8 = 4;
+
+/// probe checks whether a king and pawn versus king position is a win for the
+/// side with the pawn
+///
+/// note: squares are given from the perspective of the side with the pawn, so
+///       the pawn is always pushed towards the eighth rank
+///
+/// @param: strong_king - square of the king of the side with the pawn
+/// @param: pawn - square of the pawn
+/// @param: weak_king - square of the defending king
+/// @param: strong_to_move - whether the side with the pawn is to move
+/// @return: true if the side with the pawn wins, false if the position is a
+///          draw
+pub fn probe(strong_king: Square, pawn: Square, weak_king: Square, strong_to_move: bool) -> bool {
+    // mirror the position so that the pawn is on files A-D
+    let (strong_king, pawn, weak_king) = if pawn.file().idx() > File::D.idx() {
+        (
+            mirror_file(strong_king),
+            mirror_file(pawn),
+            mirror_file(weak_king),
+        )
+    } else {
+        (strong_king, pawn, weak_king)
+    };
+
+    let turn = if strong_to_move {
+        Sides::White
+    } else {
+        Sides::Black
+    };
+    let idx = index(turn, weak_king, strong_king, pawn);
+    BITBASE[idx / 64] & (1 << (idx % 64)) != 0
+}
+
+/// index returns the bitbase index of a position
+///
+/// @param: turn - side to move, where white is the side with the pawn
+/// @param: black_king - square of the defending king
+/// @param: white_king - square of the king of the side with the pawn
+/// @param: pawn - square of the pawn, on files A-D and ranks 2-7
+/// @return: index of the position
+#[inline]
+fn index(turn: Sides, black_king: Square, white_king: Square, pawn: Square) -> usize {
+    turn.idx()
+        | (black_king.idx() << 1)
+        | (white_king.idx() << 7)
+        | (pawn.file().idx() << 13)
+        | ((Rank::R7.idx() - pawn.rank().idx()) << 15)
+}
+
+/// `KpkPosition` is a position of the bitbase being classified
+///
+/// @type
+#[derive(Copy, Clone)]
+struct KpkPosition {
+    turn: Sides,
+    white_king: Square,
+    black_king: Square,
+    pawn: Square,
+}
+
+impl KpkPosition {
+    /// from_index decodes the position with the given bitbase index
+    ///
+    /// @param: idx - bitbase index of the position
+    /// @return: decoded position
+    fn from_index(idx: usize) -> Self {
+        let file = (idx >> 13) & 0x3;
+        let rank = Rank::R7.idx() - ((idx >> 15) & 0x7);
+        Self {
+            turn: Sides::from_idx(idx & 0x1),
+            black_king: Square::from_idx((idx >> 1) & 0x3F),
+            white_king: Square::from_idx((idx >> 7) & 0x3F),
+            pawn: Square::new(File::from_idx(file), Rank::from_idx(rank)),
+        }
+    }
+
+    /// initial classifies the position without looking at its successors
+    ///
+    /// @return: classification of the position
+    fn initial(&self) -> u8 {
+        let push = Square::from_idx(self.pawn.idx() + 8);
+
+        if king_distance(self.white_king, self.black_king) <= 1
+            || self.white_king == self.pawn
+            || self.black_king == self.pawn
+            || (self.turn == Sides::White && pawn_attacks(self.pawn) & bit(self.black_king) != 0)
+        {
+            return INVALID;
+        }
+
+        // the pawn promotes safely if the promotion square is not blocked and
+        // the defending king cannot capture the new piece
+        if self.turn == Sides::White
+            && self.pawn.rank() == Rank::R7
+            && self.white_king != push
+            && (king_distance(self.black_king, push) > 1
+                || king_distance(self.white_king, push) == 1)
+        {
+            return WIN;
+        }
+
+        if self.turn == Sides::Black {
+            let escapes = king_moves(self.black_king)
+                & !(king_moves(self.white_king) | pawn_attacks(self.pawn));
+            if escapes == 0 {
+                // a king and pawn can never checkmate, but the check is cheap
+                // enough to not rely on it
+                return if pawn_attacks(self.pawn) & bit(self.black_king) != 0 {
+                    WIN
+                } else {
+                    DRAW
+                };
+            }
+
+            // the defending king captures an undefended pawn
+            if escapes & bit(self.pawn) != 0 {
+                return DRAW;
+            }
+        }
+
+        UNKNOWN
+    }
+
+    /// classify classifies the position from the classifications of its
+    /// successors
+    ///
+    /// @param: bitbase - current classification of every position
+    /// @return: classification of the position
+    fn classify(&self, bitbase: &[u8]) -> u8 {
+        let (good, bad) = match self.turn {
+            Sides::White => (WIN, DRAW),
+            Sides::Black => (DRAW, WIN),
+        };
+
+        let mut result = INVALID;
+        match self.turn {
+            Sides::White => {
+                for to in squares(king_moves(self.white_king)) {
+                    result |= bitbase[index(Sides::Black, self.black_king, to, self.pawn)];
+                }
+
+                // promotions are only considered by the initial classification
+                let push = Square::from_idx(self.pawn.idx() + 8);
+                if self.pawn.rank() < Rank::R7 {
+                    result |= bitbase[index(Sides::Black, self.black_king, self.white_king, push)];
+                }
+                if self.pawn.rank() == Rank::R2
+                    && push != self.white_king
+                    && push != self.black_king
+                {
+                    let double_push = Square::from_idx(push.idx() + 8);
+                    result |=
+                        bitbase[index(Sides::Black, self.black_king, self.white_king, double_push)];
+                }
+            }
+            Sides::Black => {
+                for to in squares(king_moves(self.black_king)) {
+                    result |= bitbase[index(Sides::White, to, self.white_king, self.pawn)];
+                }
+            }
+        }
+
+        if result & good != 0 {
+            good
+        } else if result & UNKNOWN != 0 {
+            UNKNOWN
+        } else {
+            bad
+        }
+    }
+}
+
+/// generate generates the bitbase by retrograde analysis
+///
+/// note: positions that cannot be resolved once no more positions change are
+///       draws, since the side with the pawn cannot force progress in them
+///
+/// @return: bitbase of the positions won by the side with the pawn
+fn generate() -> Box<[u64]> {
+    let positions = (0..POSITIONS)
+        .map(KpkPosition::from_index)
+        .collect::<Vec<_>>();
+    let mut bitbase = positions
+        .iter()
+        .map(KpkPosition::initial)
+        .collect::<Vec<_>>();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (idx, position) in positions.iter().enumerate() {
+            if bitbase[idx] == UNKNOWN {
+                bitbase[idx] = position.classify(&bitbase);
+                changed |= bitbase[idx] != UNKNOWN;
+            }
+        }
+    }
+
+    let mut bits = vec![0u64; POSITIONS.div_ceil(64)];
+    for (idx, result) in bitbase.into_iter().enumerate() {
+        if result == WIN {
+            bits[idx / 64] |= 1 << (idx % 64);
+        }
+    }
+    bits.into_boxed_slice()
+}
+
+/// bit returns the raw bitboard of a square
+///
+/// @param: square - square to get the bitboard of
+/// @return: raw bitboard with only the square set
+#[inline]
+const fn bit(square: Square) -> u64 {
+    1 << square.idx()
+}
+
+/// KING_MOVES is a constant array of the raw bitboards of the squares a king
+/// can move to from each square
+const KING_MOVES: [u64; Square::TOTAL] = {
+    let mut moves = [0; Square::TOTAL];
+
+    let mut from = 0;
+    while from < Square::TOTAL {
+        let mut to = 0;
+        while to < Square::TOTAL {
+            if to != from && king_distance(Square::from_idx(from), Square::from_idx(to)) == 1 {
+                moves[from] |= 1 << to;
+            }
+            to += 1;
+        }
+        from += 1;
+    }
+
+    moves
+};
+
+/// king_moves returns the raw bitboard of the squares a king can move to
+///
+/// @param: square - square of the king
+/// @return: raw bitboard of the squares next to the king
+#[inline]
+const fn king_moves(square: Square) -> u64 {
+    KING_MOVES[square.idx()]
+}
+
+/// pawn_attacks returns the raw bitboard of the squares a white pawn attacks
+///
+/// @param: square - square of the pawn
+/// @return: raw bitboard of the squares attacked by the pawn
+#[inline]
+const fn pawn_attacks(square: Square) -> u64 {
+    let mut attacks = 0;
+    if square.file().idx() > File::A.idx() {
+        attacks |= 1 << (square.idx() + 7);
+    }
+    if square.file().idx() < File::H.idx() {
+        attacks |= 1 << (square.idx() + 9);
+    }
+    attacks
+}
+
+/// squares iterates over the squares of a raw bitboard
+///
+/// @param: bitboard - raw bitboard to iterate over
+/// @return: iterator over the squares set in the bitboard
+#[inline]
+fn squares(mut bitboard: u64) -> impl Iterator<Item = Square> {
+    std::iter::from_fn(move || {
+        if bitboard == 0 {
+            return None;
+        }
+        let square = Square::from_idx(bitboard.trailing_zeros() as usize);
+        bitboard &= bitboard - 1;
+        Some(square)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+    use chess_kit_position::{
+        DefaultPosition, Fen, PositionAttacks, PositionMoves, PositionView, Setup,
+    };
+    use chess_kit_primitives::{Black, MoveList, Pieces, White};
+
+    type TestPosition = DefaultPosition<DefaultAttackTable>;
+    type TestMoveGenerator = DefaultMoveGenerator<DefaultAttackTable>;
+
+    fn load(white_king: Square, pawn: Square, black_king: Square, turn: Sides) -> TestPosition {
+        let mut board = [[None; 8]; 8];
+        board[white_king.rank().idx()][white_king.file().idx()] = Some('K');
+        board[pawn.rank().idx()][pawn.file().idx()] = Some('P');
+        board[black_king.rank().idx()][black_king.file().idx()] = Some('k');
+
+        let placement = board
+            .iter()
+            .rev()
+            .map(|rank| {
+                let mut row = String::new();
+                let mut empty = 0;
+                for square in rank {
+                    match square {
+                        Some(piece) => {
+                            if empty > 0 {
+                                row.push_str(&empty.to_string());
+                                empty = 0;
+                            }
+                            row.push(*piece);
+                        }
+                        None => empty += 1,
+                    }
+                }
+                if empty > 0 {
+                    row.push_str(&empty.to_string());
+                }
+                row
+            })
+            .collect::<Vec<_>>()
+            .join("/");
+        let turn = match turn {
+            Sides::White => 'w',
+            Sides::Black => 'b',
+        };
+
+        let fen = format!("{placement} {turn} - - 0 1");
+        TestPosition::try_from(Setup::from(Fen::try_from(fen.as_str()).unwrap())).unwrap()
+    }
+
+    fn legal_moves(position: &TestPosition) -> MoveList {
+        let mut moves = MoveList::new();
+        TestMoveGenerator::new().generate_legal_moves(position, &mut moves);
+        moves
+    }
+
+    /// solves a position one ply ahead with the move generator, looking up the
+    /// bitbase for every successor that is still a king and pawn endgame
+    fn solve(position: &mut TestPosition) -> bool {
+        let moves = legal_moves(position);
+        if moves.is_empty() {
+            return position.turn() == Sides::Black && !position.checkers().is_empty();
+        }
+
+        let turn = position.turn();
+        let mut wins = moves.as_slice().iter().map(|mv| {
+            let _ = position.play_unchecked(*mv);
+            let win = successor_wins(position);
+            position.undo(*mv);
+            win
+        });
+        match turn {
+            Sides::White => wins.any(|win| win),
+            Sides::Black => wins.all(|win| win),
+        }
+    }
+
+    fn successor_wins(position: &TestPosition) -> bool {
+        if let Some(pawn) = position.get_piece::<White>(Pieces::Pawn).first() {
+            return probe(
+                position.king_square::<White>(),
+                pawn,
+                position.king_square::<Black>(),
+                position.turn() == Sides::White,
+            );
+        }
+
+        // the pawn was either captured or promoted, and only a queen or rook
+        // that survives the reply wins against a lone king
+        let promoted =
+            position.get_piece::<White>(Pieces::Queen) | position.get_piece::<White>(Pieces::Rook);
+        let Some(promoted) = promoted.first() else {
+            return false;
+        };
+
+        let replies = legal_moves(position);
+        if replies.is_empty() {
+            return !position.checkers().is_empty();
+        }
+        !replies
+            .as_slice()
+            .iter()
+            .any(|reply| reply.to() == promoted)
+    }
+
+    #[test]
+    fn bitbase_matches_a_search_of_every_position() {
+        let mut positions = 0;
+        for turn in [Sides::White, Sides::Black] {
+            for white_king in squares(u64::MAX) {
+                for black_king in squares(u64::MAX) {
+                    for pawn in squares(u64::MAX) {
+                        if pawn.rank() == Rank::R1
+                            || pawn.rank() == Rank::R8
+                            || king_distance(white_king, black_king) <= 1
+                            || pawn == white_king
+                            || pawn == black_king
+                            || (turn == Sides::White && pawn_attacks(pawn) & bit(black_king) != 0)
+                        {
+                            continue;
+                        }
+                        let mut position = load(white_king, pawn, black_king, turn);
+                        assert_eq!(
+                            probe(white_king, pawn, black_king, turn == Sides::White),
+                            solve(&mut position),
+                            "{turn:?} to move with K{white_king:?} P{pawn:?} k{black_king:?}",
+                        );
+                        positions += 1;
+                    }
+                }
+            }
+        }
+
+        // every position with the pawn on files E-H is probed through its mirror
+        assert!(positions > 300_000);
+    }
+
+    #[test]
+    fn classifies_well_known_positions() {
+        // the key squares in front of the pawn win regardless of the move
+        assert!(probe(Square::E6, Square::E5, Square::E8, true));
+        assert!(probe(Square::E6, Square::E5, Square::E8, false));
+        // the opposition decides with the king in front of its pawn
+        assert!(!probe(Square::E5, Square::E4, Square::E7, true));
+        assert!(probe(Square::E5, Square::E4, Square::E7, false));
+        // rook pawns are drawn once the defending king holds the corner
+        assert!(!probe(Square::B6, Square::A5, Square::A8, true));
+    }
+}
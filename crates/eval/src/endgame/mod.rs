@@ -0,0 +1,228 @@
+mod evaluators;
+pub mod kpk;
+mod scaling;
+mod signature;
+mod state;
+
+pub use signature::MaterialSignature;
+pub use state::EndgameEvalState;
+
+use crate::Score;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Bitboard, Black, File, Pieces, Sides, Square, White};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// KNOWN_WIN is the score of a position known to be won, which is above any
+/// regular evaluation but below every checkmate score
+pub const KNOWN_WIN: Score = 10_000;
+
+/// SCALE_FACTOR_NORMAL is the scale factor that leaves a score unchanged
+pub const SCALE_FACTOR_NORMAL: i32 = 64;
+
+/// MAX_PIECES is the largest number of pieces, kings included, of any endgame
+/// with specialized knowledge
+const MAX_PIECES: u32 = 6;
+
+/// `EvaluateFn` evaluates an endgame from the strong side's perspective
+pub type EvaluateFn = fn(&EndgameView, Sides) -> Score;
+
+/// `ScaleFn` returns the factor, out of SCALE_FACTOR_NORMAL, by which the
+/// strong side's advantage is scaled in an endgame, or None if the regular
+/// evaluation should be kept
+pub type ScaleFn = fn(&EndgameView, Sides) -> Option<i32>;
+
+/// `Endgame` is the specialized knowledge about an endgame
+///
+/// @type
+#[derive(Copy, Clone, Debug)]
+pub enum Endgame {
+    Evaluate(EvaluateFn), // replaces the regular evaluation
+    Scale(ScaleFn),       // scales the regular evaluation
+}
+
+/// ENDGAMES is the knowledge about each endgame, keyed by the material
+/// signature of the endgame and paired with the side the knowledge is for
+static ENDGAMES: LazyLock<HashMap<MaterialSignature, (Sides, Endgame)>> = LazyLock::new(|| {
+    let mut endgames = HashMap::new();
+    for (code, endgame) in [
+        ("KPK", Endgame::Evaluate(evaluators::kpk)),
+        ("KBNK", Endgame::Evaluate(evaluators::kbnk)),
+        ("KRKP", Endgame::Evaluate(evaluators::krkp)),
+        ("KBPK", Endgame::Scale(scaling::wrong_bishop)),
+        ("KBPPK", Endgame::Scale(scaling::wrong_bishop)),
+        ("KBPPPK", Endgame::Scale(scaling::wrong_bishop)),
+    ] {
+        for strong in [Sides::White, Sides::Black] {
+            let signature = MaterialSignature::from_code(code, strong)
+                .expect("endgame codes are valid signatures");
+            endgames.insert(signature, (strong, endgame));
+        }
+    }
+    endgames
+});
+
+/// probe returns the specialized knowledge about the endgame of a material
+/// signature
+///
+/// @param: signature - material signature of the endgame
+/// @return: side the knowledge is for and the knowledge, or None if there is
+///          no specialized knowledge about the endgame
+pub fn probe(signature: MaterialSignature) -> Option<(Sides, Endgame)> {
+    ENDGAMES.get(&signature).copied()
+}
+
+/// evaluate refines the evaluation of a position with the specialized
+/// knowledge about its endgame
+///
+/// @param: position - position to evaluate
+/// @param: score - regular evaluation of the position from white's perspective
+/// @return: refined evaluation of the position from white's perspective
+#[inline]
+pub fn evaluate<P: PositionView>(position: &P, score: Score) -> Score {
+    if position.total_occupancy().count_ones() > MAX_PIECES {
+        return score;
+    }
+    refine(&EndgameView::from_position(position), score)
+}
+
+/// refine refines the evaluation of a piece placement with the specialized
+/// knowledge about its endgame
+///
+/// @param: view - piece placement to evaluate
+/// @param: score - regular evaluation of the placement from white's perspective
+/// @return: refined evaluation of the placement from white's perspective
+#[inline]
+fn refine(view: &EndgameView, score: Score) -> Score {
+    if view.occupancy().count_ones() > MAX_PIECES {
+        return score;
+    }
+    let Some((strong, endgame)) = probe(MaterialSignature::from_view(view)) else {
+        return score;
+    };
+
+    let sign = match strong {
+        Sides::White => 1,
+        Sides::Black => -1,
+    };
+    match endgame {
+        Endgame::Evaluate(evaluate) => sign * evaluate(view, strong),
+        // note: scaling only ever reduces the advantage of the strong side, so
+        //       scores in favour of the weak side are kept as they are
+        Endgame::Scale(scale) => match scale(view, strong) {
+            Some(factor) if sign * score > 0 => score * factor / SCALE_FACTOR_NORMAL,
+            _ => score,
+        },
+    }
+}
+
+/// `EndgameView` is the piece placement of a position with endgame knowledge
+///
+/// @type
+#[derive(Copy, Clone)]
+pub struct EndgameView {
+    bitboards: [[Bitboard; Pieces::TOTAL]; Sides::TOTAL],
+    turn: Sides,
+}
+
+impl EndgameView {
+    /// from_position copies the piece placement of a position
+    ///
+    /// @param: position - position to copy the piece placement of
+    /// @return: piece placement of the position
+    pub fn from_position<P: PositionView>(position: &P) -> Self {
+        let mut bitboards = [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL];
+        for piece in Pieces::ALL {
+            bitboards[Sides::White][piece] = position.get_piece::<White>(piece);
+            bitboards[Sides::Black][piece] = position.get_piece::<Black>(piece);
+        }
+
+        Self {
+            bitboards,
+            turn: position.turn(),
+        }
+    }
+
+    /// pieces returns the pieces of a type a side has
+    ///
+    /// @param: side - side owning the pieces
+    /// @param: piece - type of the pieces
+    /// @return: bitboard of the pieces
+    #[inline]
+    pub fn pieces(&self, side: Sides, piece: Pieces) -> Bitboard {
+        self.bitboards[side][piece]
+    }
+
+    /// occupancy returns the squares occupied by any piece
+    ///
+    /// @return: bitboard of the occupied squares
+    #[inline]
+    pub fn occupancy(&self) -> Bitboard {
+        self.bitboards
+            .iter()
+            .flatten()
+            .fold(Bitboard::empty(), |occupancy, &pieces| occupancy | pieces)
+    }
+
+    /// square returns the square of a side's only piece of a type, from a
+    /// side's perspective
+    ///
+    /// note: endgames are evaluated from the perspective of their strong side,
+    ///       so that the evaluators only need to handle one orientation
+    ///
+    /// @param: side - side owning the piece
+    /// @param: piece - type of the piece
+    /// @param: perspective - side whose perspective the square is given from
+    /// @return: square of the piece
+    #[inline]
+    pub fn square(&self, side: Sides, piece: Pieces, perspective: Sides) -> Square {
+        relative_square(perspective, self.bitboards[side][piece].first_unchecked())
+    }
+
+    /// turn returns the side to move
+    ///
+    /// @return: side to move
+    #[inline]
+    pub fn turn(&self) -> Sides {
+        self.turn
+    }
+}
+
+/// king_distance returns the number of king moves between two squares
+///
+/// @param: from - first square
+/// @param: to - second square
+/// @return: number of king moves between the squares
+#[inline]
+const fn king_distance(from: Square, to: Square) -> u8 {
+    let files = from.file().idx().abs_diff(to.file().idx());
+    let ranks = from.rank().idx().abs_diff(to.rank().idx());
+    (if files > ranks { files } else { ranks }) as u8
+}
+
+/// relative_square returns a square from a side's perspective, where the
+/// side's pieces start on the first ranks
+///
+/// @param: side - side whose perspective to take
+/// @param: square - square to convert
+/// @return: square from the side's perspective
+#[inline]
+const fn relative_square(side: Sides, square: Square) -> Square {
+    match side {
+        Sides::White => square,
+        Sides::Black => Square::INVERTED[square.idx()],
+    }
+}
+
+/// mirror_file returns the square mirrored across the middle of the board
+/// between the D and E files
+///
+/// @param: square - square to mirror
+/// @return: mirrored square
+#[inline]
+const fn mirror_file(square: Square) -> Square {
+    Square::new(
+        File::from_idx(File::H.idx() - square.file().idx()),
+        square.rank(),
+    )
+}
@@ -0,0 +1,57 @@
+use super::{EndgameView, king_distance, relative_square};
+use chess_kit_primitives::{Bitboard, File, Pieces, Rank, Sides, Square};
+
+/// wrong_bishop recognizes the draw of a bishop and rook pawns when the bishop
+/// does not control the promotion square and the defending king reaches it
+///
+/// @param: view - piece placement of the position
+/// @param: strong - side with the bishop and pawns
+/// @return: scale factor of 0 if the position is a draw, None otherwise
+pub(super) fn wrong_bishop(view: &EndgameView, strong: Sides) -> Option<i32> {
+    let pawns = view.pieces(strong, Pieces::Pawn);
+    let weak_king = view.square(strong.other(), Pieces::King, strong);
+    let bishop = view.square(strong, Pieces::Bishop, Sides::White);
+
+    let file = [File::A, File::H]
+        .into_iter()
+        .find(|file| (pawns & !Bitboard::file(*file)).is_empty())?;
+
+    // square colors are compared on the real board, since flipping the board
+    // for black swaps them
+    let queening = Square::new(file, Rank::R8);
+    let is_wrong_bishop = relative_square(strong, queening).is_white() != bishop.is_white();
+
+    (is_wrong_bishop && king_distance(weak_king, queening) <= 1).then_some(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::endgame::evaluate;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, Setup};
+
+    fn evaluate_fen(fen: &str, score: i32) -> i32 {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        evaluate(&position, score)
+    }
+
+    #[test]
+    fn draws_rook_pawns_with_the_wrong_bishop() {
+        // the light-squared bishop cannot drive the king out of the dark h8
+        assert_eq!(evaluate_fen("7k/8/6K1/7P/8/8/8/5B2 w - - 0 1", 400), 0);
+        assert_eq!(evaluate_fen("2b5/8/8/8/p7/p7/8/K1k5 w - - 0 1", -400), 0);
+    }
+
+    #[test]
+    fn keeps_the_score_otherwise() {
+        // the dark-squared bishop controls h8
+        assert_eq!(evaluate_fen("7k/8/6K1/7P/8/8/8/2B5 w - - 0 1", 400), 400);
+        // the defending king is too far from the corner
+        assert_eq!(evaluate_fen("8/8/4k1K1/7P/8/8/8/5B2 w - - 0 1", 400), 400);
+        // scores in favour of the defending side are not scaled
+        assert_eq!(evaluate_fen("7k/8/6K1/7P/8/8/8/5B2 w - - 0 1", -50), -50);
+    }
+}
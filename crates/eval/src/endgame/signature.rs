@@ -0,0 +1,154 @@
+use super::EndgameView;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Black, Pieces, Sides, White};
+
+/// PIECE_BITS is the number of bits used to store the count of each piece
+const PIECE_BITS: usize = 4;
+
+/// PIECE_MASK is the mask of the count of a single piece
+const PIECE_MASK: u64 = (1 << PIECE_BITS) - 1;
+
+/// `MaterialSignature` is a compact key of how many pieces of each type each
+/// side has, which identifies the endgame a position belongs to
+///
+/// @type
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct MaterialSignature(u64);
+
+impl MaterialSignature {
+    /// from_position computes the material signature of a position
+    ///
+    /// @param: position - position to compute the signature of
+    /// @return: material signature of the position
+    pub fn from_position<P: PositionView>(position: &P) -> Self {
+        let mut signature = Self::default();
+        for piece in Pieces::ALL {
+            signature.add(
+                Sides::White,
+                piece,
+                position.get_piece::<White>(piece).count_ones(),
+            );
+            signature.add(
+                Sides::Black,
+                piece,
+                position.get_piece::<Black>(piece).count_ones(),
+            );
+        }
+        signature
+    }
+
+    /// from_view computes the material signature of a piece placement
+    ///
+    /// @param: view - piece placement to compute the signature of
+    /// @return: material signature of the piece placement
+    pub fn from_view(view: &EndgameView) -> Self {
+        let mut signature = Self::default();
+        for side in [Sides::White, Sides::Black] {
+            for piece in Pieces::ALL {
+                signature.add(side, piece, view.pieces(side, piece).count_ones());
+            }
+        }
+        signature
+    }
+
+    /// from_code parses the material signature of an endgame code such as
+    /// `KBNK`, which lists the pieces of the strong side followed by the
+    /// pieces of the weak side, each starting with their king
+    ///
+    /// @param: code - endgame code to parse
+    /// @param: strong - side owning the first set of pieces in the code
+    /// @return: material signature of the code, or None if the code is invalid
+    pub fn from_code(code: &str, strong: Sides) -> Option<Self> {
+        let (strong_pieces, weak_pieces) = code.strip_prefix('K')?.split_once('K')?;
+
+        let mut signature = Self::default();
+        for (side, pieces) in [(strong, strong_pieces), (strong.other(), weak_pieces)] {
+            signature.add(side, Pieces::King, 1);
+            for symbol in pieces.chars() {
+                let piece = match symbol {
+                    'P' => Pieces::Pawn,
+                    'N' => Pieces::Knight,
+                    'B' => Pieces::Bishop,
+                    'R' => Pieces::Rook,
+                    'Q' => Pieces::Queen,
+                    _ => return None,
+                };
+                signature.add(side, piece, 1);
+            }
+        }
+        Some(signature)
+    }
+
+    /// count returns the number of pieces of a type a side has
+    ///
+    /// @param: side - side to count the pieces of
+    /// @param: piece - type of the pieces to count
+    /// @return: number of pieces
+    #[inline]
+    pub const fn count(&self, side: Sides, piece: Pieces) -> u32 {
+        ((self.0 >> Self::shift(side, piece)) & PIECE_MASK) as u32
+    }
+
+    /// add adds pieces of a type to a side
+    ///
+    /// @param: side - side to add the pieces to
+    /// @param: piece - type of the pieces to add
+    /// @param: count - number of pieces to add
+    /// @return: void
+    /// @side-effects: modifies the signature
+    #[inline]
+    fn add(&mut self, side: Sides, piece: Pieces, count: u32) {
+        self.0 += u64::from(count) << Self::shift(side, piece);
+    }
+
+    /// shift returns the bit offset of the count of a side's pieces of a type
+    ///
+    /// @param: side - side owning the pieces
+    /// @param: piece - type of the pieces
+    /// @return: bit offset of the count
+    #[inline]
+    const fn shift(side: Sides, piece: Pieces) -> usize {
+        (side.idx() * Pieces::TOTAL + piece.idx()) * PIECE_BITS
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, Setup};
+
+    fn signature(fen: &str) -> MaterialSignature {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        MaterialSignature::from_position(&position)
+    }
+
+    #[test]
+    fn parses_endgame_codes_for_either_strong_side() {
+        let white_strong = MaterialSignature::from_code("KBNK", Sides::White).unwrap();
+        let black_strong = MaterialSignature::from_code("KBNK", Sides::Black).unwrap();
+
+        assert_eq!(white_strong, signature("8/8/4k3/8/8/8/8/1NB1K3 w - - 0 1"));
+        assert_eq!(black_strong, signature("1nb1k3/8/8/8/8/4K3/8/8 w - - 0 1"));
+        assert_eq!(white_strong.count(Sides::White, Pieces::Bishop), 1);
+        assert_eq!(white_strong.count(Sides::Black, Pieces::Knight), 0);
+        assert!(MaterialSignature::from_code("KXK", Sides::White).is_none());
+        assert!(MaterialSignature::from_code("BNK", Sides::White).is_none());
+    }
+
+    #[test]
+    fn counts_every_piece_of_the_starting_position() {
+        let signature =
+            MaterialSignature::from_position(&DefaultPosition::<DefaultAttackTable>::default());
+
+        for side in [Sides::White, Sides::Black] {
+            assert_eq!(signature.count(side, Pieces::Pawn), 8);
+            assert_eq!(signature.count(side, Pieces::Knight), 2);
+            assert_eq!(signature.count(side, Pieces::Queen), 1);
+            assert_eq!(signature.count(side, Pieces::King), 1);
+        }
+    }
+}
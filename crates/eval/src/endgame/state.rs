@@ -0,0 +1,162 @@
+use super::{EndgameView, refine};
+use crate::{EvalState, EvalTrace, Score, StandaloneEvalState};
+use chess_kit_collections::Copyable;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Bitboard, MoveDelta, PieceDeltaKind, Pieces, Sides};
+
+/// `EndgameEvalState` is an evaluation state that refines the score of another
+/// evaluation state with the specialized knowledge about the endgame of the
+/// position, if there is any
+///
+/// @type
+#[derive(Clone)]
+pub struct EndgameEvalState<EvalStateT: EvalState> {
+    inner: EvalStateT, // evaluation state whose score is refined
+    view: EndgameView, // incrementally updated piece placement
+}
+
+impl<EvalStateT: EvalState> EndgameEvalState<EvalStateT> {
+    /// new wraps the evaluation state of a position
+    ///
+    /// @param: inner - evaluation state of the position to refine
+    /// @param: position - position the evaluation state was initialized from
+    /// @return: refined evaluation state of the position
+    #[inline]
+    pub fn new<P: PositionView>(inner: EvalStateT, position: &P) -> Self {
+        Self {
+            inner,
+            view: EndgameView::from_position(position),
+        }
+    }
+
+    /// inner returns the evaluation state whose score is refined
+    ///
+    /// @return: reference to the wrapped evaluation state
+    #[inline]
+    pub fn inner(&self) -> &EvalStateT {
+        &self.inner
+    }
+}
+
+impl<EvalStateT: EvalState> EvalState for EndgameEvalState<EvalStateT> {
+    /// apply updates the wrapped evaluation state and the piece placement with
+    /// the piece changes of one move
+    ///
+    /// note: every move adds at least one piece of the side that moved, which
+    ///       is how the side to move is tracked without access to the position
+    ///
+    /// @impl: EvalState::apply
+    #[inline]
+    fn apply(&mut self, delta: MoveDelta) {
+        self.inner.apply(delta);
+        for change in delta.iter() {
+            let pieces = &mut self.view.bitboards[change.side()][change.piece()];
+            match change.kind() {
+                PieceDeltaKind::Added => {
+                    pieces.set_at(change.square());
+                    self.view.turn = change.side().other();
+                }
+                PieceDeltaKind::Removed => pieces.remove_at(change.square()),
+            }
+        }
+    }
+
+    /// score returns the score of the wrapped evaluation state, refined with
+    /// the knowledge about the endgame of the piece placement
+    ///
+    /// @impl: EvalState::score
+    #[inline]
+    fn score(&mut self) -> Score {
+        refine(&self.view, self.inner.score())
+    }
+
+    /// trace breaks the score of the wrapped evaluation state down into its
+    /// terms, and refines the final score like `score` does
+    ///
+    /// @impl: EvalState::trace
+    fn trace<P: PositionView>(&self, position: &P) -> EvalTrace {
+        let mut trace = self.inner.trace(position);
+        trace.score = refine(&self.view, trace.score);
+        trace
+    }
+}
+
+impl<EvalStateT: StandaloneEvalState> StandaloneEvalState for EndgameEvalState<EvalStateT> {
+    /// from_position initializes the wrapped evaluation state and the piece
+    /// placement of a position
+    ///
+    /// @impl: StandaloneEvalState::from_position
+    #[inline]
+    fn from_position<P: PositionView>(position: &P) -> Self {
+        Self::new(EvalStateT::from_position(position), position)
+    }
+}
+
+impl<EvalStateT: EvalState> Copyable for EndgameEvalState<EvalStateT> {
+    /// copy_from copies the contents of another EndgameEvalState into this one
+    ///
+    /// @impl: Copyable::copy_from
+    #[inline]
+    fn copy_from(&mut self, other: &Self) {
+        self.inner.copy_from(&other.inner);
+        self.view = other.view;
+    }
+}
+
+impl<EvalStateT: EvalState> Default for EndgameEvalState<EvalStateT> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            inner: EvalStateT::default(),
+            view: EndgameView {
+                bitboards: [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL],
+                turn: Sides::White,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PSQTEvalState;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, PositionMoves, Setup};
+    use chess_kit_primitives::{Move, Square};
+
+    fn position(fen: &str) -> DefaultPosition<DefaultAttackTable> {
+        DefaultPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn refines_the_wrapped_score_with_endgame_knowledge() {
+        // the light-squared bishop cannot drive the king out of the dark h8
+        let position = position("7k/8/6K1/7P/8/8/8/5B2 w - - 0 1");
+        let mut state = EndgameEvalState::<PSQTEvalState>::from_position(&position);
+
+        assert_ne!(PSQTEvalState::from_position(&position).score(), 0);
+        assert_eq!(state.score(), 0);
+        assert_eq!(state.trace(&position).score, 0);
+    }
+
+    #[test]
+    fn tracks_the_piece_placement_incrementally() {
+        let mut position = position("7k/8/6K1/7P/8/8/8/5B2 w - - 0 1");
+        let mut incremental = EndgameEvalState::<PSQTEvalState>::from_position(&position);
+
+        for mv in [
+            Move::new(Square::F1, Square::D3),
+            Move::new(Square::H8, Square::G8),
+            Move::new(Square::D3, Square::C4),
+            Move::new(Square::G8, Square::H8),
+            Move::new(Square::H5, Square::H6),
+        ] {
+            incremental.apply(position.play_unchecked(mv));
+            let mut fresh = EndgameEvalState::<PSQTEvalState>::from_position(&position);
+
+            assert_eq!(incremental.view.turn, fresh.view.turn);
+            assert_eq!(incremental.view.bitboards, fresh.view.bitboards);
+            assert_eq!(incremental.score(), fresh.score());
+        }
+    }
+}
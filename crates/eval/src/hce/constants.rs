@@ -0,0 +1,64 @@
+use crate::psqt::PieceValue as PV;
+use chess_kit_primitives::{Pieces, Rank};
+
+/// PASSED_PAWN is the bonus for a passed pawn by its rank relative to its side
+pub const PASSED_PAWN: [PV; Rank::TOTAL] = [
+    PV::new(0, 0),
+    PV::new(5, 10),
+    PV::new(5, 15),
+    PV::new(10, 25),
+    PV::new(20, 45),
+    PV::new(35, 75),
+    PV::new(60, 120),
+    PV::new(0, 0),
+];
+
+/// ISOLATED_PAWN is the penalty for a pawn with no friendly pawns on the files
+/// next to it
+pub const ISOLATED_PAWN: PV = PV::new(-10, -15);
+
+/// DOUBLED_PAWN is the penalty for a pawn with a friendly pawn in front of it
+/// on the same file
+pub const DOUBLED_PAWN: PV = PV::new(-10, -20);
+
+/// BACKWARD_PAWN is the penalty for a pawn that cannot be supported by a
+/// friendly pawn and whose stop square is controlled by an enemy pawn
+pub const BACKWARD_PAWN: PV = PV::new(-8, -10);
+
+/// ROOK_OPEN_FILE is the bonus for a rook on a file without pawns
+pub const ROOK_OPEN_FILE: PV = PV::new(25, 10);
+
+/// ROOK_SEMI_OPEN_FILE is the bonus for a rook on a file without friendly
+/// pawns but with enemy pawns
+pub const ROOK_SEMI_OPEN_FILE: PV = PV::new(12, 6);
+
+/// BISHOP_PAIR is the bonus for having bishops on both square colors
+pub const BISHOP_PAIR: PV = PV::new(30, 50);
+
+/// MOBILITY is the value of each safe square a piece can move to, relative to
+/// the piece's MOBILITY_BASELINE
+///
+/// TODO: we have a leading 0 for Pieces::NONE, remove when possible
+pub const MOBILITY: [PV; Pieces::TOTAL] = [
+    PV::new(0, 0),
+    PV::new(0, 0),
+    PV::new(4, 4),
+    PV::new(5, 5),
+    PV::new(2, 4),
+    PV::new(1, 2),
+    PV::new(0, 0),
+];
+
+/// MOBILITY_BASELINE is the number of safe squares at which a piece's mobility
+/// is scored as neutral
+pub const MOBILITY_BASELINE: [i32; Pieces::TOTAL] = [0, 0, 4, 7, 7, 14, 0];
+
+/// KING_ATTACK_WEIGHTS is the weight of a piece attacking the enemy king zone
+pub const KING_ATTACK_WEIGHTS: [i32; Pieces::TOTAL] = [0, 0, 20, 20, 40, 80, 0];
+
+/// KING_ATTACK_SCALING is the percentage of the summed attacker weights that
+/// is scored by the number of pieces attacking the enemy king zone
+///
+/// note: a single attacker is rarely dangerous, so attacks are only scored
+///       once at least two pieces take part in them
+pub const KING_ATTACK_SCALING: [i32; 8] = [0, 0, 50, 75, 88, 94, 97, 99];
@@ -0,0 +1,117 @@
+use chess_kit_primitives::{Bitboard, File, Rank, Sides, Square};
+
+/// FILE_A is the raw bitboard of the A file
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+
+/// RANK_1 is the raw bitboard of the first rank
+const RANK_1: u64 = 0xFF;
+
+/// ADJACENT_FILES is a constant array of the files next to each file
+pub(super) const ADJACENT_FILES: [Bitboard; File::TOTAL] = {
+    let mut masks = [Bitboard::empty(); File::TOTAL];
+
+    let mut file = 0;
+    while file < File::TOTAL {
+        masks[file] = Bitboard::new(adjacent_files(file));
+        file += 1;
+    }
+
+    masks
+};
+
+/// FORWARD_RANKS is a constant array of the ranks strictly in front of each
+/// rank from each side's perspective
+pub(super) const FORWARD_RANKS: [[Bitboard; Rank::TOTAL]; Sides::TOTAL] = {
+    let mut masks = [[Bitboard::empty(); Rank::TOTAL]; Sides::TOTAL];
+
+    let mut side = 0;
+    while side < Sides::TOTAL {
+        let mut rank = 0;
+        while rank < Rank::TOTAL {
+            masks[side][rank] = Bitboard::new(forward_ranks(side, rank));
+            rank += 1;
+        }
+        side += 1;
+    }
+
+    masks
+};
+
+/// FORWARD_FILE is a constant array of the squares in front of each square on
+/// its file from each side's perspective
+pub(super) const FORWARD_FILE: [[Bitboard; Square::TOTAL]; Sides::TOTAL] = {
+    let mut masks = [[Bitboard::empty(); Square::TOTAL]; Sides::TOTAL];
+
+    let mut side = 0;
+    while side < Sides::TOTAL {
+        let mut square = 0;
+        while square < Square::TOTAL {
+            let file = FILE_A << (square % 8);
+            masks[side][square] = Bitboard::new(file & forward_ranks(side, square / 8));
+            square += 1;
+        }
+        side += 1;
+    }
+
+    masks
+};
+
+/// PASSED_PAWN_MASKS is a constant array of the squares that must be free of
+/// enemy pawns for a pawn on each square to be passed, from each side's
+/// perspective
+pub(super) const PASSED_PAWN_MASKS: [[Bitboard; Square::TOTAL]; Sides::TOTAL] = {
+    let mut masks = [[Bitboard::empty(); Square::TOTAL]; Sides::TOTAL];
+
+    let mut side = 0;
+    while side < Sides::TOTAL {
+        let mut square = 0;
+        while square < Square::TOTAL {
+            let files = (FILE_A << (square % 8)) | adjacent_files(square % 8);
+            masks[side][square] = Bitboard::new(files & forward_ranks(side, square / 8));
+            square += 1;
+        }
+        side += 1;
+    }
+
+    masks
+};
+
+/// adjacent_files returns the raw bitboard of the files next to a file
+///
+/// @param: file - index of the file
+/// @return: raw bitboard of the adjacent files
+const fn adjacent_files(file: usize) -> u64 {
+    let mut mask = 0;
+    if file > 0 {
+        mask |= FILE_A << (file - 1);
+    }
+    if file < File::TOTAL - 1 {
+        mask |= FILE_A << (file + 1);
+    }
+    mask
+}
+
+/// forward_ranks returns the raw bitboard of the ranks strictly in front of a
+/// rank from a side's perspective
+///
+/// @param: side - index of the side
+/// @param: rank - index of the rank
+/// @return: raw bitboard of the ranks in front of the rank
+const fn forward_ranks(side: usize, rank: usize) -> u64 {
+    let mut mask = 0;
+
+    let mut other = 0;
+    while other < Rank::TOTAL {
+        let in_front = if side == Sides::White.idx() {
+            other > rank
+        } else {
+            other < rank
+        };
+        if in_front {
+            mask |= RANK_1 << (other * 8);
+        }
+        other += 1;
+    }
+
+    mask
+}
@@ -0,0 +1,369 @@
+pub mod constants;
+mod masks;
+mod pawns;
+mod pieces;
+
+pub use pawns::{PAWN_TABLE_SIZE_MB, PawnEntry, PawnTable};
+
+use crate::psqt::PieceValue;
+use crate::{EvalState, EvalTrace, PSQTEvalState, Score, StandaloneEvalState};
+use chess_kit_attack_table::AttackTable;
+use chess_kit_collections::Copyable;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{
+    Bitboard, Black, MoveDelta, PieceDeltaKind, Pieces, Sides, White, ZobristKey, ZobristTable,
+    call_as,
+};
+use std::marker::PhantomData;
+
+/// `HceEvalState` is the evaluation state implementation for the hand-crafted
+/// evaluation, which adds pawn structure, rook file, mobility, king attack and
+/// bishop pair terms on top of the PSQT evaluation
+///
+/// note: the state keeps its own copy of the piece placement, since scoring
+///       only has access to the state. the pawn key is taken from the position
+///       and kept in sync with it through the applied deltas. default states
+///       only fill the spare slots of an accumulator and take the pawn table
+///       of the state they are copied from
+///
+/// @type
+pub struct HceEvalState<AT: AttackTable> {
+    // cache of evaluated pawn structures, or None for a spare slot
+    pawn_table: Option<PawnTable>,
+    // material and piece-square evaluation
+    psqt: PSQTEvalState,
+    // piece placement of each side
+    bitboards: [[Bitboard; Pieces::TOTAL]; Sides::TOTAL],
+    // key of the pawn structure, used to look up the pawn table
+    pawn_key: ZobristKey,
+    _attack_table: PhantomData<AT>,
+}
+
+impl<AT: AttackTable> EvalState for HceEvalState<AT> {
+    /// apply updates the evaluation state with the piece changes of one move
+    ///
+    /// @impl: EvalState::apply
+    #[inline]
+    fn apply(&mut self, delta: MoveDelta) {
+        self.psqt.apply(delta);
+
+        for change in delta.iter() {
+            let (side, piece, square) = (change.side(), change.piece(), change.square());
+            match change.kind() {
+                PieceDeltaKind::Added => self.bitboards[side][piece].set_at(square),
+                PieceDeltaKind::Removed => self.bitboards[side][piece].remove_at(square),
+            }
+
+            if piece == Pieces::Pawn {
+                self.pawn_key ^= call_as!(side, |SideT| {
+                    ZobristTable::piece::<SideT>(Pieces::Pawn, square)
+                });
+            }
+        }
+    }
+
+    /// score returns the evaluation score of this state
+    ///
+    /// @impl: EvalState::score
+    #[inline]
+    fn score(&mut self) -> Score {
+        let terms = self.terms();
+        self.psqt.score() + self.psqt.tapered(terms)
+    }
+
+    /// trace breaks the hand-crafted score down into the PSQT piece terms and
+    /// each of the hand-crafted terms
+    ///
+    /// @impl: EvalState::trace
+    fn trace<P: PositionView>(&self, position: &P) -> EvalTrace {
+        let mut state = self.clone();
+        let mut trace = EvalTrace::new(state.score());
+        trace.phase = Some(state.psqt.weighted_phase());
+        PSQTEvalState::trace_pieces(position, &mut trace);
+
+        let [white, black] = pawns::evaluate_pawn_sides::<AT>(state.pawns());
+        trace.push("Pawn structure", white.into(), black.into());
+
+        let white = pieces::evaluate_pieces::<White, AT>(&state.bitboards);
+        let black = pieces::evaluate_pieces::<Black, AT>(&state.bitboards);
+        trace.push("Mobility", white.mobility.into(), black.mobility.into());
+        trace.push(
+            "King attack",
+            white.king_attack.into(),
+            black.king_attack.into(),
+        );
+        trace.push(
+            "Rook files",
+            white.rook_files.into(),
+            black.rook_files.into(),
+        );
+        trace.push(
+            "Bishop pair",
+            white.bishop_pair.into(),
+            black.bishop_pair.into(),
+        );
+
+        trace
+    }
+}
+
+impl<AT: AttackTable> StandaloneEvalState for HceEvalState<AT> {
+    /// from_position initializes the hand-crafted evaluation state from a
+    /// position, with a pawn table of its own
+    ///
+    /// @impl: StandaloneEvalState::from_position
+    #[inline]
+    fn from_position<P: PositionView>(position: &P) -> Self {
+        Self::with_pawn_table(PawnTable::default(), position)
+    }
+}
+
+impl<AT: AttackTable> HceEvalState<AT> {
+    /// with_pawn_table initializes the hand-crafted evaluation state from a
+    /// position, caching pawn structures in the given table
+    ///
+    /// note: states derived from this one share the table, so its owner can
+    ///       clear it between games
+    ///
+    /// @param: pawn_table - cache of evaluated pawn structures
+    /// @param: position - position to initialize the state from
+    /// @return: eval state of the position
+    pub fn with_pawn_table<P: PositionView>(pawn_table: PawnTable, position: &P) -> Self {
+        let mut bitboards = [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL];
+        for piece in Pieces::ALL {
+            bitboards[Sides::White][piece] = position.get_piece::<White>(piece);
+            bitboards[Sides::Black][piece] = position.get_piece::<Black>(piece);
+        }
+
+        Self {
+            pawn_table: Some(pawn_table),
+            psqt: PSQTEvalState::from_position(position),
+            bitboards,
+            pawn_key: position.pawn_key(),
+            _attack_table: PhantomData,
+        }
+    }
+
+    /// pawn_key returns the key of the evaluated pawn structure
+    ///
+    /// @return: key of the pawns of both sides
+    #[inline]
+    pub fn pawn_key(&self) -> ZobristKey {
+        self.pawn_key
+    }
+
+    /// pawn_structure returns the evaluation of the pawn structure, looking it
+    /// up in the state's pawn table
+    ///
+    /// @return: evaluation of the pawn structure
+    /// @side-effects: caches the evaluation in the state's pawn table
+    #[inline]
+    pub fn pawn_structure(&self) -> PawnEntry {
+        self.pawn_table
+            .as_ref()
+            .expect("spare slots are copied over before they are scored")
+            .probe::<AT>(self.pawn_key, self.pawns())
+    }
+
+    /// pawns returns the pawns of each side
+    ///
+    /// @return: pawn bitboards of each side
+    #[inline]
+    fn pawns(&self) -> [Bitboard; Sides::TOTAL] {
+        [
+            self.bitboards[Sides::White][Pieces::Pawn],
+            self.bitboards[Sides::Black][Pieces::Pawn],
+        ]
+    }
+
+    /// terms returns the hand-crafted evaluation terms added on top of the PSQT
+    /// evaluation
+    ///
+    /// @return: untapered score of the terms, white minus black
+    /// @side-effects: caches the pawn structure in the state's pawn table
+    #[inline]
+    fn terms(&self) -> PieceValue {
+        self.pawn_structure().score + pieces::evaluate_pieces::<White, AT>(&self.bitboards).total()
+            - pieces::evaluate_pieces::<Black, AT>(&self.bitboards).total()
+    }
+}
+
+impl<AT: AttackTable> Clone for HceEvalState<AT> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            pawn_table: self.pawn_table.clone(),
+            psqt: self.psqt,
+            bitboards: self.bitboards,
+            pawn_key: self.pawn_key,
+            _attack_table: PhantomData,
+        }
+    }
+}
+
+impl<AT: AttackTable> Copyable for HceEvalState<AT> {
+    /// copy_from copies the contents of another HceEvalState into this one
+    ///
+    /// note: the pawn table is only cloned when this slot held another one, so
+    ///       the reference count is not touched on every move of a search
+    ///
+    /// @impl: Copyable::copy_from
+    #[inline]
+    fn copy_from(&mut self, other: &Self) {
+        self.psqt = other.psqt;
+        self.bitboards = other.bitboards;
+        self.pawn_key = other.pawn_key;
+        let same_table = match (&self.pawn_table, &other.pawn_table) {
+            (Some(ours), Some(theirs)) => ours.shares_entries_with(theirs),
+            (None, None) => true,
+            _ => false,
+        };
+        if !same_table {
+            self.pawn_table.clone_from(&other.pawn_table);
+        }
+    }
+}
+
+impl<AT: AttackTable> Default for HceEvalState<AT> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pawn_table: None,
+            psqt: PSQTEvalState::default(),
+            bitboards: [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL],
+            pawn_key: ZobristKey::default(),
+            _attack_table: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hce::constants::BISHOP_PAIR;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, PositionMoves, Setup};
+    use chess_kit_primitives::{Move, Square};
+
+    type TestPosition = DefaultPosition<DefaultAttackTable>;
+    type TestEvalState = HceEvalState<DefaultAttackTable>;
+
+    fn load(fen: &str) -> TestPosition {
+        TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+    }
+
+    fn score(fen: &str) -> Score {
+        TestEvalState::from_position(&load(fen)).score()
+    }
+
+    #[test]
+    fn symmetric_positions_are_neutral() {
+        let mut starting_position = TestEvalState::from_position(&TestPosition::default());
+
+        assert_eq!(starting_position.score(), 0);
+        assert_eq!(score("4k3/pp4pp/8/8/8/8/PP4PP/4K3 w - - 0 1"), 0);
+    }
+
+    #[test]
+    fn color_flipped_positions_negate_the_score() {
+        for (fen, flipped) in [
+            (
+                "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+                "rnbqk2r/pppp1ppp/5n2/2b1p3/4P3/2N2N2/PPPP1PPP/R1BQKB1R b KQkq - 4 4",
+            ),
+            (
+                "4k3/p7/8/2p5/4P3/3P4/P7/R3K3 w - - 0 1",
+                "r3k3/p7/3p4/4p3/2P5/8/P7/4K3 b - - 0 1",
+            ),
+        ] {
+            assert_eq!(score(fen), -score(flipped));
+        }
+    }
+
+    #[test]
+    fn incremental_deltas_match_fresh_position_initialization() {
+        for (fen, moves) in [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                vec![
+                    Move::new(Square::E2, Square::E4),
+                    Move::new(Square::D7, Square::D5),
+                    Move::new(Square::E4, Square::D5),
+                    Move::new(Square::G8, Square::F6),
+                ],
+            ),
+            (
+                "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1",
+                vec![
+                    Move::new(Square::E5, Square::D6).with_en_passant(),
+                    Move::new(Square::E8, Square::G8).with_castle(),
+                    Move::new(Square::B7, Square::A8).with_promotion(Pieces::Knight),
+                    Move::new(Square::F8, Square::A8),
+                    Move::new(Square::E1, Square::G1).with_castle(),
+                ],
+            ),
+        ] {
+            let mut position = load(fen);
+            let mut incremental = TestEvalState::from_position(&position);
+
+            for mv in moves {
+                incremental.apply(position.play_unchecked(mv));
+                let mut fresh = TestEvalState::from_position(&position);
+
+                assert_eq!(incremental.bitboards, fresh.bitboards);
+                assert_eq!(incremental.pawn_key(), position.pawn_key());
+                assert_eq!(incremental.score(), fresh.score());
+            }
+        }
+    }
+
+    #[test]
+    fn cached_pawn_structures_match_fresh_evaluations() {
+        let pawn_table = PawnTable::default();
+        let state = TestEvalState::with_pawn_table(
+            pawn_table.clone(),
+            &load("4k3/p7/8/2p5/4P3/3P4/P7/R3K3 w - - 0 1"),
+        );
+        let fresh = pawns::evaluate_pawn_structure::<DefaultAttackTable>(state.pawns());
+
+        for _ in 0..2 {
+            assert_eq!(state.pawn_structure().score, fresh.score);
+        }
+        pawn_table.clear();
+        assert_eq!(state.pawn_structure().score, fresh.score);
+    }
+
+    #[test]
+    fn spare_slots_take_the_pawn_table_of_the_copied_state() {
+        let pawn_table = PawnTable::default();
+        let state = TestEvalState::with_pawn_table(pawn_table.clone(), &TestPosition::default());
+        let mut spare = TestEvalState::default();
+
+        spare.copy_from(&state);
+        assert!(
+            spare
+                .pawn_table
+                .as_ref()
+                .is_some_and(|table| table.shares_entries_with(&pawn_table))
+        );
+        assert_eq!(spare.score(), state.clone().score());
+    }
+
+    #[test]
+    fn traces_report_the_hand_crafted_terms_per_side() {
+        let position = load("4k3/p7/8/8/8/8/8/B3K2B w - - 0 1");
+        let trace = TestEvalState::from_position(&position).trace(&position);
+
+        let bishop_pair = trace.term("Bishop pair").unwrap();
+        assert_eq!(bishop_pair.scores, [BISHOP_PAIR.into(), (0, 0)]);
+        let pawn_structure = trace.term("Pawn structure").unwrap();
+        assert_eq!(
+            pawn_structure.total(),
+            TestEvalState::from_position(&position)
+                .pawn_structure()
+                .score
+                .into()
+        );
+        assert_eq!(trace.score, TestEvalState::from_position(&position).score());
+    }
+}
@@ -0,0 +1,232 @@
+use super::constants::{BACKWARD_PAWN, DOUBLED_PAWN, ISOLATED_PAWN, PASSED_PAWN};
+use super::masks::{ADJACENT_FILES, FORWARD_FILE, FORWARD_RANKS, PASSED_PAWN_MASKS};
+use crate::psqt::PieceValue;
+use chess_kit_attack_table::{AttackTable, PawnDirections};
+use chess_kit_collections::{HashFn, HashKey, Map, Value};
+use chess_kit_primitives::{Bitboard, Black, Side, Sides, White, ZobristKey};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// PAWN_TABLE_SIZE_MB is the default size of a pawn structure cache
+pub const PAWN_TABLE_SIZE_MB: usize = 1;
+
+/// PawnEntry is the cached evaluation of a pawn structure
+///
+/// @type
+#[derive(Copy, Clone, Default, Debug)]
+pub struct PawnEntry {
+    /// pawn structure score, white minus black
+    pub score: PieceValue,
+}
+
+impl Value for PawnEntry {
+    /// priority returns the replacement priority of the entry
+    ///
+    /// note: every pawn structure is equally cheap to recompute, so entries are
+    ///       replaced without preference
+    ///
+    /// @impl: Value::priority
+    #[inline]
+    fn priority(&self) -> i8 {
+        0
+    }
+}
+
+/// PawnKeyHashFn hashes a pawn key into the pawn table
+struct PawnKeyHashFn;
+
+impl HashFn<ZobristKey> for PawnKeyHashFn {
+    #[inline]
+    fn hash(key: &ZobristKey) -> HashKey {
+        // split the pawn key into an index and a tag the same way the
+        // transposition table splits position keys
+        let index = u32::from(*key >> 32u64) as usize;
+        let tag = u32::from(*key);
+        HashKey { index, tag }
+    }
+}
+
+/// `PawnTable` is a cache of evaluated pawn structures
+///
+/// note: clones share their entries, so the owner of a table can clear it for
+///       every eval state it was handed to, e.g. when a new game starts. the
+///       entries are not synchronized, so each search thread owns its table
+///
+/// @type
+#[derive(Clone)]
+pub struct PawnTable {
+    entries: Rc<RefCell<Map<ZobristKey, PawnEntry, PawnKeyHashFn>>>,
+}
+
+impl PawnTable {
+    /// new creates an empty pawn table
+    ///
+    /// @param: size_mb - memory size of the table in megabytes
+    /// @return: empty pawn table
+    pub fn new(size_mb: usize) -> Self {
+        Self {
+            entries: Rc::new(RefCell::new(Map::new(size_mb))),
+        }
+    }
+
+    /// clear removes every cached pawn structure
+    ///
+    /// @return: void
+    /// @side-effects: clears the entries shared by every clone of the table
+    pub fn clear(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    /// shares_entries_with returns whether both tables are clones of each other
+    ///
+    /// @param: other - table to compare with
+    /// @return: true if the tables share their entries
+    #[inline]
+    pub(super) fn shares_entries_with(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.entries, &other.entries)
+    }
+
+    /// probe returns the evaluation of a pawn structure, computing and caching
+    /// it if it is not cached yet
+    ///
+    /// @param: pawn_key - key of the pawn structure
+    /// @param: pawns - pawns of each side
+    /// @return: evaluation of the pawn structure
+    /// @side-effects: caches the evaluation in the table
+    #[inline]
+    pub(super) fn probe<AT: AttackTable>(
+        &self,
+        pawn_key: ZobristKey,
+        pawns: [Bitboard; Sides::TOTAL],
+    ) -> PawnEntry {
+        let mut entries = self.entries.borrow_mut();
+        if let Some(entry) = entries.get(&pawn_key) {
+            return *entry;
+        }
+
+        let entry = evaluate_pawn_structure::<AT>(pawns);
+        entries.set(&pawn_key, entry);
+        entry
+    }
+}
+
+impl Default for PawnTable {
+    /// default creates an empty pawn table of the default size
+    ///
+    /// @impl: Default::default
+    fn default() -> Self {
+        Self::new(PAWN_TABLE_SIZE_MB)
+    }
+}
+
+/// evaluate_pawn_structure evaluates a pawn structure from scratch
+///
+/// @param: pawns - pawns of each side
+/// @return: evaluation of the pawn structure
+pub(super) fn evaluate_pawn_structure<AT: AttackTable>(
+    pawns: [Bitboard; Sides::TOTAL],
+) -> PawnEntry {
+    let [white, black] = evaluate_pawn_sides::<AT>(pawns);
+    PawnEntry {
+        score: white - black,
+    }
+}
+
+/// evaluate_pawn_sides evaluates the pawns of each side from scratch
+///
+/// @param: pawns - pawns of each side
+/// @return: score of each side's pawns
+pub(super) fn evaluate_pawn_sides<AT: AttackTable>(
+    pawns: [Bitboard; Sides::TOTAL],
+) -> [PieceValue; Sides::TOTAL] {
+    [
+        evaluate_side::<White, AT>(pawns),
+        evaluate_side::<Black, AT>(pawns),
+    ]
+}
+
+/// evaluate_side evaluates SideT's pawns
+///
+/// @marker: SideT - side to evaluate the pawns of
+/// @param: pawns - pawns of each side
+/// @return: score of SideT's pawns
+fn evaluate_side<SideT: Side, AT: AttackTable>(pawns: [Bitboard; Sides::TOTAL]) -> PieceValue {
+    let ours = pawns[SideT::SIDE];
+    let theirs = pawns[<SideT::Other as Side>::SIDE];
+    let their_attacks = AT::all_pawn_targets::<SideT::Other>(theirs, PawnDirections::Left)
+        | AT::all_pawn_targets::<SideT::Other>(theirs, PawnDirections::Right);
+
+    let mut score = PieceValue::default();
+    for square in ours.iter() {
+        let neighbours = ours & ADJACENT_FILES[square.file()];
+        let is_doubled = (ours & FORWARD_FILE[SideT::SIDE][square]).not_empty();
+
+        if neighbours.is_empty() {
+            score += ISOLATED_PAWN;
+        } else {
+            // a pawn whose neighbours have all advanced past it can never be
+            // defended by a pawn, which is only a weakness if it cannot safely
+            // advance to catch up either
+            let supporters = neighbours & !FORWARD_RANKS[SideT::SIDE][square.rank()];
+            let stop = AT::all_pawn_targets::<SideT>(Bitboard::square(square), PawnDirections::Up);
+            if supporters.is_empty() && stop.intersects(their_attacks) {
+                score += BACKWARD_PAWN;
+            }
+        }
+
+        if is_doubled {
+            score += DOUBLED_PAWN;
+        } else if (theirs & PASSED_PAWN_MASKS[SideT::SIDE][square]).is_empty() {
+            let rank = match SideT::SIDE {
+                Sides::White => square.rank().idx(),
+                Sides::Black => 7 - square.rank().idx(),
+            };
+            score += PASSED_PAWN[rank];
+        }
+    }
+
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hce::constants::PASSED_PAWN;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
+    use chess_kit_primitives::Pieces;
+
+    fn pawn_structure(fen: &str) -> PawnEntry {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        evaluate_pawn_structure::<DefaultAttackTable>([
+            position.get_piece::<White>(Pieces::Pawn),
+            position.get_piece::<Black>(Pieces::Pawn),
+        ])
+    }
+
+    #[test]
+    fn penalizes_isolated_and_doubled_pawns() {
+        // c2 is isolated and doubled, c3 and c7 are isolated
+        let entry = pawn_structure("4k3/2p5/8/8/8/2P5/2P5/4K3 w - - 0 1");
+
+        assert_eq!(entry.score, ISOLATED_PAWN + DOUBLED_PAWN);
+    }
+
+    #[test]
+    fn rewards_passed_pawns_by_relative_rank() {
+        let entry = pawn_structure("4k3/p7/8/4P3/8/8/8/4K3 w - - 0 1");
+
+        assert_eq!(entry.score, PASSED_PAWN[4] - PASSED_PAWN[1]);
+    }
+
+    #[test]
+    fn penalizes_backward_pawns() {
+        // d3 cannot be supported by e4 and its stop square is attacked by c5
+        let entry = pawn_structure("4k3/8/8/2p5/4P3/3P4/8/4K3 w - - 0 1");
+
+        assert_eq!(entry.score, BACKWARD_PAWN + PASSED_PAWN[3] - ISOLATED_PAWN);
+    }
+}
@@ -0,0 +1,183 @@
+use super::constants::{
+    BISHOP_PAIR, KING_ATTACK_SCALING, KING_ATTACK_WEIGHTS, MOBILITY, MOBILITY_BASELINE,
+    ROOK_OPEN_FILE, ROOK_SEMI_OPEN_FILE,
+};
+use crate::psqt::PieceValue;
+use chess_kit_attack_table::{AttackTable, PawnDirections};
+use chess_kit_primitives::{Bitboard, Pieces, Side, Sides};
+
+/// LIGHT_SQUARES is the bitboard of all light squares on the board
+const LIGHT_SQUARES: Bitboard = Bitboard::new(0x55AA_55AA_55AA_55AA);
+
+/// `PieceTerms` is the breakdown of the piece evaluation of one side
+///
+/// @type
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub(super) struct PieceTerms {
+    /// safe squares the pieces can move to
+    pub(super) mobility: PieceValue,
+    /// pressure on the enemy king zone
+    pub(super) king_attack: PieceValue,
+    /// rooks on open and semi-open files
+    pub(super) rook_files: PieceValue,
+    /// bishops on both square colors
+    pub(super) bishop_pair: PieceValue,
+}
+
+impl PieceTerms {
+    /// total returns the sum of all piece terms
+    ///
+    /// @return: score of the side's pieces
+    #[inline]
+    pub(super) fn total(&self) -> PieceValue {
+        self.mobility + self.king_attack + self.rook_files + self.bishop_pair
+    }
+}
+
+/// evaluate_pieces evaluates the activity of SideT's pieces and the pressure
+/// they put on the enemy king
+///
+/// @marker: SideT - side to evaluate the pieces of
+/// @param: bitboards - piece bitboards of each side
+/// @return: breakdown of the score of SideT's pieces
+pub(super) fn evaluate_pieces<SideT: Side, AT: AttackTable>(
+    bitboards: &[[Bitboard; Pieces::TOTAL]; Sides::TOTAL],
+) -> PieceTerms {
+    let ours = &bitboards[SideT::SIDE];
+    let theirs = &bitboards[<SideT::Other as Side>::SIDE];
+    let our_occupancy = occupancy(ours);
+    let occupancy = our_occupancy | occupancy(theirs);
+
+    // squares attacked by enemy pawns are not counted towards mobility, since
+    // a piece moving there can be taken by a pawn
+    let their_pawns = theirs[Pieces::Pawn];
+    let their_pawn_attacks =
+        AT::all_pawn_targets::<SideT::Other>(their_pawns, PawnDirections::Left)
+            | AT::all_pawn_targets::<SideT::Other>(their_pawns, PawnDirections::Right);
+    let mobility_area = !(our_occupancy | their_pawn_attacks);
+
+    let king_zone = match theirs[Pieces::King].first() {
+        Some(king) => AT::king_targets(king) | Bitboard::square(king),
+        None => Bitboard::empty(),
+    };
+    let mut attackers = 0;
+    let mut attack_weight = 0;
+
+    let mut terms = PieceTerms::default();
+    for piece in [Pieces::Knight, Pieces::Bishop, Pieces::Rook, Pieces::Queen] {
+        for square in ours[piece].iter() {
+            let targets = match piece {
+                Pieces::Knight => AT::knight_targets(square),
+                Pieces::Bishop => AT::bishop_targets(square, occupancy),
+                Pieces::Rook => AT::rook_targets(square, occupancy),
+                _ => AT::queen_targets(square, occupancy),
+            };
+
+            let mobility = (targets & mobility_area).count_ones() as i32;
+            terms.mobility += MOBILITY[piece] * (mobility - MOBILITY_BASELINE[piece]);
+
+            if targets.intersects(king_zone) {
+                attackers += 1;
+                attack_weight += KING_ATTACK_WEIGHTS[piece];
+            }
+
+            if piece == Pieces::Rook {
+                let file = Bitboard::file(square.file());
+                if !file.intersects(ours[Pieces::Pawn]) {
+                    terms.rook_files += if file.intersects(their_pawns) {
+                        ROOK_SEMI_OPEN_FILE
+                    } else {
+                        ROOK_OPEN_FILE
+                    };
+                }
+            }
+        }
+    }
+
+    let scaling = KING_ATTACK_SCALING[attackers.min(KING_ATTACK_SCALING.len() - 1)];
+    terms.king_attack = PieceValue::new(attack_weight * scaling / 100, 0);
+
+    let bishops = ours[Pieces::Bishop];
+    if bishops.intersects(LIGHT_SQUARES) && bishops.intersects(!LIGHT_SQUARES) {
+        terms.bishop_pair = BISHOP_PAIR;
+    }
+
+    terms
+}
+
+/// occupancy returns the squares occupied by any of a side's pieces
+///
+/// @param: bitboards - piece bitboards of the side
+/// @return: occupancy bitboard of the side
+#[inline]
+fn occupancy(bitboards: &[Bitboard; Pieces::TOTAL]) -> Bitboard {
+    bitboards
+        .iter()
+        .fold(Bitboard::empty(), |occupancy, bitboard| {
+            occupancy | *bitboard
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
+    use chess_kit_primitives::{Black, White};
+
+    fn white_pieces(fen: &str) -> PieceValue {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        let mut bitboards = [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL];
+        for piece in Pieces::ALL {
+            bitboards[Sides::White][piece] = position.get_piece::<White>(piece);
+            bitboards[Sides::Black][piece] = position.get_piece::<Black>(piece);
+        }
+        evaluate_pieces::<White, DefaultAttackTable>(&bitboards).total()
+    }
+
+    #[test]
+    fn rewards_rooks_on_open_and_semi_open_files() {
+        // the rook is boxed in by its own pieces, so only its file changes
+        let closed = white_pieces("4k3/8/8/8/P7/8/N7/RK6 w - - 0 1");
+        let semi_open = white_pieces("4k3/p7/8/8/8/8/N7/RK6 w - - 0 1");
+        let open = white_pieces("4k3/8/8/8/8/8/N7/RK6 w - - 0 1");
+
+        assert_eq!(open - closed, ROOK_OPEN_FILE);
+        assert_eq!(semi_open - closed, ROOK_SEMI_OPEN_FILE);
+    }
+
+    #[test]
+    fn rewards_bishops_on_both_square_colors() {
+        // every second bishop below has exactly the baseline mobility
+        let single = white_pieces("4k3/8/8/8/8/8/8/B3K3 w - - 0 1");
+        let pair = white_pieces("4k3/8/8/8/8/8/8/B3K2B w - - 0 1");
+        let same_color = white_pieces("4k3/8/8/8/8/8/8/B3K1B1 w - - 0 1");
+
+        assert_eq!(pair - single, BISHOP_PAIR);
+        assert_eq!(same_color - single, PieceValue::default());
+    }
+
+    #[test]
+    fn scales_king_attacks_by_the_number_of_attackers() {
+        // the queen and rook both hit the squares around the black king, and
+        // black is to move since the queen gives check
+        let lone_queen = white_pieces("6k1/8/8/8/2Q5/8/8/4K3 b - - 0 1");
+        let queen_and_rook = white_pieces("6k1/8/8/8/2Q5/8/8/4K2R b - - 0 1");
+        let rook = white_pieces("6k1/8/8/8/8/8/8/4K2R b - - 0 1");
+        let king = white_pieces("6k1/8/8/8/8/8/8/4K3 b - - 0 1");
+
+        let attack = (queen_and_rook - lone_queen) - (rook - king);
+        assert_eq!(
+            attack,
+            PieceValue::new(
+                (KING_ATTACK_WEIGHTS[Pieces::Queen] + KING_ATTACK_WEIGHTS[Pieces::Rook])
+                    * KING_ATTACK_SCALING[2]
+                    / 100,
+                0
+            )
+        );
+    }
+}
@@ -1,10 +1,17 @@
 pub mod accumulator;
+pub mod endgame;
+pub mod hce;
+pub mod nnue;
 pub mod noop_eval;
 pub mod psqt;
+pub mod trace;
 
 pub use accumulator::DefaultAccumulator;
+pub use hce::HceEvalState;
+pub use nnue::NnueEvalState;
 pub use noop_eval::NoOpEvalState;
 pub use psqt::PSQTEvalState;
+pub use trace::{EvalTerm, EvalTrace};
 
 use chess_kit_collections::Copyable;
 use chess_kit_position::PositionView;
@@ -13,9 +20,6 @@ use chess_kit_primitives::MoveDelta;
 pub type Score = i32;
 
 pub trait EvalState: Copyable {
-    /// Initializes evaluation state from a complete position view.
-    fn from_position<P: PositionView>(position: &P) -> Self;
-
     /// Applies the piece changes made by one move.
     fn apply(&mut self, delta: MoveDelta);
 
@@ -23,6 +27,29 @@ pub trait EvalState: Copyable {
     ///
     /// @return: evaluation score of this state
     fn score(&mut self) -> Score;
+
+    /// trace breaks the score of this state down into its terms
+    ///
+    /// note: the default implementation reports only the final score, so
+    ///       evaluations with interpretable terms should override it
+    ///
+    /// @param: position - position this state evaluates
+    /// @return: breakdown of the position's evaluation
+    fn trace<P: PositionView>(&self, _position: &P) -> EvalTrace {
+        EvalTrace::new(self.clone().score())
+    }
+}
+
+/// `StandaloneEvalState` is an evaluation state that can be initialized from a
+/// position alone
+///
+/// note: evaluations that need more than the position, like the network of
+///       `NnueEvalState`, provide their own constructors instead
+///
+/// @trait
+pub trait StandaloneEvalState: EvalState {
+    /// Initializes evaluation state from a complete position view.
+    fn from_position<P: PositionView>(position: &P) -> Self;
 }
 
 /// `Accumulator` is a trait that defines a type that provides operations to
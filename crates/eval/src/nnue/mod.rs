@@ -0,0 +1,357 @@
+mod network;
+mod simd;
+
+pub use network::{HIDDEN_SIZE, HiddenLayer, INPUT_SIZE, Network, NetworkError, QA, QB, SCALE};
+
+use std::sync::Arc;
+
+use crate::{EvalState, Score};
+use chess_kit_collections::Copyable;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Black, MoveDelta, PieceDeltaKind, Pieces, Sides, Square, White};
+
+/// `NnueEvalState` is the evaluation state implementation for a quantized
+/// (768 -> N)x2 -> 1 perspective network
+///
+/// The state holds one hidden layer accumulator per perspective, which is
+/// updated incrementally from the piece changes of each move. States are only
+/// created with `with_network`, so every evaluated state has a network
+///
+/// note: default states only fill the spare slots of an accumulator and take
+///       the network of the state they are copied from
+///
+/// @type
+#[derive(Clone)]
+pub struct NnueEvalState {
+    // network the accumulators belong to, or None for a spare slot
+    network: Option<Arc<Network>>,
+    // hidden layer seen from each side's perspective
+    accumulators: [HiddenLayer; Sides::TOTAL],
+    // side to move
+    turn: Sides,
+}
+
+impl NnueEvalState {
+    /// with_network initializes an eval state for the given network from a
+    /// position
+    ///
+    /// note: states derived from this one share the network rather than copy it
+    ///
+    /// @param: network - network to evaluate the position with
+    /// @param: position - position to initialize the state from
+    /// @return: eval state of the position
+    pub fn with_network<P: PositionView>(network: Arc<Network>, position: &P) -> Self {
+        let mut state = Self {
+            turn: position.turn(),
+            ..Self::default()
+        };
+
+        state.accumulators = [network.feature_biases; Sides::TOTAL];
+        for piece in Pieces::ALL {
+            for square in position.get_piece::<White>(piece) {
+                Self::add(
+                    &mut state.accumulators,
+                    &network,
+                    Sides::White,
+                    piece,
+                    square,
+                );
+            }
+            for square in position.get_piece::<Black>(piece) {
+                Self::add(
+                    &mut state.accumulators,
+                    &network,
+                    Sides::Black,
+                    piece,
+                    square,
+                );
+            }
+        }
+        state.network = Some(network);
+
+        state
+    }
+
+    /// add activates the feature of a piece in both perspectives
+    ///
+    /// @param: accumulators - hidden layers of both perspectives
+    /// @param: network - network the accumulators belong to
+    /// @param: side - side of the piece
+    /// @param: piece - piece to add
+    /// @param: square - square of the piece
+    /// @return: void
+    /// @side-effects: modifies the accumulators
+    #[inline]
+    fn add(
+        accumulators: &mut [HiddenLayer; Sides::TOTAL],
+        network: &Network,
+        side: Sides,
+        piece: Pieces,
+        square: Square,
+    ) {
+        for perspective in [Sides::White, Sides::Black] {
+            let feature = feature_index(perspective, side, piece, square);
+            accumulators[perspective].add(&network.feature_weights[feature]);
+        }
+    }
+
+    /// remove deactivates the feature of a piece in both perspectives
+    ///
+    /// @param: accumulators - hidden layers of both perspectives
+    /// @param: network - network the accumulators belong to
+    /// @param: side - side of the piece
+    /// @param: piece - piece to remove
+    /// @param: square - square of the piece
+    /// @return: void
+    /// @side-effects: modifies the accumulators
+    #[inline]
+    fn remove(
+        accumulators: &mut [HiddenLayer; Sides::TOTAL],
+        network: &Network,
+        side: Sides,
+        piece: Pieces,
+        square: Square,
+    ) {
+        for perspective in [Sides::White, Sides::Black] {
+            let feature = feature_index(perspective, side, piece, square);
+            accumulators[perspective].sub(&network.feature_weights[feature]);
+        }
+    }
+}
+
+impl EvalState for NnueEvalState {
+    /// apply updates the accumulators with the piece changes of one move
+    ///
+    /// note: every move adds at least one piece of the side that moved, which
+    ///       is how the side to move is tracked without access to the position
+    ///
+    /// @impl: EvalState::apply
+    #[inline]
+    fn apply(&mut self, delta: MoveDelta) {
+        for change in delta.iter() {
+            let (side, piece, square) = (change.side(), change.piece(), change.square());
+            match change.kind() {
+                PieceDeltaKind::Added => {
+                    self.turn = side.other();
+                    if let Some(network) = &self.network {
+                        Self::add(&mut self.accumulators, network, side, piece, square);
+                    }
+                }
+                PieceDeltaKind::Removed => {
+                    if let Some(network) = &self.network {
+                        Self::remove(&mut self.accumulators, network, side, piece, square);
+                    }
+                }
+            }
+        }
+    }
+
+    /// score runs the output layer over the side to move's and the opponent's
+    /// accumulators
+    ///
+    /// @impl: EvalState::score
+    #[inline]
+    fn score(&mut self) -> Score {
+        let network = self
+            .network
+            .as_ref()
+            .expect("spare slots are copied over before they are scored");
+
+        let us = &self.accumulators[self.turn];
+        let them = &self.accumulators[self.turn.other()];
+        let output = simd::crelu_dot(us, &network.output_weights[0])
+            + simd::crelu_dot(them, &network.output_weights[1])
+            + i32::from(network.output_bias);
+        let score = output * SCALE / (QA * QB);
+
+        // the network scores the position for the side to move, but scores are
+        // reported from white's point of view
+        match self.turn {
+            Sides::White => score,
+            Sides::Black => -score,
+        }
+    }
+}
+
+impl Copyable for NnueEvalState {
+    /// copy_from copies the accumulators of another NnueEvalState into this one
+    ///
+    /// note: the network is only cloned when this slot held another one, so
+    ///       the reference count is not touched on every move of a search
+    ///
+    /// @impl: Copyable::copy_from
+    #[inline]
+    fn copy_from(&mut self, other: &Self) {
+        self.accumulators = other.accumulators;
+        self.turn = other.turn;
+        let same_network = match (&self.network, &other.network) {
+            (Some(ours), Some(theirs)) => Arc::ptr_eq(ours, theirs),
+            (None, None) => true,
+            _ => false,
+        };
+        if !same_network {
+            self.network.clone_from(&other.network);
+        }
+    }
+}
+
+impl Default for NnueEvalState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            network: None,
+            accumulators: [HiddenLayer::ZERO; Sides::TOTAL],
+            turn: Sides::White,
+        }
+    }
+}
+
+/// feature_index returns the input feature of a piece seen from the given
+/// perspective
+///
+/// note: each perspective sees its own pieces first and the board from its own
+///       side, so black's perspective swaps the sides and flips the ranks
+///
+/// @param: perspective - side whose accumulator the feature belongs to
+/// @param: side - side of the piece
+/// @param: piece - piece on the square
+/// @param: square - square of the piece
+/// @return: index of the feature in the network inputs
+#[inline]
+fn feature_index(perspective: Sides, side: Sides, piece: Pieces, square: Square) -> usize {
+    let (relative_side, relative_square) = match perspective {
+        Sides::White => (side.idx(), square.idx()),
+        Sides::Black => (side.other().idx(), square.idx() ^ 56),
+    };
+
+    relative_side * (Square::TOTAL * Pieces::ALL.len())
+        + (piece.idx() - 1) * Square::TOTAL
+        + relative_square
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, PositionMoves, Setup};
+    use chess_kit_primitives::Move;
+    use chess_kit_rng::Rng;
+    use std::sync::LazyLock;
+
+    type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+    /// TEST_NETWORK is a network with deterministic pseudo-random weights
+    static TEST_NETWORK: LazyLock<Arc<Network>> = LazyLock::new(|| {
+        let mut rng = Rng::new(1);
+        let bytes = (0..Network::FILE_SIZE / 2)
+            // keep weights small enough that accumulators cannot overflow
+            .flat_map(|_| (rng.below(401) as i16 - 200).to_le_bytes())
+            .collect::<Vec<_>>();
+        Arc::new(Network::from_bytes(&bytes).unwrap())
+    });
+
+    fn load(fen: &str) -> TestPosition {
+        TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+    }
+
+    fn score(fen: &str) -> Score {
+        NnueEvalState::with_network(TEST_NETWORK.clone(), &load(fen)).score()
+    }
+
+    #[test]
+    fn round_trips_networks_through_the_file_format() {
+        let mut bytes = TEST_NETWORK.to_bytes();
+        assert_eq!(bytes.len(), Network::FILE_SIZE);
+        assert_eq!(Network::from_bytes(&bytes).unwrap(), **TEST_NETWORK);
+
+        bytes.resize(Network::PADDED_FILE_SIZE, 0);
+        assert_eq!(Network::from_bytes(&bytes).unwrap(), **TEST_NETWORK);
+    }
+
+    #[test]
+    fn rejects_network_files_of_the_wrong_size() {
+        let bytes = vec![0; Network::FILE_SIZE - 2];
+
+        assert!(matches!(
+            Network::from_bytes(&bytes),
+            Err(NetworkError::Size { expected, actual })
+                if expected == Network::FILE_SIZE && actual == Network::FILE_SIZE - 2
+        ));
+    }
+
+    #[test]
+    fn color_flipped_positions_negate_the_score() {
+        for (fen, flipped) in [
+            (
+                "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4",
+                "rnbqk2r/pppp1ppp/5n2/2b1p3/4P3/2N2N2/PPPP1PPP/R1BQKB1R b KQkq - 4 4",
+            ),
+            (
+                "4k3/8/8/8/8/8/8/3QK3 w - - 0 1",
+                "3qk3/8/8/8/8/8/8/4K3 b - - 0 1",
+            ),
+        ] {
+            assert_eq!(score(fen), -score(flipped));
+        }
+    }
+
+    #[test]
+    fn incremental_deltas_match_fresh_position_initialization() {
+        for (fen, moves) in [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                vec![
+                    Move::new(Square::E2, Square::E4),
+                    Move::new(Square::D7, Square::D5),
+                    Move::new(Square::E4, Square::D5),
+                    Move::new(Square::G8, Square::F6),
+                ],
+            ),
+            (
+                "r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1",
+                vec![
+                    Move::new(Square::E5, Square::D6).with_en_passant(),
+                    Move::new(Square::E8, Square::G8).with_castle(),
+                    Move::new(Square::B7, Square::A8).with_promotion(Pieces::Knight),
+                    Move::new(Square::F8, Square::A8),
+                    Move::new(Square::E1, Square::G1).with_castle(),
+                ],
+            ),
+        ] {
+            let mut position = load(fen);
+            let mut incremental = NnueEvalState::with_network(TEST_NETWORK.clone(), &position);
+
+            for mv in moves {
+                incremental.apply(position.play_unchecked(mv));
+                let mut fresh = NnueEvalState::with_network(TEST_NETWORK.clone(), &position);
+
+                assert_eq!(incremental.turn, fresh.turn);
+                assert_eq!(incremental.accumulators, fresh.accumulators);
+                assert_eq!(incremental.score(), fresh.score());
+            }
+        }
+    }
+
+    #[test]
+    fn hidden_layers_wrap_around_on_overflow() {
+        let mut layer = HiddenLayer([i16::MAX; HIDDEN_SIZE]);
+        let weights = HiddenLayer([2; HIDDEN_SIZE]);
+
+        layer.add(&weights);
+        assert_eq!(layer.0[0], i16::MIN + 1);
+
+        layer.sub(&weights);
+        assert_eq!(layer, HiddenLayer([i16::MAX; HIDDEN_SIZE]));
+    }
+
+    #[test]
+    fn spare_slots_take_the_network_of_the_copied_state() {
+        let position = load("3qk3/8/8/8/8/8/8/4K3 w - - 0 1");
+        let mut state = NnueEvalState::with_network(TEST_NETWORK.clone(), &position);
+        let mut slot = NnueEvalState::default();
+
+        slot.copy_from(&state);
+        assert!(Arc::ptr_eq(slot.network.as_ref().unwrap(), &TEST_NETWORK));
+        assert_eq!(slot.score(), state.score());
+    }
+}
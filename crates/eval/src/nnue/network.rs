@@ -0,0 +1,233 @@
+use std::fmt::{self, Display};
+use std::io;
+use std::path::Path;
+use std::slice::ChunksExact;
+
+use chess_kit_primitives::Sides;
+
+/// INPUT_SIZE is the number of input features of the network, one per
+/// (side, piece, square) combination
+pub const INPUT_SIZE: usize = 768;
+
+/// HIDDEN_SIZE is the number of neurons in each perspective's hidden layer
+pub const HIDDEN_SIZE: usize = 256;
+
+/// QA is the quantization factor of the feature transformer weights and the
+/// upper bound of the clipped ReLU activation
+pub const QA: i32 = 255;
+
+/// QB is the quantization factor of the output layer weights
+pub const QB: i32 = 64;
+
+/// SCALE converts the network output into centipawns
+pub const SCALE: i32 = 400;
+
+/// HiddenLayer is the quantized value of every neuron in a hidden layer
+///
+/// note: the layer is cache-line aligned so that SIMD code can use aligned
+///       loads on every chunk
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C, align(64))]
+pub struct HiddenLayer(pub [i16; HIDDEN_SIZE]);
+
+impl HiddenLayer {
+    /// ZERO is a hidden layer with every neuron set to zero
+    pub const ZERO: Self = Self([0; HIDDEN_SIZE]);
+
+    /// add adds the given feature weights to the hidden layer
+    ///
+    /// note: the sums wrap around like the i16 lanes of SIMD code do, so that
+    ///       networks with large weights cannot panic in debug builds. a
+    ///       removed feature subtracts the same weights, so the accumulator is
+    ///       still exact once it is back in range
+    ///
+    /// @param: weights - feature weights to add
+    /// @return: void
+    /// @side-effects: modifies the hidden layer
+    #[inline]
+    pub fn add(&mut self, weights: &Self) {
+        for (value, weight) in self.0.iter_mut().zip(&weights.0) {
+            *value = value.wrapping_add(*weight);
+        }
+    }
+
+    /// sub subtracts the given feature weights from the hidden layer
+    ///
+    /// note: the differences wrap around, like the sums of `add`
+    ///
+    /// @param: weights - feature weights to subtract
+    /// @return: void
+    /// @side-effects: modifies the hidden layer
+    #[inline]
+    pub fn sub(&mut self, weights: &Self) {
+        for (value, weight) in self.0.iter_mut().zip(&weights.0) {
+            *value = value.wrapping_sub(*weight);
+        }
+    }
+}
+
+/// NetworkError is returned when a network file cannot be loaded
+///
+/// @type
+#[derive(Debug)]
+pub enum NetworkError {
+    // the network file could not be read
+    Io(io::Error),
+    // the network file does not have the size of a network with this topology
+    Size { expected: usize, actual: usize },
+}
+
+impl Display for NetworkError {
+    /// fmt writes a human-readable description of the network error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to read network: {error}"),
+            Self::Size { expected, actual } => write!(
+                f,
+                "network file is {actual} bytes, expected {expected} bytes for a \
+                 ({INPUT_SIZE} -> {HIDDEN_SIZE})x2 -> 1 network"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for NetworkError {}
+
+impl From<io::Error> for NetworkError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// Network is a quantized (768 -> N)x2 -> 1 perspective network
+///
+/// The network file holds little-endian i16 values in the order the fields are
+/// declared in, optionally zero-padded to a multiple of 64 bytes:
+///
+/// ```text
+/// feature_weights [INPUT_SIZE][HIDDEN_SIZE]
+/// feature_biases  [HIDDEN_SIZE]
+/// output_weights  [2][HIDDEN_SIZE]   (side to move first)
+/// output_bias
+/// ```
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Network {
+    /// weights of each input feature in the hidden layer
+    pub feature_weights: Box<[HiddenLayer; INPUT_SIZE]>,
+    /// biases of the hidden layer
+    pub feature_biases: HiddenLayer,
+    /// output weights of the side to move's and the opponent's hidden layers
+    pub output_weights: [HiddenLayer; Sides::TOTAL],
+    /// bias of the output neuron
+    pub output_bias: i16,
+}
+
+impl Network {
+    /// FILE_SIZE is the number of bytes of an unpadded network file
+    pub const FILE_SIZE: usize =
+        (INPUT_SIZE * HIDDEN_SIZE + HIDDEN_SIZE + Sides::TOTAL * HIDDEN_SIZE + 1)
+            * size_of::<i16>();
+
+    /// PADDED_FILE_SIZE is the number of bytes of a network file padded to a
+    /// multiple of 64 bytes
+    pub const PADDED_FILE_SIZE: usize = Self::FILE_SIZE.next_multiple_of(64);
+
+    /// load reads a network from the file at the given path
+    ///
+    /// @param: path - path of the network file
+    /// @return: loaded network, or the network error
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, NetworkError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// from_bytes reads a network from the contents of a network file
+    ///
+    /// @param: bytes - contents of a network file
+    /// @return: loaded network, or the network error
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, NetworkError> {
+        if bytes.len() != Self::FILE_SIZE && bytes.len() != Self::PADDED_FILE_SIZE {
+            return Err(NetworkError::Size {
+                expected: Self::FILE_SIZE,
+                actual: bytes.len(),
+            });
+        }
+
+        let mut reader = WeightReader {
+            chunks: bytes.chunks_exact(size_of::<i16>()),
+        };
+        let mut feature_weights: Box<[HiddenLayer; INPUT_SIZE]> =
+            vec![HiddenLayer::ZERO; INPUT_SIZE]
+                .into_boxed_slice()
+                .try_into()
+                .expect("the feature weights have one layer per input");
+        for layer in feature_weights.iter_mut() {
+            *layer = reader.layer();
+        }
+
+        Ok(Self {
+            feature_weights,
+            feature_biases: reader.layer(),
+            output_weights: [reader.layer(), reader.layer()],
+            output_bias: reader.value(),
+        })
+    }
+
+    /// to_bytes writes the network in the network file format
+    ///
+    /// @return: unpadded contents of a network file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let layers = self
+            .feature_weights
+            .iter()
+            .chain([&self.feature_biases])
+            .chain(&self.output_weights);
+
+        let mut bytes = Vec::with_capacity(Self::FILE_SIZE);
+        for layer in layers {
+            bytes.extend(layer.0.iter().flat_map(|value| value.to_le_bytes()));
+        }
+        bytes.extend(self.output_bias.to_le_bytes());
+        bytes
+    }
+}
+
+/// WeightReader reads consecutive little-endian i16 values out of a network
+/// file whose size has already been validated
+///
+/// @type
+struct WeightReader<'a> {
+    chunks: ChunksExact<'a, u8>,
+}
+
+impl WeightReader<'_> {
+    /// value reads the next value of the network file
+    ///
+    /// @return: next value
+    /// @side-effects: advances the reader
+    fn value(&mut self) -> i16 {
+        let bytes = self
+            .chunks
+            .next()
+            .expect("the network file size was validated");
+        i16::from_le_bytes([bytes[0], bytes[1]])
+    }
+
+    /// layer reads the next hidden layer of the network file
+    ///
+    /// @return: next hidden layer
+    /// @side-effects: advances the reader
+    fn layer(&mut self) -> HiddenLayer {
+        let mut layer = HiddenLayer::ZERO;
+        for value in &mut layer.0 {
+            *value = self.value();
+        }
+        layer
+    }
+}
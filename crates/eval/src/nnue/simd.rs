@@ -0,0 +1,119 @@
+use super::network::{HiddenLayer, QA};
+
+/// crelu_dot returns the dot product of the clipped ReLU activation of a hidden
+/// layer with the given output weights
+///
+/// note: the hidden layer updates are plain loops over fixed-size arrays that
+///       the compiler already vectorizes, so only the output layer, which
+///       needs saturating activations and widening multiplies, is written
+///       with explicit intrinsics
+///
+/// note: avx2 is detected at runtime, so default builds take the avx2 path on
+///       cpus that support it. the detection is cached by the standard library
+///       and compiles away when avx2 is enabled at compile time (e.g. with
+///       `-C target-cpu=native`), and is cheap next to the dot product itself
+///
+/// @param: values - hidden layer to activate
+/// @param: weights - output weights of the hidden layer
+/// @return: sum of clamp(value, 0, QA) * weight over every neuron
+#[cfg(target_arch = "x86_64")]
+#[inline]
+pub(super) fn crelu_dot(values: &HiddenLayer, weights: &HiddenLayer) -> i32 {
+    if std::arch::is_x86_feature_detected!("avx2") {
+        // SAFETY: the cpu supports avx2
+        return unsafe { crelu_dot_avx2(values, weights) };
+    }
+    crelu_dot_scalar(values, weights)
+}
+
+/// crelu_dot_avx2 is the avx2 implementation of crelu_dot
+///
+/// @param: values - hidden layer to activate
+/// @param: weights - output weights of the hidden layer
+/// @return: sum of clamp(value, 0, QA) * weight over every neuron
+/// @requires: the cpu supports avx2
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+fn crelu_dot_avx2(values: &HiddenLayer, weights: &HiddenLayer) -> i32 {
+    use super::network::HIDDEN_SIZE;
+    use std::arch::x86_64::*;
+
+    const LANES: usize = size_of::<__m256i>() / size_of::<i16>();
+    const _: () = assert!(HIDDEN_SIZE.is_multiple_of(LANES));
+
+    let values = values.0.as_ptr().cast::<__m256i>();
+    let weights = weights.0.as_ptr().cast::<__m256i>();
+
+    // SAFETY: both layers are 64-byte aligned arrays of HIDDEN_SIZE values, so
+    //         every load is an aligned, in-bounds read of LANES values
+    unsafe {
+        let min = _mm256_setzero_si256();
+        let max = _mm256_set1_epi16(QA as i16);
+        let mut sum = _mm256_setzero_si256();
+
+        for chunk in 0..HIDDEN_SIZE / LANES {
+            let value = _mm256_load_si256(values.add(chunk));
+            let weight = _mm256_load_si256(weights.add(chunk));
+            let activated = _mm256_min_epi16(_mm256_max_epi16(value, min), max);
+
+            // note: the activation is at most QA, so each pair of products
+            //       fits in the i32 lanes produced by madd
+            sum = _mm256_add_epi32(sum, _mm256_madd_epi16(activated, weight));
+        }
+
+        let sum = _mm_add_epi32(
+            _mm256_castsi256_si128(sum),
+            _mm256_extracti128_si256::<1>(sum),
+        );
+        let sum = _mm_add_epi32(sum, _mm_shuffle_epi32::<0b01_00_11_10>(sum));
+        let sum = _mm_add_epi32(sum, _mm_shuffle_epi32::<0b10_11_00_01>(sum));
+        _mm_cvtsi128_si32(sum)
+    }
+}
+
+/// crelu_dot returns the dot product of the clipped ReLU activation of a hidden
+/// layer with the given output weights
+///
+/// @param: values - hidden layer to activate
+/// @param: weights - output weights of the hidden layer
+/// @return: sum of clamp(value, 0, QA) * weight over every neuron
+#[cfg(not(target_arch = "x86_64"))]
+#[inline]
+pub(super) fn crelu_dot(values: &HiddenLayer, weights: &HiddenLayer) -> i32 {
+    crelu_dot_scalar(values, weights)
+}
+
+/// crelu_dot_scalar is the portable implementation of crelu_dot
+///
+/// @param: values - hidden layer to activate
+/// @param: weights - output weights of the hidden layer
+/// @return: sum of clamp(value, 0, QA) * weight over every neuron
+#[inline]
+pub(super) fn crelu_dot_scalar(values: &HiddenLayer, weights: &HiddenLayer) -> i32 {
+    values
+        .0
+        .iter()
+        .zip(&weights.0)
+        .map(|(&value, &weight)| i32::from(value).clamp(0, QA) * i32::from(weight))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crelu_dot_matches_the_scalar_implementation() {
+        let mut values = HiddenLayer::ZERO;
+        let mut weights = HiddenLayer::ZERO;
+        for (i, (value, weight)) in values.0.iter_mut().zip(&mut weights.0).enumerate() {
+            *value = (i as i16 * 37) - 3000;
+            *weight = i16::MAX - (i as i16 * 101);
+        }
+
+        assert_eq!(
+            crelu_dot(&values, &weights),
+            crelu_dot_scalar(&values, &weights)
+        );
+    }
+}
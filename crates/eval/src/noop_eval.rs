@@ -1,4 +1,4 @@
-use crate::{EvalState, Score};
+use crate::{EvalState, Score, StandaloneEvalState};
 use chess_kit_collections::Copyable;
 use chess_kit_position::PositionView;
 use chess_kit_primitives::MoveDelta;
@@ -12,14 +12,6 @@ use chess_kit_primitives::MoveDelta;
 pub struct NoOpEvalState;
 
 impl EvalState for NoOpEvalState {
-    /// new creates a new, empty eval state
-    ///
-    /// @impl: EvalState::new
-    #[inline]
-    fn from_position<P: PositionView>(_: &P) -> Self {
-        Self
-    }
-
     #[inline]
     fn apply(&mut self, _: MoveDelta) {}
 
@@ -32,6 +24,16 @@ impl EvalState for NoOpEvalState {
     }
 }
 
+impl StandaloneEvalState for NoOpEvalState {
+    /// new creates a new, empty eval state
+    ///
+    /// @impl: StandaloneEvalState::from_position
+    #[inline]
+    fn from_position<P: PositionView>(_: &P) -> Self {
+        Self
+    }
+}
+
 impl Copyable for NoOpEvalState {
     /// copy_from copies the contents of another NoOpEvalState into this one
     ///
@@ -54,79 +54,73 @@ pub(super) const PIECE_TABLES: [[PSQTable; Pieces::TOTAL]; Sides::TOTAL] = {
 };
 
 #[rustfmt::skip]
-const KING_PSQT: PSQTable = 
-[
-    PV::new(0,-95), PV::new(0,-95), PV::new( 0,-90), PV::new(  0,-90), PV::new(  0,-90), PV::new(0,-90), PV::new( 0,-95), PV::new(0,-95),
-    PV::new(0,-95), PV::new(0,-50), PV::new( 0,-50), PV::new(  0,-50), PV::new(  0,-50), PV::new(0,-50), PV::new( 0,-50), PV::new(0,-95),
-    PV::new(0,-90), PV::new(0,-50), PV::new( 0,-20), PV::new(  0,-20), PV::new(  0,-20), PV::new(0,-20), PV::new( 0,-50), PV::new(0,-90),
-    PV::new(0,-90), PV::new(0,-50), PV::new( 0,-20), PV::new(  0,  0), PV::new(  0,  0), PV::new(0,-20), PV::new( 0,-50), PV::new(0,-90),
-    PV::new(0,-90), PV::new(0,-50), PV::new( 0,-20), PV::new(  0,  0), PV::new(  0,  0), PV::new(0,-20), PV::new( 0,-50), PV::new(0,-90),
-    PV::new(0,-90), PV::new(0,-50), PV::new( 0,-20), PV::new(  0,-20), PV::new(  0,-20), PV::new(0,-20), PV::new( 0,-50), PV::new(0,-90),
-    PV::new(0,-95), PV::new(0,-50), PV::new( 0,-50), PV::new(-10,-50), PV::new(-10,-50), PV::new(0,-50), PV::new( 0,-50), PV::new(0,-95),
-    PV::new(0,-95), PV::new(0,-95), PV::new(20,-90), PV::new(-10,-90), PV::new(-10,-90), PV::new(0,-90), PV::new(20,-95), PV::new(0,-95),
+const KING_PSQT: PSQTable = [
+    PV::new(   0, -95), PV::new(   0, -95), PV::new(   0, -90), PV::new(   0, -90), PV::new(   0, -90), PV::new(   0, -90), PV::new(   0, -95), PV::new(   0, -95),
+    PV::new(   0, -95), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -95),
+    PV::new(   0, -90), PV::new(   0, -50), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -50), PV::new(   0, -90),
+    PV::new(   0, -90), PV::new(   0, -50), PV::new(   0, -20), PV::new(   0,   0), PV::new(   0,   0), PV::new(   0, -20), PV::new(   0, -50), PV::new(   0, -90),
+    PV::new(   0, -90), PV::new(   0, -50), PV::new(   0, -20), PV::new(   0,   0), PV::new(   0,   0), PV::new(   0, -20), PV::new(   0, -50), PV::new(   0, -90),
+    PV::new(   0, -90), PV::new(   0, -50), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -20), PV::new(   0, -50), PV::new(   0, -90),
+    PV::new(   0, -95), PV::new(   0, -50), PV::new(   0, -50), PV::new( -10, -50), PV::new( -10, -50), PV::new(   0, -50), PV::new(   0, -50), PV::new(   0, -95),
+    PV::new(   0, -95), PV::new(   0, -95), PV::new(  20, -90), PV::new( -10, -90), PV::new( -10, -90), PV::new(   0, -90), PV::new(  20, -95), PV::new(   0, -95),
 ];
 
 #[rustfmt::skip]
-const QUEEN_PSQT: PSQTable = 
-[
-    PV::new(870,870), PV::new(880,880), PV::new(890,890), PV::new(890,890), PV::new(890,890), PV::new(890,890), PV::new(880,880), PV::new(870,870),
-    PV::new(880,880), PV::new(890,890), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(890,890), PV::new(880,880),
-    PV::new(890,890), PV::new(895,895), PV::new(910,910), PV::new(910,910), PV::new(910,910), PV::new(910,910), PV::new(895,895), PV::new(890,890),
-    PV::new(890,890), PV::new(895,895), PV::new(910,910), PV::new(920,920), PV::new(920,920), PV::new(910,910), PV::new(895,895), PV::new(890,890),
-    PV::new(890,890), PV::new(895,895), PV::new(910,910), PV::new(920,920), PV::new(920,920), PV::new(910,910), PV::new(895,895), PV::new(890,890),
-    PV::new(890,890), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(890,890),
-    PV::new(880,880), PV::new(890,890), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(895,895), PV::new(890,890), PV::new(880,880),
-    PV::new(870,870), PV::new(880,880), PV::new(890,890), PV::new(890,890), PV::new(890,890), PV::new(890,890), PV::new(880,880), PV::new(870,870),
+const QUEEN_PSQT: PSQTable = [
+    PV::new( 870, 870), PV::new( 880, 880), PV::new( 890, 890), PV::new( 890, 890), PV::new( 890, 890), PV::new( 890, 890), PV::new( 880, 880), PV::new( 870, 870),
+    PV::new( 880, 880), PV::new( 890, 890), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 890, 890), PV::new( 880, 880),
+    PV::new( 890, 890), PV::new( 895, 895), PV::new( 910, 910), PV::new( 910, 910), PV::new( 910, 910), PV::new( 910, 910), PV::new( 895, 895), PV::new( 890, 890),
+    PV::new( 890, 890), PV::new( 895, 895), PV::new( 910, 910), PV::new( 920, 920), PV::new( 920, 920), PV::new( 910, 910), PV::new( 895, 895), PV::new( 890, 890),
+    PV::new( 890, 890), PV::new( 895, 895), PV::new( 910, 910), PV::new( 920, 920), PV::new( 920, 920), PV::new( 910, 910), PV::new( 895, 895), PV::new( 890, 890),
+    PV::new( 890, 890), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 890, 890),
+    PV::new( 880, 880), PV::new( 890, 890), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 895, 895), PV::new( 890, 890), PV::new( 880, 880),
+    PV::new( 870, 870), PV::new( 880, 880), PV::new( 890, 890), PV::new( 890, 890), PV::new( 890, 890), PV::new( 890, 890), PV::new( 880, 880), PV::new( 870, 870),
 ];
 
 #[rustfmt::skip]
-const ROOK_PSQT: PSQTable = 
-[
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(515,515), PV::new(515,515), PV::new(515,515), PV::new(520,520), PV::new(520,520), PV::new(515,515), PV::new(515,515), PV::new(515,515),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(500,500),
-    PV::new(500,500), PV::new(500,500), PV::new(500,500), PV::new(510,510), PV::new(510,510), PV::new(510,510), PV::new(500,500), PV::new(500,500),
+const ROOK_PSQT: PSQTable = [
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 515, 515), PV::new( 515, 515), PV::new( 515, 515), PV::new( 520, 520), PV::new( 520, 520), PV::new( 515, 515), PV::new( 515, 515), PV::new( 515, 515),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500),
+    PV::new( 500, 500), PV::new( 500, 500), PV::new( 500, 500), PV::new( 510, 510), PV::new( 510, 510), PV::new( 510, 510), PV::new( 500, 500), PV::new( 500, 500),
 ];
 
 #[rustfmt::skip]
-const BISHOP_PSQT: PSQTable = 
-[
-    PV::new(300,300), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(300,300),
-    PV::new(305,305), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(320,320), PV::new(305,305),
-    PV::new(310,310), PV::new(320,320), PV::new(320,320), PV::new(325,325), PV::new(325,325), PV::new(320,320), PV::new(320,320), PV::new(310,310),
-    PV::new(310,310), PV::new(330,330), PV::new(330,330), PV::new(350,350), PV::new(350,350), PV::new(330,330), PV::new(330,330), PV::new(310,310),
-    PV::new(325,325), PV::new(325,325), PV::new(330,330), PV::new(345,345), PV::new(345,345), PV::new(330,330), PV::new(325,325), PV::new(325,325),
-    PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(330,330), PV::new(330,330), PV::new(325,325), PV::new(325,325), PV::new(325,325),
-    PV::new(310,310), PV::new(325,325), PV::new(325,325), PV::new(330,330), PV::new(330,330), PV::new(325,325), PV::new(325,325), PV::new(310,310),
-    PV::new(300,300), PV::new(310,310), PV::new(310,310), PV::new(310,310), PV::new(310,310), PV::new(310,310), PV::new(310,310), PV::new(300,300),
+const BISHOP_PSQT: PSQTable = [
+    PV::new( 300, 300), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 300, 300),
+    PV::new( 305, 305), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 320, 320), PV::new( 305, 305),
+    PV::new( 310, 310), PV::new( 320, 320), PV::new( 320, 320), PV::new( 325, 325), PV::new( 325, 325), PV::new( 320, 320), PV::new( 320, 320), PV::new( 310, 310),
+    PV::new( 310, 310), PV::new( 330, 330), PV::new( 330, 330), PV::new( 350, 350), PV::new( 350, 350), PV::new( 330, 330), PV::new( 330, 330), PV::new( 310, 310),
+    PV::new( 325, 325), PV::new( 325, 325), PV::new( 330, 330), PV::new( 345, 345), PV::new( 345, 345), PV::new( 330, 330), PV::new( 325, 325), PV::new( 325, 325),
+    PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 330, 330), PV::new( 330, 330), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325),
+    PV::new( 310, 310), PV::new( 325, 325), PV::new( 325, 325), PV::new( 330, 330), PV::new( 330, 330), PV::new( 325, 325), PV::new( 325, 325), PV::new( 310, 310),
+    PV::new( 300, 300), PV::new( 310, 310), PV::new( 310, 310), PV::new( 310, 310), PV::new( 310, 310), PV::new( 310, 310), PV::new( 310, 310), PV::new( 300, 300),
 ];
 
 #[rustfmt::skip]
-const KNIGHT_PSQT: PSQTable =     
-[
-    PV::new(290,290), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(290,290),
-    PV::new(300,300), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(300,300),
-    PV::new(300,300), PV::new(305,305), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(305,305), PV::new(300,300),
-    PV::new(300,300), PV::new(305,305), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(305,305), PV::new(300,300),
-    PV::new(300,300), PV::new(305,305), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(305,305), PV::new(300,300),
-    PV::new(300,300), PV::new(305,305), PV::new(320,320), PV::new(325,325), PV::new(325,325), PV::new(325,325), PV::new(305,305), PV::new(300,300),
-    PV::new(300,300), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(305,305), PV::new(300,300),
-    PV::new(290,290), PV::new(310,310), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(300,300), PV::new(310,310), PV::new(290,290),
+const KNIGHT_PSQT: PSQTable = [
+    PV::new( 290, 290), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 290, 290),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 320, 320), PV::new( 325, 325), PV::new( 325, 325), PV::new( 325, 325), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 300, 300), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 305, 305), PV::new( 300, 300),
+    PV::new( 290, 290), PV::new( 310, 310), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 300, 300), PV::new( 310, 310), PV::new( 290, 290),
 ];
 
 #[rustfmt::skip]
-const PAWN_PSQT: PSQTable = 
-[
-    PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100),
-    PV::new(160,160), PV::new(160,160), PV::new(160,160), PV::new(160,160), PV::new(170,170), PV::new(160,160), PV::new(160,160), PV::new(160,160),
-    PV::new(140,140), PV::new(140,140), PV::new(140,140), PV::new(150,150), PV::new(160,160), PV::new(140,140), PV::new(140,140), PV::new(140,140),
-    PV::new(120,120), PV::new(120,120), PV::new(120,120), PV::new(140,140), PV::new(150,150), PV::new(120,120), PV::new(120,120), PV::new(120,120),
-    PV::new(105,105), PV::new(105,105), PV::new(115,115), PV::new(130,130), PV::new(140,140), PV::new(110,110), PV::new(105,105), PV::new(105,105),
-    PV::new(105,105), PV::new(105,105), PV::new(110,110), PV::new(120,120), PV::new(130,130), PV::new(105,105), PV::new(105,105), PV::new(105,105),
-    PV::new(105,105), PV::new(105,105), PV::new(105,105), PV::new( 70, 70), PV::new( 70, 70), PV::new(105,105), PV::new(105,105), PV::new(105,105),
-    PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100), PV::new(100,100),
+const PAWN_PSQT: PSQTable = [
+    PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100),
+    PV::new( 160, 160), PV::new( 160, 160), PV::new( 160, 160), PV::new( 160, 160), PV::new( 170, 170), PV::new( 160, 160), PV::new( 160, 160), PV::new( 160, 160),
+    PV::new( 140, 140), PV::new( 140, 140), PV::new( 140, 140), PV::new( 150, 150), PV::new( 160, 160), PV::new( 140, 140), PV::new( 140, 140), PV::new( 140, 140),
+    PV::new( 120, 120), PV::new( 120, 120), PV::new( 120, 120), PV::new( 140, 140), PV::new( 150, 150), PV::new( 120, 120), PV::new( 120, 120), PV::new( 120, 120),
+    PV::new( 105, 105), PV::new( 105, 105), PV::new( 115, 115), PV::new( 130, 130), PV::new( 140, 140), PV::new( 110, 110), PV::new( 105, 105), PV::new( 105, 105),
+    PV::new( 105, 105), PV::new( 105, 105), PV::new( 110, 110), PV::new( 120, 120), PV::new( 130, 130), PV::new( 105, 105), PV::new( 105, 105), PV::new( 105, 105),
+    PV::new( 105, 105), PV::new( 105, 105), PV::new( 105, 105), PV::new(  70,  70), PV::new(  70,  70), PV::new( 105, 105), PV::new( 105, 105), PV::new( 105, 105),
+    PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100), PV::new( 100, 100),
 ];
@@ -1,14 +1,19 @@
 mod constants;
+mod parameters;
 mod piece_values;
 mod scores;
 
+pub use parameters::{PARAMETER_COUNT, PSQTFeatures, current_parameters, write_constants};
+pub use piece_values::PieceValue;
+
 use constants::{PHASE_VALUES, PIECE_TABLES};
-use piece_values::PieceValue;
 
-use crate::{EvalState, Score};
+use crate::{EvalState, EvalTrace, Score, StandaloneEvalState};
 use chess_kit_collections::Copyable;
 use chess_kit_position::PositionView;
-use chess_kit_primitives::{MoveDelta, PieceDeltaKind, Pieces, Sides, Square};
+use chess_kit_primitives::{
+    Bitboard, Black, MoveDelta, PieceDeltaKind, Pieces, Sides, Square, White,
+};
 
 pub type GamePhase = i16;
 pub type PSQTable = [PieceValue; Square::TOTAL];
@@ -26,27 +31,6 @@ pub struct PSQTEvalState {
 }
 
 impl EvalState for PSQTEvalState {
-    /// Initializes the PSQT accumulator from a position.
-    #[inline]
-    fn from_position<P: PositionView>(position: &P) -> Self {
-        let mut state = Self {
-            phase: 0,
-            scores: [PieceValue::default(); Sides::TOTAL],
-            score: 0,
-        };
-
-        for piece in Pieces::ALL {
-            for square in position.get_piece::<chess_kit_primitives::White>(piece) {
-                state.add(Sides::White, piece, square);
-            }
-            for square in position.get_piece::<chess_kit_primitives::Black>(piece) {
-                state.add(Sides::Black, piece, square);
-            }
-        }
-
-        state
-    }
-
     #[inline]
     fn apply(&mut self, delta: MoveDelta) {
         for change in delta.iter() {
@@ -70,9 +54,73 @@ impl EvalState for PSQTEvalState {
 
         self.score
     }
+
+    /// trace breaks the PSQT score down into the material and placement of
+    /// each piece type
+    ///
+    /// @impl: EvalState::trace
+    fn trace<P: PositionView>(&self, position: &P) -> EvalTrace {
+        let mut state = *self;
+        let mut trace = EvalTrace::new(state.score());
+        trace.phase = Some(state.weighted_phase());
+        Self::trace_pieces(position, &mut trace);
+        trace
+    }
+}
+
+impl StandaloneEvalState for PSQTEvalState {
+    /// Initializes the PSQT accumulator from a position.
+    #[inline]
+    fn from_position<P: PositionView>(position: &P) -> Self {
+        let mut state = Self {
+            phase: 0,
+            scores: [PieceValue::default(); Sides::TOTAL],
+            score: 0,
+        };
+
+        for piece in Pieces::ALL {
+            for square in position.get_piece::<White>(piece) {
+                state.add(Sides::White, piece, square);
+            }
+            for square in position.get_piece::<Black>(piece) {
+                state.add(Sides::Black, piece, square);
+            }
+        }
+
+        state
+    }
 }
 
 impl PSQTEvalState {
+    /// trace_pieces adds the material and placement score of each piece type
+    /// to a trace
+    ///
+    /// @param: position - position to trace
+    /// @param: trace - trace to add the piece terms to
+    /// @return: void
+    /// @side-effects: modifies the trace
+    pub(crate) fn trace_pieces<P: PositionView>(position: &P, trace: &mut EvalTrace) {
+        for (piece, name) in [
+            (Pieces::Pawn, "Pawns"),
+            (Pieces::Knight, "Knights"),
+            (Pieces::Bishop, "Bishops"),
+            (Pieces::Rook, "Rooks"),
+            (Pieces::Queen, "Queens"),
+            (Pieces::King, "Kings"),
+        ] {
+            let table = |side: Sides, pieces: Bitboard| {
+                pieces.iter().fold(PieceValue::default(), |score, square| {
+                    score + PIECE_TABLES[side][piece][square]
+                })
+            };
+            trace.push(
+                name,
+                table(Sides::White, position.get_piece::<White>(piece)).into(),
+                table(Sides::Black, position.get_piece::<Black>(piece)).into(),
+            );
+        }
+    }
+
     #[inline]
     fn add(&mut self, side: Sides, piece: Pieces, square: Square) {
         self.phase += PHASE_VALUES[piece];
@@ -117,7 +165,7 @@ mod tests {
     type TestPosition = DefaultPosition<DefaultAttackTable>;
 
     fn score(fen: &str) -> Score {
-        let position = TestPosition::from(Setup::from(Fen::try_from(fen).unwrap()));
+        let position = TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap();
         PSQTEvalState::from_position(&position).score()
     }
 
@@ -165,4 +213,21 @@ mod tests {
             assert_eq!(incremental.score(), fresh.score());
         }
     }
+
+    #[test]
+    fn traces_break_the_score_down_by_piece_type() {
+        let position = TestPosition::try_from(Setup::from(
+            Fen::try_from("r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4")
+                .unwrap(),
+        ))
+        .unwrap();
+        let trace = PSQTEvalState::from_position(&position).trace(&position);
+
+        let total = trace.terms.iter().fold((0, 0), |total, term| {
+            (total.0 + term.total().0, total.1 + term.total().1)
+        });
+        assert_eq!(trace.terms.len(), Pieces::ALL.len());
+        assert_eq!(trace.tapered(total), Some(trace.score));
+        assert_eq!(trace.score, PSQTEvalState::from_position(&position).score());
+    }
 }
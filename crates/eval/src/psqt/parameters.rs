@@ -0,0 +1,194 @@
+use super::PieceValue;
+use super::constants::{MIDDLEGAME_PHASE_MAX, MIDDLEGAME_PHASE_MIN, PHASE_VALUES, PIECE_TABLES};
+use chess_kit_position::Setup;
+use chess_kit_primitives::{Pieces, Sides, Square};
+use std::fmt::Write;
+
+/// PARAMETER_COUNT is the number of values per game phase of the PSQT
+/// evaluation, one per piece-square table entry
+pub const PARAMETER_COUNT: usize = Pieces::ALL.len() * Square::TOTAL;
+
+/// CONSTANTS is the source of the piece-square tables, whose header is kept
+/// when the tables are written back out
+const CONSTANTS: &str = include_str!("constants.rs");
+
+/// TABLE_MARKER precedes every piece-square table in `CONSTANTS`
+const TABLE_MARKER: &str = "\n#[rustfmt::skip]\n";
+
+/// `PSQTFeatures` is the sparse input of the PSQT evaluation of a position
+///
+/// note: the evaluation is linear in the table entries, so the features score
+///       a position under any table values the same way `PSQTEvalState` scores
+///       it under the current ones
+///
+/// @type
+#[derive(Clone, Debug, PartialEq)]
+pub struct PSQTFeatures {
+    /// parameter index and coefficient of each piece
+    pub terms: Vec<(u16, i8)>,
+    /// middlegame weight of the position, in [0, 1]
+    pub phase: f64,
+}
+
+impl PSQTFeatures {
+    /// evaluate returns the PSQT evaluation of the position under the given
+    /// table values
+    ///
+    /// @param: middlegame - middlegame value of each parameter
+    /// @param: endgame - endgame value of each parameter
+    /// @return: unrounded score from white's point of view
+    #[inline]
+    pub fn evaluate(
+        &self,
+        middlegame: &[f64; PARAMETER_COUNT],
+        endgame: &[f64; PARAMETER_COUNT],
+    ) -> f64 {
+        let (middlegame, endgame) =
+            self.terms
+                .iter()
+                .fold((0.0, 0.0), |(mid_score, end_score), &(index, sign)| {
+                    let sign = f64::from(sign);
+                    (
+                        mid_score + sign * middlegame[usize::from(index)],
+                        end_score + sign * endgame[usize::from(index)],
+                    )
+                });
+
+        middlegame * self.phase + endgame * (1.0 - self.phase)
+    }
+}
+
+impl From<&Setup> for PSQTFeatures {
+    /// from extracts the PSQT features of a position, with white pieces
+    /// counting positively and black pieces negatively
+    ///
+    /// @param: setup - position to extract the features of
+    /// @return: features of the position
+    fn from(setup: &Setup) -> Self {
+        let mut terms = Vec::new();
+        let mut phase = 0;
+
+        for (square, occupant) in setup.pieces().iter().enumerate() {
+            let Some((side, piece)) = *occupant else {
+                continue;
+            };
+
+            // the tables are written from black's perspective, so white's
+            // entries are found on the inverted square like in PIECE_TABLES
+            phase += PHASE_VALUES[piece];
+            terms.push(match side {
+                Sides::White => (parameter_index(piece, Square::INVERTED[square].idx()), 1),
+                Sides::Black => (parameter_index(piece, square), -1),
+            });
+        }
+
+        let phase = (phase - MIDDLEGAME_PHASE_MIN) as f64
+            / (MIDDLEGAME_PHASE_MAX - MIDDLEGAME_PHASE_MIN) as f64;
+        Self {
+            terms,
+            phase: phase.clamp(0.0, 1.0),
+        }
+    }
+}
+
+/// current_parameters returns the entries of the current piece-square tables,
+/// indexed like the terms of `PSQTFeatures`
+///
+/// @return: value of each parameter
+pub fn current_parameters() -> [PieceValue; PARAMETER_COUNT] {
+    let mut parameters = [PieceValue::default(); PARAMETER_COUNT];
+
+    for piece in Pieces::ALL {
+        // black's tables are the tables as written, since they are not
+        // inverted for its perspective
+        for (square, value) in PIECE_TABLES[Sides::Black][piece].iter().enumerate() {
+            parameters[usize::from(parameter_index(piece, square))] = *value;
+        }
+    }
+
+    parameters
+}
+
+/// write_constants writes piece-square table entries as the contents of
+/// `psqt/constants.rs`
+///
+/// @param: parameters - value of each parameter, indexed like the terms of
+///                      `PSQTFeatures`
+/// @return: contents of `psqt/constants.rs` with the given tables
+pub fn write_constants(parameters: &[PieceValue; PARAMETER_COUNT]) -> String {
+    let (header, _) = CONSTANTS
+        .split_once(TABLE_MARKER)
+        .expect("the constants define the piece-square tables");
+    let mut constants = header.to_string();
+
+    for (name, piece) in [
+        ("KING", Pieces::King),
+        ("QUEEN", Pieces::Queen),
+        ("ROOK", Pieces::Rook),
+        ("BISHOP", Pieces::Bishop),
+        ("KNIGHT", Pieces::Knight),
+        ("PAWN", Pieces::Pawn),
+    ] {
+        let _ = writeln!(constants, "{TABLE_MARKER}const {name}_PSQT: PSQTable = [");
+        for rank in 0..8 {
+            let row = (0..8)
+                .map(|file| {
+                    let value = parameters[usize::from(parameter_index(piece, rank * 8 + file))];
+                    format!("PV::new({:>4},{:>4})", value.middlegame(), value.endgame())
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(constants, "    {row},");
+        }
+        constants.push_str("];\n");
+    }
+
+    constants
+}
+
+/// parameter_index returns the parameter of a piece's table entry
+///
+/// @param: piece - piece of the table
+/// @param: entry - index of the entry in the table as written
+/// @return: index of the parameter
+#[inline]
+fn parameter_index(piece: Pieces, entry: usize) -> u16 {
+    ((piece.idx() - 1) * Square::TOTAL + entry) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EvalState, PSQTEvalState, StandaloneEvalState};
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen};
+
+    #[test]
+    fn features_evaluate_positions_like_the_psqt_eval_state() {
+        let parameters = current_parameters();
+        let middlegame = parameters.map(|value| f64::from(value.middlegame()));
+        let endgame = parameters.map(|value| f64::from(value.endgame()));
+
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "3qk3/8/8/8/8/8/8/4K3 b - - 0 1",
+        ] {
+            let setup = Setup::from(Fen::try_from(fen).unwrap());
+            let position = DefaultPosition::<DefaultAttackTable>::try_from(setup.clone()).unwrap();
+            let expected = PSQTEvalState::from_position(&position).score();
+
+            let actual = PSQTFeatures::from(&setup).evaluate(&middlegame, &endgame);
+            assert!(
+                (actual - f64::from(expected)).abs() <= 1.0,
+                "{fen}: expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn writes_the_current_constants_back_out_unchanged() {
+        assert_eq!(write_constants(&current_parameters()), CONSTANTS);
+    }
+}
@@ -1,6 +1,7 @@
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use crate::Score;
+use std::ops::{Add, AddAssign, Mul, Sub, SubAssign};
 
-#[derive(Copy, Clone, Default, Debug)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct PieceValue(i32, i32);
 
 impl PieceValue {
@@ -31,6 +32,17 @@ impl PieceValue {
     }
 }
 
+impl From<PieceValue> for (Score, Score) {
+    /// from returns the middlegame and endgame values of a piece value
+    ///
+    /// @param: value - piece value to split
+    /// @return: middlegame and endgame values
+    #[inline]
+    fn from(value: PieceValue) -> Self {
+        (value.0, value.1)
+    }
+}
+
 // ================================================
 //              ARITHMETIC OPERATIONS
 // ================================================
@@ -68,3 +80,12 @@ impl SubAssign for PieceValue {
         self.1 -= other.1;
     }
 }
+
+impl Mul<i32> for PieceValue {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, scale: i32) -> Self::Output {
+        Self(self.0 * scale, self.1 * scale)
+    }
+}
@@ -1,4 +1,5 @@
 use super::PSQTEvalState;
+use super::PieceValue;
 use super::constants::{MIDDLEGAME_PHASE_MAX, MIDDLEGAME_PHASE_MIN};
 use crate::Score;
 use chess_kit_primitives::Sides;
@@ -12,7 +13,7 @@ impl PSQTEvalState {
     /// @return: weighted phase value
     /// @side-effects: modifies the eval state
     #[inline]
-    pub(super) fn weighted_phase(&self) -> f32 {
+    pub(crate) fn weighted_phase(&self) -> f32 {
         // interpolate the phase value between the min and max
         let result = (self.phase - MIDDLEGAME_PHASE_MIN) as f32 / Self::MIDDLEGAME_PHASE_DENOM;
 
@@ -44,4 +45,17 @@ impl PSQTEvalState {
         self.score =
             (mid_score * weighted_phase + end_score * (1.0 - weighted_phase)).round() as Score;
     }
+
+    /// tapered interpolates a middlegame and endgame value pair based on the
+    /// current weighted phase value
+    ///
+    /// @param: value - value pair to interpolate
+    /// @return: interpolated score
+    #[inline]
+    pub(crate) fn tapered(&self, value: PieceValue) -> Score {
+        let weighted_phase = self.weighted_phase();
+        (value.middlegame() as f32 * weighted_phase
+            + value.endgame() as f32 * (1.0 - weighted_phase))
+            .round() as Score
+    }
 }
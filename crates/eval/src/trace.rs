@@ -0,0 +1,167 @@
+use crate::Score;
+use chess_kit_primitives::Sides;
+use std::fmt;
+
+/// `EvalTerm` is one named term of an evaluation breakdown
+///
+/// note: scores are middlegame and endgame pairs, which are blended with the
+///       phase of the trace
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EvalTerm {
+    /// display name of the term
+    pub name: &'static str,
+    /// untapered score of the term per side
+    pub scores: [(Score, Score); Sides::TOTAL],
+}
+
+impl EvalTerm {
+    /// total returns the untapered score of the term from white's perspective
+    ///
+    /// @return: white's score minus black's score
+    #[inline]
+    pub fn total(&self) -> (Score, Score) {
+        let [(white_mg, white_eg), (black_mg, black_eg)] = self.scores;
+        (white_mg - black_mg, white_eg - black_eg)
+    }
+}
+
+/// `EvalTrace` is a breakdown of how an evaluation arrived at its score
+///
+/// note: evaluations without interpretable terms report only their final score
+///
+/// @type
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EvalTrace {
+    /// terms of the evaluation in display order
+    pub terms: Vec<EvalTerm>,
+    /// middlegame weight used to blend the terms
+    pub phase: Option<f32>,
+    /// final score from white's perspective
+    pub score: Score,
+}
+
+impl EvalTrace {
+    /// new creates a trace of an evaluation without any terms
+    ///
+    /// @param: score - final score from white's perspective
+    /// @return: new evaluation trace
+    pub fn new(score: Score) -> Self {
+        Self {
+            terms: Vec::new(),
+            phase: None,
+            score,
+        }
+    }
+
+    /// push adds a term to the end of the trace
+    ///
+    /// @param: name - display name of the term
+    /// @param: white - untapered middlegame and endgame score of the term for
+    ///                 white
+    /// @param: black - untapered middlegame and endgame score of the term for
+    ///                 black
+    /// @return: void
+    /// @side-effects: modifies the trace
+    pub fn push(&mut self, name: &'static str, white: (Score, Score), black: (Score, Score)) {
+        self.terms.push(EvalTerm {
+            name,
+            scores: [white, black],
+        });
+    }
+
+    /// term returns the term with the given name
+    ///
+    /// @param: name - display name of the term
+    /// @return: the term, or None if the trace has no such term
+    pub fn term(&self, name: &str) -> Option<&EvalTerm> {
+        self.terms.iter().find(|term| term.name == name)
+    }
+
+    /// tapered blends a middlegame and endgame value pair with the trace's
+    /// phase
+    ///
+    /// @param: value - middlegame and endgame value pair to blend
+    /// @return: blended score, or None if the trace has no phase
+    pub fn tapered(&self, (middlegame, endgame): (Score, Score)) -> Option<Score> {
+        self.phase.map(|phase| {
+            (middlegame as f32 * phase + endgame as f32 * (1.0 - phase)).round() as Score
+        })
+    }
+}
+
+impl fmt::Display for EvalTrace {
+    /// fmt formats the trace as a table of terms followed by the final score
+    ///
+    /// @impl: fmt::Display::fmt
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.terms.is_empty() {
+            writeln!(
+                f,
+                "{:>16} | {:^13} | {:^13} | {:^13} | {:>7}",
+                "Term", "White", "Black", "Total", "Blended"
+            )?;
+            writeln!(
+                f,
+                "{:>16} | {:>6} {:>6} | {:>6} {:>6} | {:>6} {:>6} |",
+                "", "MG", "EG", "MG", "EG", "MG", "EG"
+            )?;
+            writeln!(f, "{}", "-".repeat(76))?;
+
+            for term in &self.terms {
+                let [white, black] = term.scores;
+                let total = term.total();
+                write!(
+                    f,
+                    "{:>16} | {:>6} {:>6} | {:>6} {:>6} | {:>6} {:>6} |",
+                    term.name, white.0, white.1, black.0, black.1, total.0, total.1
+                )?;
+                match self.tapered(total) {
+                    Some(blended) => writeln!(f, " {blended:>7}")?,
+                    None => writeln!(f, " {:>7}", "-")?,
+                }
+            }
+            writeln!(f, "{}", "-".repeat(76))?;
+        }
+
+        if let Some(phase) = self.phase {
+            writeln!(
+                f,
+                "Phase: {:.0}% middlegame, {:.0}% endgame",
+                phase * 100.0,
+                (1.0 - phase) * 100.0
+            )?;
+        }
+        write!(f, "Final evaluation: {:+} cp (white side)", self.score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_terms_as_a_table() {
+        let mut trace = EvalTrace::new(42);
+        trace.phase = Some(0.5);
+        trace.push("Mobility", (30, 10), (10, 20));
+
+        let table = trace.to_string();
+        let lines = table.lines().collect::<Vec<_>>();
+        assert_eq!(
+            lines[3],
+            "        Mobility |     30     10 |     10     20 |     20    -10 |       5"
+        );
+        assert!(table.contains("Phase: 50% middlegame, 50% endgame"));
+        assert!(table.ends_with("Final evaluation: +42 cp (white side)"));
+    }
+
+    #[test]
+    fn formats_traces_without_terms_as_the_final_score() {
+        assert_eq!(
+            EvalTrace::new(-7).to_string(),
+            "Final evaluation: -7 cp (white side)"
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_eval::endgame::EndgameEvalState;
+use chess_kit_eval::nnue::{HIDDEN_SIZE, INPUT_SIZE, Network};
+use chess_kit_eval::{
+    EvalState, HceEvalState, NnueEvalState, NoOpEvalState, PSQTEvalState, Score,
+    StandaloneEvalState,
+};
+use chess_kit_position::{DefaultPosition, Fen, Setup};
+use chess_kit_rng::Rng;
+use std::sync::Arc;
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+/// corpus loads the positions of the symmetry fixture
+///
+/// @return: line number and setup of each position
+fn corpus() -> Vec<(usize, Setup)> {
+    include_str!("fixtures/symmetry.epd")
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            let fen = Fen::try_from(line)
+                .unwrap_or_else(|error| panic!("line {line_number}: '{line}': {error}"));
+            (line_number, Setup::from(fen))
+        })
+        .collect()
+}
+
+/// score evaluates a setup from scratch
+///
+/// @param: evaluate - evaluation to score the position of the setup with
+/// @param: setup - setup to evaluate
+/// @return: score of the setup from white's point of view
+fn score(evaluate: &impl Fn(&TestPosition) -> Score, setup: &Setup) -> Score {
+    let position = TestPosition::try_from(setup.clone())
+        .unwrap_or_else(|error| panic!("'{}': {error}", Fen::from(setup.clone())));
+    evaluate(&position)
+}
+
+/// evaluate scores a position with a freshly initialized evaluation state
+///
+/// @marker: EvalStateT - evaluation to score the position with
+/// @param: position - position to evaluate
+/// @return: score of the position from white's point of view
+fn evaluate<EvalStateT: StandaloneEvalState>(position: &TestPosition) -> Score {
+    EvalStateT::from_position(position).score()
+}
+
+/// assert_flip_symmetric asserts that flipping the colors of every corpus
+/// position negates its score
+///
+/// @param: evaluate - evaluation under test
+/// @return: void
+/// @side-effects: panics on the first position whose score is not negated
+fn assert_flip_symmetric(evaluate: impl Fn(&TestPosition) -> Score) {
+    for (line_number, setup) in corpus() {
+        let flipped = setup.flipped();
+        assert_eq!(
+            score(&evaluate, &flipped),
+            -score(&evaluate, &setup),
+            "line {line_number}: '{}' flipped to '{}'",
+            Fen::from(setup.clone()),
+            Fen::from(flipped.clone()),
+        );
+    }
+}
+
+/// assert_mirror_symmetric asserts that mirroring every corpus position that
+/// allows it keeps its score, with and without flipping its colors
+///
+/// @param: evaluate - evaluation under test
+/// @return: void
+/// @side-effects: panics on the first position whose score changes
+fn assert_mirror_symmetric(evaluate: impl Fn(&TestPosition) -> Score) {
+    let mut mirrored_positions = 0;
+    for (line_number, setup) in corpus() {
+        let Some(mirrored) = setup.mirrored() else {
+            continue;
+        };
+        mirrored_positions += 1;
+
+        let expected = score(&evaluate, &setup);
+        assert_eq!(
+            score(&evaluate, &mirrored),
+            expected,
+            "line {line_number}: '{}' mirrored to '{}'",
+            Fen::from(setup.clone()),
+            Fen::from(mirrored.clone()),
+        );
+        assert_eq!(
+            score(&evaluate, &mirrored.flipped()),
+            -expected,
+            "line {line_number}: '{}' mirrored and flipped to '{}'",
+            Fen::from(setup.clone()),
+            Fen::from(mirrored.flipped()),
+        );
+    }
+    assert!(mirrored_positions > 0, "no corpus position can be mirrored");
+}
+
+/// assert_symmetric asserts that an evaluation is symmetric under every
+/// board transform
+///
+/// @param: evaluate - evaluation under test
+/// @return: void
+/// @side-effects: panics on the first asymmetric position
+fn assert_symmetric(evaluate: impl Fn(&TestPosition) -> Score) {
+    assert_flip_symmetric(&evaluate);
+    assert_mirror_symmetric(&evaluate);
+}
+
+// note: the piece-square tables favour the squares of castled kings and rooks
+//       and of the central pawns on one wing, so the evaluations built on them
+//       are only color-symmetric
+
+#[test]
+fn psqt_evaluation_is_color_symmetric() {
+    assert_flip_symmetric(evaluate::<PSQTEvalState>);
+}
+
+#[test]
+fn hce_evaluation_is_color_symmetric() {
+    assert_flip_symmetric(evaluate::<HceEvalState<DefaultAttackTable>>);
+}
+
+#[test]
+fn no_op_evaluation_is_symmetric() {
+    assert_symmetric(evaluate::<NoOpEvalState>);
+}
+
+#[test]
+fn endgame_evaluation_is_color_symmetric() {
+    assert_flip_symmetric(evaluate::<EndgameEvalState<PSQTEvalState>>);
+}
+
+#[test]
+fn endgame_knowledge_is_symmetric() {
+    // note: without a wrapped score only the specialized knowledge about the
+    //       endgames of the corpus is scored, which does not depend on the wing
+    let evaluate = evaluate::<EndgameEvalState<NoOpEvalState>>;
+    assert!(
+        corpus()
+            .iter()
+            .any(|(_, setup)| setup.mirrored().is_some() && score(&evaluate, setup) != 0),
+        "no mirrored corpus position has endgame knowledge"
+    );
+
+    assert_symmetric(evaluate);
+}
+
+/// random_network_weights returns the pseudo-random weights of a network, in
+/// the order of the network file
+///
+/// @return: weights of every layer of the network
+fn random_network_weights() -> Vec<i16> {
+    let mut rng = Rng::new(1);
+    (0..Network::FILE_SIZE / 2)
+        .map(|_| rng.below(401) as i16 - 200)
+        .collect()
+}
+
+/// network returns the network with the given weights
+///
+/// @param: weights - weights of every layer of the network
+/// @return: network with the weights
+fn network(weights: &[i16]) -> Arc<Network> {
+    let bytes = weights
+        .iter()
+        .flat_map(|weight| weight.to_le_bytes())
+        .collect::<Vec<_>>();
+    Arc::new(Network::from_bytes(&bytes).unwrap())
+}
+
+#[test]
+fn nnue_evaluation_is_color_symmetric() {
+    // note: the perspectives of the network make any network color-symmetric,
+    //       but only a network trained on mirrored data is mirror-symmetric,
+    //       so a network with pseudo-random weights is only flipped
+    let network = network(&random_network_weights());
+
+    assert_flip_symmetric(|position| {
+        NnueEvalState::with_network(network.clone(), position).score()
+    });
+}
+
+#[test]
+fn nnue_evaluation_with_mirrored_weights_is_symmetric() {
+    // note: the square is the lowest part of an input feature, so the feature
+    //       of the square on the mirrored file differs in the file bits only
+    let mut weights = random_network_weights();
+    for feature in (0..INPUT_SIZE).filter(|feature| feature & 7 >= 4) {
+        let mirrored = (feature ^ 7) * HIDDEN_SIZE;
+        weights.copy_within(mirrored..mirrored + HIDDEN_SIZE, feature * HIDDEN_SIZE);
+    }
+    let network = network(&weights);
+
+    assert_symmetric(|position| NnueEvalState::with_network(network.clone(), position).score());
+}
@@ -0,0 +1,136 @@
+use chess_kit_primitives::{Bitboard, Move, MoveList, Pieces, Square};
+
+/// ALL_PIECES is the mask of every piece type a filter can select
+const ALL_PIECES: u8 = u8::MAX;
+
+/// `MoveFilter` restricts move generation to the moves of selected piece types,
+/// from selected origin squares, to selected target squares
+///
+/// A move is generated only if it passes every restriction of the filter, so
+/// restrictions combine, e.g. the knight moves that start on b1 or g1.
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MoveFilter {
+    origins: Bitboard, // squares the moved piece may start from
+    targets: Bitboard, // squares the moved piece may end on
+    pieces: u8,        // piece types that may move, one bit per piece index
+}
+
+impl MoveFilter {
+    /// ALL is the filter that lets every move through
+    pub const ALL: Self = Self {
+        origins: Bitboard::all(),
+        targets: Bitboard::all(),
+        pieces: ALL_PIECES,
+    };
+
+    /// from_square creates a filter for the moves of the piece on a square
+    ///
+    /// @param: square - square the moves start from
+    /// @return: filter of the moves starting on the square
+    pub fn from_square(square: Square) -> Self {
+        Self::ALL.with_origins(Bitboard::square(square))
+    }
+
+    /// with_origins restricts the squares moves may start from
+    ///
+    /// @param: origins - squares the moves may start from
+    /// @return: filter restricted to the origins
+    pub fn with_origins(mut self, origins: Bitboard) -> Self {
+        self.origins &= origins;
+        self
+    }
+
+    /// with_targets restricts the squares moves may end on
+    ///
+    /// note: castling moves end on the destination square of the king
+    ///
+    /// @param: targets - squares the moves may end on
+    /// @return: filter restricted to the targets
+    pub fn with_targets(mut self, targets: Bitboard) -> Self {
+        self.targets &= targets;
+        self
+    }
+
+    /// with_piece restricts the moves to those of a piece type
+    ///
+    /// @param: piece - piece type that may move
+    /// @return: filter restricted to the piece type
+    pub const fn with_piece(mut self, piece: Pieces) -> Self {
+        self.pieces &= 1 << piece.idx();
+        self
+    }
+
+    /// origins returns the squares the moves of a piece type may start from
+    ///
+    /// @param: piece - piece type to get the origins for
+    /// @return: origin squares, or an empty bitboard if the piece cannot move
+    #[inline]
+    pub(crate) fn origins(&self, piece: Pieces) -> Bitboard {
+        if self.pieces & (1 << piece.idx()) == 0 {
+            return Bitboard::empty();
+        }
+        self.origins
+    }
+
+    /// targets returns the squares the moves may end on
+    ///
+    /// @return: target squares
+    #[inline]
+    pub(crate) const fn targets(&self) -> Bitboard {
+        self.targets
+    }
+}
+
+impl Default for MoveFilter {
+    /// default creates the filter that lets every move through
+    ///
+    /// @return: unrestricted filter
+    fn default() -> Self {
+        Self::ALL
+    }
+}
+
+/// `LegalMoves` is an iterator over the legal moves of a position
+///
+/// @type
+pub struct LegalMoves {
+    moves: MoveList, // legal moves generated for the position
+    next: usize,     // index of the next move to yield
+}
+
+impl LegalMoves {
+    /// new creates an iterator over generated legal moves
+    ///
+    /// @param: moves - legal moves to iterate over
+    /// @return: iterator over the moves
+    pub(crate) const fn new(moves: MoveList) -> Self {
+        Self { moves, next: 0 }
+    }
+}
+
+impl Iterator for LegalMoves {
+    type Item = Move;
+
+    /// next yields the next legal move
+    ///
+    /// @return: next legal move, or None once every move was yielded
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let mv = self.moves.get(self.next).copied()?;
+        self.next += 1;
+        Some(mv)
+    }
+
+    /// size_hint returns the exact number of moves left to yield
+    ///
+    /// @return: number of moves left, as both bounds
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.moves.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for LegalMoves {}
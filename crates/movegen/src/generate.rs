@@ -1,7 +1,7 @@
-use crate::{DefaultMoveGenerator, MoveGenerationStrategy, PawnOffsets, PawnRanks};
+use crate::{DefaultMoveGenerator, MoveFilter, MoveGenerationStrategy, PawnOffsets, PawnRanks};
 use chess_kit_attack_table::{AttackTable, PawnDirections};
 use chess_kit_position::{CastlingSquares, PositionAttacks, PositionView};
-use chess_kit_primitives::{Bitboard, MoveList, Pieces, Side};
+use chess_kit_primitives::{Bitboard, MoveList, MoveType, Pieces, Side};
 
 impl<AT: AttackTable> DefaultMoveGenerator<AT> {
     /// generate_moves_for_side generates all the pseudo-legal moves of the given
@@ -11,6 +11,7 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
     /// @param: position - immutable reference to the position
     /// @param: list - mutable reference to the move list
     /// @param: strategy - move generation strategy to apply
+    /// @param: filter - pieces and squares to generate moves for
     /// @return: void
     /// @side-effects: modifies the `move list`
     #[inline]
@@ -22,7 +23,13 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         position: &PositionT,
         list: &mut MoveList,
         strategy: MoveGenerationStrategy,
+        filter: MoveFilter,
     ) {
+        if strategy == MoveGenerationStrategy::QuietChecks {
+            self.generate_quiet_checks::<SideT, PositionT>(position, list, filter);
+            return;
+        }
+
         // get the set of possible destination squares for our next move based
         // on the move type and the number of pieces delivering check to the
         // king
@@ -50,16 +57,77 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
                 MoveGenerationStrategy::NonEvasions => !position.occupancy::<SideT>(),
                 MoveGenerationStrategy::Capture => position.occupancy::<SideT::Other>(),
                 MoveGenerationStrategy::Quiet => position.empty_squares(),
+                MoveGenerationStrategy::QuietChecks => unreachable!("quiet checks are filtered"),
             };
 
-            self.generate_pawn_moves::<SideT, PositionT>(position, list, destinations, strategy);
-            self.generate_queen_moves::<SideT, PositionT>(position, list, destinations);
-            self.generate_rook_moves::<SideT, PositionT>(position, list, destinations);
-            self.generate_bishop_moves::<SideT, PositionT>(position, list, destinations);
-            self.generate_knight_moves::<SideT, PositionT>(position, list, destinations);
+            self.generate_pawn_moves::<SideT, PositionT>(
+                position,
+                list,
+                destinations,
+                strategy,
+                filter,
+            );
+            self.generate_queen_moves::<SideT, PositionT>(position, list, destinations, filter);
+            self.generate_rook_moves::<SideT, PositionT>(position, list, destinations, filter);
+            self.generate_bishop_moves::<SideT, PositionT>(position, list, destinations, filter);
+            self.generate_knight_moves::<SideT, PositionT>(position, list, destinations, filter);
         }
 
-        self.generate_king_moves::<SideT, PositionT>(position, list, destinations, strategy);
+        self.generate_king_moves::<SideT, PositionT>(
+            position,
+            list,
+            destinations,
+            strategy,
+            filter,
+        );
+    }
+
+    /// generate_quiet_checks generates the quiet moves, other than castles and
+    /// promotions, that give check and pushes them to the move list
+    ///
+    /// note: a quiet move gives check if the moved piece attacks the enemy king
+    ///       from its destination, or if it uncovers an attack on the king by
+    ///       leaving the line it was blocking
+    ///
+    /// @param: position - immutable reference to the position
+    /// @param: list - mutable reference to the move list
+    /// @param: filter - pieces and squares to generate moves for
+    /// @return: void
+    /// @side-effects: modifies the `move list`
+    fn generate_quiet_checks<SideT: Side, PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        list: &mut MoveList,
+        filter: MoveFilter,
+    ) {
+        debug_assert!(
+            position.checkers().is_empty(),
+            "quiet checks are generated out of check"
+        );
+
+        let their_king = position.king_square::<SideT::Other>();
+        let discoverers =
+            position.king_blocker_pieces::<SideT::Other>() & position.occupancy::<SideT>();
+
+        let mut quiets = MoveList::new();
+        self.generate_moves_for_side::<SideT, PositionT>(
+            position,
+            &mut quiets,
+            MoveGenerationStrategy::Quiet,
+            filter,
+        );
+        for &mv in &quiets {
+            let (from, to) = (mv.from(), mv.to());
+            let piece = position.piece_at(from);
+            let direct =
+                piece != Pieces::King && position.check_squares::<SideT>(piece).has_square(to);
+            let discovered =
+                discoverers.has_square(from) && !Bitboard::in_line(from, to, their_king);
+
+            if mv.type_of() == MoveType::Normal && (direct || discovered) {
+                list.push(mv);
+            }
+        }
     }
 
     /// generate_queen_moves generates all the pseudo-legal moves of the given
@@ -76,12 +144,14 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         position: &PositionT,
         list: &mut MoveList,
         destinations: Bitboard,
+        filter: MoveFilter,
     ) {
+        let destinations = destinations & filter.targets();
         let occupancy = position.total_occupancy();
 
         // generate moves from all positions of the queen for the current side
         // to move
-        let to_move = position.get_piece::<SideT>(Pieces::Queen);
+        let to_move = position.get_piece::<SideT>(Pieces::Queen) & filter.origins(Pieces::Queen);
         for from in to_move.iter() {
             let targets = AT::queen_targets(from, occupancy) & destinations;
             self.push_moves(from, targets, list);
@@ -101,12 +171,14 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         position: &PositionT,
         list: &mut MoveList,
         destinations: Bitboard,
+        filter: MoveFilter,
     ) {
+        let destinations = destinations & filter.targets();
         let occupancy = position.total_occupancy();
 
         // generate moves from all positions of the rook for the current side
         // to move
-        let to_move = position.get_piece::<SideT>(Pieces::Rook);
+        let to_move = position.get_piece::<SideT>(Pieces::Rook) & filter.origins(Pieces::Rook);
         for from in to_move.iter() {
             let targets = AT::rook_targets(from, occupancy) & destinations;
             self.push_moves(from, targets, list);
@@ -126,12 +198,14 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         position: &PositionT,
         list: &mut MoveList,
         destinations: Bitboard,
+        filter: MoveFilter,
     ) {
+        let destinations = destinations & filter.targets();
         let occupancy = position.total_occupancy();
 
         // generate moves from all positions of the bishop for the current side
         // to move
-        let to_move = position.get_piece::<SideT>(Pieces::Bishop);
+        let to_move = position.get_piece::<SideT>(Pieces::Bishop) & filter.origins(Pieces::Bishop);
         for from in to_move.iter() {
             let targets = AT::bishop_targets(from, occupancy) & destinations;
             self.push_moves(from, targets, list);
@@ -151,10 +225,12 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         position: &PositionT,
         list: &mut MoveList,
         destinations: Bitboard,
+        filter: MoveFilter,
     ) {
+        let destinations = destinations & filter.targets();
         // generate moves from all positions of the knight for the current side
         // to move
-        let to_move = position.get_piece::<SideT>(Pieces::Knight);
+        let to_move = position.get_piece::<SideT>(Pieces::Knight) & filter.origins(Pieces::Knight);
         for from in to_move.iter() {
             let targets = AT::knight_targets(from) & destinations;
             self.push_moves(from, targets, list);
@@ -177,8 +253,10 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         list: &mut MoveList,
         destinations: Bitboard,
         strategy: MoveGenerationStrategy,
+        filter: MoveFilter,
     ) {
         let empty_squares = position.empty_squares();
+        let targets = filter.targets();
         let single_step_rank = Bitboard::rank(PawnRanks::single_step::<SideT>());
         let promotable_rank = Bitboard::rank(PawnRanks::promotable::<SideT>());
 
@@ -190,8 +268,9 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         };
 
         // get the promotable and non-promotable pawns
-        let promotable_pawns = position.get_piece::<SideT>(Pieces::Pawn) & promotable_rank;
-        let non_promotable_pawns = position.get_piece::<SideT>(Pieces::Pawn) & !promotable_rank;
+        let pawns = position.get_piece::<SideT>(Pieces::Pawn) & filter.origins(Pieces::Pawn);
+        let promotable_pawns = pawns & promotable_rank;
+        let non_promotable_pawns = pawns & !promotable_rank;
 
         // generate pawn pushes for non-promotable pawns
         if !matches!(strategy, MoveGenerationStrategy::Capture) {
@@ -214,9 +293,16 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
             }
 
             // push the pawn pushes to the move list
-            self.push_pawn_moves(single_step_pawns, PawnOffsets::push::<SideT>(), list);
+            //
+            // note: the filter's targets only apply now, since double steps are
+            //       found from the single steps
+            self.push_pawn_moves(
+                single_step_pawns & targets,
+                PawnOffsets::push::<SideT>(),
+                list,
+            );
             self.push_pawn_moves(
-                double_step_pawns,
+                double_step_pawns & targets,
                 PawnOffsets::push::<SideT>() + PawnOffsets::push::<SideT>(),
                 list,
             );
@@ -226,15 +312,20 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         if promotable_pawns.not_empty() {
             // get the target squares on the right of the promotable pawns
             let right_targets =
-                AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Right) & enemies;
+                AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Right)
+                    & enemies
+                    & targets;
 
             // get the target squares on the left of the promotable pawns
             let left_targets =
-                AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Left) & enemies;
+                AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Left)
+                    & enemies
+                    & targets;
 
             // get the squares that a pawn can push to and promote
-            let mut pushes =
-                AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Up) & empty_squares;
+            let mut pushes = AT::all_pawn_targets::<SideT>(promotable_pawns, PawnDirections::Up)
+                & empty_squares
+                & targets;
 
             // again, if the move type is evasions, we only need to generate
             // moves for squares that would block a check
@@ -265,11 +356,14 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
             // get the target squares on the right of the non-promotable pawns
             let right_targets =
                 AT::all_pawn_targets::<SideT>(non_promotable_pawns, PawnDirections::Right)
-                    & enemies;
+                    & enemies
+                    & targets;
 
             // get the target squares on the left of the non-promotable pawns
             let left_targets =
-                AT::all_pawn_targets::<SideT>(non_promotable_pawns, PawnDirections::Left) & enemies;
+                AT::all_pawn_targets::<SideT>(non_promotable_pawns, PawnDirections::Left)
+                    & enemies
+                    & targets;
 
             // push the pawn captures to the move list
             self.push_pawn_moves(right_targets, PawnOffsets::right_target::<SideT>(), list);
@@ -277,7 +371,7 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
 
             // generate en passant captures if possible
             let en_passant = position.en_passant();
-            if en_passant.is_none() {
+            if en_passant.is_none_or(|ep_square| !targets.has_square(ep_square)) {
                 return;
             }
 
@@ -321,9 +415,13 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         list: &mut MoveList,
         destinations: Bitboard,
         strategy: MoveGenerationStrategy,
+        filter: MoveFilter,
     ) {
         let king_square = position.king_square::<SideT>();
-        let targets = AT::king_targets(king_square);
+        if !filter.origins(Pieces::King).has_square(king_square) {
+            return;
+        }
+        let targets = AT::king_targets(king_square) & filter.targets();
 
         // filter the moves according to the requested move type
         let moves = match strategy {
@@ -341,7 +439,7 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
             strategy,
             MoveGenerationStrategy::NonEvasions | MoveGenerationStrategy::Quiet
         ) {
-            self.generate_castle_moves::<SideT, PositionT>(position, list);
+            self.generate_castle_moves::<SideT, PositionT>(position, list, filter);
         }
     }
 
@@ -359,6 +457,7 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         &self,
         position: &PositionT,
         list: &mut MoveList,
+        filter: MoveFilter,
     ) {
         // get the castling rights for the side to move
         let castling = position.castling();
@@ -415,6 +514,6 @@ impl<AT: AttackTable> DefaultMoveGenerator<AT> {
         }
 
         // push the castle moves to the move list
-        self.push_castling_moves(from, moves, list);
+        self.push_castling_moves(from, moves & filter.targets(), list);
     }
 }
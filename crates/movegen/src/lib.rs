@@ -1,10 +1,13 @@
+mod filter;
 mod generate;
 mod movegen;
+mod pseudo_legal;
 mod splat;
 
+pub use filter::{LegalMoves, MoveFilter};
 pub use movegen::DefaultMoveGenerator;
 
-use chess_kit_primitives::{MoveList, Rank};
+use chess_kit_primitives::{Move, MoveList, Rank};
 
 use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
 
@@ -17,6 +20,9 @@ pub enum MoveGenerationStrategy {
     Capture,
     Evasions,
     NonEvasions,
+    // quiet moves other than castles and promotions that give check, which
+    // must not be generated while the side to move is in check
+    QuietChecks,
 }
 
 /// `MoveGenerator` is a trait that defines the contract which defines the move
@@ -42,6 +48,25 @@ pub trait MoveGenerator {
         position: &PositionT,
         list: &mut MoveList,
         strategy: MoveGenerationStrategy,
+    ) {
+        self.generate_filtered_moves(position, list, strategy, MoveFilter::ALL);
+    }
+
+    /// generate_filtered_moves generates the pseudo-legal moves selected by a
+    /// strategy that pass a filter and pushes them to the move list
+    ///
+    /// @param: position - immutable reference to the position
+    /// @param: list - mutable reference to the move list
+    /// @param: strategy - subset of moves to generate
+    /// @param: filter - pieces and squares to generate moves for
+    /// @return: void
+    /// @side-effects: modifies the `move list`
+    fn generate_filtered_moves<PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        list: &mut MoveList,
+        strategy: MoveGenerationStrategy,
+        filter: MoveFilter,
     );
 
     /// generate_legal_moves generates all the legal moves from the current position
@@ -55,7 +80,56 @@ pub trait MoveGenerator {
         &self,
         position: &PositionT,
         list: &mut MoveList,
+    ) {
+        self.generate_filtered_legal_moves(position, list, MoveFilter::ALL);
+    }
+
+    /// generate_filtered_legal_moves generates the legal moves that pass a
+    /// filter and pushes them to the move list
+    ///
+    /// @param: position - immutable reference to the position
+    /// @param: list - mutable reference to the move list
+    /// @param: filter - pieces and squares to generate moves for
+    /// @return: void
+    /// @side-effects: modifies the `move list`
+    fn generate_filtered_legal_moves<PositionT: PositionView + PositionAttacks + PositionMoves>(
+        &self,
+        position: &PositionT,
+        list: &mut MoveList,
+        filter: MoveFilter,
     );
+
+    /// legal_moves iterates over the legal moves that pass a filter
+    ///
+    /// @param: position - immutable reference to the position
+    /// @param: filter - pieces and squares to generate moves for
+    /// @return: iterator over the legal moves
+    fn legal_moves<PositionT: PositionView + PositionAttacks + PositionMoves>(
+        &self,
+        position: &PositionT,
+        filter: MoveFilter,
+    ) -> LegalMoves {
+        let mut list = MoveList::new();
+        self.generate_filtered_legal_moves(position, &mut list, filter);
+        LegalMoves::new(list)
+    }
+
+    /// is_pseudo_legal checks if an arbitrary move is one of the pseudo-legal
+    /// moves that legal move generation considers in the current position
+    ///
+    /// note: this validates moves that were not generated for the position,
+    ///       such as moves read from the transposition table. a pseudo-legal
+    ///       move may still leave the king in check, which is checked by
+    ///       `PositionMoves::is_legal_move`
+    ///
+    /// @param: position - immutable reference to the position
+    /// @param: mv - move to check
+    /// @return: true if the move is pseudo-legal, false otherwise
+    fn is_pseudo_legal<PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        mv: Move,
+    ) -> bool;
 }
 
 // PawnOffsets is a per-side table of pawn movement offsets
@@ -1,7 +1,7 @@
-use crate::{MoveGenerationStrategy, MoveGenerator};
+use crate::{MoveFilter, MoveGenerationStrategy, MoveGenerator};
 use chess_kit_attack_table::AttackTable;
 use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
-use chess_kit_primitives::{MoveList, call_as, moves::MoveType::EnPassant};
+use chess_kit_primitives::{Move, MoveList, call_as, moves::MoveType::EnPassant};
 use std::marker::PhantomData;
 
 /// `DefaultMoveGenerator` is a default implementation of the `MoveGenerator` trait
@@ -22,30 +22,32 @@ impl<AT: AttackTable> MoveGenerator for DefaultMoveGenerator<AT> {
         }
     }
 
-    /// generate_moves generates all the pseudo-legal moves of the given move type
-    /// from the current position and pushes them to the move list
+    /// generate_filtered_moves generates the pseudo-legal moves of the given
+    /// strategy that pass a filter and pushes them to the move list
     ///
-    /// @impl: MoveGenerator::generate_moves
-    fn generate_moves<PositionT: PositionView + PositionAttacks>(
+    /// @impl: MoveGenerator::generate_filtered_moves
+    fn generate_filtered_moves<PositionT: PositionView + PositionAttacks>(
         &self,
         position: &PositionT,
         list: &mut MoveList,
         strategy: MoveGenerationStrategy,
+        filter: MoveFilter,
     ) {
         call_as!(position.turn(), |SideT| self
             .generate_moves_for_side::<SideT, PositionT>(
-                position, list, strategy
+                position, list, strategy, filter
             ));
     }
 
-    /// generate_legal_moves generates all the legal moves from the current position
-    /// and pushes them to the move list
+    /// generate_filtered_legal_moves generates the legal moves that pass a
+    /// filter and pushes them to the move list
     ///
-    /// @impl: MoveGenerator::generate_legal_moves
-    fn generate_legal_moves<PositionT: PositionView + PositionAttacks + PositionMoves>(
+    /// @impl: MoveGenerator::generate_filtered_legal_moves
+    fn generate_filtered_legal_moves<PositionT: PositionView + PositionAttacks + PositionMoves>(
         &self,
         position: &PositionT,
         list: &mut MoveList,
+        filter: MoveFilter,
     ) {
         // if the side to move is in check, just generate evasions during legal
         // move generation
@@ -59,7 +61,7 @@ impl<AT: AttackTable> MoveGenerator for DefaultMoveGenerator<AT> {
             let king_square = position.king_square::<SideT>();
             let pinned = position.king_blocker_pieces::<SideT>() & position.occupancy::<SideT>();
 
-            self.generate_moves_for_side::<SideT, PositionT>(position, list, strategy);
+            self.generate_moves_for_side::<SideT, PositionT>(position, list, strategy, filter);
             list.retain(|mv| {
                 !((pinned.has_square(mv.from())
                     || mv.from() == king_square
@@ -68,4 +70,17 @@ impl<AT: AttackTable> MoveGenerator for DefaultMoveGenerator<AT> {
             });
         });
     }
+
+    /// is_pseudo_legal checks if an arbitrary move is one of the pseudo-legal
+    /// moves that legal move generation considers in the current position
+    ///
+    /// @impl: MoveGenerator::is_pseudo_legal
+    fn is_pseudo_legal<PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        mv: Move,
+    ) -> bool {
+        call_as!(position.turn(), |SideT| self
+            .is_pseudo_legal_for_side::<SideT, PositionT>(position, mv))
+    }
 }
@@ -0,0 +1,188 @@
+use crate::{DefaultMoveGenerator, PawnRanks};
+use chess_kit_attack_table::AttackTable;
+use chess_kit_position::{CastlingSquares, PositionAttacks, PositionView};
+use chess_kit_primitives::{Bitboard, Move, MoveType, Pieces, Side, Square};
+
+impl<AT: AttackTable> DefaultMoveGenerator<AT> {
+    /// is_pseudo_legal_for_side checks if a move is one of the pseudo-legal
+    /// moves generated for the side to move
+    ///
+    /// note: the move is held to the strategy used by legal move generation,
+    ///       so while the side to move is in check it must be an evasion
+    ///
+    /// @marker: SideT - side to move
+    /// @param: position - immutable reference to the position
+    /// @param: mv - move to check
+    /// @return: true if the move is generated for the position, false otherwise
+    pub(crate) fn is_pseudo_legal_for_side<
+        SideT: Side,
+        PositionT: PositionView + PositionAttacks,
+    >(
+        &self,
+        position: &PositionT,
+        mv: Move,
+    ) -> bool {
+        let (from, to) = (mv.from(), mv.to());
+        let ours = position.occupancy::<SideT>();
+        if !ours.has_square(from) || ours.has_square(to) {
+            return false;
+        }
+
+        let piece = position.piece_at(from);
+        let canonical = match mv.type_of() {
+            MoveType::Normal => Move::new(from, to),
+            MoveType::Promotion => Move::new(from, to).with_promotion(mv.promoted_to()),
+            MoveType::EnPassant => Move::new(from, to).with_en_passant(),
+            MoveType::Castle => Move::new(from, to).with_castle(),
+        };
+
+        // the unused promotion bits of other move types must be clear, since
+        // the generator never sets them
+        if mv != canonical {
+            return false;
+        }
+
+        let checkers = position.checkers();
+        match (mv.type_of(), piece) {
+            (MoveType::Castle, Pieces::King) => {
+                checkers.is_empty() && self.is_castle_pseudo_legal::<SideT, PositionT>(position, to)
+            }
+            (MoveType::Castle, _) => false,
+            (_, Pieces::King) => {
+                mv.type_of() == MoveType::Normal && AT::king_targets(from).has_square(to)
+            }
+            // only the king can move out of a double check
+            _ if checkers.more_than_one() => false,
+            (_, Pieces::Pawn) => self.is_pawn_move_pseudo_legal::<SideT, PositionT>(position, mv),
+            (MoveType::Normal, piece) => {
+                let occupancy = position.total_occupancy();
+                let targets = match piece {
+                    Pieces::Queen => AT::queen_targets(from, occupancy),
+                    Pieces::Rook => AT::rook_targets(from, occupancy),
+                    Pieces::Bishop => AT::bishop_targets(from, occupancy),
+                    Pieces::Knight => AT::knight_targets(from),
+                    _ => Bitboard::empty(),
+                };
+                targets.has_square(to) && self.blocks_check::<SideT, PositionT>(position, to)
+            }
+            _ => false,
+        }
+    }
+
+    /// is_pawn_move_pseudo_legal checks if a pawn move is generated for the
+    /// side to move
+    ///
+    /// @marker: SideT - side to move
+    /// @param: position - immutable reference to the position
+    /// @param: mv - pawn move to check
+    /// @return: true if the pawn move is generated, false otherwise
+    fn is_pawn_move_pseudo_legal<SideT: Side, PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        mv: Move,
+    ) -> bool {
+        let (from, to) = (mv.from(), mv.to());
+        let checkers = position.checkers();
+
+        if mv.type_of() == MoveType::EnPassant {
+            let Some(ep_square) = position.en_passant() else {
+                return false;
+            };
+
+            // an en passant capture cannot block a check discovered by the pawn
+            // push it answers
+            let source_square = AT::pawn_pushes::<SideT>(ep_square);
+            let discovered_check = checkers.not_empty()
+                && Bitboard::between(position.king_square::<SideT>(), checkers.first_unchecked())
+                    .intersects(source_square);
+
+            return to == ep_square
+                && AT::pawn_targets::<SideT>(from).has_square(to)
+                && !discovered_check;
+        }
+
+        // pawns on the promotable rank only move by promoting
+        let promotable = from.on_rank(PawnRanks::promotable::<SideT>());
+        if promotable != (mv.type_of() == MoveType::Promotion) {
+            return false;
+        }
+
+        let empty_squares = position.empty_squares();
+        let push = AT::pawn_pushes::<SideT>(from);
+        let is_push = push.has_square(to) && empty_squares.has_square(to);
+        let is_double_push = push.intersects(empty_squares)
+            && push.intersects(Bitboard::rank(PawnRanks::single_step::<SideT>()))
+            && AT::pawn_pushes::<SideT>(push.first_unchecked()).has_square(to)
+            && empty_squares.has_square(to);
+        let enemies = if checkers.not_empty() {
+            checkers
+        } else {
+            position.occupancy::<SideT::Other>()
+        };
+        let is_capture = AT::pawn_targets::<SideT>(from).has_square(to) && enemies.has_square(to);
+
+        // pushes only answer a check by blocking it, while captures of the
+        // checker are already restricted by the enemies they target
+        ((is_push || (is_double_push && !promotable))
+            && self.blocks_check::<SideT, PositionT>(position, to))
+            || is_capture
+    }
+
+    /// is_castle_pseudo_legal checks if castling to a king destination is
+    /// generated for the side to move
+    ///
+    /// @marker: SideT - side to move
+    /// @param: position - immutable reference to the position
+    /// @param: to - destination of the king
+    /// @return: true if the castle is generated, false otherwise
+    fn is_castle_pseudo_legal<SideT: Side, PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        to: Square,
+    ) -> bool {
+        let castling = position.castling();
+        let (allowed, rook_destination, blockers) =
+            if to == CastlingSquares::kingside_destination::<SideT>() {
+                (
+                    castling.kingside::<SideT>(),
+                    CastlingSquares::kingside_rook_destination::<SideT>(),
+                    Bitboard::square(to)
+                        | Bitboard::square(CastlingSquares::kingside_rook_destination::<SideT>()),
+                )
+            } else if to == CastlingSquares::queenside_destination::<SideT>() {
+                (
+                    castling.queenside::<SideT>(),
+                    CastlingSquares::queenside_rook_destination::<SideT>(),
+                    Bitboard::square(to)
+                        | Bitboard::square(CastlingSquares::queenside_rook_destination::<SideT>())
+                        | Bitboard::square(CastlingSquares::queenside_rook_intermediate::<SideT>()),
+                )
+            } else {
+                return false;
+            };
+
+        let occupancy = position.total_occupancy();
+        allowed
+            && !occupancy.intersects(blockers)
+            && !position.is_attacked::<SideT>(rook_destination, occupancy)
+    }
+
+    /// blocks_check checks if moving a piece other than the king to a square
+    /// answers the check on the side to move, if any
+    ///
+    /// @marker: SideT - side to move
+    /// @param: position - immutable reference to the position
+    /// @param: to - destination of the move
+    /// @return: true if the side to move is not in check, or the destination
+    ///          blocks the check or captures the checker
+    fn blocks_check<SideT: Side, PositionT: PositionView + PositionAttacks>(
+        &self,
+        position: &PositionT,
+        to: Square,
+    ) -> bool {
+        let checkers = position.checkers();
+        checkers.is_empty()
+            || Bitboard::between(position.king_square::<SideT>(), checkers.first_unchecked())
+                .has_square(to)
+    }
+}
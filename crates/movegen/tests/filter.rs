@@ -0,0 +1,179 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_movegen::{DefaultMoveGenerator, MoveFilter, MoveGenerationStrategy, MoveGenerator};
+use chess_kit_position::{
+    DefaultPosition, Fen, PositionAttacks, PositionMoves, PositionView, Setup,
+};
+use chess_kit_primitives::Square::*;
+use chess_kit_primitives::{Bitboard, Black, Move, MoveList, MoveType, Pieces, Rank, Sides, White};
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+/// POSITIONS cover castling, en passant, promotions, pins and checks
+const POSITIONS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+    "4k3/8/8/8/1b6/8/4r3/4K3 w - - 0 1",
+    "4k3/8/8/8/8/8/3RB3/1K2Q3 w - - 0 1",
+];
+
+/// position parses a position from a fen string
+///
+/// @param: fen - fen string of the position
+/// @return: parsed position
+fn position(fen: &str) -> TestPosition {
+    TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+}
+
+/// legal_moves returns every legal move of a position
+///
+/// @param: generator - move generator to generate with
+/// @param: position - position to generate moves for
+/// @return: legal moves
+fn legal_moves(
+    generator: &DefaultMoveGenerator<DefaultAttackTable>,
+    position: &TestPosition,
+) -> Vec<Move> {
+    let mut moves = MoveList::new();
+    generator.generate_legal_moves(position, &mut moves);
+    moves.as_slice().to_vec()
+}
+
+/// filtered_legal_moves returns the legal moves of a position that pass a
+/// filter
+///
+/// @param: generator - move generator to generate with
+/// @param: position - position to generate moves for
+/// @param: filter - filter to apply
+/// @return: filtered legal moves
+fn filtered_legal_moves(
+    generator: &DefaultMoveGenerator<DefaultAttackTable>,
+    position: &TestPosition,
+    filter: MoveFilter,
+) -> Vec<Move> {
+    let mut moves = MoveList::new();
+    generator.generate_filtered_legal_moves(position, &mut moves, filter);
+    moves.as_slice().to_vec()
+}
+
+#[test]
+fn filtered_moves_match_the_filtered_legal_moves() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let targets = Bitboard::rank(Rank::R4) | Bitboard::square(G1);
+
+    for fen in POSITIONS {
+        let position = position(fen);
+        let all = legal_moves(&generator, &position);
+        let expected = |keep: &dyn Fn(&Move) -> bool| {
+            all.iter()
+                .copied()
+                .filter(|mv| keep(mv))
+                .collect::<Vec<_>>()
+        };
+
+        for square in [E1, E2, D5, B4, F3, E8, C5] {
+            assert_eq!(
+                filtered_legal_moves(&generator, &position, MoveFilter::from_square(square)),
+                expected(&|mv| mv.from() == square),
+                "moves from {square} in '{fen}'",
+            );
+        }
+
+        for piece in Pieces::ALL {
+            assert_eq!(
+                filtered_legal_moves(&generator, &position, MoveFilter::ALL.with_piece(piece)),
+                expected(&|mv| position.piece_at(mv.from()) == piece),
+                "moves of {piece:?} in '{fen}'",
+            );
+        }
+
+        assert_eq!(
+            filtered_legal_moves(&generator, &position, MoveFilter::ALL.with_targets(targets)),
+            expected(&|mv| targets.has_square(mv.to())),
+            "moves to the targets in '{fen}'",
+        );
+    }
+}
+
+#[test]
+fn generates_the_moves_of_a_selected_piece() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let position = position("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+
+    assert_eq!(
+        filtered_legal_moves(&generator, &position, MoveFilter::from_square(E2)),
+        [Move::new(E2, E3), Move::new(E2, E4)],
+    );
+    assert_eq!(
+        filtered_legal_moves(
+            &generator,
+            &position,
+            MoveFilter::ALL
+                .with_piece(Pieces::Knight)
+                .with_targets(Bitboard::square(F3)),
+        ),
+        [Move::new(G1, F3)],
+    );
+    assert!(filtered_legal_moves(&generator, &position, MoveFilter::from_square(E4)).is_empty());
+}
+
+#[test]
+fn quiet_checks_are_the_quiet_moves_that_give_check() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+    for fen in POSITIONS {
+        let position = position(fen);
+        if position.checkers().not_empty() {
+            continue;
+        }
+
+        let mut quiets = MoveList::new();
+        generator.generate_moves(&position, &mut quiets, MoveGenerationStrategy::Quiet);
+        let expected = quiets
+            .as_slice()
+            .iter()
+            .copied()
+            .filter(|&mv| {
+                mv.type_of() == MoveType::Normal
+                    && match position.turn() {
+                        Sides::White => position.delivers_check::<White>(mv),
+                        Sides::Black => position.delivers_check::<Black>(mv),
+                    }
+            })
+            .collect::<Vec<_>>();
+
+        let mut checks = MoveList::new();
+        generator.generate_moves(&position, &mut checks, MoveGenerationStrategy::QuietChecks);
+        assert_eq!(checks.as_slice(), expected, "quiet checks in '{fen}'");
+    }
+
+    // a direct check by the rook and a check discovered by the bishop
+    let position = position("4k3/8/8/8/8/8/3RB3/1K2Q3 w - - 0 1");
+    let mut checks = MoveList::new();
+    generator.generate_moves(&position, &mut checks, MoveGenerationStrategy::QuietChecks);
+    assert!(checks.as_slice().contains(&Move::new(D2, D8)));
+    assert!(checks.as_slice().contains(&Move::new(E2, D3)));
+    assert!(!checks.as_slice().contains(&Move::new(D2, D7)));
+}
+
+#[test]
+fn legal_move_iterator_yields_the_legal_moves() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+    for fen in POSITIONS {
+        let position = position(fen);
+        let all = legal_moves(&generator, &position);
+
+        let moves = generator.legal_moves(&position, MoveFilter::ALL);
+        assert_eq!(moves.len(), all.len());
+        assert_eq!(moves.collect::<Vec<_>>(), all, "legal moves in '{fen}'");
+
+        let mut moves = generator.legal_moves(&position, MoveFilter::default());
+        if moves.next().is_some() {
+            assert_eq!(moves.len(), all.len() - 1);
+        }
+    }
+}
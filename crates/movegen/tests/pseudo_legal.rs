@@ -0,0 +1,126 @@
+use std::collections::HashSet;
+
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerationStrategy, MoveGenerator};
+use chess_kit_position::{DefaultPosition, Fen, PositionAttacks, PositionMoves, Setup};
+use chess_kit_primitives::Square::*;
+use chess_kit_primitives::{Move, MoveList, Pieces};
+use chess_kit_rng::Rng;
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+/// SEED_POSITIONS cover castling, en passant, promotions, pins and checks
+const SEED_POSITIONS: [&str; 12] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/8/1k6/2b5/2pP4/8/5K2/8 b - d3 0 1",
+    "8/5k2/8/2Pp4/2B5/1K6/8/8 w - d6 0 1",
+    "r3k2r/8/3Q4/8/8/5q2/8/R3K2R b KQkq - 0 1",
+    "4k3/8/8/8/1b6/8/4r3/4K3 w - - 0 1",
+    "2K2r2/4P3/8/8/8/8/8/3k4 w - - 0 1",
+    // en passant captures of a pawn that discovered check, or gave it
+    "2b1k3/8/4K3/3pP3/8/8/8/8 w - d6 0 2",
+    "8/8/8/4k3/3Pp3/8/8/4K3 b - d3 0 1",
+];
+
+/// PLAYOUT_LENGTH is the number of random moves played from each seed
+const PLAYOUT_LENGTH: usize = 24;
+
+/// generated returns the pseudo-legal moves legal move generation considers
+///
+/// @param: generator - move generator to generate with
+/// @param: position - position to generate moves for
+/// @return: generated pseudo-legal moves
+fn generated(
+    generator: &DefaultMoveGenerator<DefaultAttackTable>,
+    position: &TestPosition,
+) -> MoveList {
+    let strategy = if position.checkers().not_empty() {
+        MoveGenerationStrategy::Evasions
+    } else {
+        MoveGenerationStrategy::NonEvasions
+    };
+    let mut moves = MoveList::new();
+    generator.generate_moves(position, &mut moves, strategy);
+    moves
+}
+
+/// assert_matches_generation checks every 16-bit move against the generated
+/// move list of a position
+///
+/// @param: generator - move generator under test
+/// @param: position - position to check the moves in
+/// @return: void
+/// @side-effects: panics on the first move the generator disagrees with
+fn assert_matches_generation(
+    generator: &DefaultMoveGenerator<DefaultAttackTable>,
+    position: &TestPosition,
+) {
+    let moves = generated(generator, position);
+    let expected = moves.as_slice().iter().copied().collect::<HashSet<_>>();
+
+    for data in 0..=u16::MAX {
+        let mv: Move = data.into();
+        assert_eq!(
+            generator.is_pseudo_legal(position, mv),
+            expected.contains(&mv),
+            "move {mv} ({data:#06x}, {:?}) in '{}'",
+            mv.type_of(),
+            Fen::from(position),
+        );
+    }
+}
+
+#[test]
+fn pseudo_legality_matches_generated_moves() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let mut rng = Rng::new(0x2545_F491_4F6C_DD1D);
+
+    for fen in SEED_POSITIONS {
+        let mut position =
+            TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap();
+
+        for _ in 0..PLAYOUT_LENGTH {
+            assert_matches_generation(&generator, &position);
+
+            let mut legal = MoveList::new();
+            generator.generate_legal_moves(&position, &mut legal);
+            if legal.is_empty() {
+                break;
+            }
+
+            let mv = legal.as_slice()[rng.below(legal.len())];
+            let _ = position.play_unchecked(mv);
+        }
+    }
+}
+
+#[test]
+fn rejects_moves_that_do_not_fit_the_position() {
+    let generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let position = TestPosition::try_from(Setup::from(
+        Fen::try_from("r3k2r/1P6/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1").unwrap(),
+    ))
+    .unwrap();
+    let is_pseudo_legal = |mv: Move| generator.is_pseudo_legal(&position, mv);
+
+    assert!(is_pseudo_legal(Move::new(E5, D6).with_en_passant()));
+    assert!(is_pseudo_legal(Move::new(E1, G1).with_castle()));
+    assert!(is_pseudo_legal(
+        Move::new(B7, A8).with_promotion(Pieces::Knight)
+    ));
+    // moves of the opponent, through blockers, without their flags, or with
+    // flags that do not belong to them
+    assert!(!is_pseudo_legal(Move::new(A8, A2)));
+    assert!(!is_pseudo_legal(Move::new(A1, A8).with_castle()));
+    assert!(!is_pseudo_legal(Move::new(E5, D6)));
+    assert!(!is_pseudo_legal(Move::new(E1, G1)));
+    assert!(!is_pseudo_legal(Move::new(B7, B8)));
+    assert!(!is_pseudo_legal(Move::new(E1, E2).with_en_passant()));
+    assert!(!is_pseudo_legal(
+        Move::new(A1, A3).with_promotion(Pieces::Queen)
+    ));
+}
@@ -0,0 +1,157 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_eval::{
+    Accumulator, DefaultAccumulator, NoOpEvalState, PSQTEvalState, StandaloneEvalState,
+};
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_perft::{NodeCount, PerftData, perft, perft_no_accumulator};
+use chess_kit_position::{DefaultPosition, Fen, Setup};
+use chess_kit_primitives::{Depth, MoveList};
+use chess_kit_transposition::{DefaultTranspositionTable, TranspositionTable};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use std::hint::black_box;
+
+type BenchPosition = DefaultPosition<DefaultAttackTable>;
+type BenchMoveGenerator = DefaultMoveGenerator<DefaultAttackTable>;
+
+// the transposition table is disabled so every node is generated
+const TRANSPOSITION_TABLE_SIZE: usize = 0;
+
+/// `BenchCase` is a named perft position with a depth small enough to sample
+struct BenchCase {
+    name: &'static str,
+    fen: &'static str,
+    depth: Depth,
+    nodes: NodeCount,
+}
+
+// the standard perft positions from the chess programming wiki
+const CASES: [BenchCase; 6] = [
+    BenchCase {
+        name: "start",
+        fen: "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        depth: 4,
+        nodes: 197_281,
+    },
+    BenchCase {
+        name: "kiwipete",
+        fen: "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        depth: 3,
+        nodes: 97_862,
+    },
+    BenchCase {
+        name: "position_3",
+        fen: "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        depth: 5,
+        nodes: 674_624,
+    },
+    BenchCase {
+        name: "position_4",
+        fen: "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        depth: 4,
+        nodes: 422_333,
+    },
+    BenchCase {
+        name: "position_5",
+        fen: "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        depth: 3,
+        nodes: 62_379,
+    },
+    BenchCase {
+        name: "position_6",
+        fen: "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+        depth: 3,
+        nodes: 89_890,
+    },
+];
+
+fn load(fen: &str) -> BenchPosition {
+    Setup::from(Fen::try_from(fen).expect("benchmark FEN must be valid"))
+        .try_into()
+        .expect("benchmark FEN must be legal")
+}
+
+fn perft_with<EvalStateT: StandaloneEvalState>(
+    position: &mut BenchPosition,
+    move_generator: &BenchMoveGenerator,
+    depth: Depth,
+) -> NodeCount {
+    let mut tt = DefaultTranspositionTable::<PerftData>::new(TRANSPOSITION_TABLE_SIZE);
+    let mut accumulator = DefaultAccumulator::<EvalStateT>::new();
+    accumulator.push(EvalStateT::from_position(position));
+    perft(position, move_generator, &mut tt, &mut accumulator, depth)
+}
+
+fn perft_benches(c: &mut Criterion) {
+    let move_generator = BenchMoveGenerator::new();
+
+    for case in &CASES {
+        let mut position = load(case.fen);
+        let mut tt = DefaultTranspositionTable::<PerftData>::new(TRANSPOSITION_TABLE_SIZE);
+        assert_eq!(
+            perft_no_accumulator(&mut position, &move_generator, &mut tt, case.depth),
+            case.nodes,
+            "perft mismatch for benchmark case {}",
+            case.name
+        );
+
+        let mut group = c.benchmark_group(format!("perft/{}", case.name));
+        group.throughput(Throughput::Elements(case.nodes as u64));
+        group.sample_size(10);
+
+        group.bench_function("no_accumulator", |b| {
+            b.iter(|| {
+                black_box(perft_no_accumulator(
+                    &mut position,
+                    &move_generator,
+                    &mut tt,
+                    black_box(case.depth),
+                ))
+            });
+        });
+        group.bench_function("noop_eval", |b| {
+            b.iter(|| {
+                black_box(perft_with::<NoOpEvalState>(
+                    &mut position,
+                    &move_generator,
+                    black_box(case.depth),
+                ))
+            });
+        });
+        group.bench_function("psqt_eval", |b| {
+            b.iter(|| {
+                black_box(perft_with::<PSQTEvalState>(
+                    &mut position,
+                    &move_generator,
+                    black_box(case.depth),
+                ))
+            });
+        });
+
+        group.finish();
+    }
+}
+
+fn movegen_benches(c: &mut Criterion) {
+    let move_generator = BenchMoveGenerator::new();
+    let mut group = c.benchmark_group("movegen/generate_legal_moves");
+
+    for case in &CASES {
+        let position = load(case.fen);
+        let mut moves = MoveList::new();
+        move_generator.generate_legal_moves(&position, &mut moves);
+        group.throughput(Throughput::Elements(moves.len() as u64));
+
+        group.bench_function(case.name, |b| {
+            b.iter(|| {
+                let mut moves = MoveList::new();
+                move_generator.generate_legal_moves(black_box(&position), &mut moves);
+                black_box(moves.len())
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, perft_benches, movegen_benches);
+criterion_main!(benches);
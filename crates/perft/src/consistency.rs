@@ -0,0 +1,424 @@
+use crate::perft_data::NodeCount;
+use chess_kit_eval::{Accumulator, Score, StandaloneEvalState};
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{DrawState, Fen, PositionAttacks, PositionMoves, PositionView, Setup};
+use chess_kit_primitives::{
+    Bitboard, Black, Depth, Move, MoveList, Pieces, Side, Sides, Square, White, ZobristKey,
+    ZobristTable, call_as,
+};
+use std::fmt::{self, Display};
+
+/// Inconsistency is a piece of incrementally maintained position state that
+/// differs from the same state recomputed from scratch
+///
+/// @type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Inconsistency {
+    // the zobrist key
+    Key {
+        expected: ZobristKey,
+        actual: ZobristKey,
+    },
+    // the pawn structure key
+    PawnKey {
+        expected: ZobristKey,
+        actual: ZobristKey,
+    },
+    // a side's occupancy bitboard
+    Occupancy(Sides),
+    // the total occupancy bitboard
+    TotalOccupancy,
+    // the piece map at a square
+    PieceAt(Square),
+    // the side-to-move's checkers
+    Checkers,
+    // a side's king blockers
+    KingBlockers(Sides),
+    // a side's pinning pieces
+    Pinners(Sides),
+    // a piece's checking squares
+    CheckSquares(Pieces),
+    // the legality of the position's setup
+    Setup,
+    // the draw state
+    DrawState {
+        expected: DrawState,
+        actual: DrawState,
+    },
+    // the accumulated evaluation
+    Eval {
+        expected: Score,
+        actual: Score,
+    },
+}
+
+impl Display for Inconsistency {
+    /// fmt writes a human-readable description of the inconsistency
+    ///
+    /// @param: f - formatter to write the description into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key { expected, actual } => {
+                write!(f, "zobrist key is {actual} (expected {expected})")
+            }
+            Self::PawnKey { expected, actual } => {
+                write!(f, "pawn key is {actual} (expected {expected})")
+            }
+            Self::Occupancy(side) => write!(f, "occupancy of {side} is inconsistent"),
+            Self::TotalOccupancy => write!(f, "total occupancy is inconsistent"),
+            Self::PieceAt(square) => write!(f, "piece at {square} is inconsistent"),
+            Self::Checkers => write!(f, "checkers are inconsistent"),
+            Self::KingBlockers(side) => write!(f, "king blockers of {side} are inconsistent"),
+            Self::Pinners(side) => write!(f, "pinners of {side} are inconsistent"),
+            Self::CheckSquares(piece) => write!(f, "check squares of {piece} are inconsistent"),
+            Self::Setup => write!(f, "position is not a legal setup"),
+            Self::DrawState { expected, actual } => {
+                write!(f, "draw state is {actual:?} (expected {expected:?})")
+            }
+            Self::Eval { expected, actual } => {
+                write!(f, "eval score is {actual} (expected {expected})")
+            }
+        }
+    }
+}
+
+/// ConsistencyError reports the first position at which the incremental state
+/// diverges from the recomputed state
+///
+/// @type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsistencyError {
+    /// moves played from the root to reach the position
+    pub path: Vec<Move>,
+    /// position at which the state diverges
+    pub fen: String,
+    /// first diverging piece of state
+    pub inconsistency: Inconsistency,
+}
+
+impl Display for ConsistencyError {
+    /// fmt writes a human-readable report of the divergence
+    ///
+    /// @param: f - formatter to write the report into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} after", self.inconsistency)?;
+        if self.path.is_empty() {
+            write!(f, " (root)")?;
+        }
+        for &mv in &self.path {
+            write!(f, " {mv}")?;
+        }
+        write!(f, " in '{}'", self.fen)
+    }
+}
+
+impl std::error::Error for ConsistencyError {}
+
+/// bitboards collects every piece bitboard of the position
+///
+/// @param: position - position to collect the bitboards of
+/// @return: bitboards of each side's pieces indexed by side and piece
+fn bitboards<PositionT: PositionView>(
+    position: &PositionT,
+) -> [[Bitboard; Pieces::TOTAL]; Sides::TOTAL] {
+    let mut bitboards = [[Bitboard::empty(); Pieces::TOTAL]; Sides::TOTAL];
+    for piece in Pieces::ALL {
+        bitboards[Sides::White][piece] = position.get_piece::<White>(piece);
+        bitboards[Sides::Black][piece] = position.get_piece::<Black>(piece);
+    }
+    bitboards
+}
+
+/// check_board verifies the occupancy and piece map of the position against
+/// its piece bitboards
+///
+/// @param: position - position to verify
+/// @param: bitboards - piece bitboards of the position
+/// @return: first inconsistency found, if any
+fn check_board<PositionT: PositionView>(
+    position: &PositionT,
+    bitboards: &[[Bitboard; Pieces::TOTAL]; Sides::TOTAL],
+) -> Option<Inconsistency> {
+    let mut occupancy = [Bitboard::empty(); Sides::TOTAL];
+    for side in [Sides::White, Sides::Black] {
+        for piece in Pieces::ALL {
+            occupancy[side] |= bitboards[side][piece];
+        }
+    }
+
+    if position.occupancy::<White>() != occupancy[Sides::White] {
+        return Some(Inconsistency::Occupancy(Sides::White));
+    }
+    if position.occupancy::<Black>() != occupancy[Sides::Black] {
+        return Some(Inconsistency::Occupancy(Sides::Black));
+    }
+
+    let total = occupancy[Sides::White] | occupancy[Sides::Black];
+    if position.total_occupancy() != total || position.empty_squares() != !total {
+        return Some(Inconsistency::TotalOccupancy);
+    }
+
+    for square in Square::ALL {
+        let expected = Pieces::ALL
+            .into_iter()
+            .find(|&piece| {
+                (bitboards[Sides::White][piece] | bitboards[Sides::Black][piece]).has_square(square)
+            })
+            .unwrap_or(Pieces::None);
+        if position.piece_at(square) != expected {
+            return Some(Inconsistency::PieceAt(square));
+        }
+    }
+
+    None
+}
+
+/// check_tactics verifies the check information of the position against the
+/// check information of a freshly loaded copy
+///
+/// @marker: SideT - side to move
+/// @param: position - position to verify
+/// @param: expected - freshly loaded copy of the position
+/// @return: first inconsistency found, if any
+fn check_tactics<SideT, PositionT>(
+    position: &PositionT,
+    expected: &PositionT,
+) -> Option<Inconsistency>
+where
+    SideT: Side,
+    PositionT: PositionAttacks,
+{
+    if position.checkers() != expected.checkers() {
+        return Some(Inconsistency::Checkers);
+    }
+    if position.king_blocker_pieces::<White>() != expected.king_blocker_pieces::<White>() {
+        return Some(Inconsistency::KingBlockers(Sides::White));
+    }
+    if position.king_blocker_pieces::<Black>() != expected.king_blocker_pieces::<Black>() {
+        return Some(Inconsistency::KingBlockers(Sides::Black));
+    }
+    if position.pinning_pieces::<White>() != expected.pinning_pieces::<White>() {
+        return Some(Inconsistency::Pinners(Sides::White));
+    }
+    if position.pinning_pieces::<Black>() != expected.pinning_pieces::<Black>() {
+        return Some(Inconsistency::Pinners(Sides::Black));
+    }
+
+    Pieces::ALL
+        .into_iter()
+        .find(|&piece| {
+            position.check_squares::<SideT>(piece) != expected.check_squares::<SideT>(piece)
+        })
+        .map(Inconsistency::CheckSquares)
+}
+
+/// expected_repetition derives the repetition distance of the latest position
+/// in the given history
+///
+/// note: this mirrors the threefold rules independently of the position. only
+///       positions with the same side to move within the halfmove clock can
+///       repeat, and a repetition of an already repeated position is negative
+///
+/// @param: history - key and repetition distance of each position from the root
+/// @param: halfmoves - halfmove clock of the latest position
+/// @return: signed repetition distance of the latest position
+fn expected_repetition(history: &[(ZobristKey, i16)], halfmoves: usize) -> i16 {
+    let current = history.len() - 1;
+    let (key, _) = history[current];
+
+    (4..=halfmoves.min(current))
+        .step_by(2)
+        .find_map(|distance| {
+            let (historic_key, historic_repetition) = history[current - distance];
+            (historic_key == key).then_some(if historic_repetition == 0 {
+                distance as i16
+            } else {
+                -(distance as i16)
+            })
+        })
+        .unwrap_or(0)
+}
+
+/// check_position verifies every piece of incremental state of the position
+///
+/// @param: position - position to verify
+/// @param: history - key and verified repetition distance of each ancestor
+/// @param: eval - accumulated eval state of the position
+/// @return: first inconsistency found, if any
+fn check_position<PositionT, EvalStateT>(
+    position: &PositionT,
+    history: &[(ZobristKey, i16)],
+    eval: &mut EvalStateT,
+) -> Option<Inconsistency>
+where
+    PositionT: PositionView + PositionAttacks + TryFrom<Setup>,
+    EvalStateT: StandaloneEvalState,
+{
+    let bitboards = bitboards(position);
+    if let Some(inconsistency) = check_board(position, &bitboards) {
+        return Some(inconsistency);
+    }
+
+    let key = call_as!(position.turn(), |SideT| {
+        ZobristTable::new_key::<SideT>(position.castling(), position.en_passant(), bitboards)
+    });
+    if position.key() != key {
+        return Some(Inconsistency::Key {
+            expected: key,
+            actual: position.key(),
+        });
+    }
+
+    let pawn_key = ZobristTable::new_pawn_key(bitboards);
+    if position.pawn_key() != pawn_key {
+        return Some(Inconsistency::PawnKey {
+            expected: pawn_key,
+            actual: position.pawn_key(),
+        });
+    }
+
+    let Ok(expected) = PositionT::try_from(Setup::from(Fen::from(position))) else {
+        return Some(Inconsistency::Setup);
+    };
+    let inconsistency = call_as!(position.turn(), |SideT| {
+        check_tactics::<SideT, PositionT>(position, &expected)
+    });
+    if inconsistency.is_some() {
+        return inconsistency;
+    }
+
+    let mut with_position = history.to_vec();
+    with_position.push((key, 0));
+    let draw_state = expected.draw_state().with_repetition(expected_repetition(
+        &with_position,
+        position.halfmoves() as usize,
+    ));
+    if position.draw_state() != draw_state {
+        return Some(Inconsistency::DrawState {
+            expected: draw_state,
+            actual: position.draw_state(),
+        });
+    }
+
+    let score = EvalStateT::from_position(position).score();
+    if eval.score() != score {
+        return Some(Inconsistency::Eval {
+            expected: score,
+            actual: eval.score(),
+        });
+    }
+
+    None
+}
+
+/// `perft_check` walks the perft tree from the current position and verifies,
+/// at every node, that the incrementally maintained state matches the state
+/// recomputed from scratch
+///
+/// the zobrist keys are recomputed with `ZobristTable::new_key` and
+/// `ZobristTable::new_pawn_key`, the occupancy from the piece bitboards, the
+/// check information and material draw flag from a freshly loaded copy of the
+/// position, the repetition distance from the keys played from the root, and
+/// the evaluation with `StandaloneEvalState::from_position`
+///
+/// note: repetitions are only derived from the moves played from the root, so
+///       the position should have no history before the root
+///
+/// @param: position - mutable reference to the position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: accumulator - mutable reference to the eval accumulator
+/// @param: depth - depth to walk the perft tree to
+/// @return: number of leaf nodes at the given depth, or the first divergence
+/// @side-effects: the position and accumulator are restored before returning
+pub fn perft_check<MoveGeneratorT, PositionT, AccumulatorT, EvalStateT>(
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    accumulator: &mut AccumulatorT,
+    depth: Depth,
+) -> Result<NodeCount, ConsistencyError>
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves + TryFrom<Setup>,
+    AccumulatorT: Accumulator<EvalStateT>,
+    EvalStateT: StandaloneEvalState,
+{
+    let mut path = Vec::new();
+    let mut history = Vec::new();
+    perft_check_recursive(
+        position,
+        move_generator,
+        accumulator,
+        &mut path,
+        &mut history,
+        depth,
+    )
+}
+
+/// perft_check_recursive verifies the current position and recurses into each
+/// legal move
+///
+/// @param: position - mutable reference to the position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: accumulator - mutable reference to the eval accumulator
+/// @param: path - moves played from the root to reach the position
+/// @param: history - key and repetition distance of each ancestor
+/// @param: depth - remaining depth to walk
+/// @return: number of leaf nodes at the given depth, or the first divergence
+fn perft_check_recursive<MoveGeneratorT, PositionT, AccumulatorT, EvalStateT>(
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    accumulator: &mut AccumulatorT,
+    path: &mut Vec<Move>,
+    history: &mut Vec<(ZobristKey, i16)>,
+    depth: Depth,
+) -> Result<NodeCount, ConsistencyError>
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves + TryFrom<Setup>,
+    AccumulatorT: Accumulator<EvalStateT>,
+    EvalStateT: StandaloneEvalState,
+{
+    if let Some(inconsistency) = check_position(position, history, accumulator.latest_mut()) {
+        return Err(ConsistencyError {
+            path: path.clone(),
+            fen: Fen::from(&*position).to_string(),
+            inconsistency,
+        });
+    }
+
+    // base case: if the depth is 0, this is a leaf node
+    if depth == 0 {
+        return Ok(1);
+    }
+
+    let mut moves = MoveList::new();
+    move_generator.generate_legal_moves(position, &mut moves);
+
+    history.push((position.key(), position.draw_state().repetition()));
+    let mut nodes = 0;
+    for &mv in &moves {
+        let eval = accumulator.push_next();
+        let delta = position.play_unchecked(mv);
+        eval.apply(delta);
+        path.push(mv);
+
+        let result = perft_check_recursive(
+            position,
+            move_generator,
+            accumulator,
+            path,
+            history,
+            depth - 1,
+        );
+
+        path.pop();
+        position.undo(mv);
+        accumulator.pop();
+        nodes += result?;
+    }
+    history.pop();
+
+    Ok(nodes)
+}
@@ -0,0 +1,217 @@
+use crate::DivideReference;
+use crate::perft::perft_divide;
+use crate::perft_data::{NodeCount, PerftData};
+use crate::reference::{Divide, ReferenceError};
+use chess_kit_eval::{Accumulator, EvalState};
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{Fen, PositionAttacks, PositionMoves, PositionView};
+use chess_kit_primitives::{Depth, Move};
+use chess_kit_transposition::TranspositionTable;
+use std::fmt::{self, Display};
+
+/// DivideMismatch describes the deepest position at which the move generator
+/// disagrees with a divide reference
+///
+/// @type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DivideMismatch {
+    /// moves played from the root to reach the position
+    pub path: Vec<Move>,
+    /// position at which the divides disagree
+    pub fen: String,
+    /// depth of the disagreeing divides
+    pub depth: Depth,
+    /// moves only the reference generates
+    pub missing: Vec<String>,
+    /// moves only the move generator generates
+    pub extra: Vec<Move>,
+    /// moves generated by both with their (actual, expected) counts
+    pub miscounted: Vec<(Move, NodeCount, NodeCount)>,
+}
+
+impl DivideMismatch {
+    /// is_move_mismatch returns true if the move generator generates a different
+    /// set of moves than the reference at the reported position
+    ///
+    /// @return: true if moves are missing or extra, false if only counts differ
+    pub fn is_move_mismatch(&self) -> bool {
+        !self.missing.is_empty() || !self.extra.is_empty()
+    }
+
+    /// compare builds the mismatch between an actual and an expected divide
+    ///
+    /// @param: path - moves played from the root to reach the position
+    /// @param: fen - position that was divided
+    /// @param: depth - depth of the divides
+    /// @param: actual - divide produced by the move generator
+    /// @param: expected - divide produced by the reference
+    /// @return: mismatch between the divides, or None if they agree
+    fn compare(
+        path: &[Move],
+        fen: &Fen,
+        depth: Depth,
+        actual: &[(Move, NodeCount)],
+        expected: &Divide,
+    ) -> Option<Self> {
+        let mut extra = Vec::new();
+        let mut miscounted = Vec::new();
+        for &(mv, nodes) in actual {
+            match expected.get(&uci_notation(mv)) {
+                Some(&expected_nodes) if expected_nodes != nodes => {
+                    miscounted.push((mv, nodes, expected_nodes))
+                }
+                Some(_) => {}
+                None => extra.push(mv),
+            }
+        }
+
+        let missing = expected
+            .keys()
+            .filter(|mv| {
+                !actual
+                    .iter()
+                    .any(|&(actual, _)| uci_notation(actual) == **mv)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() && extra.is_empty() && miscounted.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            path: path.to_vec(),
+            fen: fen.to_string(),
+            depth,
+            missing,
+            extra,
+            miscounted,
+        })
+    }
+}
+
+impl Display for DivideMismatch {
+    /// fmt writes a human-readable report of the mismatch
+    ///
+    /// @param: f - formatter to write the report into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "path:")?;
+        if self.path.is_empty() {
+            write!(f, " (root)")?;
+        }
+        for &mv in &self.path {
+            write!(f, " {}", uci_notation(mv))?;
+        }
+        writeln!(f)?;
+        writeln!(f, "fen: {}", self.fen)?;
+        writeln!(f, "depth: {}", self.depth)?;
+
+        for mv in &self.missing {
+            writeln!(f, "missing: {mv}")?;
+        }
+        for &mv in &self.extra {
+            writeln!(f, "extra: {}", uci_notation(mv))?;
+        }
+        for &(mv, actual, expected) in &self.miscounted {
+            writeln!(
+                f,
+                "miscounted: {}: {actual} (expected {expected})",
+                uci_notation(mv)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// uci_notation formats a move the way UCI engines print it in a divide
+///
+/// @param: mv - move to format
+/// @return: move in lowercase UCI notation
+fn uci_notation(mv: Move) -> String {
+    mv.to_string().to_ascii_lowercase()
+}
+
+/// `perft_diff` compares the divide of the current position against a reference
+/// and descends into the first miscounted move until it finds the deepest
+/// position at which the move generator and the reference disagree
+///
+/// note: the search stops at the first position where moves are missing or
+///       extra, since that is the minimal reproduction of the bug. if every
+///       move of a descended position agrees with the reference, the parent
+///       mismatch is reported instead
+///
+/// @param: position - mutable reference to the position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: tt - mutable reference to the transposition table
+/// @param: accumulator - mutable reference to the eval accumulator
+/// @param: reference - reference to compare the divides against
+/// @param: depth - depth to calculate the perft for
+/// @return: deepest mismatch found, or None if the divides agree
+/// @side-effects: the position and accumulator are restored before returning
+pub fn perft_diff<
+    MoveGeneratorT,
+    PositionT,
+    AccumulatorT,
+    EvalStateT,
+    TranspositionTableT,
+    ReferenceT,
+>(
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    tt: &mut TranspositionTableT,
+    accumulator: &mut AccumulatorT,
+    reference: &mut ReferenceT,
+    depth: Depth,
+) -> Result<Option<DivideMismatch>, ReferenceError>
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves,
+    AccumulatorT: Accumulator<EvalStateT>,
+    EvalStateT: EvalState,
+    TranspositionTableT: TranspositionTable<PerftData>,
+    ReferenceT: DivideReference,
+{
+    let mut path = Vec::new();
+    let mut depth = depth;
+    let mut mismatch = None;
+
+    let result = loop {
+        let fen = Fen::from(&*position);
+        let actual = perft_divide(position, move_generator, tt, accumulator, depth);
+        let expected = match reference.divide(&fen, depth) {
+            Ok(expected) => expected,
+            Err(error) => break Err(error),
+        };
+
+        // if this position agrees with the reference, the parent mismatch is
+        // the deepest one we can report
+        let Some(current) = DivideMismatch::compare(&path, &fen, depth, &actual, &expected) else {
+            break Ok(mismatch);
+        };
+
+        // a different set of moves is the minimal reproduction, and a depth 1
+        // divide cannot be descended any further
+        if current.is_move_mismatch() || depth == 1 {
+            break Ok(Some(current));
+        }
+
+        // descend into the first miscounted move
+        let mv = current.miscounted[0].0;
+        let eval = accumulator.push_next();
+        let delta = position.play_unchecked(mv);
+        eval.apply(delta);
+
+        path.push(mv);
+        depth -= 1;
+        mismatch = Some(current);
+    };
+
+    // restore the position to the root
+    for &mv in path.iter().rev() {
+        position.undo(mv);
+        accumulator.pop();
+    }
+
+    result
+}
@@ -1,5 +1,28 @@
+pub mod consistency;
+pub mod diff;
 pub mod perft;
 pub mod perft_data;
+pub mod reference;
 
-pub use perft::{perft, perft_divide_print};
+pub use consistency::{ConsistencyError, Inconsistency, perft_check};
+pub use diff::{DivideMismatch, perft_diff};
+pub use perft::{perft, perft_divide, perft_divide_print, perft_no_accumulator};
 pub use perft_data::{NodeCount, PerftData};
+pub use reference::{Divide, EpdReference, ReferenceError, UciReference};
+
+use chess_kit_position::Fen;
+use chess_kit_primitives::Depth;
+
+/// `DivideReference` is a trait that defines a source of expected perft divides
+/// used to diff the move generator against
+///
+/// @trait
+pub trait DivideReference {
+    /// divide returns the expected node count of each root move of the given
+    /// position at the given depth
+    ///
+    /// @param: fen - position to divide
+    /// @param: depth - depth to divide the perft at
+    /// @return: expected node count keyed by each root move in UCI notation
+    fn divide(&mut self, fen: &Fen, depth: Depth) -> Result<Divide, ReferenceError>;
+}
@@ -34,7 +34,7 @@ where
 
     // check if we have a cached result in the transposition table
     if let Some(data) = tt.probe(position.key())
-        && data.depth() == depth
+        && data.matches(position.key(), depth)
     {
         return data.node_count();
     }
@@ -43,15 +43,15 @@ where
     let mut moves = MoveList::new();
     move_generator.generate_legal_moves(position, &mut moves);
 
+    // bulk count the leaf nodes instead of making each move, since every legal
+    // move at depth 1 is exactly one leaf node
+    if depth == 1 {
+        return moves.len();
+    }
+
     // count the number of nodes at each branch of the move list
     let mut nodes = 0;
     for &mv in &moves {
-        // just count leaf nodes instead of diving deeper
-        if depth == 1 {
-            nodes += 1;
-            continue;
-        }
-
         // get the next eval state from the accumulator
         let eval = accumulator.push_next();
 
@@ -70,7 +70,64 @@ where
     }
 
     // cache the result in the transposition table
-    tt.insert(position.key(), PerftData::new(depth, nodes));
+    tt.insert(position.key(), PerftData::new(position.key(), depth, nodes));
+
+    nodes
+}
+
+/// `perft_no_accumulator` calculates the number of leaf nodes at the given depth
+/// from the current position without maintaining any evaluation state
+///
+/// note: this variant isolates the cost of move generation and move making from
+///       the cost of incrementally updating an `EvalState` at every node
+///
+/// @param: position - mutable reference to the position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: tt - mutable reference to the transposition table
+/// @param: depth - depth to calculate the perft for
+/// @return: number of leaf nodes at the given depth
+pub fn perft_no_accumulator<MoveGeneratorT, PositionT, TranspositionTableT>(
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    tt: &mut TranspositionTableT,
+    depth: Depth,
+) -> NodeCount
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves,
+    TranspositionTableT: TranspositionTable<PerftData>,
+{
+    // base case: if the depth is 0, return 1
+    if depth == 0 {
+        return 1;
+    }
+
+    // check if we have a cached result in the transposition table
+    if let Some(data) = tt.probe(position.key())
+        && data.matches(position.key(), depth)
+    {
+        return data.node_count();
+    }
+
+    // get all the possible moves from the current position
+    let mut moves = MoveList::new();
+    move_generator.generate_legal_moves(position, &mut moves);
+
+    // bulk count the leaf nodes instead of making each move
+    if depth == 1 {
+        return moves.len();
+    }
+
+    // count the number of nodes at each branch of the move list
+    let mut nodes = 0;
+    for &mv in &moves {
+        let _ = position.play_unchecked(mv);
+        nodes += perft_no_accumulator(position, move_generator, tt, depth - 1);
+        position.undo(mv);
+    }
+
+    // cache the result in the transposition table
+    tt.insert(position.key(), PerftData::new(position.key(), depth, nodes));
 
     nodes
 }
@@ -83,7 +140,7 @@ where
 /// @param: tt - mutable reference to the transposition table
 /// @param: depth - depth to calculate the perft for
 /// @return: vector of branches of moves from the current position
-fn perft_divide<MoveGeneratorT, PositionT, AccumulatorT, EvalStateT, TranspositionTableT>(
+pub fn perft_divide<MoveGeneratorT, PositionT, AccumulatorT, EvalStateT, TranspositionTableT>(
     position: &mut PositionT,
     move_generator: &MoveGeneratorT,
     tt: &mut TranspositionTableT,
@@ -1,22 +1,48 @@
 use chess_kit_collections::Value;
-use chess_kit_primitives::Depth;
+use chess_kit_primitives::{Depth, ZobristKey};
 use chess_kit_transposition::NodeData;
 use std::fmt::{self, Display};
 
 pub type NodeCount = usize;
 
+/// `PerftData` is the node count cached for one position and depth
+///
+/// note: the transposition table only checks 16 bits of the key, which is
+///       enough for a search but not for exact counts, so the entry keeps 32
+///       more bits of the key to verify on probes
 #[derive(Debug, Clone, Copy)]
-pub struct PerftData(Depth, NodeCount);
+pub struct PerftData(Depth, u32, NodeCount);
 
 impl PerftData {
     /// new creates a new perft data
     ///
+    /// @param: key - zobrist key of the position
     /// @param: depth - depth of the perft data
     /// @param: node_count - number of nodes at the given depth
     /// @return: new perft data
     #[inline]
-    pub fn new(depth: Depth, node_count: NodeCount) -> Self {
-        Self(depth, node_count)
+    pub fn new(key: ZobristKey, depth: Depth, node_count: NodeCount) -> Self {
+        Self(depth, Self::key_check(key), node_count)
+    }
+
+    /// key_check returns the bits of a key that the perft data is verified with
+    ///
+    /// @param: key - zobrist key of the position
+    /// @return: bits 16 to 47 of the key
+    #[inline]
+    fn key_check(key: ZobristKey) -> u32 {
+        (key.const_unwrap() >> 16) as u32
+    }
+
+    /// matches checks if the perft data counts the nodes of a position at a
+    /// depth
+    ///
+    /// @param: key - zobrist key of the position
+    /// @param: depth - depth of the count
+    /// @return: true if the data holds the count, false otherwise
+    #[inline]
+    pub fn matches(&self, key: ZobristKey, depth: Depth) -> bool {
+        self.0 == depth && self.1 == Self::key_check(key)
     }
 
     /// depth returns the depth of the perft data
@@ -33,7 +59,7 @@ impl PerftData {
     /// @return: number of nodes at the given depth
     #[inline]
     pub fn node_count(&self) -> NodeCount {
-        self.1
+        self.2
     }
 }
 
@@ -63,7 +89,7 @@ impl Default for PerftData {
     /// @return: new instance of a node with no data
     #[inline]
     fn default() -> Self {
-        Self(0, 0)
+        Self(0, 0, 0)
     }
 }
 
@@ -0,0 +1,316 @@
+use crate::{DivideReference, NodeCount};
+use chess_kit_position::Fen;
+use chess_kit_primitives::Depth;
+use std::collections::{BTreeMap, HashMap};
+use std::ffi::OsStr;
+use std::fmt::{self, Display};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// Divide is the node count of each root move keyed by the move in UCI notation
+///
+/// @type
+pub type Divide = BTreeMap<String, NodeCount>;
+
+/// ReferenceError is returned when a divide reference cannot supply the expected
+/// divide for a position
+///
+/// @type
+#[derive(Debug)]
+pub enum ReferenceError {
+    // the reference could not be read or written
+    Io(io::Error),
+    // the reference output could not be parsed
+    Parse(String),
+    // the reference has no divide for the position
+    MissingDivide { fen: String, depth: Depth },
+    // the reference engine closed its output
+    EngineExited,
+}
+
+impl Display for ReferenceError {
+    /// fmt writes a human-readable description of the reference error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "reference i/o error: {error}"),
+            Self::Parse(line) => write!(f, "unable to parse reference divide: {line}"),
+            Self::MissingDivide { fen, depth } => {
+                write!(f, "reference has no divide for '{fen}' at depth {depth}")
+            }
+            Self::EngineExited => write!(f, "reference engine exited unexpectedly"),
+        }
+    }
+}
+
+impl std::error::Error for ReferenceError {}
+
+impl From<io::Error> for ReferenceError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// position_key returns the part of a FEN string that identifies a position for
+/// perft purposes
+///
+/// note: the move clocks do not affect perft counts, so they are dropped to let
+///       references match positions reached along different move orders
+///
+/// @param: fen - FEN string to extract the key from
+/// @return: piece placement, side to move, castling, and en passant segments
+fn position_key(fen: &str) -> String {
+    fen.split_whitespace().take(4).collect::<Vec<_>>().join(" ")
+}
+
+/// parse_divide_entry parses a single `<move>: <count>` or `<move>:<count>`
+/// divide entry
+///
+/// @param: entry - divide entry to parse
+/// @return: move in lowercase UCI notation and its node count, or None if the
+///          entry is not a divide entry
+fn parse_divide_entry(entry: &str) -> Option<(String, NodeCount)> {
+    let (mv, count) = entry.split_once(':')?;
+    let mv = mv.trim();
+    if !(4..=5).contains(&mv.len()) || !mv.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+
+    let count = count.trim().parse::<NodeCount>().ok()?;
+    Some((mv.to_ascii_lowercase(), count))
+}
+
+/// EpdReference is a divide reference backed by an EPD-style file of expected
+/// divides
+///
+/// Each line holds a FEN followed by one `;D<depth>` section per depth, and each
+/// section lists the expected `<move>:<count>` pairs of that depth:
+///
+/// ```text
+/// 8/8/8/8/8/8/6k1/4K2R w K - 0 1 ;D1 e1d1:1 e1d2:1 e1e2:1 e1f1:1 e1f2:1 e1g1:1 h1f1:1 ...
+/// ```
+///
+/// Blank lines and lines starting with `#` are ignored
+///
+/// @type
+#[derive(Debug, Default, Clone)]
+pub struct EpdReference {
+    divides: HashMap<(String, Depth), Divide>,
+}
+
+impl EpdReference {
+    /// open reads the expected divides from the file at the given path
+    ///
+    /// @param: path - path of the file to read
+    /// @return: divide reference holding every divide in the file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, ReferenceError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// parse reads the expected divides from the given file contents
+    ///
+    /// @param: contents - contents of an expected divides file
+    /// @return: divide reference holding every divide in the contents
+    pub fn parse(contents: &str) -> Result<Self, ReferenceError> {
+        let mut divides = HashMap::new();
+
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut sections = line.split(';');
+            let fen = position_key(sections.next().unwrap_or_default());
+            for section in sections {
+                let mut entries = section.split_whitespace();
+                let depth = entries
+                    .next()
+                    .and_then(|depth| depth.strip_prefix('D'))
+                    .and_then(|depth| depth.parse::<Depth>().ok())
+                    .ok_or_else(|| ReferenceError::Parse(line.to_owned()))?;
+
+                let divide = entries
+                    .map(|entry| {
+                        parse_divide_entry(entry)
+                            .ok_or_else(|| ReferenceError::Parse(line.to_owned()))
+                    })
+                    .collect::<Result<Divide, _>>()?;
+                divides.insert((fen.clone(), depth), divide);
+            }
+        }
+
+        Ok(Self { divides })
+    }
+}
+
+impl DivideReference for EpdReference {
+    /// divide looks up the expected divide for the given position and depth
+    ///
+    /// @impl: DivideReference::divide
+    fn divide(&mut self, fen: &Fen, depth: Depth) -> Result<Divide, ReferenceError> {
+        let fen = position_key(&fen.to_string());
+        self.divides
+            .get(&(fen.clone(), depth))
+            .cloned()
+            .ok_or(ReferenceError::MissingDivide { fen, depth })
+    }
+}
+
+/// UciReference is a divide reference backed by an external UCI engine that
+/// supports the `go perft <depth>` extension
+///
+/// @type
+#[derive(Debug)]
+pub struct UciReference {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl UciReference {
+    /// spawn starts the engine at the given path and waits for it to be ready
+    ///
+    /// @param: program - path of the engine executable
+    /// @return: divide reference backed by the running engine
+    pub fn spawn(program: impl AsRef<OsStr>) -> Result<Self, ReferenceError> {
+        let mut child = Command::new(program)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        let stdin = child.stdin.take().ok_or(ReferenceError::EngineExited)?;
+        let stdout = BufReader::new(child.stdout.take().ok_or(ReferenceError::EngineExited)?);
+
+        let mut reference = Self {
+            child,
+            stdin,
+            stdout,
+        };
+        reference.send("uci")?;
+        reference.wait_for("uciok")?;
+        reference.send("isready")?;
+        reference.wait_for("readyok")?;
+
+        Ok(reference)
+    }
+
+    /// send writes a single command line to the engine
+    ///
+    /// @param: command - command to send
+    /// @return: void
+    /// @side-effects: writes to the engine's standard input
+    fn send(&mut self, command: &str) -> Result<(), ReferenceError> {
+        writeln!(self.stdin, "{command}")?;
+        self.stdin.flush()?;
+        Ok(())
+    }
+
+    /// read_line reads the next line of engine output
+    ///
+    /// @return: next line of output without its line terminator
+    /// @side-effects: reads from the engine's standard output
+    fn read_line(&mut self) -> Result<String, ReferenceError> {
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            return Err(ReferenceError::EngineExited);
+        }
+        Ok(line.trim().to_owned())
+    }
+
+    /// wait_for discards engine output until the given token is read
+    ///
+    /// @param: token - line to wait for
+    /// @return: void
+    /// @side-effects: reads from the engine's standard output
+    fn wait_for(&mut self, token: &str) -> Result<(), ReferenceError> {
+        while self.read_line()? != token {}
+        Ok(())
+    }
+}
+
+impl DivideReference for UciReference {
+    /// divide runs `go perft` on the engine and collects the printed divide
+    ///
+    /// note: the divide ends at the engine's node total, which is printed as
+    ///       `Nodes searched: <n>` by Stockfish and `Total: <n>` by others
+    ///
+    /// @impl: DivideReference::divide
+    fn divide(&mut self, fen: &Fen, depth: Depth) -> Result<Divide, ReferenceError> {
+        self.send(&format!("position fen {fen}"))?;
+        self.send(&format!("go perft {depth}"))?;
+
+        let mut divide = Divide::new();
+        loop {
+            let line = self.read_line()?;
+            if line.starts_with("Nodes searched") || line.starts_with("Total") {
+                return Ok(divide);
+            }
+            if let Some((mv, count)) = parse_divide_entry(&line) {
+                divide.insert(mv, count);
+            }
+        }
+    }
+}
+
+impl Drop for UciReference {
+    fn drop(&mut self) {
+        // best effort shutdown, the engine is killed if it cannot be asked to quit
+        if self.send("quit").is_err() {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KING_AND_ROOK: &str = "8/8/8/8/8/8/6k1/4K2R w K - 0 1";
+
+    #[test]
+    fn parses_divide_entries_from_engine_output() {
+        assert_eq!(
+            parse_divide_entry("e2e4: 20"),
+            Some(("e2e4".to_owned(), 20))
+        );
+        assert_eq!(parse_divide_entry("a7a8Q:3"), Some(("a7a8q".to_owned(), 3)));
+        assert_eq!(parse_divide_entry("info string NNUE enabled"), None);
+        assert_eq!(parse_divide_entry("Nodes searched: 400"), None);
+    }
+
+    #[test]
+    fn looks_up_divides_by_position_and_depth() {
+        let contents = format!("# comment\n\n{KING_AND_ROOK} ;D1 e1d1:1 h1h2:1 ;D2 e1d1:5\n");
+        let mut reference = EpdReference::parse(&contents).unwrap();
+
+        // the move clocks are ignored when matching positions
+        let fen = Fen::try_from("8/8/8/8/8/8/6k1/4K2R w K - 7 42").unwrap();
+
+        let divide = reference.divide(&fen, 1).unwrap();
+        assert_eq!(divide.len(), 2);
+        assert_eq!(divide["h1h2"], 1);
+        assert_eq!(reference.divide(&fen, 2).unwrap()["e1d1"], 5);
+        assert!(matches!(
+            reference.divide(&fen, 3),
+            Err(ReferenceError::MissingDivide { depth: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_malformed_divide_sections() {
+        for contents in [
+            format!("{KING_AND_ROOK} ;1 e1d1:1"),
+            format!("{KING_AND_ROOK} ;D1 e1d1"),
+            format!("{KING_AND_ROOK} ;D1 e1d1:x"),
+        ] {
+            assert!(matches!(
+                EpdReference::parse(&contents),
+                Err(ReferenceError::Parse(_))
+            ));
+        }
+    }
+}
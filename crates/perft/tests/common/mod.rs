@@ -1,7 +1,7 @@
 use std::time::Instant;
 
 use chess_kit_attack_table::DefaultAttackTable;
-use chess_kit_eval::{Accumulator, DefaultAccumulator, EvalState};
+use chess_kit_eval::{Accumulator, DefaultAccumulator, StandaloneEvalState};
 use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
 use chess_kit_perft::{NodeCount, PerftData, perft};
 use chess_kit_position::{DefaultPosition, Fen, Setup};
@@ -20,11 +20,12 @@ const TRANSPOSITION_TABLE_SIZE: usize = 0;
 /// @side-effects: prints timing information and panics if the node count differs
 pub(crate) fn assert_perft<EvalStateT>(fen: &str, depth: Depth, expected_nodes: NodeCount)
 where
-    EvalStateT: EvalState,
+    EvalStateT: StandaloneEvalState,
 {
     let parsed_fen =
         Fen::try_from(fen).unwrap_or_else(|error| panic!("invalid perft FEN '{fen}': {error}"));
-    let mut position: DefaultPosition<DefaultAttackTable> = Setup::from(parsed_fen).into();
+    let mut position: DefaultPosition<DefaultAttackTable> =
+        Setup::from(parsed_fen).try_into().unwrap();
     let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
     let mut transposition_table =
         DefaultTranspositionTable::<PerftData>::new(TRANSPOSITION_TABLE_SIZE);
@@ -0,0 +1,125 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_collections::Copyable;
+use chess_kit_eval::{
+    Accumulator, DefaultAccumulator, EvalState, PSQTEvalState, Score, StandaloneEvalState,
+};
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_perft::{ConsistencyError, Inconsistency, NodeCount, perft_check};
+use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
+use chess_kit_primitives::{Depth, MoveDelta};
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+fn load(fen: &str) -> TestPosition {
+    Setup::from(Fen::try_from(fen).unwrap()).try_into().unwrap()
+}
+
+fn check<EvalStateT: StandaloneEvalState>(
+    fen: &str,
+    depth: Depth,
+) -> Result<NodeCount, ConsistencyError> {
+    let mut position = load(fen);
+    let root = position.key();
+    let mut accumulator = DefaultAccumulator::<EvalStateT>::new();
+    accumulator.push(EvalStateT::from_position(&position));
+
+    let result = perft_check(
+        &mut position,
+        &DefaultMoveGenerator::<DefaultAttackTable>::new(),
+        &mut accumulator,
+        depth,
+    );
+
+    assert_eq!(
+        position.key(),
+        root,
+        "perft_check must restore the root position"
+    );
+    result
+}
+
+/// `StaleEvalState` counts the pieces on the board but deliberately ignores move
+/// deltas so that it diverges from `StandaloneEvalState::from_position` after a
+/// capture
+#[derive(Clone, Copy, Default)]
+struct StaleEvalState {
+    pieces: Score,
+}
+
+impl EvalState for StaleEvalState {
+    fn apply(&mut self, _: MoveDelta) {}
+
+    fn score(&mut self) -> Score {
+        self.pieces
+    }
+}
+
+impl StandaloneEvalState for StaleEvalState {
+    fn from_position<P: PositionView>(position: &P) -> Self {
+        Self {
+            pieces: position.total_occupancy().count_ones() as Score,
+        }
+    }
+}
+
+impl Copyable for StaleEvalState {
+    fn copy_from(&mut self, other: &Self) {
+        *self = *other;
+    }
+}
+
+#[test]
+fn incremental_state_matches_recomputed_state() {
+    for (fen, depth, expected_nodes) in [
+        (
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            3,
+            8902,
+        ),
+        (
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            2,
+            2039,
+        ),
+        ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238),
+        (
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            3,
+            9467,
+        ),
+    ] {
+        assert_eq!(
+            check::<PSQTEvalState>(fen, depth),
+            Ok(expected_nodes),
+            "inconsistent state for FEN '{fen}'"
+        );
+    }
+}
+
+#[test]
+fn repetitions_match_the_recomputed_draw_state() {
+    // knight and king shuffles repeat the root position after four plies
+    assert!(check::<PSQTEvalState>("4k1n1/8/8/8/8/8/8/4K1N1 w - - 0 1", 5).is_ok());
+}
+
+#[test]
+fn reports_the_move_sequence_of_the_first_divergence() {
+    let error = check::<StaleEvalState>("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", 1).unwrap_err();
+
+    assert_eq!(
+        error
+            .path
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>(),
+        ["e4d5"]
+    );
+    assert_eq!(error.fen, "4k3/8/8/3P4/8/8/8/4K3 b - - 0 1");
+    assert!(matches!(
+        error.inconsistency,
+        Inconsistency::Eval {
+            expected: 3,
+            actual: 4
+        }
+    ));
+}
@@ -0,0 +1,176 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_eval::{Accumulator, DefaultAccumulator, NoOpEvalState, StandaloneEvalState};
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_perft::{
+    Divide, DivideReference, EpdReference, NodeCount, PerftData, ReferenceError, perft_diff,
+};
+use chess_kit_position::{DefaultPosition, Fen, PositionMoves, PositionView, Setup};
+use chess_kit_primitives::{Depth, MoveList, ZobristKey};
+use chess_kit_transposition::{DefaultTranspositionTable, TranspositionTable};
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+type TestMoveGenerator = DefaultMoveGenerator<DefaultAttackTable>;
+
+const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+const AFTER_E4_E5: &str = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2";
+
+fn load(fen: &str) -> TestPosition {
+    Setup::from(Fen::try_from(fen).unwrap()).try_into().unwrap()
+}
+
+/// `Fault` is a deliberate disagreement injected into the test reference
+enum Fault {
+    None,
+    Phantom(&'static str), // the reference generates an extra move with one leaf
+    Dropped(&'static str), // the reference does not generate a legal move
+}
+
+/// `FaultyReference` computes divides with the move generator under test, except
+/// at one target position where it injects a fault
+struct FaultyReference {
+    target: ZobristKey,
+    fault: Fault,
+    move_generator: TestMoveGenerator,
+}
+
+impl FaultyReference {
+    fn new(target: &str, fault: Fault) -> Self {
+        Self {
+            target: load(target).key(),
+            fault,
+            move_generator: TestMoveGenerator::new(),
+        }
+    }
+
+    fn divide_position(&self, position: &mut TestPosition, depth: Depth) -> Divide {
+        let mut moves = MoveList::new();
+        self.move_generator
+            .generate_legal_moves(position, &mut moves);
+
+        let mut divide = Divide::new();
+        for &mv in &moves {
+            let _ = position.play_unchecked(mv);
+            let nodes = self.count(position, depth - 1);
+            position.undo(mv);
+            divide.insert(mv.to_string().to_ascii_lowercase(), nodes);
+        }
+
+        if position.key() == self.target {
+            match self.fault {
+                Fault::None => {}
+                Fault::Phantom(mv) => {
+                    divide.insert(mv.to_owned(), 1);
+                }
+                Fault::Dropped(mv) => {
+                    divide.remove(mv);
+                }
+            }
+        }
+
+        divide
+    }
+
+    fn count(&self, position: &mut TestPosition, depth: Depth) -> NodeCount {
+        if depth == 0 {
+            return 1;
+        }
+        self.divide_position(position, depth).values().sum()
+    }
+}
+
+impl DivideReference for FaultyReference {
+    fn divide(&mut self, fen: &Fen, depth: Depth) -> Result<Divide, ReferenceError> {
+        Ok(self.divide_position(&mut load(&fen.to_string()), depth))
+    }
+}
+
+fn diff<ReferenceT: DivideReference>(
+    fen: &str,
+    reference: &mut ReferenceT,
+    depth: Depth,
+) -> Result<Option<chess_kit_perft::DivideMismatch>, ReferenceError> {
+    let mut position = load(fen);
+    let root = position.key();
+    let mut tt = DefaultTranspositionTable::<PerftData>::new(0);
+    let mut accumulator = DefaultAccumulator::<NoOpEvalState>::new();
+    accumulator.push(NoOpEvalState::from_position(&position));
+
+    let result = perft_diff(
+        &mut position,
+        &TestMoveGenerator::new(),
+        &mut tt,
+        &mut accumulator,
+        reference,
+        depth,
+    );
+
+    assert_eq!(
+        position.key(),
+        root,
+        "perft_diff must restore the root position"
+    );
+    result
+}
+
+#[test]
+fn reports_no_mismatch_when_the_reference_agrees() {
+    let mut reference = FaultyReference::new(START_POSITION, Fault::None);
+
+    assert_eq!(diff(START_POSITION, &mut reference, 3).unwrap(), None);
+}
+
+#[test]
+fn descends_to_the_position_with_a_missing_move() {
+    let mut reference = FaultyReference::new(AFTER_E4_E5, Fault::Phantom("a1a5"));
+
+    let mismatch = diff(START_POSITION, &mut reference, 3).unwrap().unwrap();
+
+    assert_eq!(
+        mismatch
+            .path
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>(),
+        ["e2e4", "e7e5"]
+    );
+    assert_eq!(mismatch.fen, AFTER_E4_E5);
+    assert_eq!(mismatch.depth, 1);
+    assert_eq!(mismatch.missing, ["a1a5"]);
+    assert!(mismatch.extra.is_empty());
+}
+
+#[test]
+fn descends_to_the_position_with_an_extra_move() {
+    let mut reference = FaultyReference::new(AFTER_E4_E5, Fault::Dropped("g1f3"));
+
+    let mismatch = diff(START_POSITION, &mut reference, 4).unwrap().unwrap();
+
+    assert_eq!(mismatch.fen, AFTER_E4_E5);
+    assert_eq!(mismatch.depth, 2);
+    assert!(mismatch.missing.is_empty());
+    assert_eq!(
+        mismatch
+            .extra
+            .iter()
+            .map(|mv| mv.to_string())
+            .collect::<Vec<_>>(),
+        ["g1f3"]
+    );
+}
+
+#[test]
+fn reports_miscounted_moves_from_an_expected_divides_file() {
+    let mut reference =
+        EpdReference::parse(&format!("{START_POSITION} ;D1 a2a3:1 b2b3:1 ;D2 a2a3:21")).unwrap();
+
+    // the depth 1 divide is incomplete, so the root moves differ
+    let mismatch = diff(START_POSITION, &mut reference, 1).unwrap().unwrap();
+    assert_eq!(mismatch.extra.len(), 18);
+
+    // the depth 2 divide lists a single miscounted move, so the diff stops at
+    // the root instead of descending
+    assert!(matches!(
+        diff(START_POSITION, &mut reference, 2),
+        Ok(Some(mismatch)) if mismatch.extra.len() == 19
+    ));
+}
@@ -0,0 +1,77 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_eval::{Accumulator, DefaultAccumulator, PSQTEvalState, StandaloneEvalState};
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_perft::{NodeCount, PerftData, perft, perft_no_accumulator};
+use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
+use chess_kit_primitives::Depth;
+use chess_kit_transposition::{DefaultTranspositionTable, TranspositionTable};
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+
+const CASES: [(&str, Depth, NodeCount); 4] = [
+    (
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        3,
+        8902,
+    ),
+    (
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        3,
+        97862,
+    ),
+    ("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", 4, 43238),
+    (
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        3,
+        9467,
+    ),
+];
+
+fn load(fen: &str) -> TestPosition {
+    Setup::from(Fen::try_from(fen).unwrap()).try_into().unwrap()
+}
+
+#[test]
+fn counts_the_same_nodes_as_the_accumulating_perft() {
+    let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+    for (fen, depth, expected_nodes) in CASES {
+        let mut position = load(fen);
+        let root = position.key();
+
+        let mut tt = DefaultTranspositionTable::<PerftData>::new(0);
+        let nodes = perft_no_accumulator(&mut position, &move_generator, &mut tt, depth);
+        assert_eq!(nodes, expected_nodes, "perft mismatch for FEN '{fen}'");
+        assert_eq!(
+            position.key(),
+            root,
+            "position must be restored for FEN '{fen}'"
+        );
+
+        let mut tt = DefaultTranspositionTable::<PerftData>::new(0);
+        let mut accumulator = DefaultAccumulator::<PSQTEvalState>::new();
+        accumulator.push(PSQTEvalState::from_position(&position));
+        assert_eq!(
+            perft(
+                &mut position,
+                &move_generator,
+                &mut tt,
+                &mut accumulator,
+                depth
+            ),
+            nodes
+        );
+    }
+}
+
+#[test]
+fn bulk_counts_the_legal_moves_at_depth_one() {
+    let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let mut tt = DefaultTranspositionTable::<PerftData>::new(0);
+    let mut position = load(CASES[1].0);
+
+    assert_eq!(
+        perft_no_accumulator(&mut position, &move_generator, &mut tt, 1),
+        48
+    );
+}
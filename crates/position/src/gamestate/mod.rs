@@ -7,12 +7,13 @@ mod state;
 pub use draw::DrawState;
 pub(crate) use state::PositionState;
 
-use chess_kit_collections::Stack;
+use chess_kit_collections::GrowableStack;
 
 /// History stores the private position state for each reversible ply
 ///
 /// History provides the state snapshots used to restore incremental metadata and tactical
-/// information when a move is undone
+/// information when a move is undone. It grows with the game, so arbitrarily long games keep
+/// their full history for repetition detection
 ///
 /// @type
-pub(crate) type History = Stack<PositionState>;
+pub(crate) type History = GrowableStack<PositionState>;
@@ -5,7 +5,7 @@ use core::mem::size_of;
 /// PositionMetadata contains non-board information describing a position and its identity
 ///
 /// PositionMetadata is the portion of a history entry copied forward before a move updates
-/// the side to move, clocks, rights, incremental key, and draw state
+/// the side to move, clocks, rights, incremental keys, and draw state
 ///
 /// @type
 #[derive(Clone, Copy)]
@@ -16,6 +16,7 @@ pub(crate) struct PositionMetadata {
     pub(crate) halfmoves: Clock,
     pub(crate) fullmoves: Clock,
     pub(crate) key: ZobristKey,
+    pub(crate) pawn_key: ZobristKey,
     pub(crate) draw_state: DrawState,
 }
 
@@ -28,12 +29,13 @@ impl Default for PositionMetadata {
             halfmoves: 0,
             fullmoves: 0,
             key: ZobristKey::default(),
+            pawn_key: ZobristKey::default(),
             draw_state: DrawState::default(),
         }
     }
 }
 
-const _: () = assert!(size_of::<PositionMetadata>() == 24);
+const _: () = assert!(size_of::<PositionMetadata>() == 32);
 
 #[cfg(test)]
 mod tests {
@@ -41,6 +43,6 @@ mod tests {
 
     #[test]
     fn metadata_size_is_stable() {
-        assert_eq!(size_of::<PositionMetadata>(), 24);
+        assert_eq!(size_of::<PositionMetadata>(), 32);
     }
 }
@@ -93,6 +93,14 @@ impl PositionState {
         self.metadata.key
     }
 
+    /// pawn_key returns the incremental pawn structure key
+    ///
+    /// @return: key of the pawns of both sides
+    #[inline]
+    pub(crate) fn pawn_key(&self) -> ZobristKey {
+        self.metadata.pawn_key
+    }
+
     /// checkers returns the pieces checking the side-to-move's king
     ///
     /// @return: bitboard of checking pieces
@@ -238,6 +246,27 @@ impl PositionState {
         self.metadata.key ^= key;
     }
 
+    /// set_pawn_key replaces the incremental pawn structure key
+    ///
+    /// @param: pawn_key - pawn structure key to set
+    /// @return: void
+    /// @side-effects: modifies the current state
+    #[inline]
+    pub(crate) fn set_pawn_key(&mut self, pawn_key: ZobristKey) {
+        self.metadata.pawn_key = pawn_key;
+    }
+
+    /// update_pawn_key applies a Zobrist delta to the current pawn structure
+    /// key
+    ///
+    /// @param: key - Zobrist delta to apply
+    /// @return: void
+    /// @side-effects: modifies the current state
+    #[inline]
+    pub(crate) fn update_pawn_key(&mut self, key: ZobristKey) {
+        self.metadata.pawn_key ^= key;
+    }
+
     /// set_checkers replaces the pieces checking the side-to-move's king
     ///
     /// @param: checkers - bitboard of checking pieces
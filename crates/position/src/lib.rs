@@ -16,11 +16,14 @@ pub use errors::PlayError;
 pub use gamestate::DrawState;
 pub(crate) use gamestate::{History, PositionState};
 pub use position::DefaultPosition;
-pub use setup::{FENError, Fen, Setup};
+pub use setup::{
+    FENError, Fen, PackedError, PackedPosition, PackedReader, PackedWriter, Setup, SetupBuilder,
+    SetupError, SetupErrors,
+};
 pub use snapshot::PositionSnapshot;
 
 use chess_kit_primitives::{
-    Bitboard, Castling, Move, MoveDelta, Pieces, Side, Sides, Square, ZobristKey,
+    Bitboard, Castling, Clock, Move, MoveDelta, Pieces, Side, Sides, Square, ZobristKey,
 };
 use std::fmt::Display;
 
@@ -93,10 +96,33 @@ pub trait PositionView {
     /// @return: current castling rights
     fn castling(&self) -> Castling;
 
+    /// halfmoves gets the number of halfmoves since the last capture or pawn
+    /// move
+    ///
+    /// @return: current halfmove clock
+    fn halfmoves(&self) -> Clock;
+
+    /// fullmoves gets the current fullmove number
+    ///
+    /// @return: current fullmove number
+    fn fullmoves(&self) -> Clock;
+
     /// key gets the unique key identifier for the current position
     ///
     /// @return: unique identifier of the position
     fn key(&self) -> ZobristKey;
+
+    /// pawn_key gets the key identifying the pawn structure of the current
+    /// position
+    ///
+    /// @return: key of the pawns of both sides
+    fn pawn_key(&self) -> ZobristKey;
+
+    /// draw_state gets the incrementally maintained draw information for the
+    /// current position
+    ///
+    /// @return: draw information for the current position
+    fn draw_state(&self) -> DrawState;
 }
 
 /// `PositionAttacks` is a trait that defines all the attack-related queries that
@@ -229,10 +229,13 @@ where
     fn move_piece<SideT: Side>(&mut self, piece: Pieces, from: Square, to: Square) {
         self.move_piece_no_incrementals::<SideT>(piece, from, to);
 
-        // update the zobrist key
+        // update the zobrist keys
         let key =
             ZobristTable::piece::<SideT>(piece, from) ^ ZobristTable::piece::<SideT>(piece, to);
         self.state_mut().update_key(key);
+        if piece == Pieces::Pawn {
+            self.state_mut().update_pawn_key(key);
+        }
     }
 
     /// capture_piece captures SideT's piece at the given square
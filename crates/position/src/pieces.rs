@@ -36,6 +36,9 @@ where
         self.remove_piece_no_incrementals::<SideT>(piece, square);
         let delta = ZobristTable::piece::<SideT>(piece, square);
         self.state_mut().update_key(delta);
+        if piece == Pieces::Pawn {
+            self.state_mut().update_pawn_key(delta);
+        }
     }
 
     /// set_piece_no_incrementals sets SideT's piece on the given square without
@@ -64,6 +67,9 @@ where
         self.set_piece_no_incrementals::<SideT>(piece, square);
         let delta = ZobristTable::piece::<SideT>(piece, square);
         self.state_mut().update_key(delta);
+        if piece == Pieces::Pawn {
+            self.state_mut().update_pawn_key(delta);
+        }
     }
 
     /// set_en_passant sets the en passant square in the state
@@ -1,4 +1,4 @@
-use crate::setup::Setup;
+use crate::setup::{Setup, SetupErrors};
 use crate::{History, PositionState, PositionView};
 use chess_kit_attack_table::AttackTable;
 use chess_kit_primitives::{Bitboard, Pieces, Side, Sides, Square, ZobristTable, call_as};
@@ -121,6 +121,8 @@ impl<AT: AttackTable> DefaultPosition<AT> {
             self.bitboards,
         );
         self.state_mut().set_key(key);
+        let pawn_key = ZobristTable::new_pawn_key(self.bitboards);
+        self.state_mut().set_pawn_key(pawn_key);
         self.update_material_draw_state();
 
         let checkers = self.is_checked_by::<SideT>();
@@ -134,16 +136,19 @@ impl<AT: AttackTable> Default for DefaultPosition<AT> {
     ///
     /// @return: standard starting position
     fn default() -> Self {
-        Setup::default().into()
+        Self::from_setup(Setup::default())
     }
 }
 
-impl<AT: AttackTable> From<Setup> for DefaultPosition<AT> {
-    /// from creates a position from a validated setup
+impl<AT: AttackTable> TryFrom<Setup> for DefaultPosition<AT> {
+    type Error = SetupErrors;
+
+    /// try_from creates a position from a setup that follows the rules of chess
     ///
-    /// @param: setup - validated setup to load
-    /// @return: initialized position
-    fn from(setup: Setup) -> Self {
-        Self::from_setup(setup)
+    /// @param: setup - setup to validate and load
+    /// @return: initialized position, or every rule the setup breaks
+    fn try_from(setup: Setup) -> Result<Self, Self::Error> {
+        setup.validate::<AT>()?;
+        Ok(Self::from_setup(setup))
     }
 }
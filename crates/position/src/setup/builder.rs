@@ -0,0 +1,262 @@
+use super::{PieceOnSquare, Setup, SetupErrors};
+use crate::PositionSnapshot;
+use chess_kit_attack_table::AttackTable;
+use chess_kit_primitives::{Castling, Clock, Pieces, Sides, Square};
+
+/// SetupBuilder edits the pieces and state of a setup, square by square, and
+/// validates the result once it is built
+///
+/// The builder starts from an empty board with white to move, no castling
+/// rights, no en passant square and the clocks of a new game.
+///
+/// @type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetupBuilder {
+    setup: Setup, // setup being edited, which may be illegal until built
+}
+
+impl SetupBuilder {
+    /// new creates a builder for an empty board
+    ///
+    /// @return: builder of an empty board with white to move
+    pub const fn new() -> Self {
+        Self {
+            setup: Setup {
+                pieces: [None; Square::TOTAL],
+                side_to_move: Sides::White,
+                castling: Castling::NONE,
+                en_passant: None,
+                halfmoves: 0,
+                fullmoves: 1,
+            },
+        }
+    }
+
+    /// with_piece places a piece on a square, replacing any piece already there
+    ///
+    /// @param: square - square to place the piece on
+    /// @param: side - side that owns the piece
+    /// @param: piece - piece to place
+    /// @return: updated builder
+    pub const fn with_piece(mut self, square: Square, side: Sides, piece: Pieces) -> Self {
+        self.setup.pieces[square.idx()] = Some((side, piece));
+        self
+    }
+
+    /// without_piece removes the piece on a square, if any
+    ///
+    /// @param: square - square to clear
+    /// @return: updated builder
+    pub const fn without_piece(mut self, square: Square) -> Self {
+        self.setup.pieces[square.idx()] = None;
+        self
+    }
+
+    /// with_side_to_move sets the side whose turn it is
+    ///
+    /// @param: side - side to move
+    /// @return: updated builder
+    pub const fn with_side_to_move(mut self, side: Sides) -> Self {
+        self.setup.side_to_move = side;
+        self
+    }
+
+    /// with_castling sets the available castling rights
+    ///
+    /// @param: castling - available castling rights
+    /// @return: updated builder
+    pub const fn with_castling(mut self, castling: Castling) -> Self {
+        self.setup.castling = castling;
+        self
+    }
+
+    /// with_en_passant sets the en passant target square
+    ///
+    /// @param: en_passant - en passant target square, or None to clear it
+    /// @return: updated builder
+    pub const fn with_en_passant(mut self, en_passant: Option<Square>) -> Self {
+        self.setup.en_passant = en_passant;
+        self
+    }
+
+    /// with_halfmoves sets the number of halfmoves since the last zeroing move
+    ///
+    /// @param: halfmoves - halfmove clock
+    /// @return: updated builder
+    pub const fn with_halfmoves(mut self, halfmoves: Clock) -> Self {
+        self.setup.halfmoves = halfmoves;
+        self
+    }
+
+    /// with_fullmoves sets the current fullmove number
+    ///
+    /// @param: fullmoves - fullmove number
+    /// @return: updated builder
+    pub const fn with_fullmoves(mut self, fullmoves: Clock) -> Self {
+        self.setup.fullmoves = fullmoves;
+        self
+    }
+
+    /// piece_at returns the side and piece currently on a square
+    ///
+    /// @param: square - square to inspect
+    /// @return: occupying side and piece, or None when the square is empty
+    pub const fn piece_at(&self, square: Square) -> PieceOnSquare {
+        self.setup.pieces[square.idx()]
+    }
+
+    /// flipped swaps the colors of the setup, mirroring the board between the
+    /// ranks so that each side's pieces keep their relative placement
+    ///
+    /// @return: updated builder
+    pub fn flipped(mut self) -> Self {
+        self.setup = self.setup.flipped();
+        self
+    }
+
+    /// mirrored mirrors the board between the files, so that pieces on the
+    /// a-file move to the h-file and vice versa
+    ///
+    /// note: the castling rights are dropped, since the kings and rooks no
+    ///       longer stand on the squares castling moves them from
+    ///
+    /// @return: updated builder
+    pub fn mirrored(mut self) -> Self {
+        self.setup.castling = Castling::NONE;
+        self.setup = self
+            .setup
+            .mirrored()
+            .expect("setups without castling rights can be mirrored");
+        self
+    }
+
+    /// build validates the edited setup
+    ///
+    /// @marker: AT - attack table used to find checks
+    /// @return: legal setup, or every rule the setup breaks
+    pub fn build<AT: AttackTable>(self) -> Result<Setup, SetupErrors> {
+        self.setup.validate::<AT>()?;
+        Ok(self.setup)
+    }
+}
+
+impl Default for SetupBuilder {
+    /// default creates a builder for an empty board
+    ///
+    /// @return: builder of an empty board with white to move
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<Setup> for SetupBuilder {
+    /// from starts editing an existing setup
+    ///
+    /// @param: setup - setup to edit
+    /// @return: builder holding the setup
+    fn from(setup: Setup) -> Self {
+        Self { setup }
+    }
+}
+
+impl From<&PositionSnapshot> for SetupBuilder {
+    /// from starts editing the pieces and side to move of a snapshot
+    ///
+    /// note: a snapshot holds no castling rights, en passant square or clocks,
+    ///       so those start as they do for an empty board
+    ///
+    /// @param: snapshot - snapshot to edit
+    /// @return: builder holding the snapshot's board
+    fn from(snapshot: &PositionSnapshot) -> Self {
+        let mut builder = Self::new().with_side_to_move(snapshot.side_to_move());
+        for idx in 0..Square::TOTAL {
+            let square = Square::from_idx(idx);
+            builder.setup.pieces[idx] = snapshot.piece_at(square);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Fen, SetupError};
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_primitives::{Black, White};
+
+    fn fen(builder: SetupBuilder) -> String {
+        Fen::from(builder.build::<DefaultAttackTable>().unwrap()).to_string()
+    }
+
+    fn builder(fen: &str) -> SetupBuilder {
+        SetupBuilder::from(Setup::from(Fen::try_from(fen).unwrap()))
+    }
+
+    #[test]
+    fn places_and_removes_pieces_on_an_empty_board() {
+        let builder = SetupBuilder::new()
+            .with_piece(Square::E1, Sides::White, Pieces::King)
+            .with_piece(Square::H1, Sides::White, Pieces::Rook)
+            .with_piece(Square::E8, Sides::Black, Pieces::King)
+            .with_piece(Square::D5, Sides::Black, Pieces::Pawn)
+            .with_piece(Square::E5, Sides::White, Pieces::Pawn)
+            .with_piece(Square::A2, Sides::White, Pieces::Queen)
+            .without_piece(Square::A2)
+            .with_castling(Castling::WHITE_KING)
+            .with_en_passant(Some(Square::D6))
+            .with_halfmoves(0)
+            .with_fullmoves(12);
+
+        assert_eq!(builder.piece_at(Square::A2), None);
+        assert_eq!(fen(builder), "4k3/8/8/3pP3/8/8/8/4K2R w K d6 0 12");
+    }
+
+    #[test]
+    fn rejects_illegal_edits_when_built() {
+        let errors = builder("4k3/8/8/8/8/8/8/4K2R w K - 0 1")
+            .without_piece(Square::H1)
+            .with_piece(Square::E7, Sides::White, Pieces::Queen)
+            .build::<DefaultAttackTable>()
+            .unwrap_err();
+
+        assert_eq!(
+            errors.errors(),
+            [
+                SetupError::OpponentInCheck,
+                SetupError::CastlingWithoutPieces(Castling::WHITE_KING),
+            ]
+        );
+    }
+
+    #[test]
+    fn edits_existing_setups_and_snapshots() {
+        assert_eq!(
+            fen(SetupBuilder::from(Setup::default())
+                .without_piece(Square::E2)
+                .with_piece(Square::E4, Sides::White, Pieces::Pawn)
+                .with_side_to_move(Sides::Black)
+                .with_en_passant(Some(Square::E3))),
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"
+        );
+
+        let snapshot = PositionSnapshot::empty::<White>()
+            .with_piece::<White>(Square::E1, Pieces::King)
+            .with_piece::<Black>(Square::E8, Pieces::King);
+        assert_eq!(
+            fen(SetupBuilder::from(&snapshot)),
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1"
+        );
+    }
+
+    #[test]
+    fn flips_colors_and_mirrors_files() {
+        let flipped = builder("r3k3/8/8/8/3pP3/8/8/4K2R b Kq e3 0 1").flipped();
+        assert_eq!(fen(flipped), "4k2r/8/8/3Pp3/8/8/8/R3K3 w Qk e6 0 1");
+
+        let mirrored = builder("4k3/8/8/3pP3/8/8/8/4K2R w K d6 0 1").mirrored();
+        assert_eq!(fen(mirrored), "3k4/8/8/3Pp3/8/8/8/R2K4 w - e6 0 1");
+
+        let setup = builder("r3k3/8/8/8/3pP3/8/8/4K2R b Kq e3 0 1");
+        assert_eq!(setup.clone().flipped().flipped(), setup);
+    }
+}
@@ -1,3 +1,4 @@
+use chess_kit_primitives::{Castling, Sides, Square};
 use std::fmt::{self, Display};
 
 /// `FENError` is an enum that represents the errors that can occur when parsing a FEN string
@@ -34,3 +35,126 @@ impl Display for FENError {
         write!(f, "Error in FEN string: {error}")
     }
 }
+
+/// `PackedError` is an enum that represents the errors that can occur when packing a
+/// position or unpacking a packed position
+///
+/// @type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PackedError {
+    InvalidPieces,    // the occupancy and piece nibbles must describe a legal set of pieces
+    InvalidFlags,     // the flags byte must only contain the turn and castling rights
+    InvalidEnPassant, // the en passant byte must be a third or sixth rank square or 64
+    TooManyPieces,    // a packed position can hold at most 32 pieces
+}
+
+impl std::error::Error for PackedError {}
+
+impl Display for PackedError {
+    /// fmt writes a human-readable description of the unpacking error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let error = match self {
+            Self::InvalidPieces => "Invalid occupancy or pieces",
+            Self::InvalidFlags => "Invalid turn or castling flags",
+            Self::InvalidEnPassant => "Invalid en passant square",
+            Self::TooManyPieces => "Too many pieces to pack",
+        };
+        write!(f, "Error in packed position: {error}")
+    }
+}
+
+/// `SetupError` is an enum that represents a rule of chess broken by a setup
+///
+/// @type
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetupError {
+    KingCount(Sides),                // each side must have exactly one king
+    TooManyPieces(Sides),            // each side can have at most 16 pieces
+    PawnOnBackRank(Square),          // pawns cannot stand on the first or eighth rank
+    OpponentInCheck,                 // the side not to move cannot be in check
+    CastlingWithoutPieces(Castling), // castling rights need the king and rook on their home squares
+    InvalidEnPassant(Square), // the en passant square must be behind a pawn that just double-pushed
+}
+
+impl std::error::Error for SetupError {}
+
+impl Display for SetupError {
+    /// fmt writes a human-readable description of the broken rule
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::KingCount(side) => write!(f, "{} must have exactly one king", side_name(*side)),
+            Self::TooManyPieces(side) => {
+                write!(f, "{} has more than 16 pieces", side_name(*side))
+            }
+            Self::PawnOnBackRank(square) => write!(f, "pawn on the back rank square {square}"),
+            Self::OpponentInCheck => write!(f, "the side not to move is in check"),
+            Self::CastlingWithoutPieces(right) => write!(
+                f,
+                "castling right {right} without the king and rook on their home squares"
+            ),
+            Self::InvalidEnPassant(square) => write!(
+                f,
+                "en passant square {square} without a pawn that just double-pushed"
+            ),
+        }
+    }
+}
+
+/// `SetupErrors` is the list of every rule of chess broken by a setup
+///
+/// @type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupErrors(Vec<SetupError>);
+
+impl SetupErrors {
+    /// new creates a list of broken rules
+    ///
+    /// @param: errors - rules broken by a setup
+    /// @return: list of broken rules
+    pub(crate) const fn new(errors: Vec<SetupError>) -> Self {
+        Self(errors)
+    }
+
+    /// errors returns the broken rules in the order they were found
+    ///
+    /// @return: rules broken by the setup
+    pub fn errors(&self) -> &[SetupError] {
+        &self.0
+    }
+}
+
+impl std::error::Error for SetupErrors {}
+
+impl Display for SetupErrors {
+    /// fmt writes every broken rule, separated by semicolons
+    ///
+    /// @param: f - formatter to write the errors into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Illegal setup: ")?;
+        for (index, error) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// side_name returns the display name of a side
+///
+/// @param: side - side to name
+/// @return: capitalized name of the side
+const fn side_name(side: Sides) -> &'static str {
+    match side {
+        Sides::White => "White",
+        Sides::Black => "Black",
+    }
+}
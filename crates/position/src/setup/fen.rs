@@ -1,5 +1,7 @@
 use super::{FENError, PieceOnSquare, Setup};
+use crate::PositionView;
 use chess_kit_primitives::{Black, Castling, Clock, Pieces, Sides, Square, White};
+use std::fmt::{self, Display};
 use std::str::FromStr;
 
 const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
@@ -262,6 +264,89 @@ impl From<Fen> for Setup {
     }
 }
 
+impl From<Setup> for Fen {
+    /// from converts a format-independent setup into FEN data
+    ///
+    /// @param: setup - position setup to convert
+    /// @return: FEN data describing the setup
+    fn from(setup: Setup) -> Self {
+        Self {
+            pieces: setup.pieces,
+            side_to_move: setup.side_to_move,
+            castling: setup.castling,
+            en_passant: setup.en_passant,
+            halfmoves: setup.halfmoves,
+            fullmoves: setup.fullmoves,
+        }
+    }
+}
+
+impl<PositionT> From<&PositionT> for Fen
+where
+    PositionT: PositionView,
+{
+    /// from captures the current state of a position view as FEN data
+    ///
+    /// @param: position - position view to capture
+    /// @return: FEN data describing the position
+    fn from(position: &PositionT) -> Self {
+        Setup::from(position).into()
+    }
+}
+
+impl Display for Fen {
+    /// fmt writes the FEN data as a Forsyth-Edwards Notation string
+    ///
+    /// @param: f - formatter to write the FEN string into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rank in (0..8).rev() {
+            let mut empty_squares = 0;
+            for file in 0..8 {
+                let Some((side, piece)) = self.pieces[rank * 8 + file] else {
+                    empty_squares += 1;
+                    continue;
+                };
+
+                if empty_squares > 0 {
+                    write!(f, "{empty_squares}")?;
+                    empty_squares = 0;
+                }
+                match side {
+                    Sides::White => write!(f, "{piece}")?,
+                    Sides::Black => write!(f, "{}", piece.to_string().to_lowercase())?,
+                }
+            }
+
+            if empty_squares > 0 {
+                write!(f, "{empty_squares}")?;
+            }
+            if rank > 0 {
+                write!(f, "/")?;
+            }
+        }
+
+        let side_to_move = match self.side_to_move {
+            Sides::White => "w",
+            Sides::Black => "b",
+        };
+        write!(f, " {side_to_move} ")?;
+
+        if self.castling == Castling::none() {
+            write!(f, "-")?;
+        } else {
+            write!(f, "{}", self.castling)?;
+        }
+
+        match self.en_passant {
+            Some(square) => write!(f, " {square}")?,
+            None => write!(f, " -")?,
+        }
+
+        write!(f, " {} {}", self.halfmoves, self.fullmoves)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -312,6 +397,18 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn formats_parsed_fen_back_into_the_same_string() {
+        for fen in [
+            START_POSITION,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+        ] {
+            assert_eq!(Fen::try_from(fen).unwrap().to_string(), fen);
+        }
+    }
+
     #[test]
     fn clocks_use_the_u32_primitive() {
         let setup =
@@ -1,8 +1,14 @@
+mod builder;
 mod errors;
 mod fen;
+mod packed;
+mod transform;
+mod validate;
 
+pub use builder::*;
 pub use errors::*;
 pub use fen::*;
+pub use packed::*;
 
 use chess_kit_primitives::{Castling, Clock, Pieces, Sides, Square};
 
@@ -0,0 +1,386 @@
+use super::{PackedError, Setup};
+use crate::PositionView;
+use chess_kit_primitives::{Castling, Clock, Pieces, Sides, Square};
+use std::io::{self, Read, Write};
+
+/// NO_EN_PASSANT is the en passant byte of a position without an en passant
+/// square
+const NO_EN_PASSANT: u8 = Square::TOTAL as u8;
+
+/// CASTLING_RIGHTS are the castling rights in the order of their flag bits
+const CASTLING_RIGHTS: [Castling; 4] = [
+    Castling::WHITE_KING,
+    Castling::WHITE_QUEEN,
+    Castling::BLACK_KING,
+    Castling::BLACK_QUEEN,
+];
+
+/// PackedPosition is a position packed into [`PackedPosition::SIZE`] bytes
+///
+/// The bytes hold, in order and little-endian:
+///
+/// | bytes | contents                                                      |
+/// |:-----:|:--------------------------------------------------------------|
+/// | 0-7   | occupied squares as a bitboard                                |
+/// | 8-23  | pieces of the occupied squares in ascending order, one per    |
+/// |       | nibble and low nibble first, as the piece index plus 8 for    |
+/// |       | black pieces                                                  |
+/// | 24    | side to move in bit 0, castling rights in bits 1-4            |
+/// | 25    | en passant square, or 64 without one                          |
+/// | 26    | halfmove clock                                                |
+/// | 27-28 | fullmove number                                               |
+///
+/// note: clocks beyond the range of their bytes are saturated when packed
+/// note: positions with more than [`PackedPosition::MAX_PIECES`] pieces cannot
+///       be packed
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PackedPosition([u8; PackedPosition::SIZE]);
+
+impl PackedPosition {
+    /// SIZE is the number of bytes of a packed position
+    pub const SIZE: usize = 29;
+
+    /// MAX_PIECES is the largest number of pieces a packed position can hold
+    pub const MAX_PIECES: usize = 32;
+
+    /// from_bytes wraps the bytes of a packed position
+    ///
+    /// note: the bytes are only validated when unpacked into a setup
+    ///
+    /// @param: bytes - bytes of the packed position
+    /// @return: packed position
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        Self(bytes)
+    }
+
+    /// as_bytes returns the bytes of the packed position
+    ///
+    /// @return: bytes of the packed position
+    #[inline]
+    pub const fn as_bytes(&self) -> &[u8; Self::SIZE] {
+        &self.0
+    }
+
+    /// from_position packs the current state of a position view
+    ///
+    /// @param: position - position view to pack
+    /// @return: packed position, or the packed position error
+    pub fn from_position<PositionT: PositionView>(
+        position: &PositionT,
+    ) -> Result<Self, PackedError> {
+        Self::try_from(&Setup::from(position))
+    }
+}
+
+impl TryFrom<&Setup> for PackedPosition {
+    type Error = PackedError;
+
+    /// try_from packs a setup
+    ///
+    /// @param: setup - setup to pack
+    /// @return: packed position, or the packed position error
+    fn try_from(setup: &Setup) -> Result<Self, Self::Error> {
+        let pieces = setup.pieces();
+        if pieces.iter().flatten().count() > Self::MAX_PIECES {
+            return Err(PackedError::TooManyPieces);
+        }
+
+        let mut bytes = [0u8; Self::SIZE];
+        let mut occupancy = 0u64;
+        let mut nibble = 0;
+        for (square, piece) in pieces.iter().enumerate() {
+            let Some((side, piece)) = piece else {
+                continue;
+            };
+            occupancy |= 1 << square;
+            bytes[8 + nibble / 2] |= piece_code(*side, *piece) << (4 * (nibble % 2));
+            nibble += 1;
+        }
+        bytes[..8].copy_from_slice(&occupancy.to_le_bytes());
+
+        let mut flags = setup.side_to_move().idx() as u8;
+        let castling = setup.castling();
+        for (bit, right) in CASTLING_RIGHTS.into_iter().enumerate() {
+            if castling & right == right {
+                flags |= 1 << (bit + 1);
+            }
+        }
+        bytes[24] = flags;
+        bytes[25] = setup
+            .en_passant()
+            .map_or(NO_EN_PASSANT, |square| square.idx() as u8);
+        bytes[26] = setup.halfmoves().min(Clock::from(u8::MAX)) as u8;
+        bytes[27..29]
+            .copy_from_slice(&(setup.fullmoves().min(Clock::from(u16::MAX)) as u16).to_le_bytes());
+        Ok(Self(bytes))
+    }
+}
+
+impl TryFrom<&PackedPosition> for Setup {
+    type Error = PackedError;
+
+    /// try_from unpacks and validates a packed position
+    ///
+    /// @param: packed - packed position to unpack
+    /// @return: setup of the position, or the packed position error
+    fn try_from(packed: &PackedPosition) -> Result<Self, Self::Error> {
+        let bytes = packed.as_bytes();
+        let occupancy = u64::from_le_bytes(bytes[..8].try_into().expect("8 occupancy bytes"));
+        if occupancy.count_ones() as usize > PackedPosition::MAX_PIECES {
+            return Err(PackedError::InvalidPieces);
+        }
+
+        let mut pieces = [None; Square::TOTAL];
+        let mut king_counts = [0_u8; Sides::TOTAL];
+        let mut nibble = 0;
+        for (square, piece) in pieces.iter_mut().enumerate() {
+            if occupancy & (1 << square) == 0 {
+                continue;
+            }
+            let code = (bytes[8 + nibble / 2] >> (4 * (nibble % 2))) & 0xF;
+            let (side, kind) = decode_piece(code).ok_or(PackedError::InvalidPieces)?;
+            if kind == Pieces::King {
+                king_counts[side] += 1;
+            }
+            *piece = Some((side, kind));
+            nibble += 1;
+        }
+        // the nibbles after the last piece are unused
+        let unused = (nibble..PackedPosition::MAX_PIECES)
+            .any(|nibble| (bytes[8 + nibble / 2] >> (4 * (nibble % 2))) & 0xF != 0);
+        if unused || king_counts != [1, 1] {
+            return Err(PackedError::InvalidPieces);
+        }
+
+        let flags = bytes[24];
+        if flags >> 5 != 0 {
+            return Err(PackedError::InvalidFlags);
+        }
+        let side_to_move = Sides::from_idx(usize::from(flags & 1));
+        let castling = CASTLING_RIGHTS
+            .into_iter()
+            .enumerate()
+            .filter(|(bit, _)| flags & (1 << (bit + 1)) != 0)
+            .fold(Castling::none(), |castling, (_, right)| castling | right);
+
+        let en_passant = match bytes[25] {
+            NO_EN_PASSANT => None,
+            // en passant squares are on the third and sixth ranks
+            square @ (16..=23 | 40..=47) => Some(Square::from_idx(usize::from(square))),
+            _ => return Err(PackedError::InvalidEnPassant),
+        };
+
+        Ok(Self {
+            pieces,
+            side_to_move,
+            castling,
+            en_passant,
+            halfmoves: Clock::from(bytes[26]),
+            fullmoves: Clock::from(u16::from_le_bytes([bytes[27], bytes[28]])),
+        })
+    }
+}
+
+impl TryFrom<PackedPosition> for Setup {
+    type Error = PackedError;
+
+    /// try_from unpacks and validates a packed position
+    ///
+    /// @param: packed - packed position to unpack
+    /// @return: setup of the position, or the packed position error
+    fn try_from(packed: PackedPosition) -> Result<Self, Self::Error> {
+        Self::try_from(&packed)
+    }
+}
+
+/// PackedWriter writes packed positions to a stream of records
+///
+/// @type
+pub struct PackedWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> PackedWriter<W> {
+    /// new creates a writer of packed positions
+    ///
+    /// @param: writer - destination of the records
+    /// @return: new packed position writer
+    pub const fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// write writes a packed position as the next record
+    ///
+    /// @param: packed - packed position to write
+    /// @return: Ok on success, or the io error
+    pub fn write(&mut self, packed: &PackedPosition) -> io::Result<()> {
+        self.writer.write_all(packed.as_bytes())
+    }
+
+    /// into_inner flushes the writer and returns its destination
+    ///
+    /// @return: destination of the records, or the io error
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// PackedReader reads packed positions from a stream of records
+///
+/// @type
+pub struct PackedReader<R: Read> {
+    reader: R,
+}
+
+impl<R: Read> PackedReader<R> {
+    /// new creates a reader of packed positions
+    ///
+    /// @param: reader - source of the records
+    /// @return: new packed position reader
+    pub const fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: Read> Iterator for PackedReader<R> {
+    type Item = io::Result<PackedPosition>;
+
+    /// next reads the next record of the stream
+    ///
+    /// @return: next packed position, None at the end of the stream, or the
+    ///          io error of a truncated record
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0u8; PackedPosition::SIZE];
+        let mut read = 0;
+        while read < bytes.len() {
+            match self.reader.read(&mut bytes[read..]) {
+                Ok(0) if read == 0 => return None,
+                Ok(0) => {
+                    return Some(Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "truncated packed position",
+                    )));
+                }
+                Ok(count) => read += count,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Some(Err(error)),
+            }
+        }
+        Some(Ok(PackedPosition(bytes)))
+    }
+}
+
+/// piece_code returns the nibble of a piece
+///
+/// @param: side - side owning the piece
+/// @param: piece - type of the piece
+/// @return: piece index, plus 8 for black pieces
+#[inline]
+fn piece_code(side: Sides, piece: Pieces) -> u8 {
+    piece.idx() as u8 | ((side.idx() as u8) << 3)
+}
+
+/// decode_piece returns the piece of a nibble
+///
+/// @param: code - nibble of the piece
+/// @return: side and type of the piece, or None for an invalid nibble
+#[inline]
+fn decode_piece(code: u8) -> Option<(Sides, Pieces)> {
+    let piece = match code & 7 {
+        1 => Pieces::Pawn,
+        2 => Pieces::Knight,
+        3 => Pieces::Bishop,
+        4 => Pieces::Rook,
+        5 => Pieces::Queen,
+        6 => Pieces::King,
+        _ => return None,
+    };
+    Some((Sides::from_idx(usize::from(code >> 3)), piece))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fen;
+
+    fn packed(fen: &str) -> PackedPosition {
+        PackedPosition::try_from(&Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+    }
+
+    #[test]
+    fn packs_positions_into_fixed_size_records() {
+        let bytes = *packed("4k3/8/8/8/8/8/4P3/4K3 b Kq e3 3 300").as_bytes();
+
+        assert_eq!(
+            u64::from_le_bytes(bytes[..8].try_into().unwrap()),
+            (1 << 4) | (1 << 12) | (1 << 60)
+        );
+        // white king on e1, white pawn on e2 and black king on e8
+        assert_eq!(bytes[8..11], [0x16, 0x0E, 0x00]);
+        assert_eq!(bytes[24], 0b10011);
+        assert_eq!(bytes[25], Square::E3.idx() as u8);
+        assert_eq!(bytes[26], 3);
+        assert_eq!(u16::from_le_bytes([bytes[27], bytes[28]]), 300);
+    }
+
+    #[test]
+    fn rejects_invalid_records() {
+        let valid = *packed("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").as_bytes();
+        let invalid = |edit: fn(&mut [u8; PackedPosition::SIZE])| {
+            let mut bytes = valid;
+            edit(&mut bytes);
+            Setup::try_from(PackedPosition::from_bytes(bytes)).unwrap_err()
+        };
+
+        assert!(Setup::try_from(PackedPosition::from_bytes(valid)).is_ok());
+        assert_eq!(invalid(|bytes| bytes[8] = 0x17), PackedError::InvalidPieces);
+        assert_eq!(invalid(|bytes| bytes[9] = 0x0F), PackedError::InvalidPieces);
+        assert_eq!(invalid(|bytes| bytes[9] = 0x6E), PackedError::InvalidPieces);
+        assert_eq!(invalid(|bytes| bytes[0] |= 2), PackedError::InvalidPieces);
+        assert_eq!(invalid(|bytes| bytes[24] = 0x20), PackedError::InvalidFlags);
+        assert_eq!(
+            invalid(|bytes| bytes[25] = 30),
+            PackedError::InvalidEnPassant
+        );
+    }
+
+    #[test]
+    fn rejects_setups_with_too_many_pieces() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/P7/PPPPPPPP/RNBQKBNR w - - 0 1",
+            "rnbqkbnr/pppppppp/pppppppp/8/8/PPPPPPPP/PPPPPPPP/RNBQKBNR w - - 0 1",
+        ] {
+            assert_eq!(
+                PackedPosition::try_from(&Setup::from(Fen::try_from(fen).unwrap())),
+                Err(PackedError::TooManyPieces)
+            );
+        }
+    }
+
+    #[test]
+    fn streams_records_through_files() {
+        let positions = [
+            packed("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+            packed("4k3/8/8/8/8/8/4P3/4K3 b - - 12 60"),
+        ];
+        let mut writer = PackedWriter::new(Vec::new());
+        for position in &positions {
+            writer.write(position).unwrap();
+        }
+        let bytes = writer.into_inner().unwrap();
+        assert_eq!(bytes.len(), 2 * PackedPosition::SIZE);
+
+        let read = PackedReader::new(bytes.as_slice())
+            .collect::<io::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(read, positions);
+
+        let mut truncated = PackedReader::new(&bytes[..PackedPosition::SIZE + 3]);
+        assert!(truncated.next().unwrap().is_ok());
+        assert!(truncated.next().unwrap().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use super::Setup;
+use crate::PositionView;
+use chess_kit_primitives::{Black, Castling, Sides, Square, White};
+
+/// CASTLING_PAIRS are the castling rights of white with the matching rights of
+/// black
+const CASTLING_PAIRS: [(Castling, Castling); 2] = [
+    (Castling::WHITE_KING, Castling::BLACK_KING),
+    (Castling::WHITE_QUEEN, Castling::BLACK_QUEEN),
+];
+
+impl Setup {
+    /// flipped swaps the colors of the setup, mirroring the board between the
+    /// ranks so that each side's pieces keep their relative placement
+    ///
+    /// note: the side to move, castling rights and en passant square are
+    ///       swapped along with the pieces, so the flipped setup is the same
+    ///       game seen from the other side of the board
+    ///
+    /// @return: color-flipped setup
+    pub fn flipped(&self) -> Self {
+        let mut pieces = [None; Square::TOTAL];
+        for (idx, piece) in self.pieces.iter().enumerate() {
+            pieces[idx ^ 56] = piece.map(|(side, piece)| (side.other(), piece));
+        }
+
+        let mut castling = Castling::NONE;
+        for (white, black) in CASTLING_PAIRS {
+            if self.castling & white == white {
+                castling |= black;
+            }
+            if self.castling & black == black {
+                castling |= white;
+            }
+        }
+
+        Self {
+            pieces,
+            side_to_move: self.side_to_move.other(),
+            castling,
+            en_passant: self
+                .en_passant
+                .map(|square| Square::from_idx(square.idx() ^ 56)),
+            halfmoves: self.halfmoves,
+            fullmoves: self.fullmoves,
+        }
+    }
+
+    /// mirrored mirrors the board between the files, so that pieces on the
+    /// a-file move to the h-file and vice versa
+    ///
+    /// note: castling is not symmetric between the wings, so a setup with
+    ///       castling rights has no mirrored counterpart
+    ///
+    /// @return: mirrored setup, or None if either side can still castle
+    pub fn mirrored(&self) -> Option<Self> {
+        if self.castling != Castling::NONE {
+            return None;
+        }
+
+        let mut pieces = [None; Square::TOTAL];
+        for (idx, piece) in self.pieces.iter().enumerate() {
+            pieces[idx ^ 7] = *piece;
+        }
+
+        Some(Self {
+            pieces,
+            en_passant: self
+                .en_passant
+                .map(|square| Square::from_idx(square.idx() ^ 7)),
+            ..self.clone()
+        })
+    }
+}
+
+impl<PositionT> From<&PositionT> for Setup
+where
+    PositionT: PositionView,
+{
+    /// from captures the current state of a position view as a setup
+    ///
+    /// @param: position - position view to capture
+    /// @return: setup describing the position
+    fn from(position: &PositionT) -> Self {
+        let mut pieces = [None; Square::TOTAL];
+        for square in position.occupancy::<White>() {
+            pieces[square.idx()] = Some((Sides::White, position.piece_at(square)));
+        }
+        for square in position.occupancy::<Black>() {
+            pieces[square.idx()] = Some((Sides::Black, position.piece_at(square)));
+        }
+
+        Self {
+            pieces,
+            side_to_move: position.turn(),
+            castling: position.castling(),
+            en_passant: position.en_passant(),
+            halfmoves: position.halfmoves(),
+            fullmoves: position.fullmoves(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DefaultPosition, Fen};
+    use chess_kit_attack_table::DefaultAttackTable;
+
+    fn setup(fen: &str) -> Setup {
+        Setup::from(Fen::try_from(fen).unwrap())
+    }
+
+    fn fen(setup: Setup) -> String {
+        Fen::from(setup).to_string()
+    }
+
+    #[test]
+    fn flips_colors_with_the_game_state() {
+        let start = Setup::default();
+        assert_eq!(
+            fen(start.flipped()),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq - 0 1"
+        );
+
+        let setup = setup("r3k3/8/8/8/3pP3/8/8/4K2R b Kq e3 4 20");
+        assert_eq!(
+            fen(setup.flipped()),
+            "4k2r/8/8/3Pp3/8/8/8/R3K3 w Qk e6 4 20"
+        );
+        assert_eq!(setup.flipped().flipped(), setup);
+    }
+
+    #[test]
+    fn mirrors_only_setups_without_castling_rights() {
+        let setup = setup("4k3/8/8/3pP3/8/8/8/4K2R w - d6 0 1");
+        let mirrored = setup.mirrored().unwrap();
+
+        assert_eq!(fen(mirrored.clone()), "3k4/8/8/3Pp3/8/8/8/R2K4 w - e6 0 1");
+        assert_eq!(mirrored.mirrored(), Some(setup.clone()));
+        assert_eq!(
+            fen(setup.flipped().mirrored().unwrap()),
+            "r2k4/8/8/8/3pP3/8/8/3K4 b - e3 0 1"
+        );
+        assert_eq!(Setup::default().mirrored(), None);
+    }
+
+    #[test]
+    fn captures_positions_as_setups() {
+        let fen_text = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 3 12";
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(setup(fen_text)).unwrap();
+
+        assert_eq!(Setup::from(&position), setup(fen_text));
+    }
+}
@@ -0,0 +1,235 @@
+use super::{Setup, SetupError, SetupErrors};
+use chess_kit_attack_table::AttackTable;
+use chess_kit_primitives::{Bitboard, Castling, Pieces, Side, Sides, Square, call_as};
+
+/// MAX_PIECES is the largest number of pieces a side can have
+const MAX_PIECES: usize = 16;
+
+/// CASTLING_HOMES are the castling rights with the home squares of the king and
+/// rook they need
+const CASTLING_HOMES: [(Castling, Sides, Square, Square); 4] = [
+    (Castling::WHITE_KING, Sides::White, Square::E1, Square::H1),
+    (Castling::WHITE_QUEEN, Sides::White, Square::E1, Square::A1),
+    (Castling::BLACK_KING, Sides::Black, Square::E8, Square::H8),
+    (Castling::BLACK_QUEEN, Sides::Black, Square::E8, Square::A8),
+];
+
+impl Setup {
+    /// validate checks the setup against the rules of chess
+    ///
+    /// note: the checks are those a position relies on, so a valid setup is
+    ///       not necessarily reachable from the starting position
+    ///
+    /// @marker: AT - attack table used to find checks
+    /// @return: Ok for a legal setup, or every rule the setup breaks
+    pub fn validate<AT: AttackTable>(&self) -> Result<(), SetupErrors> {
+        let mut errors = Vec::new();
+
+        let mut kings = [None; Sides::TOTAL];
+        let mut king_counts = [0; Sides::TOTAL];
+        let mut piece_counts = [0; Sides::TOTAL];
+        for (idx, piece) in self.pieces.iter().enumerate() {
+            let Some((side, piece)) = *piece else {
+                continue;
+            };
+            let square = Square::from_idx(idx);
+            piece_counts[side] += 1;
+            if piece == Pieces::King {
+                king_counts[side] += 1;
+                kings[side] = Some(square);
+            }
+            if piece == Pieces::Pawn && !(8..56).contains(&idx) {
+                errors.push(SetupError::PawnOnBackRank(square));
+            }
+        }
+        for side in [Sides::White, Sides::Black] {
+            if king_counts[side] != 1 {
+                errors.push(SetupError::KingCount(side));
+            }
+            if piece_counts[side] > MAX_PIECES {
+                errors.push(SetupError::TooManyPieces(side));
+            }
+        }
+
+        // the check can only be told apart with exactly one king per side
+        let opponent = self.side_to_move.other();
+        if king_counts == [1, 1]
+            && let Some(king) = kings[opponent]
+            && call_as!(opponent, |SideT| self.is_attacked::<AT, SideT>(king))
+        {
+            errors.push(SetupError::OpponentInCheck);
+        }
+
+        for (right, side, king, rook) in CASTLING_HOMES {
+            if self.castling & right == right
+                && (self.pieces[king.idx()] != Some((side, Pieces::King))
+                    || self.pieces[rook.idx()] != Some((side, Pieces::Rook)))
+            {
+                errors.push(SetupError::CastlingWithoutPieces(right));
+            }
+        }
+
+        if let Some(square) = self.en_passant
+            && !self.is_en_passant_valid(square)
+        {
+            errors.push(SetupError::InvalidEnPassant(square));
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(SetupErrors::new(errors))
+        }
+    }
+
+    /// is_en_passant_valid checks that an en passant square is behind a pawn
+    /// of the side not to move that just double-pushed
+    ///
+    /// @param: square - en passant square to check
+    /// @return: true if the pawn's double push can have created the square
+    fn is_en_passant_valid(&self, square: Square) -> bool {
+        let idx = square.idx();
+        let (pushed, origin) = match self.side_to_move {
+            Sides::White if (40..48).contains(&idx) => (idx - 8, idx + 8),
+            Sides::Black if (16..24).contains(&idx) => (idx + 8, idx - 8),
+            _ => return false,
+        };
+
+        self.pieces[pushed] == Some((self.side_to_move.other(), Pieces::Pawn))
+            && self.pieces[idx].is_none()
+            && self.pieces[origin].is_none()
+    }
+
+    /// is_attacked checks if a square of SideT is attacked by SideT::Other
+    ///
+    /// note: this mirrors `PositionAttacks::is_attacked`, with the bitboards
+    ///       collected from the setup's pieces
+    ///
+    /// @marker: AT - attack table used to find the attacks
+    /// @marker: SideT - side whose square is checked
+    /// @param: square - square to check
+    /// @return: true if a piece of SideT::Other attacks the square
+    fn is_attacked<AT: AttackTable, SideT: Side>(&self, square: Square) -> bool {
+        let mut occupancy = Bitboard::empty();
+        let mut attackers = [Bitboard::empty(); Pieces::TOTAL];
+        for (idx, piece) in self.pieces.iter().enumerate() {
+            let Some((side, piece)) = *piece else {
+                continue;
+            };
+            occupancy.set_at(Square::from_idx(idx));
+            if side == SideT::Other::SIDE {
+                attackers[piece].set_at(Square::from_idx(idx));
+            }
+        }
+
+        let queens = attackers[Pieces::Queen];
+        AT::rook_targets(square, occupancy).intersects(attackers[Pieces::Rook] | queens)
+            || AT::bishop_targets(square, occupancy).intersects(attackers[Pieces::Bishop] | queens)
+            || AT::knight_targets(square).intersects(attackers[Pieces::Knight])
+            || AT::pawn_targets::<SideT>(square).intersects(attackers[Pieces::Pawn])
+            || AT::king_targets(square).intersects(attackers[Pieces::King])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Fen;
+    use chess_kit_attack_table::DefaultAttackTable;
+
+    fn errors(fen: &str) -> Vec<SetupError> {
+        Setup::from(Fen::try_from(fen).unwrap())
+            .validate::<DefaultAttackTable>()
+            .map_or_else(|errors| errors.errors().to_vec(), |()| Vec::new())
+    }
+
+    #[test]
+    fn accepts_legal_setups() {
+        assert_eq!(
+            errors("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"),
+            []
+        );
+        assert_eq!(
+            errors("rnbqkbnr/pppp1ppp/8/8/3Pp3/8/PPP1PPPP/RNBQKBNR b KQkq d3 0 2"),
+            []
+        );
+        assert_eq!(errors("4k3/8/8/8/8/8/8/4K2R w K - 0 1"), []);
+        // the side to move may be in check, even by two pieces
+        assert_eq!(errors("4k3/8/8/8/1b6/8/4r3/4K3 w - - 0 1"), []);
+    }
+
+    #[test]
+    fn rejects_the_side_not_to_move_in_check() {
+        assert_eq!(
+            errors("4k3/8/8/8/8/8/8/4R1K1 w - - 0 1"),
+            [SetupError::OpponentInCheck]
+        );
+        assert_eq!(
+            errors("4k3/3P4/8/8/8/8/8/6K1 w - - 0 1"),
+            [SetupError::OpponentInCheck]
+        );
+        assert_eq!(
+            errors("4k3/8/3N4/8/8/8/8/6K1 w - - 0 1"),
+            [SetupError::OpponentInCheck]
+        );
+        assert_eq!(
+            errors("4k3/8/8/8/B7/8/8/6K1 w - - 0 1"),
+            [SetupError::OpponentInCheck]
+        );
+        assert_eq!(
+            errors("8/8/8/8/8/8/4k3/4K3 b - - 0 1"),
+            [SetupError::OpponentInCheck]
+        );
+        // blocked sliders and pieces of the side not to move give no check
+        assert_eq!(errors("4k3/8/2p5/8/B7/8/8/6K1 w - - 0 1"), []);
+        assert_eq!(errors("4k3/8/8/8/8/8/8/4R1K1 b - - 0 1"), []);
+    }
+
+    #[test]
+    fn lists_every_violation() {
+        assert_eq!(
+            errors("P3k3/8/8/8/8/8/8/4K2p b KQ e3 0 1"),
+            [
+                SetupError::PawnOnBackRank(Square::H1),
+                SetupError::PawnOnBackRank(Square::A8),
+                SetupError::CastlingWithoutPieces(Castling::WHITE_KING),
+                SetupError::CastlingWithoutPieces(Castling::WHITE_QUEEN),
+                SetupError::InvalidEnPassant(Square::E3),
+            ]
+        );
+        assert_eq!(
+            errors("4k3/8/8/8/8/8/8/R3K3 w Kq - 0 1"),
+            [
+                SetupError::CastlingWithoutPieces(Castling::WHITE_KING),
+                SetupError::CastlingWithoutPieces(Castling::BLACK_QUEEN),
+            ]
+        );
+        assert_eq!(
+            errors("qqqqkqqq/qqqqqqqq/qq6/8/8/8/8/4K3 w - - 0 1"),
+            [SetupError::TooManyPieces(Sides::Black)]
+        );
+    }
+
+    #[test]
+    fn rejects_en_passant_squares_without_a_double_push() {
+        let invalid = [SetupError::InvalidEnPassant(Square::D6)];
+        // the square of the side to move, without a pawn, and with a blocker
+        assert_eq!(errors("4k3/8/8/3p4/8/8/8/4K3 b - d6 0 1"), invalid);
+        assert_eq!(errors("4k3/8/8/8/8/8/8/4K3 w - d6 0 1"), invalid);
+        assert_eq!(errors("4k3/3n4/8/3p4/8/8/8/4K3 w - d6 0 1"), invalid);
+        assert_eq!(errors("4k3/8/8/3p4/8/8/8/4K3 w - d6 0 1"), []);
+    }
+
+    #[test]
+    fn formats_every_violation() {
+        let errors = Setup::from(Fen::try_from("4k3/8/8/8/8/8/8/4K2p w Q - 0 1").unwrap())
+            .validate::<DefaultAttackTable>()
+            .unwrap_err();
+
+        assert_eq!(
+            errors.to_string(),
+            "Illegal setup: pawn on the back rank square h1; castling right Q without the \
+             king and rook on their home squares"
+        );
+    }
+}
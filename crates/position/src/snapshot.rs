@@ -146,7 +146,7 @@ mod tests {
     #[test]
     fn dispatches_a_runtime_turn_to_its_marker_type() {
         let fen = Fen::try_from("4k3/8/8/8/8/8/8/4K3 b - - 0 1").unwrap();
-        let position = DefaultPosition::<DefaultAttackTable>::from(Setup::from(fen));
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(fen)).unwrap();
 
         let snapshot = PositionSnapshot::from(&position);
 
@@ -1,7 +1,7 @@
 use crate::position::DefaultPosition;
 use crate::{DrawState, PositionState, PositionView};
 use chess_kit_attack_table::AttackTable;
-use chess_kit_primitives::{Bitboard, Castling, Pieces, Side, Sides, Square, ZobristKey};
+use chess_kit_primitives::{Bitboard, Castling, Clock, Pieces, Side, Sides, Square, ZobristKey};
 
 impl<AT> DefaultPosition<AT>
 where
@@ -22,14 +22,6 @@ where
     pub(crate) fn state_mut(&mut self) -> &mut PositionState {
         self.history.top_mut()
     }
-
-    /// draw_state returns the incrementally maintained draw information
-    ///
-    /// @return: draw information for the current position
-    #[inline]
-    pub fn draw_state(&self) -> DrawState {
-        self.state().draw_state()
-    }
 }
 
 impl<AT> PositionView for DefaultPosition<AT>
@@ -116,6 +108,23 @@ where
         self.state().castling()
     }
 
+    /// halfmoves gets the number of halfmoves since the last capture or pawn
+    /// move
+    ///
+    /// @impl: PositionView::halfmoves
+    #[inline]
+    fn halfmoves(&self) -> Clock {
+        self.state().halfmoves()
+    }
+
+    /// fullmoves gets the current fullmove number
+    ///
+    /// @impl: PositionView::fullmoves
+    #[inline]
+    fn fullmoves(&self) -> Clock {
+        self.state().fullmoves()
+    }
+
     /// key gets the unique key identifier for the current position
     ///
     /// @impl: PositionView::key
@@ -123,4 +132,21 @@ where
     fn key(&self) -> ZobristKey {
         self.state().key()
     }
+
+    /// pawn_key gets the key identifying the pawn structure of the current
+    /// position
+    ///
+    /// @impl: PositionView::pawn_key
+    #[inline]
+    fn pawn_key(&self) -> ZobristKey {
+        self.state().pawn_key()
+    }
+
+    /// draw_state gets the incrementally maintained draw information
+    ///
+    /// @impl: PositionView::draw_state
+    #[inline]
+    fn draw_state(&self) -> DrawState {
+        self.state().draw_state()
+    }
 }
@@ -8,7 +8,7 @@ const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq
 fn converts_a_validated_fen_into_a_position() {
     let fen = Fen::try_from(START_POSITION).unwrap();
     let setup = Setup::from(fen);
-    let position = DefaultPosition::<DefaultAttackTable>::from(setup);
+    let position = DefaultPosition::<DefaultAttackTable>::try_from(setup).unwrap();
 
     assert_eq!(position.turn(), Sides::White);
     assert_eq!(position.piece_at(Square::A1), Pieces::Rook);
@@ -38,3 +38,13 @@ fn setup_defaults_to_the_start_position() {
         Some((Sides::Black, Pieces::King))
     );
 }
+
+#[test]
+fn captures_a_position_as_a_fen() {
+    let fen = "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R b KQkq - 3 7";
+    let position =
+        DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(Fen::try_from(fen).unwrap()))
+            .unwrap();
+
+    assert_eq!(Fen::from(&position).to_string(), fen);
+}
@@ -0,0 +1,154 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_position::{
+    DefaultPosition, Fen, PackedPosition, PackedReader, PackedWriter, PositionMoves, Setup,
+};
+use chess_kit_primitives::{Move, Square};
+use chess_kit_rng::Rng;
+
+/// random_fen generates a FEN string of a random but well-formed position
+fn random_fen(rng: &mut Rng) -> String {
+    let mut board = [None; 64];
+    for king in ['K', 'k'] {
+        loop {
+            let square = rng.below(64);
+            if board[square].is_none() {
+                board[square] = Some(king);
+                break;
+            }
+        }
+    }
+    for _ in 0..rng.below(31) {
+        let piece = b"PNBRQpnbrq"[rng.below(10)] as char;
+        let square = rng.below(64);
+        let rank = square / 8;
+        let back_rank = rank == 0 || rank == 7;
+        if board[square].is_none() && !(piece.eq_ignore_ascii_case(&'p') && back_rank) {
+            board[square] = Some(piece);
+        }
+    }
+
+    let mut placement = String::new();
+    for rank in (0..8).rev() {
+        let mut empty = 0;
+        for file in 0..8 {
+            match board[rank * 8 + file] {
+                Some(piece) => {
+                    if empty > 0 {
+                        placement.push_str(&empty.to_string());
+                        empty = 0;
+                    }
+                    placement.push(piece);
+                }
+                None => empty += 1,
+            }
+        }
+        if empty > 0 {
+            placement.push_str(&empty.to_string());
+        }
+        if rank > 0 {
+            placement.push('/');
+        }
+    }
+
+    let turn = if rng.below(2) == 0 { "w" } else { "b" };
+    let castling = ["K", "Q", "k", "q"]
+        .into_iter()
+        .filter(|_| rng.below(2) == 0)
+        .collect::<String>();
+    let castling = if castling.is_empty() {
+        "-".to_owned()
+    } else {
+        castling
+    };
+    let en_passant = match rng.below(3) {
+        0 => format!("{}3", (b'a' + rng.below(8) as u8) as char),
+        1 => format!("{}6", (b'a' + rng.below(8) as u8) as char),
+        _ => "-".to_owned(),
+    };
+    let halfmoves = rng.below(256);
+    let fullmoves = 1 + rng.below(usize::from(u16::MAX));
+
+    format!("{placement} {turn} {castling} {en_passant} {halfmoves} {fullmoves}")
+}
+
+#[test]
+fn packing_round_trips_like_fen() {
+    let mut rng = Rng::new(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..10_000 {
+        let fen = random_fen(&mut rng);
+        let setup = Setup::from(Fen::try_from(fen.as_str()).unwrap());
+
+        let packed = PackedPosition::try_from(&setup).unwrap();
+        let unpacked = Setup::try_from(packed).unwrap();
+
+        assert_eq!(unpacked, setup, "FEN: {fen}");
+        assert_eq!(Fen::from(unpacked).to_string(), fen);
+    }
+}
+
+#[test]
+fn packing_positions_matches_packing_their_fen() {
+    for line in include_str!("fixtures/zobrist.epd").lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fen = line.split('|').next().unwrap();
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        let packed = PackedPosition::from_position(&position).unwrap();
+
+        // positions drop uncapturable en passant squares, so compare with their own FEN
+        let setup = Setup::from(Fen::from(&position));
+        assert_eq!(
+            packed,
+            PackedPosition::try_from(&setup).unwrap(),
+            "FEN: {fen}"
+        );
+        assert_eq!(Setup::try_from(packed).unwrap(), setup, "FEN: {fen}");
+    }
+}
+
+#[test]
+fn packing_played_positions_matches_their_fen() {
+    let mut position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::default()).unwrap();
+    for (from, to) in [
+        (Square::E2, Square::E4),
+        (Square::C7, Square::C5),
+        (Square::G1, Square::F3),
+        (Square::D7, Square::D6),
+        (Square::E4, Square::E5),
+        (Square::F7, Square::F5),
+    ] {
+        let _ = position.play_unchecked(Move::new(from, to));
+        let unpacked = Setup::try_from(PackedPosition::from_position(&position).unwrap()).unwrap();
+        assert_eq!(Fen::from(unpacked), Fen::from(&position));
+    }
+}
+
+#[test]
+fn streams_random_positions_through_records() {
+    let mut rng = Rng::new(42);
+    let positions = (0..100)
+        .map(|_| {
+            PackedPosition::try_from(&Setup::from(
+                Fen::try_from(random_fen(&mut rng).as_str()).unwrap(),
+            ))
+            .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut writer = PackedWriter::new(Vec::new());
+    for position in &positions {
+        writer.write(position).unwrap();
+    }
+    let bytes = writer.into_inner().unwrap();
+
+    let read = PackedReader::new(bytes.as_slice())
+        .collect::<std::io::Result<Vec<_>>>()
+        .unwrap();
+    assert_eq!(read, positions);
+}
@@ -7,7 +7,7 @@ type TestPosition = DefaultPosition<DefaultAttackTable>;
 const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
 
 fn load(fen: &str) -> TestPosition {
-    Setup::from(Fen::try_from(fen).unwrap()).into()
+    Setup::from(Fen::try_from(fen).unwrap()).try_into().unwrap()
 }
 
 fn changes(delta: MoveDelta) -> Vec<PieceDelta> {
@@ -162,3 +162,55 @@ fn zeroing_move_clears_repetition_state() {
     let _ = position.play_unchecked(Move::new(Square::E2, Square::E4));
     assert_eq!(position.draw_state().repetition(), 0);
 }
+
+#[test]
+fn tracks_repetition_across_games_longer_than_the_initial_history_capacity() {
+    // the rook tours a closed 32-square cycle while the black king walks a
+    // 5-square cycle behind its pawns, so a position first repeats after
+    // lcm(32, 5) = 160 moves by each side
+    let mut position = load("5k2/8/5ppp/8/8/8/R7/K7 w - - 0 1");
+    let rook_tour = (0..8)
+        .map(|file| 8 + file)
+        .chain((1..8).rev().map(|file| 16 + file))
+        .chain((1..8).map(|file| 24 + file))
+        .chain((1..8).rev().map(|file| 32 + file))
+        .chain([32, 24, 16])
+        .map(|index| Square::ALL[index])
+        .collect::<Vec<_>>();
+    let king_cycle = [Square::F8, Square::G8, Square::H8, Square::H7, Square::G7];
+    assert_eq!(rook_tour.len(), 32);
+
+    let game = (0..300)
+        .flat_map(|turn| {
+            [
+                Move::new(rook_tour[turn % 32], rook_tour[(turn + 1) % 32]),
+                Move::new(king_cycle[turn % 5], king_cycle[(turn + 1) % 5]),
+            ]
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(game.len(), 600);
+
+    let mut repetitions = vec![position.draw_state().repetition()];
+    for (ply, mv) in game.iter().enumerate() {
+        let _ = position.play_unchecked(*mv);
+        let expected = if ply + 1 >= 320 { 320 } else { 0 };
+        assert_eq!(
+            position.draw_state().repetition(),
+            expected,
+            "ply {}",
+            ply + 1
+        );
+        repetitions.push(expected);
+    }
+    assert_eq!(position.halfmoves(), 600);
+
+    for mv in game.iter().rev() {
+        position.undo(*mv);
+        repetitions.pop();
+        assert_eq!(
+            position.draw_state().repetition(),
+            *repetitions.last().unwrap()
+        );
+    }
+    assert_eq!(position.halfmoves(), 0);
+}
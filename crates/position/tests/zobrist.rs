@@ -1,6 +1,12 @@
 use chess_kit_attack_table::DefaultAttackTable;
-use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
-use chess_kit_primitives::ZobristKey;
+use chess_kit_position::{DefaultPosition, Fen, PositionMoves, PositionView, Setup};
+use chess_kit_primitives::{Move, Square, ZobristKey};
+
+const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+fn load(fen: &str) -> DefaultPosition<DefaultAttackTable> {
+    Setup::from(Fen::try_from(fen).unwrap()).try_into().unwrap()
+}
 
 #[test]
 fn zobrist_keys_match_known_positions() {
@@ -24,7 +30,7 @@ fn zobrist_keys_match_known_positions() {
 
         let fen = Fen::try_from(fen_text)
             .unwrap_or_else(|err| panic!("error loading FEN '{fen_text}': {err}"));
-        let position = DefaultPosition::<DefaultAttackTable>::from(Setup::from(fen));
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(fen)).unwrap();
 
         let zobrist_key = position.key();
         assert_eq!(
@@ -39,3 +45,22 @@ fn zobrist_keys_match_known_positions() {
         );
     }
 }
+
+#[test]
+fn pawn_keys_only_track_the_pawn_structure() {
+    let mut position = load(START_POSITION);
+    let start = position.pawn_key();
+
+    let _ = position.play_unchecked(Move::new(Square::G1, Square::F3));
+    assert_eq!(position.pawn_key(), start);
+
+    let _ = position.play_unchecked(Move::new(Square::E7, Square::E5));
+    assert_ne!(position.pawn_key(), start);
+    assert_eq!(
+        position.pawn_key(),
+        load("rnbqkbnr/pppp1ppp/8/4p3/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").pawn_key()
+    );
+
+    position.undo(Move::new(Square::E7, Square::E5));
+    assert_eq!(position.pawn_key(), start);
+}
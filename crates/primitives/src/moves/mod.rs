@@ -135,6 +135,31 @@ impl Move {
     }
 }
 
+impl From<u16> for Move {
+    /// from reinterprets 16 bits as a move
+    ///
+    /// note: every 16-bit value is a well-formed move, though not necessarily
+    ///       one that can be played in a given position
+    ///
+    /// @param: data - move data following the schema of [`Move`]
+    /// @return: move holding the data
+    #[inline]
+    fn from(data: u16) -> Self {
+        Self { data }
+    }
+}
+
+impl From<Move> for u16 {
+    /// from returns the 16 bits of a move
+    ///
+    /// @param: mv - move to convert
+    /// @return: move data following the schema of [`Move`]
+    #[inline]
+    fn from(mv: Move) -> Self {
+        mv.data
+    }
+}
+
 impl Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}{}", self.from(), self.to())?;
@@ -188,4 +213,14 @@ mod tests {
         assert_eq!(mv.promoted_to(), Pieces::Knight);
         assert_eq!(mv.type_of(), MoveType::Promotion);
     }
+
+    #[test]
+    fn round_trips_through_its_16_bits() {
+        let mv = Move::new(G7, H8).with_promotion(Pieces::Rook);
+        assert_eq!(<Move as From<u16>>::from(u16::from(mv)), mv);
+
+        let all_bits: Move = 0xFFFF.into();
+        assert_eq!(all_bits.type_of(), MoveType::Castle);
+        assert_eq!(all_bits.promoted_to(), Pieces::Queen);
+    }
 }
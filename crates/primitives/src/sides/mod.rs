@@ -152,6 +152,18 @@ pub enum Sides {
 
 impl Sides {
     pub const TOTAL: usize = 2;
+
+    /// other returns the opposing side
+    ///
+    /// @param: self - side to flip
+    /// @return: the opposing side
+    #[inline]
+    pub const fn other(self) -> Sides {
+        match self {
+            Sides::White => Sides::Black,
+            Sides::Black => Sides::White,
+        }
+    }
 }
 
 impl Side for White {
@@ -210,6 +222,13 @@ mod tests {
         assert_eq!(evaluations, 1);
     }
 
+    #[test]
+    fn other_returns_the_opposing_side() {
+        assert_eq!(Sides::White.other(), Sides::Black);
+        assert_eq!(Sides::Black.other(), Sides::White);
+        assert_eq!(Sides::White.other(), <White as Side>::Other::SIDE);
+    }
+
     #[test]
     fn define_sides_supports_runtime_and_marker_lookups() {
         assert_eq!(TestSideValues::VALUE[Sides::White], WHITE_VALUE);
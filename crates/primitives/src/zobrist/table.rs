@@ -35,6 +35,25 @@ impl ZobristTable {
         key
     }
 
+    /// new_pawn_key generates a new zobrist key for the pawn structure of the
+    /// given position
+    ///
+    /// note: the pawn key only hashes the pawns of both sides, so positions
+    ///       sharing a pawn structure share a pawn key
+    ///
+    /// @param: bitboards - bitboards to generate the pawn key for
+    /// @return: zobrist key for the pawns of the given position
+    pub fn new_pawn_key(bitboards: [[Bitboard; Pieces::TOTAL]; Sides::TOTAL]) -> ZobristKey {
+        let mut key = ZobristKey::default();
+        for square in bitboards[Sides::White][Pieces::Pawn].iter() {
+            key ^= ZobristTable::piece::<crate::White>(Pieces::Pawn, square);
+        }
+        for square in bitboards[Sides::Black][Pieces::Pawn].iter() {
+            key ^= ZobristTable::piece::<crate::Black>(Pieces::Pawn, square);
+        }
+        key
+    }
+
     /// piece returns the random value for the given side, piece, and square
     ///
     /// @marker: SideT - side to get the random value for
@@ -64,6 +83,27 @@ impl ZobristTable {
         SIDE_RANDOMS[SideT::SIDE]
     }
 
+    /// fingerprint returns a hash of every random value in the table, which
+    /// identifies the zobrist scheme that keys were generated with
+    ///
+    /// note: keys stored by one build, such as in a saved transposition table,
+    ///       are only meaningful to builds with the same fingerprint
+    ///
+    /// @return: fingerprint of the zobrist random values
+    pub fn fingerprint() -> u64 {
+        PIECE_RANDOMS
+            .iter()
+            .flatten()
+            .flatten()
+            .chain(&CASTLING_RANDOMS)
+            .chain(&SIDE_RANDOMS)
+            .chain(&EN_PASSANT_RANDOMS)
+            // note: FNV-1a over the randoms, so that their order matters too
+            .fold(0xCBF2_9CE4_8422_2325, |hash, random| {
+                (hash ^ random.const_unwrap()).wrapping_mul(0x0000_0100_0000_01B3)
+            })
+    }
+
     /// en_passant returns the random value for the given en passant square
     /// or the random value associated with an absence of en passant
     ///
@@ -0,0 +1,9 @@
+//! Seedable pseudo-random number generation.
+//!
+//! [`Rng`] is the one reproducible generator of the workspace: it proposes
+//! candidate magic numbers, picks the opening moves of self-play games, and
+//! generates the random inputs of tests and benchmarks.
+
+mod rng;
+
+pub use rng::Rng;
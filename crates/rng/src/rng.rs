@@ -0,0 +1,63 @@
+/// Rng is a small, seedable pseudo-random number generator (splitmix64), which
+/// repeats the same sequence for the same seed
+///
+/// note: every seed, zero included, produces a full-period sequence
+///
+/// @type
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// new creates a generator from a seed
+    ///
+    /// @param: seed - seed of the generated sequence
+    /// @return: new generator
+    pub const fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// next_u64 returns the next number of the sequence
+    ///
+    /// @return: pseudo-random number
+    /// @side-effects: advances the generator
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// below returns a number in `0..bound`
+    ///
+    /// @param: bound - exclusive, positive upper bound
+    /// @return: pseudo-random number below the bound
+    /// @side-effects: advances the generator
+    pub fn below(&mut self, bound: usize) -> usize {
+        // note: the modulo bias is negligible for the small bounds used here
+        (self.next_u64() % bound as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_the_sequence_of_a_seed() {
+        let mut first = Rng::new(7);
+        let mut second = Rng::new(7);
+        let mut other = Rng::new(8);
+        let sequence = (0..8).map(|_| first.next_u64()).collect::<Vec<_>>();
+
+        assert_eq!(
+            sequence,
+            (0..8).map(|_| second.next_u64()).collect::<Vec<_>>()
+        );
+        assert_ne!(
+            sequence,
+            (0..8).map(|_| other.next_u64()).collect::<Vec<_>>()
+        );
+        assert!((0..100).all(|_| first.below(3) < 3));
+    }
+}
@@ -2,6 +2,7 @@ use chess_kit_eval::{Accumulator, EvalState};
 use chess_kit_movegen::MoveGenerator;
 use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
 use chess_kit_primitives::Depth;
+use chess_kit_tablebase::EndgameTablebase;
 use chess_kit_transposition::TranspositionTable;
 
 use crate::{Negamax, SearchNode, SearchResult};
@@ -9,7 +10,8 @@ use crate::{Negamax, SearchNode, SearchResult};
 /// iterative_deepening searches successively deeper depths with negamax
 ///
 /// The transposition table is retained between iterations so shallower results
-/// provide hash moves to deeper searches. The returned score and best move come
+/// provide hash moves to deeper searches, and every call starts a new table
+/// generation so that entries of earlier searches are replaced first. The returned score and best move come
 /// from the requested depth, while the node count is cumulative across every
 /// completed iteration.
 ///
@@ -27,8 +29,9 @@ pub fn iterative_deepening<
     AccumulatorT,
     EvalStateT,
     TranspositionTableT,
+    TablebaseT,
 >(
-    negamax: &mut Negamax,
+    negamax: &mut Negamax<TablebaseT>,
     position: &mut PositionT,
     move_generator: &MoveGeneratorT,
     transposition_table: &mut TranspositionTableT,
@@ -41,8 +44,10 @@ where
     AccumulatorT: Accumulator<EvalStateT>,
     EvalStateT: EvalState,
     TranspositionTableT: TranspositionTable<SearchNode>,
+    TablebaseT: EndgameTablebase,
 {
     assert!(depth >= 0, "search depth must be non-negative");
+    transposition_table.new_search();
 
     if depth == 0 {
         return negamax.search(
@@ -54,7 +59,107 @@ where
         );
     }
 
+    let (result, _) = deepen(
+        negamax,
+        position,
+        move_generator,
+        transposition_table,
+        accumulator,
+        depth,
+        u64::MAX,
+    );
+    result
+}
+
+/// iterative_deepening_nodes searches successively deeper depths with negamax
+/// until the cumulative node count reaches a budget
+///
+/// note: the budget is only checked between iterations, so every started
+///       iteration completes and the last one may overshoot the budget. this
+///       keeps the result deterministic for a given position and budget
+///
+/// note: like `iterative_deepening`, every call starts a new generation of the
+///       transposition table
+///
+/// @param: negamax - fixed-depth negamax search invoked for each iteration
+/// @param: position - mutable reference to the root position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: transposition_table - mutable reference to the transposition table
+/// @param: accumulator - mutable reference to the evaluation accumulator
+/// @param: depth - maximum depth to search
+/// @param: nodes - number of nodes after which no further iteration starts
+/// @return: final best move and score with a cumulative visited node count,
+///          and the depth of the last completed iteration
+/// @side-effects: updates the transposition table and negamax node count
+pub fn iterative_deepening_nodes<
+    MoveGeneratorT,
+    PositionT,
+    AccumulatorT,
+    EvalStateT,
+    TranspositionTableT,
+    TablebaseT,
+>(
+    negamax: &mut Negamax<TablebaseT>,
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    transposition_table: &mut TranspositionTableT,
+    accumulator: &mut AccumulatorT,
+    depth: Depth,
+    nodes: u64,
+) -> (SearchResult, Depth)
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves,
+    AccumulatorT: Accumulator<EvalStateT>,
+    EvalStateT: EvalState,
+    TranspositionTableT: TranspositionTable<SearchNode>,
+    TablebaseT: EndgameTablebase,
+{
+    assert!(depth > 0, "search depth must be positive");
+    transposition_table.new_search();
+
+    deepen(
+        negamax,
+        position,
+        move_generator,
+        transposition_table,
+        accumulator,
+        depth,
+        nodes,
+    )
+}
+
+/// deepen runs the iterations of an iterative deepening search
+///
+/// @param: negamax - fixed-depth negamax search invoked for each iteration
+/// @param: position - mutable reference to the root position
+/// @param: move_generator - immutable reference to the move generator
+/// @param: transposition_table - mutable reference to the transposition table
+/// @param: accumulator - mutable reference to the evaluation accumulator
+/// @param: depth - maximum positive depth to search
+/// @param: budget - number of nodes after which no further iteration starts
+/// @return: result of the last completed iteration with a cumulative visited
+///          node count, and the depth of that iteration
+/// @side-effects: updates the transposition table and negamax node count
+fn deepen<MoveGeneratorT, PositionT, AccumulatorT, EvalStateT, TranspositionTableT, TablebaseT>(
+    negamax: &mut Negamax<TablebaseT>,
+    position: &mut PositionT,
+    move_generator: &MoveGeneratorT,
+    transposition_table: &mut TranspositionTableT,
+    accumulator: &mut AccumulatorT,
+    depth: Depth,
+    budget: u64,
+) -> (SearchResult, Depth)
+where
+    MoveGeneratorT: MoveGenerator,
+    PositionT: PositionView + PositionAttacks + PositionMoves,
+    AccumulatorT: Accumulator<EvalStateT>,
+    EvalStateT: EvalState,
+    TranspositionTableT: TranspositionTable<SearchNode>,
+    TablebaseT: EndgameTablebase,
+{
     let mut completed = None;
+    let mut completed_depth = 0;
     let mut nodes = 0;
     for current_depth in 1..=depth {
         let result = negamax.search(
@@ -66,8 +171,15 @@ where
         );
         nodes += result.nodes;
         completed = Some(result);
+        completed_depth = current_depth;
+        if nodes >= budget {
+            break;
+        }
     }
 
     let completed = completed.expect("a positive search depth must complete an iteration");
-    SearchResult::new(completed.best_move, completed.score, nodes)
+    (
+        SearchResult::new(completed.best_move, completed.score, nodes),
+        completed_depth,
+    )
 }
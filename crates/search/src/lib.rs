@@ -4,6 +4,7 @@ mod negamax;
 mod quiescence;
 pub mod types;
 
-pub use iterative_deepening::iterative_deepening;
+pub use iterative_deepening::{iterative_deepening, iterative_deepening_nodes};
 pub use negamax::Negamax;
+pub use negamax::NoTablebase;
 pub use types::*;
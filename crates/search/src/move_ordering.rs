@@ -108,7 +108,7 @@ mod tests {
     type TestPosition = DefaultPosition<DefaultAttackTable>;
 
     fn load(fen: &str) -> TestPosition {
-        TestPosition::from(Setup::from(Fen::try_from(fen).unwrap()))
+        TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
     }
 
     #[test]
@@ -1,19 +1,31 @@
 use chess_kit_eval::{Accumulator, EvalState, Score};
 use chess_kit_movegen::MoveGenerator;
 use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
-use chess_kit_primitives::{Depth, Move, MoveList, Sides, ZobristKey};
+use chess_kit_primitives::{Depth, Move, MoveList, Sides, call_as};
+use chess_kit_tablebase::{EndgameTablebase, Wdl};
 use chess_kit_transposition::TranspositionTable;
+use std::sync::Arc;
 
 use crate::{Bound, SearchNode, SearchResult, move_ordering, quiescence};
 
 /// Negamax is a fixed-depth negamax search with alpha-beta pruning
 ///
+/// The search is generic over the endgame tablebases it probes, so that
+/// tablebase probes are statically dispatched. Searches without tablebases
+/// use `NoTablebase`
+///
 /// @type
-#[derive(Default)]
-pub struct Negamax {
+pub struct Negamax<TablebaseT: EndgameTablebase = NoTablebase> {
     nodes: u64,
+    tablebases: Option<Arc<TablebaseT>>, // endgame tablebases probed during search
+    root_moves: Vec<Move>,               // root moves kept by the tablebases
 }
 
+/// `NoTablebase` is the tablebase of searches that probe no tablebases
+///
+/// @marker-type
+pub struct NoTablebase;
+
 /// SearchContext groups the mutable state shared by recursive search calls
 ///
 /// @type
@@ -23,18 +35,132 @@ struct SearchContext<'a, MoveGeneratorT, TranspositionTableT, AccumulatorT> {
     accumulator: &'a mut AccumulatorT,
 }
 
+// the scores and helpers that do not depend on the tablebases are defined on
+// the default search, so that `Negamax::CHECKMATE_SCORE` and `Negamax::new()`
+// need no type annotations
 impl Negamax {
     /// Score used to represent a checkmate at the root of the search.
-    pub const CHECKMATE_SCORE: Score = 100_000;
+    ///
+    /// note: mate scores stay within 16 bits, as stored in the transposition
+    ///       table
+    pub const CHECKMATE_SCORE: Score = 32_000;
 
     pub(crate) const INFINITY: Score = 1_000_000;
-    const MATE_SCORE_THRESHOLD: Score = Self::CHECKMATE_SCORE - i8::MAX as Score;
 
-    /// new creates a new negamax search
+    /// Score used to represent a tablebase win at the root of the search,
+    /// below every mate score.
+    pub const TABLEBASE_WIN_SCORE: Score = Self::CHECKMATE_SCORE / 2;
+
+    /// Scores at least this far from zero count the plies to a mate or to a
+    /// tablebase win, and are stored relative to their node in the table.
+    const DISTANCE_SCORE_THRESHOLD: Score = Self::TABLEBASE_WIN_SCORE - i8::MAX as Score;
+
+    /// new creates a new negamax search without tablebases
     ///
     /// @return: new negamax search
     pub const fn new() -> Self {
-        Self { nodes: 0 }
+        Self::with_tablebases(None)
+    }
+
+    /// tablebase_score converts a tablebase outcome into a search score, where
+    /// wins and losses drawn by the fifty-move rule score as draws
+    ///
+    /// @param: wdl - outcome from the side to move's perspective
+    /// @param: ply - distance of the current node from the root
+    /// @return: score of the outcome, preferring the closest wins
+    #[inline]
+    const fn tablebase_score(wdl: Wdl, ply: Depth) -> Score {
+        match wdl {
+            Wdl::Win => Self::TABLEBASE_WIN_SCORE - ply as Score,
+            Wdl::Loss => -Self::TABLEBASE_WIN_SCORE + ply as Score,
+            Wdl::CursedWin | Wdl::Draw | Wdl::BlessedLoss => 0,
+        }
+    }
+
+    /// score_to_tt converts a root-relative mate or tablebase score into
+    /// table-relative form
+    ///
+    /// @param: score - score to store in the transposition table
+    /// @param: ply - distance of the current node from the root
+    /// @return: score normalized for transposition-table storage
+    #[inline]
+    const fn score_to_tt(score: Score, ply: Depth) -> Score {
+        if score >= Self::DISTANCE_SCORE_THRESHOLD {
+            score + ply as Score
+        } else if score <= -Self::DISTANCE_SCORE_THRESHOLD {
+            score - ply as Score
+        } else {
+            score
+        }
+    }
+
+    /// score_from_tt converts a table-relative mate or tablebase score into
+    /// root-relative form
+    ///
+    /// @param: score - score read from the transposition table
+    /// @param: ply - distance of the current node from the root
+    /// @return: score normalized for the current search root
+    #[inline]
+    const fn score_from_tt(score: Score, ply: Depth) -> Score {
+        if score >= Self::DISTANCE_SCORE_THRESHOLD {
+            score - ply as Score
+        } else if score <= -Self::DISTANCE_SCORE_THRESHOLD {
+            score + ply as Score
+        } else {
+            score
+        }
+    }
+
+    /// evaluate returns the static evaluation from the side-to-move's perspective
+    ///
+    /// @param: position - immutable reference to the position to evaluate
+    /// @param: accumulator - mutable reference to the evaluation accumulator
+    /// @return: static evaluation from the side-to-move's perspective
+    pub(crate) fn evaluate<PositionT, AccumulatorT, EvalStateT>(
+        position: &PositionT,
+        accumulator: &mut AccumulatorT,
+    ) -> Score
+    where
+        PositionT: PositionView,
+        AccumulatorT: Accumulator<EvalStateT>,
+        EvalStateT: EvalState,
+    {
+        let score = accumulator.latest_mut().score();
+        match position.turn() {
+            Sides::White => score,
+            Sides::Black => -score,
+        }
+    }
+}
+
+impl<TablebaseT: EndgameTablebase> Negamax<TablebaseT> {
+    /// with_tablebases creates a new negamax search probing the given
+    /// tablebases
+    ///
+    /// @param: tablebases - tablebases to probe, or None to disable probing
+    /// @return: new negamax search
+    pub const fn with_tablebases(tablebases: Option<Arc<TablebaseT>>) -> Self {
+        Self {
+            nodes: 0,
+            tablebases,
+            root_moves: Vec::new(),
+        }
+    }
+
+    /// set_tablebases selects the endgame tablebases probed during search
+    ///
+    /// @param: tablebases - tablebases to probe, or None to disable probing
+    /// @return: void
+    /// @side-effects: replaces the probed tablebases
+    pub fn set_tablebases(&mut self, tablebases: Option<Arc<TablebaseT>>) {
+        self.tablebases = tablebases;
+    }
+
+    /// tablebases returns the endgame tablebases probed during search
+    ///
+    /// @return: probed tablebases, if any
+    pub fn tablebases(&self) -> Option<&Arc<TablebaseT>> {
+        self.tablebases.as_ref()
     }
 
     /// search searches a position to a fixed depth and returns the best move
@@ -72,6 +198,15 @@ impl Negamax {
         assert!(depth >= 0, "search depth must be non-negative");
 
         self.nodes = 0;
+        // root positions covered by the tablebases only search the moves that
+        // preserve their outcome best
+        self.root_moves = match &self.tablebases {
+            Some(tablebases) if tablebases.covers(position) => tablebases
+                .best_root_moves(position, move_generator)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        };
+
         let mut context = SearchContext {
             move_generator,
             transposition_table,
@@ -82,8 +217,8 @@ impl Negamax {
             &mut context,
             depth,
             0,
-            -Self::INFINITY,
-            Self::INFINITY,
+            -Negamax::INFINITY,
+            Negamax::INFINITY,
         );
 
         SearchResult::new(best_move, score, self.nodes)
@@ -136,18 +271,39 @@ impl Negamax {
         if let Some(node) = cached
             && node.depth() >= depth
         {
-            let score = Self::score_from_tt(node.score(), ply);
+            let score = Negamax::score_from_tt(node.score(), ply);
             let cutoff = match node.bound() {
                 Bound::Exact => true,
                 Bound::Lower => score >= beta,
                 Bound::Upper => score <= alpha,
             };
 
-            if cutoff {
+            // the table only checks part of the key, so the root only trusts
+            // entries whose move it could actually play
+            if cutoff
+                && (ply > 0
+                    || self.is_root_move(position, context.move_generator, node.best_move()))
+            {
                 return (score, node.best_move());
             }
         }
 
+        // positions right after a capture or pawn move are scored by the
+        // tablebases, as later positions may be drawn by the fifty-move rule
+        if ply > 0
+            && position.halfmoves() == 0
+            && let Some(tablebases) = &self.tablebases
+            && tablebases.covers(position)
+            && let Some(wdl) = tablebases.probe_wdl(position, context.move_generator)
+        {
+            let score = Negamax::tablebase_score(wdl, ply);
+            context.transposition_table.insert(
+                key,
+                SearchNode::new(depth, Negamax::score_to_tt(score, ply), Bound::Exact, None),
+            );
+            return (score, None);
+        }
+
         let mut moves = MoveList::new();
         context
             .move_generator
@@ -155,27 +311,34 @@ impl Negamax {
 
         if moves.is_empty() {
             let score = if position.checkers().not_empty() {
-                -Self::CHECKMATE_SCORE + Score::from(ply)
+                -Negamax::CHECKMATE_SCORE + Score::from(ply)
             } else {
                 0
             };
-            Self::store(
-                context.transposition_table,
+            context.transposition_table.insert(
                 key,
-                SearchNode::new(depth, Self::score_to_tt(score, ply), Bound::Exact, None),
+                SearchNode::new(depth, Negamax::score_to_tt(score, ply), Bound::Exact, None),
             );
             return (score, None);
         }
 
+        if ply == 0 && !self.root_moves.is_empty() {
+            moves.retain(|mv| self.root_moves.contains(mv));
+        }
         move_ordering::order_moves(position, &mut moves, hash_move);
 
         let original_alpha = alpha;
-        let mut best_score = -Self::INFINITY;
+        let mut best_score = -Negamax::INFINITY;
         let mut best_move = None;
 
         for &mv in &moves {
             let eval = context.accumulator.push_next();
             let delta = position.play_unchecked(mv);
+            // start loading the child's entry while its evaluation is updated.
+            // quiescence does not probe the table, so only interior children do
+            if depth > 1 {
+                context.transposition_table.prefetch(position.key());
+            }
             eval.apply(delta);
 
             let (child_score, _) =
@@ -203,91 +366,93 @@ impl Negamax {
         } else {
             Bound::Exact
         };
-        Self::store(
-            context.transposition_table,
-            key,
-            SearchNode::new(depth, Self::score_to_tt(best_score, ply), bound, best_move),
+        let node = SearchNode::new(
+            depth,
+            Negamax::score_to_tt(best_score, ply),
+            bound,
+            best_move,
         );
+        context.transposition_table.insert(key, node);
 
         (best_score, best_move)
     }
 
-    /// store caches a search node unless a deeper entry already exists for the
-    /// same position
+    /// is_root_move checks if the best move of a transposition table entry can
+    /// be played from the root
     ///
-    /// @param: transposition_table - mutable reference to the transposition table
-    /// @param: key - zobrist key of the searched position
-    /// @param: node - search node to cache
-    /// @return: void
-    /// @side-effects: may modify the transposition table
-    fn store<TranspositionTableT>(
-        transposition_table: &mut TranspositionTableT,
-        key: ZobristKey,
-        node: SearchNode,
-    ) where
-        TranspositionTableT: TranspositionTable<SearchNode>,
+    /// note: an entry of another position whose key check collides with the
+    ///       root's may hold any move, so the move must be legal and kept by
+    ///       the tablebases. an entry without a move only answers a root
+    ///       without legal moves
+    ///
+    /// @param: position - immutable reference to the root position
+    /// @param: move_generator - immutable reference to the move generator
+    /// @param: mv - best move of the entry, if any
+    /// @return: true if the entry's move can be played from the root
+    fn is_root_move<MoveGeneratorT, PositionT>(
+        &self,
+        position: &PositionT,
+        move_generator: &MoveGeneratorT,
+        mv: Option<Move>,
+    ) -> bool
+    where
+        MoveGeneratorT: MoveGenerator,
+        PositionT: PositionView + PositionAttacks + PositionMoves,
     {
-        let should_replace = match transposition_table.probe(key) {
-            Some(cached) => cached.depth() <= node.depth(),
-            None => true,
+        let Some(mv) = mv else {
+            let mut moves = MoveList::new();
+            move_generator.generate_legal_moves(position, &mut moves);
+            return moves.is_empty();
         };
 
-        if should_replace {
-            transposition_table.insert(key, node);
-        }
+        move_generator.is_pseudo_legal(position, mv)
+            && call_as!(position.turn(), |SideT| position.is_legal_move::<SideT>(mv))
+            && (self.root_moves.is_empty() || self.root_moves.contains(&mv))
     }
+}
 
-    /// score_to_tt converts a root-relative mate score into table-relative form
-    ///
-    /// @param: score - score to store in the transposition table
-    /// @param: ply - distance of the current node from the root
-    /// @return: score normalized for transposition-table storage
+impl<TablebaseT: EndgameTablebase> Default for Negamax<TablebaseT> {
     #[inline]
-    const fn score_to_tt(score: Score, ply: Depth) -> Score {
-        if score >= Self::MATE_SCORE_THRESHOLD {
-            score + ply as Score
-        } else if score <= -Self::MATE_SCORE_THRESHOLD {
-            score - ply as Score
-        } else {
-            score
-        }
+    fn default() -> Self {
+        Self::with_tablebases(None)
     }
+}
 
-    /// score_from_tt converts a table-relative mate score into root-relative form
+impl EndgameTablebase for NoTablebase {
+    /// covers never covers a position, since there are no tables
     ///
-    /// @param: score - score read from the transposition table
-    /// @param: ply - distance of the current node from the root
-    /// @return: score normalized for the current search root
+    /// @impl: EndgameTablebase::covers
     #[inline]
-    const fn score_from_tt(score: Score, ply: Depth) -> Score {
-        if score >= Self::MATE_SCORE_THRESHOLD {
-            score - ply as Score
-        } else if score <= -Self::MATE_SCORE_THRESHOLD {
-            score + ply as Score
-        } else {
-            score
-        }
+    fn covers<PositionT: PositionView>(&self, _position: &PositionT) -> bool {
+        false
     }
 
-    /// evaluate returns the static evaluation from the side-to-move's perspective
-    ///
-    /// @param: position - immutable reference to the position to evaluate
-    /// @param: accumulator - mutable reference to the evaluation accumulator
-    /// @return: static evaluation from the side-to-move's perspective
-    pub(crate) fn evaluate<PositionT, AccumulatorT, EvalStateT>(
-        position: &PositionT,
-        accumulator: &mut AccumulatorT,
-    ) -> Score
+    /// @impl: EndgameTablebase::probe_wdl
+    #[inline]
+    fn probe_wdl<PositionT, MoveGeneratorT>(
+        &self,
+        _position: &mut PositionT,
+        _move_generator: &MoveGeneratorT,
+    ) -> Option<Wdl>
     where
-        PositionT: PositionView,
-        AccumulatorT: Accumulator<EvalStateT>,
-        EvalStateT: EvalState,
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
     {
-        let score = accumulator.latest_mut().score();
-        match position.turn() {
-            Sides::White => score,
-            Sides::Black => -score,
-        }
+        None
+    }
+
+    /// @impl: EndgameTablebase::best_root_moves
+    #[inline]
+    fn best_root_moves<PositionT, MoveGeneratorT>(
+        &self,
+        _position: &mut PositionT,
+        _move_generator: &MoveGeneratorT,
+    ) -> Option<Vec<Move>>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        None
     }
 }
 
@@ -312,6 +477,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn transposition_scores_preserve_tablebase_distance_across_plies() {
+        let winning_score = Negamax::TABLEBASE_WIN_SCORE - 9;
+        let stored_winning_score = Negamax::score_to_tt(winning_score, 5);
+        assert_eq!(
+            Negamax::score_from_tt(stored_winning_score, 2),
+            winning_score + 3
+        );
+
+        let losing_score = -Negamax::TABLEBASE_WIN_SCORE + 9;
+        let stored_losing_score = Negamax::score_to_tt(losing_score, 5);
+        assert_eq!(
+            Negamax::score_from_tt(stored_losing_score, 2),
+            losing_score - 3
+        );
+    }
+
     #[test]
     fn transposition_scores_leave_normal_evaluations_unchanged() {
         assert_eq!(Negamax::score_to_tt(900, 12), 900);
@@ -1,13 +1,14 @@
 use chess_kit_collections::Value;
 use chess_kit_eval::Score;
 use chess_kit_primitives::{Depth, Move};
-use chess_kit_transposition::NodeData;
+use chess_kit_transposition::{NodeData, PersistentNode};
 
 /// Bound describes how a cached score relates to the alpha-beta window that
 /// produced it
 ///
 /// @type
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
 pub enum Bound {
     #[default]
     Exact,
@@ -17,16 +18,25 @@ pub enum Bound {
 
 /// SearchNode is the search information cached for one position
 ///
+/// note: the node is packed into 8 bytes so that a transposition table bucket
+///       of them fits in a cache line. scores are stored as 16 bits, which
+///       holds every score of the search, and a null move stands in for a
+///       missing best move
+///
 /// @type
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct SearchNode {
-    depth: Depth,
-    score: Score,
-    bound: Bound,
-    best_move: Option<Move>,
+    best_move: Move,  // best move found, or the null move if none was found
+    score: i16,       // score produced by the search
+    static_eval: i16, // static evaluation of the position, or NO_EVAL
+    depth: Depth,     // remaining search depth represented by the entry
+    bound: Bound,     // relationship between the score and its window
 }
 
 impl SearchNode {
+    /// NO_EVAL marks a node without a static evaluation
+    const NO_EVAL: i16 = i16::MIN;
+
     /// new creates a transposition-table entry for a searched position
     ///
     /// @param: depth - remaining search depth represented by the entry
@@ -34,15 +44,42 @@ impl SearchNode {
     /// @param: bound - relationship between the score and its alpha-beta window
     /// @param: best_move - best move found while producing the entry
     /// @return: new search node
-    pub const fn new(depth: Depth, score: Score, bound: Bound, best_move: Option<Move>) -> Self {
+    pub fn new(depth: Depth, score: Score, bound: Bound, best_move: Option<Move>) -> Self {
         Self {
+            best_move: best_move.unwrap_or_default(),
+            score: Self::pack_score(score),
+            static_eval: Self::NO_EVAL,
             depth,
-            score,
             bound,
-            best_move,
         }
     }
 
+    /// with_static_eval sets the static evaluation of the position
+    ///
+    /// note: negamax does not store static evaluations yet, since no pruning
+    ///       heuristic reads them. the slot keeps the node layout and file
+    ///       format stable for when one does
+    ///
+    /// @param: static_eval - static evaluation from the side-to-move's perspective
+    /// @return: search node holding the static evaluation
+    pub fn with_static_eval(mut self, static_eval: Score) -> Self {
+        self.static_eval = Self::pack_score(static_eval);
+        self
+    }
+
+    /// pack_score narrows a score to the 16 bits stored in the node
+    ///
+    /// @param: score - score to narrow
+    /// @return: score as stored in the node
+    #[inline]
+    fn pack_score(score: Score) -> i16 {
+        debug_assert!(
+            score > Score::from(Self::NO_EVAL) && score <= Score::from(i16::MAX),
+            "score {score} does not fit in a search node"
+        );
+        score.clamp(Score::from(Self::NO_EVAL) + 1, Score::from(i16::MAX)) as i16
+    }
+
     /// depth returns the remaining search depth represented by this entry
     ///
     /// @return: remaining search depth represented by the entry
@@ -56,7 +93,19 @@ impl SearchNode {
     /// @return: cached score
     #[inline]
     pub const fn score(&self) -> Score {
-        self.score
+        self.score as Score
+    }
+
+    /// static_eval returns the cached static evaluation
+    ///
+    /// @return: static evaluation from the side-to-move's perspective, or None
+    ///          if it was not computed
+    #[inline]
+    pub const fn static_eval(&self) -> Option<Score> {
+        match self.static_eval {
+            Self::NO_EVAL => None,
+            static_eval => Some(static_eval as Score),
+        }
     }
 
     /// bound returns how the score relates to its original alpha-beta window
@@ -71,8 +120,17 @@ impl SearchNode {
     ///
     /// @return: cached best move, or None if no move was found
     #[inline]
-    pub const fn best_move(&self) -> Option<Move> {
-        self.best_move
+    pub fn best_move(&self) -> Option<Move> {
+        (self.best_move != Move::default()).then_some(self.best_move)
+    }
+}
+
+impl Default for SearchNode {
+    /// default creates an empty search node
+    ///
+    /// @impl: Default::default
+    fn default() -> Self {
+        Self::new(0, 0, Bound::Exact, None)
     }
 }
 
@@ -86,6 +144,42 @@ impl NodeData for SearchNode {
     }
 }
 
+impl PersistentNode for SearchNode {
+    const SIZE: usize = 8;
+
+    /// write_bytes serializes the node as its little-endian best move, score and
+    /// static evaluation, followed by its depth and bound
+    ///
+    /// @impl: PersistentNode::write_bytes
+    fn write_bytes(&self, bytes: &mut [u8]) {
+        bytes[..2].copy_from_slice(&u16::from(self.best_move).to_le_bytes());
+        bytes[2..4].copy_from_slice(&self.score.to_le_bytes());
+        bytes[4..6].copy_from_slice(&self.static_eval.to_le_bytes());
+        bytes[6] = self.depth as u8;
+        bytes[7] = self.bound as u8;
+    }
+
+    /// read_bytes deserializes a node written by `write_bytes`
+    ///
+    /// @impl: PersistentNode::read_bytes
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        let bound = match bytes[7] {
+            0 => Bound::Exact,
+            1 => Bound::Lower,
+            2 => Bound::Upper,
+            _ => return None,
+        };
+
+        Some(Self {
+            best_move: u16::from_le_bytes([bytes[0], bytes[1]]).into(),
+            score: i16::from_le_bytes([bytes[2], bytes[3]]),
+            static_eval: i16::from_le_bytes([bytes[4], bytes[5]]),
+            depth: bytes[6] as Depth,
+            bound,
+        })
+    }
+}
+
 impl Value for SearchNode {
     /// priority returns the replacement priority of this entry
     ///
@@ -95,3 +189,43 @@ impl Value for SearchNode {
         self.depth
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_primitives::Square;
+
+    #[test]
+    fn search_nodes_pack_into_eight_bytes() {
+        assert_eq!(size_of::<SearchNode>(), 8);
+    }
+
+    #[test]
+    fn search_nodes_round_trip_their_fields() {
+        let mv = Move::new(Square::E2, Square::E4);
+        let node = SearchNode::new(7, -31_950, Bound::Lower, Some(mv)).with_static_eval(-42);
+
+        assert_eq!(node.depth(), 7);
+        assert_eq!(node.score(), -31_950);
+        assert_eq!(node.bound(), Bound::Lower);
+        assert_eq!(node.best_move(), Some(mv));
+        assert_eq!(node.static_eval(), Some(-42));
+
+        let empty = SearchNode::default();
+        assert_eq!(empty.best_move(), None);
+        assert_eq!(empty.static_eval(), None);
+    }
+
+    #[test]
+    fn search_nodes_round_trip_through_bytes() {
+        let mv = Move::new(Square::G1, Square::F3);
+        let node = SearchNode::new(12, 250, Bound::Upper, Some(mv)).with_static_eval(-8);
+        let mut bytes = [0; SearchNode::SIZE];
+
+        node.write_bytes(&mut bytes);
+        assert_eq!(SearchNode::read_bytes(&bytes), Some(node));
+
+        bytes[7] = 3;
+        assert_eq!(SearchNode::read_bytes(&bytes), None);
+    }
+}
@@ -1,10 +1,12 @@
 use chess_kit_attack_table::DefaultAttackTable;
 use chess_kit_collections::Copyable;
-use chess_kit_eval::{Accumulator, DefaultAccumulator, EvalState, Score};
+use chess_kit_eval::{Accumulator, DefaultAccumulator, EvalState, Score, StandaloneEvalState};
 use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
 use chess_kit_position::{DefaultPosition, Fen, PositionView, Setup};
 use chess_kit_primitives::{Move, MoveDelta, PieceDeltaKind, Pieces, Sides, Square};
-use chess_kit_search::{Bound, Negamax, SearchNode, iterative_deepening};
+use chess_kit_search::{
+    Bound, Negamax, SearchNode, iterative_deepening, iterative_deepening_nodes,
+};
 use chess_kit_transposition::{DefaultTranspositionTable, TranspositionTable};
 
 type TestPosition = DefaultPosition<DefaultAttackTable>;
@@ -29,7 +31,7 @@ impl MaterialEvalState {
     }
 }
 
-impl EvalState for MaterialEvalState {
+impl StandaloneEvalState for MaterialEvalState {
     fn from_position<P: PositionView>(position: &P) -> Self {
         let mut state = Self::default();
         for piece in Pieces::ALL {
@@ -44,7 +46,9 @@ impl EvalState for MaterialEvalState {
         }
         state
     }
+}
 
+impl EvalState for MaterialEvalState {
     fn apply(&mut self, delta: MoveDelta) {
         for change in delta.iter() {
             let value = Self::piece_value(change.piece());
@@ -79,7 +83,7 @@ fn load_with_table_size(
     TestTranspositionTable,
     TestAccumulator,
 ) {
-    let position = TestPosition::from(Setup::from(Fen::try_from(fen).unwrap()));
+    let position = TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap();
     let eval = MaterialEvalState::from_position(&position);
     let mut accumulator = TestAccumulator::new();
     accumulator.push(eval);
@@ -286,6 +290,45 @@ fn iterative_deepening_visits_each_depth_and_stores_the_final_root() {
     assert_eq!(root.best_move(), None);
 }
 
+#[test]
+fn iterative_deepening_stops_deepening_once_the_node_budget_is_spent() {
+    let fen = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+    let (mut position, move_generator, mut transposition_table, mut accumulator) = load(fen);
+    let (shallow, depth) = iterative_deepening_nodes(
+        &mut Negamax::new(),
+        &mut position,
+        &move_generator,
+        &mut transposition_table,
+        &mut accumulator,
+        6,
+        1,
+    );
+    assert_eq!(depth, 1);
+    assert!(shallow.best_move.is_some());
+
+    let (mut position, move_generator, mut transposition_table, mut accumulator) = load(fen);
+    let (budgeted, depth) = iterative_deepening_nodes(
+        &mut Negamax::new(),
+        &mut position,
+        &move_generator,
+        &mut transposition_table,
+        &mut accumulator,
+        4,
+        u64::MAX,
+    );
+    let (mut position, move_generator, mut transposition_table, mut accumulator) = load(fen);
+    let unlimited = iterative_deepening(
+        &mut Negamax::new(),
+        &mut position,
+        &move_generator,
+        &mut transposition_table,
+        &mut accumulator,
+        4,
+    );
+    assert_eq!(depth, 4);
+    assert_eq!(budgeted, unlimited);
+}
+
 #[test]
 fn search_restores_state_and_prunes_the_tree() {
     let (mut position, move_generator, mut transposition_table, mut accumulator) =
@@ -408,3 +451,23 @@ fn transposition_table_reduces_nodes_in_a_cold_search() {
         uncached.nodes,
     );
 }
+
+#[test]
+fn root_ignores_colliding_entries_with_unplayable_moves() {
+    let (mut position, move_generator, mut transposition_table, mut accumulator) =
+        load("4k3/8/8/8/8/8/4q3/3Q2K1 w - - 0 1");
+    // an entry of another position sharing the root's key check
+    let colliding = SearchNode::new(8, 0, Bound::Exact, Some(Move::new(Square::A2, Square::A4)));
+    transposition_table.insert(position.key(), colliding);
+
+    let result = Negamax::new().search(
+        &mut position,
+        &move_generator,
+        &mut transposition_table,
+        &mut accumulator,
+        1,
+    );
+
+    assert_eq!(result.best_move, Some(Move::new(Square::D1, Square::E2)));
+    assert_eq!(result.score, 900);
+}
@@ -0,0 +1,208 @@
+use std::sync::LazyLock;
+
+/// MAX_PIECES is the largest number of pieces, kings included, of a table
+pub(crate) const MAX_PIECES: usize = 7;
+
+/// ENCODING holds the lookup tables used to map positions to table indices
+pub(crate) static ENCODING: LazyLock<Encoding> = LazyLock::new(Encoding::new);
+
+/// `Encoding` is the set of lookup tables used to map the squares of the
+/// pieces of a position to its index in a table
+///
+/// note: squares are numbered from a1 = 0 to h8 = 63 as in the table files
+///
+/// @type
+pub(crate) struct Encoding {
+    /// ways to choose k pieces out of n squares, indexed [k][n]
+    pub(crate) binomial: [[u64; 64]; MAX_PIECES - 1],
+    /// rank of a pawn square among the squares of a leading pawn, where the
+    /// leading pawn has the highest rank
+    pub(crate) map_pawns: [usize; 64],
+    /// index of the leading pawns when the first one is on a square
+    pub(crate) lead_pawn_idx: [[u64; 64]; MAX_PIECES - 1],
+    /// number of leading pawn placements per file of the first leading pawn
+    pub(crate) lead_pawns_size: [[u64; 4]; MAX_PIECES - 1],
+    /// index of a square below the a1-h8 diagonal
+    pub(crate) map_b1h1h7: [u64; 64],
+    /// index of a square in the a1-d1-d4 triangle
+    pub(crate) map_a1d1d4: [u64; 64],
+    /// index of two kings, the first in the a1-d1-d4 triangle
+    pub(crate) map_kk: [[u64; 64]; 10],
+}
+
+impl Encoding {
+    /// new builds the lookup tables
+    ///
+    /// @return: initialized lookup tables
+    fn new() -> Self {
+        let mut encoding = Self {
+            binomial: [[0; 64]; MAX_PIECES - 1],
+            map_pawns: [0; 64],
+            lead_pawn_idx: [[0; 64]; MAX_PIECES - 1],
+            lead_pawns_size: [[0; 4]; MAX_PIECES - 1],
+            map_b1h1h7: [0; 64],
+            map_a1d1d4: [0; 64],
+            map_kk: [[0; 64]; 10],
+        };
+
+        let mut code = 0;
+        for square in 0..64 {
+            if off_diagonal(square) < 0 {
+                encoding.map_b1h1h7[square] = code;
+                code += 1;
+            }
+        }
+
+        // squares on the diagonal are encoded after the ones below it
+        let mut diagonal = Vec::new();
+        code = 0;
+        for square in 0..=27 {
+            if off_diagonal(square) < 0 && file(square) <= 3 {
+                encoding.map_a1d1d4[square] = code;
+                code += 1;
+            } else if off_diagonal(square) == 0 && file(square) <= 3 {
+                diagonal.push(square);
+            }
+        }
+        for square in diagonal {
+            encoding.map_a1d1d4[square] = code;
+            code += 1;
+        }
+
+        // kings are never adjacent, and when the first king is on the diagonal
+        // the second one is never above it. positions with both kings on the
+        // diagonal are encoded last
+        let mut both_on_diagonal = Vec::new();
+        code = 0;
+        for idx in 0..10 {
+            for first in 0..=27 {
+                // b1 is the only square mapped to 0
+                if encoding.map_a1d1d4[first] != idx as u64 || (idx == 0 && first != 1) {
+                    continue;
+                }
+                for second in 0..64 {
+                    if distance(first, second) <= 1
+                        || (off_diagonal(first) == 0 && off_diagonal(second) > 0)
+                    {
+                        continue;
+                    }
+                    if off_diagonal(first) == 0 && off_diagonal(second) == 0 {
+                        both_on_diagonal.push((idx, second));
+                    } else {
+                        encoding.map_kk[idx][second] = code;
+                        code += 1;
+                    }
+                }
+            }
+        }
+        for (idx, second) in both_on_diagonal {
+            encoding.map_kk[idx][second] = code;
+            code += 1;
+        }
+
+        encoding.binomial[0][0] = 1;
+        for n in 1..64 {
+            for k in 0..(MAX_PIECES - 1).min(n + 1) {
+                encoding.binomial[k][n] = if k > 0 {
+                    encoding.binomial[k - 1][n - 1]
+                } else {
+                    0
+                } + if k < n {
+                    encoding.binomial[k][n - 1]
+                } else {
+                    0
+                };
+            }
+        }
+
+        // the leading pawn is the one closest to the edge, and among those on
+        // the same file the one with the lowest rank
+        let mut available = 47;
+        for count in 1..MAX_PIECES - 1 {
+            for file in 0..4 {
+                let mut idx = 0;
+                for rank in 1..7 {
+                    let square = rank * 8 + file;
+                    if count == 1 {
+                        encoding.map_pawns[square] = available;
+                        encoding.map_pawns[square ^ 7] = available - 1;
+                        available = available.saturating_sub(2);
+                    }
+                    encoding.lead_pawn_idx[count][square] = idx;
+                    idx += encoding.binomial[count - 1][encoding.map_pawns[square]];
+                }
+                encoding.lead_pawns_size[count][file] = idx;
+            }
+        }
+
+        encoding
+    }
+}
+
+/// file returns the file of a square, from 0 for the a-file
+///
+/// @param: square - square to get the file of
+/// @return: file of the square
+#[inline]
+pub(crate) const fn file(square: usize) -> usize {
+    square & 7
+}
+
+/// rank returns the rank of a square, from 0 for the first rank
+///
+/// @param: square - square to get the rank of
+/// @return: rank of the square
+#[inline]
+pub(crate) const fn rank(square: usize) -> usize {
+    square >> 3
+}
+
+/// off_diagonal returns how far above the a1-h8 diagonal a square is
+///
+/// @param: square - square to locate
+/// @return: positive above the diagonal, negative below it, and zero on it
+#[inline]
+pub(crate) const fn off_diagonal(square: usize) -> isize {
+    rank(square) as isize - file(square) as isize
+}
+
+/// distance returns the number of king moves between two squares
+///
+/// @param: from - first square
+/// @param: to - second square
+/// @return: number of king moves between the squares
+#[inline]
+pub(crate) const fn distance(from: usize, to: usize) -> usize {
+    let files = file(from).abs_diff(file(to));
+    let ranks = rank(from).abs_diff(rank(to));
+    if files > ranks { files } else { ranks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_every_legal_placement_of_two_kings() {
+        let encoding = &*ENCODING;
+        let codes = encoding.map_kk.iter().flatten().copied().max().unwrap();
+
+        assert_eq!(codes, 461);
+        assert_eq!(encoding.map_b1h1h7.iter().max(), Some(&27));
+        assert_eq!(encoding.map_a1d1d4[1], 0);
+        assert_eq!(encoding.map_a1d1d4[27], 9);
+    }
+
+    #[test]
+    fn ranks_leading_pawns_from_the_edge() {
+        let encoding = &*ENCODING;
+
+        // a2 and h2 are the best candidates to lead, d7 and e7 the worst
+        assert_eq!(encoding.map_pawns[8], 47);
+        assert_eq!(encoding.map_pawns[15], 46);
+        assert_eq!(encoding.map_pawns[51], 1);
+        assert_eq!(encoding.map_pawns[52], 0);
+        assert_eq!(encoding.binomial[2][5], 10);
+        assert_eq!(encoding.lead_pawns_size[1], [6; 4]);
+    }
+}
@@ -0,0 +1,34 @@
+use std::fmt::{self, Display};
+use std::io;
+
+/// SyzygyError is returned when tablebase files cannot be read
+///
+/// @type
+#[derive(Debug)]
+pub enum SyzygyError {
+    // a tablebase directory or file could not be read
+    Io(io::Error),
+    // a table file does not follow the Syzygy format
+    Corrupt(&'static str),
+}
+
+impl Display for SyzygyError {
+    /// fmt writes a human-readable description of the tablebase error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to read tablebases: {error}"),
+            Self::Corrupt(reason) => write!(f, "corrupt table file: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for SyzygyError {}
+
+impl From<io::Error> for SyzygyError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
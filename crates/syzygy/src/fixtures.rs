@@ -0,0 +1,669 @@
+use crate::MaterialKey;
+use crate::encoding::{distance, file, rank};
+use crate::table::{Board, FLAG_LOSS_PLIES, FLAG_SINGLE_VALUE, FLAG_WIN_PLIES, Table, TableKind};
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_eval::endgame::kpk;
+use chess_kit_position::{DefaultPosition, Fen, Setup};
+use chess_kit_primitives::{Pieces, Sides, Square};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// Piece is the side, type and square of a piece of a fixture position
+pub(crate) type Piece = (Sides, Pieces, usize);
+
+/// TABLES is the material of the fixture tables
+pub(crate) const TABLES: [&str; 5] = ["KPvK", "KQvK", "KRvK", "KBvK", "KNvK"];
+
+/// BLOCK_SIZE_LOG2 and SPAN_LOG2 size the blocks and the sparse index entries
+/// of the compressed data written
+const BLOCK_SIZE_LOG2: u8 = 6;
+const SPAN_LOG2: u8 = 6;
+
+/// DIRECTORY is a directory holding the fixture tables, written the first
+/// time it is used
+///
+/// note: the fixtures are written in the format of the official tables, which
+///       `tests/official.rs` probes, from results that are verified
+///       independently: the KPK bitbase of the evaluation, and the geometry of
+///       the other endgames
+pub(crate) static DIRECTORY: LazyLock<PathBuf> = LazyLock::new(|| {
+    let directory = std::env::temp_dir().join(format!("chess-kit-syzygy-{}", std::process::id()));
+    std::fs::create_dir_all(&directory).unwrap();
+
+    write_table(&directory, "KPvK", TableKind::Wdl, kpk_wdl);
+    write_table(&directory, "KPvK", TableKind::Dtz, |turn, pieces| {
+        KPK_DTZ.get(turn, pieces).map(|dtz| dtz as usize - 1)
+    });
+    write_table(&directory, "KQvK", TableKind::Wdl, major_piece_wdl);
+    write_table(&directory, "KRvK", TableKind::Wdl, major_piece_wdl);
+    write_table(&directory, "KBvK", TableKind::Wdl, |_, _| Some(2));
+    write_table(&directory, "KNvK", TableKind::Wdl, |_, _| Some(2));
+    directory
+});
+
+/// KPK_DTZ is the distance to zeroing of every king and pawn versus king
+/// position won by the side with the pawn
+pub(crate) static KPK_DTZ: LazyLock<KpkDtz> = LazyLock::new(KpkDtz::new);
+
+/// `KpkDtz` is the distance to zeroing of king and pawn versus king positions,
+/// in plies, indexed by the squares of the white king, black king and pawn
+///
+/// @type
+pub(crate) struct KpkDtz {
+    white: Vec<i32>, // won positions with white to move, 0 elsewhere
+    black: Vec<i32>, // lost positions with black to move, 0 elsewhere
+}
+
+impl KpkDtz {
+    /// new solves the distances by retrograde analysis, where a position with
+    /// a winning pawn move is 1 ply from zeroing
+    ///
+    /// @return: distances of every position
+    fn new() -> Self {
+        let mut dtz = Self {
+            white: vec![0; 64 * 64 * 64],
+            black: vec![0; 64 * 64 * 64],
+        };
+
+        let mut won = vec![false; 64 * 64 * 64];
+        let mut lost = vec![false; 64 * 64 * 64];
+        for (wk, bk, pawn) in kpk_squares() {
+            let pieces = kpk_pieces(wk, bk, pawn);
+            let valid = |turn| wk != bk && wk != pawn && bk != pawn && is_valid(turn, &pieces);
+            lost[idx(wk, bk, pawn)] =
+                valid(Sides::Black) && kpk_wdl(Sides::Black, &pieces) == Some(0);
+            won[idx(wk, bk, pawn)] =
+                valid(Sides::White) && kpk_wdl(Sides::White, &pieces) == Some(4);
+            if !won[idx(wk, bk, pawn)] {
+                continue;
+            }
+            let wins_by_pawn_move = pawn_pushes(wk, bk, pawn).into_iter().any(|to| {
+                if rank(to) == 7 {
+                    [Pieces::Queen, Pieces::Rook].into_iter().any(|piece| {
+                        major_piece_wdl(
+                            Sides::Black,
+                            &[(Sides::White, piece, to), pieces[1], pieces[2]],
+                        ) == Some(0)
+                    })
+                } else {
+                    kpk_wdl(Sides::Black, &kpk_pieces(wk, bk, to)) == Some(0)
+                }
+            });
+            if wins_by_pawn_move {
+                dtz.white[idx(wk, bk, pawn)] = 1;
+            }
+        }
+
+        // every round only extends the distances known after the previous one,
+        // so the distances found in a round all grow by one ply
+        loop {
+            let mut updates = Vec::new();
+            for (wk, bk, pawn) in kpk_squares() {
+                let i = idx(wk, bk, pawn);
+                if lost[i] && dtz.black[i] == 0 {
+                    let longest = king_moves(bk)
+                        .filter(|to| distance(*to, wk) > 1 && !pawn_attacks(pawn, *to))
+                        .map(|to| dtz.white[idx(wk, to, pawn)])
+                        .try_fold(0, |longest, dtz| (dtz > 0).then_some(longest.max(dtz)));
+                    if let Some(longest) = longest {
+                        updates.push((Sides::Black, i, 1 + longest));
+                    }
+                }
+
+                if won[i] && dtz.white[i] == 0 {
+                    let best = king_moves(wk)
+                        .filter(|to| *to != pawn && distance(*to, bk) > 1)
+                        .map(|to| dtz.black[idx(to, bk, pawn)])
+                        .filter(|dtz| *dtz > 0)
+                        .min();
+                    if let Some(best) = best {
+                        updates.push((Sides::White, i, 1 + best));
+                    }
+                }
+            }
+
+            if updates.is_empty() {
+                break;
+            }
+            for (turn, i, value) in updates {
+                match turn {
+                    Sides::White => dtz.white[i] = value,
+                    Sides::Black => dtz.black[i] = value,
+                }
+            }
+        }
+        dtz
+    }
+
+    /// get returns the distance to zeroing of a won position with white to
+    /// move or a lost position with black to move
+    ///
+    /// @param: turn - side to move
+    /// @param: pieces - white pawn, white king and black king
+    /// @return: distance in plies, or None if the position is drawn
+    pub(crate) fn get(&self, turn: Sides, pieces: &[Piece]) -> Option<i32> {
+        let [(_, _, pawn), (_, _, wk), (_, _, bk)] = *pieces else {
+            return None;
+        };
+        let dtz = match turn {
+            Sides::White => self.white[idx(wk, bk, pawn)],
+            Sides::Black => self.black[idx(wk, bk, pawn)],
+        };
+        (dtz > 0).then_some(dtz)
+    }
+}
+
+/// idx returns the index of a king and pawn versus king position
+#[inline]
+fn idx(wk: usize, bk: usize, pawn: usize) -> usize {
+    (wk * 64 + bk) * 64 + pawn
+}
+
+/// kpk_squares iterates the squares of the white king, black king and pawn
+fn kpk_squares() -> impl Iterator<Item = (usize, usize, usize)> {
+    (0..64).flat_map(|wk| (0..64).flat_map(move |bk| (8..56).map(move |pawn| (wk, bk, pawn))))
+}
+
+/// kpk_pieces returns the pieces of a king and pawn versus king position, in
+/// the order the fixture positions list them
+fn kpk_pieces(wk: usize, bk: usize, pawn: usize) -> [Piece; 3] {
+    [
+        (Sides::White, Pieces::Pawn, pawn),
+        (Sides::White, Pieces::King, wk),
+        (Sides::Black, Pieces::King, bk),
+    ]
+}
+
+/// king_moves iterates the squares adjacent to a square
+fn king_moves(square: usize) -> impl Iterator<Item = usize> {
+    let (f, r) = (file(square) as isize, rank(square) as isize);
+    (-1..=1)
+        .flat_map(move |df| (-1..=1).map(move |dr| (f + df, r + dr)))
+        .filter(move |(file, rank)| {
+            (0..8).contains(file) && (0..8).contains(rank) && (*file, *rank) != (f, r)
+        })
+        .map(|(file, rank)| (rank * 8 + file) as usize)
+}
+
+/// pawn_pushes returns the squares a white pawn can be pushed to
+fn pawn_pushes(wk: usize, bk: usize, pawn: usize) -> Vec<usize> {
+    let empty = |square: usize| square != wk && square != bk;
+    let mut pushes = Vec::new();
+    if empty(pawn + 8) {
+        pushes.push(pawn + 8);
+        if rank(pawn) == 1 && empty(pawn + 16) {
+            pushes.push(pawn + 16);
+        }
+    }
+    pushes
+}
+
+/// pawn_attacks checks whether a white pawn attacks a square
+fn pawn_attacks(pawn: usize, square: usize) -> bool {
+    rank(square) == rank(pawn) + 1 && file(square).abs_diff(file(pawn)) == 1
+}
+
+/// attacks checks whether a white piece attacks a square
+///
+/// @param: piece - type of the piece
+/// @param: from - square of the piece
+/// @param: to - square to check
+/// @param: blockers - squares of the other pieces
+/// @return: true if the piece attacks the square
+pub(crate) fn attacks(piece: Pieces, from: usize, to: usize, blockers: &[usize]) -> bool {
+    let (files, ranks) = (file(from).abs_diff(file(to)), rank(from).abs_diff(rank(to)));
+    // sliders walk towards the square until they reach it or are blocked
+    let slides = |straight: bool, diagonal: bool| {
+        if from == to || !((straight && (files == 0 || ranks == 0)) || (diagonal && files == ranks))
+        {
+            return false;
+        }
+        let step = (rank(to) as isize - rank(from) as isize).signum() * 8
+            + (file(to) as isize - file(from) as isize).signum();
+        let mut square = from as isize + step;
+        while square != to as isize {
+            if blockers.contains(&(square as usize)) {
+                return false;
+            }
+            square += step;
+        }
+        true
+    };
+    match piece {
+        Pieces::Pawn => pawn_attacks(from, to),
+        Pieces::Knight => (files == 1 && ranks == 2) || (files == 2 && ranks == 1),
+        Pieces::Bishop => slides(false, true),
+        Pieces::Rook => slides(true, false),
+        Pieces::Queen => slides(true, true),
+        Pieces::King => distance(from, to) == 1,
+        Pieces::None => false,
+    }
+}
+
+/// is_valid checks whether a fixture position is legal, with the kings apart
+/// and the side not to move out of check
+///
+/// @param: turn - side to move
+/// @param: pieces - pieces of the position, with black only having a king
+/// @return: true if the position is legal
+pub(crate) fn is_valid(turn: Sides, pieces: &[Piece]) -> bool {
+    let square = |side, piece| {
+        pieces
+            .iter()
+            .find(|(s, p, _)| *s == side && *p == piece)
+            .map(|(_, _, square)| *square)
+    };
+    let (Some(wk), Some(bk)) = (
+        square(Sides::White, Pieces::King),
+        square(Sides::Black, Pieces::King),
+    ) else {
+        return false;
+    };
+    if distance(wk, bk) <= 1 {
+        return false;
+    }
+    turn == Sides::Black
+        || !pieces.iter().any(|(side, piece, from)| {
+            let blockers = pieces
+                .iter()
+                .map(|(_, _, square)| *square)
+                .filter(|square| square != from && *square != bk)
+                .collect::<Vec<_>>();
+            *side == Sides::White && attacks(*piece, *from, bk, &blockers)
+        })
+}
+
+/// placements returns every legal position of a table, with the pieces of
+/// the table name given to white
+///
+/// @param: code - table name
+/// @return: side to move and pieces of every legal position
+pub(crate) fn placements(code: &str) -> Vec<(Sides, Vec<Piece>)> {
+    let mut pieces = pieces(code);
+    let mut placements = Vec::new();
+    place(&mut pieces, 0, &mut placements);
+    placements
+}
+
+/// pieces returns the pieces of a table in the order they are encoded, with
+/// the pawns leading and the pieces of the table name given to white
+fn pieces(code: &str) -> Vec<Piece> {
+    let (white, black) = code.split_once('v').unwrap();
+    let mut pieces = Vec::new();
+    for (side, symbols) in [(Sides::White, white), (Sides::Black, black)] {
+        for symbol in symbols.chars() {
+            let piece = match symbol {
+                'P' => Pieces::Pawn,
+                'N' => Pieces::Knight,
+                'B' => Pieces::Bishop,
+                'R' => Pieces::Rook,
+                'Q' => Pieces::Queen,
+                _ => Pieces::King,
+            };
+            pieces.push((side, piece, 0));
+        }
+    }
+    // pawns lead, as in the encoded order of the tables
+    pieces.sort_by_key(|(_, piece, _)| *piece != Pieces::Pawn);
+    pieces
+}
+
+/// place recursively places the pieces from the given one onwards
+fn place(pieces: &mut Vec<Piece>, next: usize, placements: &mut Vec<(Sides, Vec<Piece>)>) {
+    if next == pieces.len() {
+        for turn in [Sides::White, Sides::Black] {
+            if is_valid(turn, pieces) {
+                placements.push((turn, pieces.clone()));
+            }
+        }
+        return;
+    }
+    let squares = if pieces[next].1 == Pieces::Pawn {
+        8..56
+    } else {
+        0..64
+    };
+    for square in squares {
+        if pieces[..next].iter().all(|(_, _, other)| *other != square) {
+            pieces[next].2 = square;
+            place(pieces, next + 1, placements);
+        }
+    }
+}
+
+/// kpk_wdl returns the stored value of a king and pawn versus king position,
+/// from the KPK bitbase of the evaluation
+///
+/// @param: turn - side to move
+/// @param: pieces - white pawn, white king and black king
+/// @return: outcome for the side to move plus two
+pub(crate) fn kpk_wdl(turn: Sides, pieces: &[Piece]) -> Option<usize> {
+    let [(_, _, pawn), (_, _, wk), (_, _, bk)] = *pieces else {
+        return None;
+    };
+    let wins = kpk::probe(
+        Square::from_idx(wk),
+        Square::from_idx(pawn),
+        Square::from_idx(bk),
+        turn == Sides::White,
+    );
+    Some(match (wins, turn) {
+        (false, _) => 2,
+        (true, Sides::White) => 4,
+        (true, Sides::Black) => 0,
+    })
+}
+
+/// major_piece_wdl returns the stored value of a king and queen or rook versus
+/// king position, which is only drawn when black can capture the piece or is
+/// stalemated
+///
+/// @param: turn - side to move
+/// @param: pieces - white king and piece, and black king
+/// @return: outcome for the side to move plus two
+pub(crate) fn major_piece_wdl(turn: Sides, pieces: &[Piece]) -> Option<usize> {
+    let find = |side, king: bool| {
+        pieces
+            .iter()
+            .find(|(s, piece, _)| *s == side && (*piece == Pieces::King) == king)
+            .copied()
+    };
+    let (Some((_, piece, square)), Some((_, _, wk)), Some((_, _, bk))) = (
+        find(Sides::White, false),
+        find(Sides::White, true),
+        find(Sides::Black, true),
+    ) else {
+        return None;
+    };
+    if turn == Sides::White {
+        return Some(4);
+    }
+
+    let mut escapes = false;
+    for to in king_moves(bk).filter(|to| distance(*to, wk) > 1) {
+        if to == square {
+            return Some(2);
+        }
+        escapes |= !attacks(piece, square, to, &[wk]);
+    }
+    let checked = attacks(piece, square, bk, &[wk]);
+    Some(if escapes || checked { 0 } else { 2 })
+}
+
+/// position creates a position from fixture pieces
+///
+/// @param: turn - side to move
+/// @param: pieces - pieces of the position
+/// @param: flipped - whether to swap the colors and flip the board
+/// @return: position with the pieces
+pub(crate) fn position(
+    turn: Sides,
+    pieces: &[Piece],
+    flipped: bool,
+) -> DefaultPosition<DefaultAttackTable> {
+    let mut board = [['.'; 8]; 8];
+    for &(side, piece, square) in pieces {
+        let (side, square) = if flipped {
+            (side.other(), square ^ 56)
+        } else {
+            (side, square)
+        };
+        let symbol = ['.', 'p', 'n', 'b', 'r', 'q', 'k'][piece.idx()];
+        board[rank(square)][file(square)] = if side == Sides::White {
+            symbol.to_ascii_uppercase()
+        } else {
+            symbol
+        };
+    }
+
+    let mut fen = String::new();
+    for row in board.iter().rev() {
+        let mut empty = 0;
+        for symbol in row {
+            if *symbol == '.' {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+                empty = 0;
+            }
+            fen.push(*symbol);
+        }
+        if empty > 0 {
+            fen.push_str(&empty.to_string());
+        }
+        fen.push('/');
+    }
+    fen.pop();
+    let turn = if flipped { turn.other() } else { turn };
+    fen.push_str(if turn == Sides::White {
+        " w - - 0 1"
+    } else {
+        " b - - 0 1"
+    });
+
+    DefaultPosition::try_from(Setup::from(Fen::try_from(fen.as_str()).unwrap())).unwrap()
+}
+
+/// `Pairs` is the values of one part of a table before they are compressed
+///
+/// @type
+#[derive(Clone)]
+enum Pairs {
+    Single(usize),     // every position has the same value
+    Coded(Vec<usize>), // values coded with a fixed number of bits each
+}
+
+/// write_table writes a 3-piece table file in the Syzygy format, storing the
+/// values with a code of fixed length
+///
+/// note: the indices of the positions are computed by the table itself, and
+///       two positions with different values sharing an index fail the write
+///
+/// @param: directory - directory to write the table to
+/// @param: code - table name
+/// @param: kind - kind of the table
+/// @param: value - stored value of a position, or None if any value may be
+///                 stored
+/// @return: void
+/// @side-effects: writes the table file
+fn write_table(
+    directory: &Path,
+    code: &str,
+    kind: TableKind,
+    value: impl Fn(Sides, &[Piece]) -> Option<usize>,
+) {
+    let key = MaterialKey::from_code(code).unwrap();
+    let flags = match kind {
+        TableKind::Wdl => 0,
+        // white to move is stored, with distances in plies
+        TableKind::Dtz => FLAG_WIN_PLIES | FLAG_LOSS_PLIES,
+    };
+    let (sides, files) = (
+        if kind == TableKind::Wdl { 2 } else { 1 },
+        if code.contains('P') { 4 } else { 1 },
+    );
+
+    // the table is read once with a single value to compute the indices
+    let layout = Table::new(
+        kind,
+        key,
+        encode(
+            code,
+            kind,
+            flags,
+            &vec![vec![Pairs::Single(0); files]; sides],
+        ),
+    )
+    .unwrap();
+
+    let mut values = vec![vec![HashMap::new(); files]; sides];
+    for (turn, pieces) in placements(code) {
+        let Some(value) = value(turn, &pieces) else {
+            continue;
+        };
+        let Some((side, file, idx)) = layout.index(&Board::new(turn, &pieces)) else {
+            continue;
+        };
+        let stored = *values[side][file].entry(idx).or_insert(value);
+        assert_eq!(stored, value, "{code} stores two values at index {idx}");
+    }
+
+    let pairs = values
+        .into_iter()
+        .enumerate()
+        .map(|(side, values)| {
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(file, values)| {
+                    let mut counts = HashMap::new();
+                    for value in values.values() {
+                        *counts.entry(*value).or_insert(0) += 1;
+                    }
+                    let common = counts
+                        .iter()
+                        .max_by_key(|(_, count)| **count)
+                        .map_or(0, |(v, _)| *v);
+                    if counts.len() <= 1 {
+                        return Pairs::Single(common);
+                    }
+                    let size = layout.pairs[side][file].size() as usize;
+                    Pairs::Coded(
+                        (0..size as u64)
+                            .map(|idx| values.get(&idx).copied().unwrap_or(common))
+                            .collect(),
+                    )
+                })
+                .collect()
+        })
+        .collect::<Vec<Vec<_>>>();
+
+    let path = directory.join(code).with_extension(kind.extension());
+    std::fs::write(path, encode(code, kind, flags, &pairs)).unwrap();
+}
+
+/// encode lays out a table file
+///
+/// @param: code - table name
+/// @param: kind - kind of the table
+/// @param: flags - flags of the compressed data
+/// @param: pairs - values of each side to move and file
+/// @return: contents of the table file
+fn encode(code: &str, kind: TableKind, flags: u8, pairs: &[Vec<Pairs>]) -> Vec<u8> {
+    let has_pawns = code.contains('P');
+    let mut bytes = kind.magic().to_vec();
+    bytes.push(u8::from(pairs.len() == 2) | u8::from(has_pawns) << 1);
+
+    let order = pieces(code)
+        .into_iter()
+        .map(|(side, piece, _)| crate::table::code(side, piece))
+        .collect::<Vec<_>>();
+    for _ in &pairs[0] {
+        bytes.push(0);
+        bytes.extend(order.iter().map(|piece| piece | piece << 4));
+    }
+    pad(&mut bytes, 2);
+
+    for file in 0..pairs[0].len() {
+        for side in pairs {
+            match coded(&side[file]) {
+                None => {
+                    let Pairs::Single(value) = side[file] else {
+                        unreachable!()
+                    };
+                    bytes.extend([flags | FLAG_SINGLE_VALUE, value as u8]);
+                }
+                Some((_, symbols, bits, _, blocks)) => {
+                    bytes.extend([flags, BLOCK_SIZE_LOG2, SPAN_LOG2, 0]);
+                    bytes.extend((blocks as u32).to_le_bytes());
+                    bytes.extend([bits as u8, bits as u8, 0, 0]);
+                    bytes.extend((symbols as u16).to_le_bytes());
+                    for symbol in 0..symbols {
+                        bytes.extend([symbol as u8, 0xF0 | (symbol >> 8) as u8, 0xFF]);
+                    }
+                    if symbols & 1 != 0 {
+                        bytes.push(0);
+                    }
+                }
+            }
+        }
+    }
+    if kind == TableKind::Dtz {
+        pad(&mut bytes, 2);
+    }
+
+    let span = 1 << SPAN_LOG2;
+    for file in 0..pairs[0].len() {
+        for side in pairs {
+            let Some((values, _, _, per_block, blocks)) = coded(&side[file]) else {
+                continue;
+            };
+            for start in (0..values.len()).step_by(span) {
+                let middle = start + span / 2;
+                let block = (middle / per_block).min(blocks - 1);
+                bytes.extend((block as u32).to_le_bytes());
+                bytes.extend(((middle - block * per_block) as u16).to_le_bytes());
+            }
+        }
+    }
+    for file in 0..pairs[0].len() {
+        for side in pairs {
+            let Some((values, _, _, per_block, _)) = coded(&side[file]) else {
+                continue;
+            };
+            for block in values.chunks(per_block) {
+                bytes.extend((block.len() as u16 - 1).to_le_bytes());
+            }
+        }
+    }
+    for file in 0..pairs[0].len() {
+        for side in pairs {
+            pad(&mut bytes, 64);
+            let Some((values, _, bits, per_block, _)) = coded(&side[file]) else {
+                continue;
+            };
+            for block in values.chunks(per_block) {
+                let mut data = vec![0u8; 1 << BLOCK_SIZE_LOG2];
+                for (i, value) in block.iter().enumerate() {
+                    for bit in 0..bits {
+                        if value >> (bits - 1 - bit) & 1 != 0 {
+                            let position = i * bits + bit;
+                            data[position / 8] |= 0x80 >> (position % 8);
+                        }
+                    }
+                }
+                bytes.extend(data);
+            }
+        }
+    }
+    bytes
+}
+
+/// coded returns the values of a part coded with the fewest bits holding its
+/// largest value, with their number of symbols, bits, values per block and
+/// blocks
+fn coded(pairs: &Pairs) -> Option<(&[usize], usize, usize, usize, usize)> {
+    let Pairs::Coded(values) = pairs else {
+        return None;
+    };
+    let symbols = values.iter().max().unwrap() + 1;
+    let bits = (usize::BITS - (symbols - 1).leading_zeros()).max(1) as usize;
+    let per_block = (8 << BLOCK_SIZE_LOG2) / bits;
+    Some((
+        values,
+        symbols,
+        bits,
+        per_block,
+        values.len().div_ceil(per_block),
+    ))
+}
+
+/// pad pads the bytes of a table file to a multiple of an alignment
+fn pad(bytes: &mut Vec<u8>, alignment: usize) {
+    while !bytes.len().is_multiple_of(alignment) {
+        bytes.push(0);
+    }
+}
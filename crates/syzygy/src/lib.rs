@@ -0,0 +1,22 @@
+//! Syzygy endgame tablebase probing.
+//!
+//! [`Tablebases`] implements the `EndgameTablebase` probing contract of
+//! chess-kit-tablebase by reading the `.rtbw` (win/draw/loss) and `.rtbz`
+//! (distance to zeroing) table files of one or more directories and probing
+//! positions with few enough pieces, searching the captures the tables leave
+//! out with the move generator.
+
+mod encoding;
+mod error;
+mod material;
+mod table;
+mod tablebases;
+
+#[cfg(test)]
+mod fixtures;
+
+pub use error::SyzygyError;
+pub use tablebases::RankedMove;
+pub use tablebases::Tablebases;
+
+pub(crate) use material::MaterialKey;
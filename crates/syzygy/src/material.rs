@@ -0,0 +1,124 @@
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Black, Pieces, Sides, White};
+
+/// `MaterialKey` is the number of pieces of each type each side has, which
+/// identifies the table a position is stored in
+///
+/// @type
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct MaterialKey([[u8; Pieces::TOTAL]; Sides::TOTAL]);
+
+impl MaterialKey {
+    /// from_position counts the pieces of a position
+    ///
+    /// @param: position - position to count the pieces of
+    /// @return: material key of the position
+    pub(crate) fn from_position<P: PositionView>(position: &P) -> Self {
+        let mut key = Self::default();
+        for piece in Pieces::ALL {
+            key.0[Sides::White][piece] = position.get_piece::<White>(piece).count_ones() as u8;
+            key.0[Sides::Black][piece] = position.get_piece::<Black>(piece).count_ones() as u8;
+        }
+        key
+    }
+
+    /// from_code parses a table name such as `KRPvKN`, which lists the pieces
+    /// of the side the table is computed for followed by the pieces of the
+    /// other side
+    ///
+    /// @param: code - table name to parse
+    /// @return: material key with the first pieces given to white, or None if
+    ///          the name is not a valid table name
+    pub(crate) fn from_code(code: &str) -> Option<Self> {
+        let (white, black) = code.split_once('v')?;
+
+        let mut key = Self::default();
+        for (side, pieces) in [(Sides::White, white), (Sides::Black, black)] {
+            for symbol in pieces.chars() {
+                let piece = match symbol {
+                    'P' => Pieces::Pawn,
+                    'N' => Pieces::Knight,
+                    'B' => Pieces::Bishop,
+                    'R' => Pieces::Rook,
+                    'Q' => Pieces::Queen,
+                    'K' => Pieces::King,
+                    _ => return None,
+                };
+                key.add(side, piece);
+            }
+            if key.0[side][Pieces::King] != 1 {
+                return None;
+            }
+        }
+        Some(key)
+    }
+
+    /// flipped returns the material key with the pieces of the sides swapped
+    ///
+    /// @return: material key with the colors swapped
+    #[inline]
+    pub(crate) const fn flipped(self) -> Self {
+        Self([self.0[1], self.0[0]])
+    }
+
+    /// add adds a piece to a side
+    ///
+    /// @param: side - side to add the piece to
+    /// @param: piece - type of the piece
+    /// @return: void
+    /// @side-effects: modifies the material key
+    #[inline]
+    pub(crate) fn add(&mut self, side: Sides, piece: Pieces) {
+        self.0[side][piece] += 1;
+    }
+
+    /// count returns the number of pieces of a type a side has
+    ///
+    /// @param: side - side owning the pieces
+    /// @param: piece - type of the pieces
+    /// @return: number of pieces
+    #[inline]
+    pub(crate) fn count(&self, side: Sides, piece: Pieces) -> usize {
+        usize::from(self.0[side][piece])
+    }
+
+    /// total returns the number of pieces of both sides, kings included
+    ///
+    /// @return: number of pieces
+    #[inline]
+    pub(crate) fn total(&self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .map(|count| usize::from(*count))
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_position::{DefaultPosition, Fen, Setup};
+
+    fn key(fen: &str) -> MaterialKey {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        MaterialKey::from_position(&position)
+    }
+
+    #[test]
+    fn parses_table_names_for_white_and_flips_them_for_black() {
+        let krvkn = MaterialKey::from_code("KRvKN").unwrap();
+
+        assert_eq!(krvkn, key("4k3/8/8/3n4/8/8/8/R3K3 w - - 0 1"));
+        assert_eq!(krvkn.flipped(), key("r3k3/8/8/8/3N4/8/8/4K3 w - - 0 1"));
+        assert_eq!(krvkn.count(Sides::White, Pieces::Rook), 1);
+        assert_eq!(krvkn.total(), 4);
+        assert!(MaterialKey::from_code("KRvN").is_none());
+        assert!(MaterialKey::from_code("KXvK").is_none());
+        assert!(MaterialKey::from_code("KRK").is_none());
+    }
+}
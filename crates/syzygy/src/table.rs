@@ -0,0 +1,1003 @@
+use crate::encoding::{ENCODING, MAX_PIECES, file, off_diagonal, rank};
+use crate::{MaterialKey, SyzygyError};
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Pieces, Sides, White};
+use chess_kit_tablebase::Wdl;
+
+/// Flags of the header of a table file
+const HEADER_SPLIT: u8 = 1; // both sides to move are stored
+const HEADER_HAS_PAWNS: u8 = 2; // the table is split by the file of its leading pawn
+
+/// Flags of the compressed data of a table
+pub(crate) const FLAG_STM: u8 = 1; // side to move stored in a DTZ table
+pub(crate) const FLAG_MAPPED: u8 = 2; // DTZ values are mapped through a table
+pub(crate) const FLAG_WIN_PLIES: u8 = 4; // DTZ values of wins are in plies
+pub(crate) const FLAG_LOSS_PLIES: u8 = 8; // DTZ values of losses are in plies
+pub(crate) const FLAG_WIDE: u8 = 16; // DTZ map values are 16 bits wide
+pub(crate) const FLAG_SINGLE_VALUE: u8 = 128; // every position has the same value
+
+/// `TableKind` is the kind of information a table file stores
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TableKind {
+    Wdl, // outcome of each position
+    Dtz, // distance to the next capture or pawn move of each position
+}
+
+impl TableKind {
+    /// magic returns the bytes every table file of this kind starts with
+    ///
+    /// @return: magic bytes of the table files
+    #[inline]
+    pub(crate) const fn magic(self) -> [u8; 4] {
+        match self {
+            Self::Wdl => [0x71, 0xE8, 0x23, 0x5D],
+            Self::Dtz => [0xD7, 0x66, 0x0C, 0xA5],
+        }
+    }
+
+    /// extension returns the file extension of the table files of this kind
+    ///
+    /// @return: file extension without the leading dot
+    #[inline]
+    pub(crate) const fn extension(self) -> &'static str {
+        match self {
+            Self::Wdl => "rtbw",
+            Self::Dtz => "rtbz",
+        }
+    }
+}
+
+/// `Board` is the placement of the pieces of a position in the form used to
+/// index tables
+///
+/// @type
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Board {
+    // piece codes and squares, in ascending order of squares
+    pieces: [(u8, usize); MAX_PIECES],
+    // number of pieces on the board
+    len: usize,
+    // side to move
+    turn: Sides,
+    // material of the board
+    key: MaterialKey,
+}
+
+impl Board {
+    /// new creates a board from a list of pieces
+    ///
+    /// @param: turn - side to move
+    /// @param: pieces - side, type and square of every piece on the board
+    /// @return: board with the pieces
+    #[cfg(test)]
+    pub(crate) fn new(turn: Sides, pieces: &[(Sides, Pieces, usize)]) -> Self {
+        let mut board = Self {
+            pieces: [(0, 0); MAX_PIECES],
+            len: pieces.len(),
+            turn,
+            key: MaterialKey::default(),
+        };
+        for (slot, (side, piece, square)) in board.pieces.iter_mut().zip(pieces) {
+            *slot = (code(*side, *piece), *square);
+            board.key.add(*side, *piece);
+        }
+        board.pieces[..board.len].sort_unstable_by_key(|(_, square)| *square);
+        board
+    }
+
+    /// from_position copies the pieces of a position
+    ///
+    /// @param: position - position with at most MAX_PIECES pieces
+    /// @param: key - material key of the position
+    /// @return: board of the position
+    pub(crate) fn from_position<P: PositionView>(position: &P, key: MaterialKey) -> Self {
+        let mut board = Self {
+            pieces: [(0, 0); MAX_PIECES],
+            len: 0,
+            turn: position.turn(),
+            key,
+        };
+        let white = position.occupancy::<White>();
+        for square in position.total_occupancy() {
+            let side = if white.has_square(square) {
+                Sides::White
+            } else {
+                Sides::Black
+            };
+            board.pieces[board.len] = (code(side, position.piece_at(square)), square.idx());
+            board.len += 1;
+        }
+        board
+    }
+
+    /// pieces returns the piece codes and squares of the board
+    ///
+    /// @return: pieces in ascending order of squares
+    #[inline]
+    fn pieces(&self) -> &[(u8, usize)] {
+        &self.pieces[..self.len]
+    }
+}
+
+/// code returns the code of a piece in table files
+///
+/// @param: side - side owning the piece
+/// @param: piece - type of the piece
+/// @return: piece code, with the side in the fourth bit
+#[inline]
+pub(crate) const fn code(side: Sides, piece: Pieces) -> u8 {
+    (side.idx() << 3 | piece.idx()) as u8
+}
+
+/// `PairsData` describes the compressed values of one part of a table, which
+/// are Huffman-coded symbols that each expand to a sequence of values
+///
+/// note: offsets point into the bytes of the table file
+///
+/// @type
+#[derive(Clone, Default, Debug)]
+pub(crate) struct PairsData {
+    pub(crate) flags: u8,
+    block_size: usize,         // size of each block of compressed data in bytes
+    span: usize,               // number of values between sparse index entries
+    blocks: usize,             // number of blocks of compressed data
+    min_sym_len: usize,        // shortest code length, or the single value
+    lowest_sym: usize,         // offset of the lowest symbol of each code length
+    btree: usize,              // offset of the symbol pair tree
+    block_lengths: usize,      // offset of the number of values in each block
+    block_lengths_size: usize, // number of entries of the block lengths
+    sparse_index: usize,       // offset of the sparse index of the blocks
+    sparse_index_size: usize,  // number of entries of the sparse index
+    data: usize,               // offset of the first block
+    base64: Vec<u64>,          // lowest code of each code length, left-aligned
+    symlen: Vec<u16>,          // number of values, minus one, of each symbol
+    pub(crate) pieces: [u8; MAX_PIECES], // piece codes in the encoded order
+    pub(crate) group_idx: [u64; MAX_PIECES + 1], // multiplier of each group
+    pub(crate) group_len: [usize; MAX_PIECES + 1], // number of pieces of each group
+    map_idx: [usize; 4],       // offset of the DTZ map of each outcome
+}
+
+impl PairsData {
+    /// size returns the number of positions of the compressed data
+    ///
+    /// @return: number of indices of the compressed data
+    #[inline]
+    pub(crate) fn size(&self) -> u64 {
+        let groups = self
+            .group_len
+            .iter()
+            .position(|len| *len == 0)
+            .unwrap_or(MAX_PIECES);
+        self.group_idx[groups]
+    }
+}
+
+/// `Table` is a loaded table file
+///
+/// @type
+pub(crate) struct Table {
+    kind: TableKind,
+    bytes: Vec<u8>,
+    key: MaterialKey,  // material with the pieces of the table name for white
+    key2: MaterialKey, // material with the pieces of the table name for black
+    has_pawns: bool,
+    has_unique_pieces: bool,
+    pawn_count: [usize; 2], // pawns of the leading and the other side
+    pub(crate) pairs: Vec<Vec<PairsData>>, // compressed data per side to move and file
+    dtz_map: usize,         // offset of the DTZ maps
+}
+
+impl Table {
+    /// new parses a table file
+    ///
+    /// @param: kind - kind of the table
+    /// @param: key - material of the table, with the pieces of the table name
+    ///               given to white
+    /// @param: bytes - contents of the table file
+    /// @return: parsed table, or the tablebase error
+    pub(crate) fn new(
+        kind: TableKind,
+        key: MaterialKey,
+        bytes: Vec<u8>,
+    ) -> Result<Self, SyzygyError> {
+        if bytes.get(..4) != Some(&kind.magic()[..]) {
+            return Err(SyzygyError::Corrupt("invalid magic bytes"));
+        }
+        let piece_count = key.total();
+        if piece_count > MAX_PIECES {
+            return Err(SyzygyError::Corrupt("too many pieces"));
+        }
+
+        let white_pawns = key.count(Sides::White, Pieces::Pawn);
+        let black_pawns = key.count(Sides::Black, Pieces::Pawn);
+        // the side with fewer pawns leads, as it compresses better
+        let white_leads = black_pawns == 0 || (white_pawns > 0 && black_pawns >= white_pawns);
+        let mut table = Self {
+            kind,
+            bytes,
+            key,
+            key2: key.flipped(),
+            has_pawns: white_pawns + black_pawns > 0,
+            has_unique_pieces: [Sides::White, Sides::Black].into_iter().any(|side| {
+                [
+                    Pieces::Pawn,
+                    Pieces::Knight,
+                    Pieces::Bishop,
+                    Pieces::Rook,
+                    Pieces::Queen,
+                ]
+                .into_iter()
+                .any(|piece| key.count(side, piece) == 1)
+            }),
+            pawn_count: if white_leads {
+                [white_pawns, black_pawns]
+            } else {
+                [black_pawns, white_pawns]
+            },
+            pairs: Vec::new(),
+            dtz_map: 0,
+        };
+        table.parse(piece_count)?;
+        Ok(table)
+    }
+
+    /// parse reads the layout of the compressed data of the table
+    ///
+    /// @param: piece_count - number of pieces of the table
+    /// @return: Ok on success, or the tablebase error
+    /// @side-effects: initializes the compressed data of the table
+    fn parse(&mut self, piece_count: usize) -> Result<(), SyzygyError> {
+        let split = self.kind == TableKind::Wdl && self.key != self.key2;
+        let flags = read_u8(&self.bytes, 4)?;
+        if (flags & HEADER_SPLIT != 0) != split || (flags & HEADER_HAS_PAWNS != 0) != self.has_pawns
+        {
+            return Err(SyzygyError::Corrupt("header does not match the table name"));
+        }
+
+        let sides = if split { 2 } else { 1 };
+        let files = if self.has_pawns { 4 } else { 1 };
+        let both_pawns = self.has_pawns && self.pawn_count[1] > 0;
+        self.pairs = vec![vec![PairsData::default(); files]; sides];
+
+        let mut pos = 5;
+        for file in 0..files {
+            let first = read_u8(&self.bytes, pos)?;
+            let second = if both_pawns {
+                read_u8(&self.bytes, pos + 1)?
+            } else {
+                0xFF
+            };
+            let order = [
+                [usize::from(first & 0xF), usize::from(second & 0xF)],
+                [usize::from(first >> 4), usize::from(second >> 4)],
+            ];
+            pos += 1 + usize::from(both_pawns);
+
+            for k in 0..piece_count {
+                let byte = read_u8(&self.bytes, pos)?;
+                for (side, pairs) in self.pairs.iter_mut().enumerate() {
+                    pairs[file].pieces[k] = if side == 0 { byte & 0xF } else { byte >> 4 };
+                }
+                pos += 1;
+            }
+            for (side, order) in order.into_iter().enumerate().take(sides) {
+                self.set_groups(side, file, order, piece_count);
+            }
+        }
+        pos += pos & 1;
+
+        for file in 0..files {
+            for side in 0..sides {
+                pos = set_sizes(&mut self.pairs[side][file], &self.bytes, pos)?;
+            }
+        }
+
+        if self.kind == TableKind::Dtz {
+            pos = self.set_dtz_map(pos)?;
+        }
+
+        for file in 0..files {
+            for side in 0..sides {
+                let pairs = &mut self.pairs[side][file];
+                pairs.sparse_index = pos;
+                pos += pairs.sparse_index_size * 6;
+            }
+        }
+        for file in 0..files {
+            for side in 0..sides {
+                let pairs = &mut self.pairs[side][file];
+                pairs.block_lengths = pos;
+                pos += pairs.block_lengths_size * 2;
+            }
+        }
+        for file in 0..files {
+            for side in 0..sides {
+                // blocks are aligned to 64 bytes
+                pos = (pos + 0x3F) & !0x3F;
+                let pairs = &mut self.pairs[side][file];
+                pairs.data = pos;
+                pos += pairs.blocks * pairs.block_size;
+            }
+        }
+
+        if pos > self.bytes.len() {
+            return Err(SyzygyError::Corrupt("truncated table file"));
+        }
+        Ok(())
+    }
+
+    /// set_groups splits the pieces of a table into the groups they are
+    /// encoded in, and computes the multiplier of each group
+    ///
+    /// note: the pieces of a group can be placed in N(g) ways, and the groups
+    ///       are encoded as g1 * N(g2) * N(g3) + g2 * N(g3) + g3 in the order
+    ///       given by the table
+    ///
+    /// @param: side - side to move of the compressed data
+    /// @param: file - file of the leading pawn of the compressed data
+    /// @param: order - encoding order of the leading group and of the
+    ///                 remaining pawns
+    /// @param: piece_count - number of pieces of the table
+    /// @return: void
+    /// @side-effects: initializes the groups of the compressed data
+    fn set_groups(&mut self, side: usize, file: usize, order: [usize; 2], piece_count: usize) {
+        let encoding = &*ENCODING;
+        let pairs = &mut self.pairs[side][file];
+
+        // the leading group holds the kings, or the three unique pieces, or
+        // the leading pawns
+        let mut first_len: isize = if self.has_pawns {
+            0
+        } else if self.has_unique_pieces {
+            3
+        } else {
+            2
+        };
+        let mut n = 0;
+        pairs.group_len[n] = 1;
+        for i in 1..piece_count {
+            first_len -= 1;
+            if first_len > 0 || pairs.pieces[i] == pairs.pieces[i - 1] {
+                pairs.group_len[n] += 1;
+            } else {
+                n += 1;
+                pairs.group_len[n] = 1;
+            }
+        }
+        n += 1;
+        pairs.group_len[n] = 0;
+
+        let both_pawns = self.has_pawns && self.pawn_count[1] > 0;
+        let mut next = if both_pawns { 2 } else { 1 };
+        let mut free_squares =
+            64 - pairs.group_len[0] - if both_pawns { pairs.group_len[1] } else { 0 };
+        let mut idx = 1u64;
+        let mut k = 0;
+        while next < n || k == order[0] || k == order[1] {
+            if k == order[0] {
+                pairs.group_idx[0] = idx;
+                idx *= if self.has_pawns {
+                    encoding.lead_pawns_size[pairs.group_len[0]][file]
+                } else if self.has_unique_pieces {
+                    31332
+                } else {
+                    462
+                };
+            } else if k == order[1] {
+                pairs.group_idx[1] = idx;
+                idx *= encoding.binomial[pairs.group_len[1]][48 - pairs.group_len[0]];
+            } else {
+                pairs.group_idx[next] = idx;
+                idx *= encoding.binomial[pairs.group_len[next]][free_squares];
+                free_squares -= pairs.group_len[next];
+                next += 1;
+            }
+            k += 1;
+        }
+        pairs.group_idx[n] = idx;
+    }
+
+    /// set_dtz_map reads the offsets of the maps of the DTZ values
+    ///
+    /// @param: pos - offset of the maps
+    /// @return: offset after the maps, or the tablebase error
+    /// @side-effects: initializes the DTZ maps of the table
+    fn set_dtz_map(&mut self, mut pos: usize) -> Result<usize, SyzygyError> {
+        self.dtz_map = pos;
+        for pairs in &mut self.pairs[0] {
+            if pairs.flags & FLAG_MAPPED == 0 {
+                continue;
+            }
+            if pairs.flags & FLAG_WIDE != 0 {
+                pos += pos & 1;
+                for idx in &mut pairs.map_idx {
+                    *idx = (pos - self.dtz_map) / 2 + 1;
+                    pos += 2 * usize::from(read_u16_le(&self.bytes, pos)?) + 2;
+                }
+            } else {
+                for idx in &mut pairs.map_idx {
+                    *idx = pos - self.dtz_map + 1;
+                    pos += usize::from(read_u8(&self.bytes, pos)?) + 1;
+                }
+            }
+        }
+        Ok(pos + (pos & 1))
+    }
+
+    /// wdl looks up the outcome of a position in a WDL table
+    ///
+    /// @param: board - position to look up
+    /// @return: outcome of the position from the side to move's perspective
+    pub(crate) fn wdl(&self, board: &Board) -> Wdl {
+        wdl_from_value(self.lookup(board).map_or(0, |(value, _)| value))
+    }
+
+    /// dtz looks up the distance to the next capture or pawn move of a
+    /// position in a DTZ table
+    ///
+    /// @param: board - position to look up
+    /// @param: wdl - outcome of the position
+    /// @return: distance in plies, or None if the table only stores the other
+    ///          side to move
+    pub(crate) fn dtz(&self, board: &Board, wdl: Wdl) -> Option<i32> {
+        let (value, file) = self.lookup(board)?;
+        let pairs = &self.pairs[0][file];
+
+        let mut value = value;
+        if pairs.flags & FLAG_MAPPED != 0 {
+            let map = pairs.map_idx[match wdl {
+                Wdl::Win | Wdl::Draw => 0,
+                Wdl::Loss => 1,
+                Wdl::CursedWin => 2,
+                Wdl::BlessedLoss => 3,
+            }] + value;
+            value = if pairs.flags & FLAG_WIDE != 0 {
+                usize::from(le_u16(&self.bytes, self.dtz_map + 2 * map))
+            } else {
+                usize::from(byte(&self.bytes, self.dtz_map + map))
+            };
+        }
+
+        let value = value as i32;
+        let in_moves = match wdl {
+            Wdl::Win => pairs.flags & FLAG_WIN_PLIES == 0,
+            Wdl::Loss => pairs.flags & FLAG_LOSS_PLIES == 0,
+            Wdl::CursedWin | Wdl::BlessedLoss => true,
+            Wdl::Draw => false,
+        };
+        Some(if in_moves { value * 2 } else { value } + 1)
+    }
+
+    /// lookup decompresses the value stored for a position
+    ///
+    /// @param: board - position to look up
+    /// @return: stored value and file of its leading pawn, or None if the
+    ///          table only stores the other side to move
+    fn lookup(&self, board: &Board) -> Option<(usize, usize)> {
+        let (side, file, idx) = self.index(board)?;
+        Some((self.decompress(&self.pairs[side][file], idx), file))
+    }
+
+    /// index computes where the value of a position is stored
+    ///
+    /// @param: board - position to locate
+    /// @return: side to move and file of the compressed data holding the
+    ///          position, and index of the position in it, or None if the
+    ///          table only stores the other side to move
+    pub(crate) fn index(&self, board: &Board) -> Option<(usize, usize, u64)> {
+        let encoding = &*ENCODING;
+
+        // tables are stored with the side of the table name as white, and
+        // symmetric tables only with white to move, so other positions are
+        // looked up with the colors swapped and the board flipped
+        let symmetric_black_to_move = self.key == self.key2 && board.turn == Sides::Black;
+        let black_stronger = board.key != self.key;
+        let flip = symmetric_black_to_move || black_stronger;
+        let (flip_color, flip_squares) = if flip { (8, 56) } else { (0, 0) };
+        let stm = usize::from(flip) ^ board.turn.idx();
+
+        let mut squares = [0; MAX_PIECES];
+        let mut pieces = [0; MAX_PIECES];
+        let mut size = 0;
+        let mut lead_pawns = 0;
+        let mut lead_pawn = None;
+        let mut tb_file = 0;
+
+        // tables with pawns are split by the file of the leading pawn, which
+        // is the pawn closest to the edge of the board
+        if self.has_pawns {
+            let pawn = self.pairs[0][0].pieces[0] ^ flip_color;
+            for &(piece, square) in board.pieces() {
+                if piece == pawn {
+                    squares[size] = square ^ flip_squares;
+                    size += 1;
+                }
+            }
+            lead_pawns = size;
+            lead_pawn = Some(pawn);
+
+            let lead = (1..size).fold(0, |lead, i| {
+                if encoding.map_pawns[squares[i]] > encoding.map_pawns[squares[lead]] {
+                    i
+                } else {
+                    lead
+                }
+            });
+            squares.swap(0, lead);
+            tb_file = file(squares[0]).min(7 - file(squares[0]));
+        }
+
+        if self.kind == TableKind::Dtz {
+            let stored = usize::from(self.pairs[0][tb_file].flags & FLAG_STM);
+            if stored != stm && (self.key != self.key2 || self.has_pawns) {
+                return None;
+            }
+        }
+
+        for &(piece, square) in board.pieces() {
+            if Some(piece) != lead_pawn {
+                squares[size] = square ^ flip_squares;
+                pieces[size] = piece ^ flip_color;
+                size += 1;
+            }
+        }
+
+        let side = stm % self.pairs.len();
+        let pairs = &self.pairs[side][tb_file];
+
+        // order the pieces as they are encoded in the table
+        for i in lead_pawns..size.saturating_sub(1) {
+            for j in i + 1..size {
+                if pairs.pieces[i] == pieces[j] {
+                    pieces.swap(i, j);
+                    squares.swap(i, j);
+                    break;
+                }
+            }
+        }
+
+        // mirror the board so that the leading piece is on files a-d
+        if file(squares[0]) > 3 {
+            for square in &mut squares[..size] {
+                *square ^= 7;
+            }
+        }
+
+        let mut idx;
+        if self.has_pawns {
+            idx = encoding.lead_pawn_idx[lead_pawns][squares[0]];
+            squares[1..lead_pawns].sort_by_key(|square| encoding.map_pawns[*square]);
+            for (i, square) in squares.iter().enumerate().take(lead_pawns).skip(1) {
+                idx += encoding.binomial[i][encoding.map_pawns[*square]];
+            }
+        } else {
+            // mirror the board so that the leading piece is on ranks 1-4, and
+            // the first leading piece off the a1-h8 diagonal is below it
+            if rank(squares[0]) > 3 {
+                for square in &mut squares[..size] {
+                    *square ^= 56;
+                }
+            }
+            for i in 0..pairs.group_len[0] {
+                let off = off_diagonal(squares[i]);
+                if off == 0 {
+                    continue;
+                }
+                if off > 0 {
+                    for square in &mut squares[i..size] {
+                        *square = ((*square >> 3) | (*square << 3)) & 63;
+                    }
+                }
+                break;
+            }
+
+            idx = if self.has_unique_pieces {
+                self.unique_pieces_index(&squares)
+            } else {
+                encoding.map_kk[encoding.map_a1d1d4[squares[0]] as usize][squares[1]]
+            };
+        }
+
+        // encode the remaining groups, mapping each square down by the number
+        // of squares of earlier groups below it
+        idx *= pairs.group_idx[0];
+        let mut start = pairs.group_len[0];
+        let mut remaining_pawns = self.has_pawns && self.pawn_count[1] > 0;
+        let mut next = 1;
+        while pairs.group_len[next] != 0 {
+            let len = pairs.group_len[next];
+            squares[start..start + len].sort_unstable();
+
+            let mut n = 0;
+            for i in 0..len {
+                let square = squares[start + i];
+                let adjust = squares[..start]
+                    .iter()
+                    .filter(|other| square > **other)
+                    .count();
+                n +=
+                    encoding.binomial[i + 1][square - adjust - if remaining_pawns { 8 } else { 0 }];
+            }
+
+            remaining_pawns = false;
+            idx += n * pairs.group_idx[next];
+            start += len;
+            next += 1;
+        }
+
+        Some((side, tb_file, idx))
+    }
+
+    /// unique_pieces_index encodes the leading group of three unique pieces
+    ///
+    /// @param: squares - squares of the pieces, with the leading piece in the
+    ///                   a1-d1-d4 triangle
+    /// @return: index of the leading group
+    fn unique_pieces_index(&self, squares: &[usize; MAX_PIECES]) -> u64 {
+        let encoding = &*ENCODING;
+        let [first, second, third, ..] = *squares;
+        let adjust1 = usize::from(second > first);
+        let adjust2 = usize::from(third > first) + usize::from(third > second);
+
+        if off_diagonal(first) != 0 {
+            (encoding.map_a1d1d4[first] * 63 + (second - adjust1) as u64) * 62
+                + (third - adjust2) as u64
+        } else if off_diagonal(second) != 0 {
+            (6 * 63 + rank(first) as u64 * 28 + encoding.map_b1h1h7[second]) * 62
+                + (third - adjust2) as u64
+        } else if off_diagonal(third) != 0 {
+            6 * 63 * 62
+                + 4 * 28 * 62
+                + rank(first) as u64 * 7 * 28
+                + (rank(second) - adjust1) as u64 * 28
+                + encoding.map_b1h1h7[third]
+        } else {
+            6 * 63 * 62
+                + 4 * 28 * 62
+                + 4 * 7 * 28
+                + rank(first) as u64 * 7 * 6
+                + (rank(second) - adjust1) as u64 * 6
+                + (rank(third) - adjust2) as u64
+        }
+    }
+
+    /// decompress returns the value at an index of compressed data
+    ///
+    /// @param: pairs - compressed data to read
+    /// @param: idx - index of the value
+    /// @return: value at the index
+    fn decompress(&self, pairs: &PairsData, idx: u64) -> usize {
+        if pairs.flags & FLAG_SINGLE_VALUE != 0 {
+            return pairs.min_sym_len;
+        }
+        let bytes = &self.bytes;
+        let block_length = |block: usize| i64::from(le_u16(bytes, pairs.block_lengths + 2 * block));
+
+        // the sparse index locates the value in the middle of every span, and
+        // the blocks around it are walked until the one holding idx is found
+        let span = pairs.span as u64;
+        let entry = pairs.sparse_index + 6 * (idx / span) as usize;
+        let mut block = le_u32(bytes, entry) as usize;
+        let mut offset = i64::from(le_u16(bytes, entry + 4));
+        offset += (idx % span) as i64 - (span / 2) as i64;
+
+        while offset < 0 && block > 0 {
+            block -= 1;
+            offset += block_length(block) + 1;
+        }
+        while offset > block_length(block) && block < pairs.blocks {
+            offset -= block_length(block) + 1;
+            block += 1;
+        }
+
+        // walk the symbols of the block until the one holding the value
+        let mut ptr = pairs.data + block * pairs.block_size;
+        let mut buffer = be_u64(bytes, ptr);
+        ptr += 8;
+        let mut buffer_size = 64;
+        let mut sym;
+        loop {
+            let mut len = 0;
+            while len + 1 < pairs.base64.len() && buffer < pairs.base64[len] {
+                len += 1;
+            }
+
+            sym = (buffer - pairs.base64[len])
+                .checked_shr((64 - len - pairs.min_sym_len) as u32)
+                .unwrap_or(0) as usize;
+            sym += usize::from(le_u16(bytes, pairs.lowest_sym + 2 * len));
+            let values = i64::from(*pairs.symlen.get(sym).unwrap_or(&0)) + 1;
+            if offset < values {
+                break;
+            }
+
+            offset -= values;
+            len += pairs.min_sym_len;
+            buffer = buffer.checked_shl(len as u32).unwrap_or(0);
+            buffer_size -= len as i64;
+            if buffer_size <= 32 {
+                buffer_size += 32;
+                buffer |= u64::from(be_u32(bytes, ptr)) << (64 - buffer_size);
+                ptr += 4;
+            }
+        }
+
+        // expand the symbol until the value is reached
+        while pairs.symlen.get(sym).is_some_and(|len| *len != 0) {
+            let (left, right) = symbol_pair(bytes, pairs.btree, sym);
+            let values = i64::from(pairs.symlen[left]) + 1;
+            if offset < values {
+                sym = left;
+            } else {
+                offset -= values;
+                sym = right;
+            }
+        }
+        symbol_pair(bytes, pairs.btree, sym).0
+    }
+}
+
+/// wdl_from_value converts the value stored in a WDL table
+///
+/// @param: value - stored value, from 0 for a loss to 4 for a win
+/// @return: outcome represented by the value
+#[inline]
+pub(crate) const fn wdl_from_value(value: usize) -> Wdl {
+    match value {
+        0 => Wdl::Loss,
+        1 => Wdl::BlessedLoss,
+        3 => Wdl::CursedWin,
+        4 => Wdl::Win,
+        _ => Wdl::Draw,
+    }
+}
+
+/// set_sizes reads the header of compressed data
+///
+/// @param: pairs - compressed data to initialize
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the header
+/// @return: offset after the header, or the tablebase error
+/// @side-effects: initializes the compressed data
+fn set_sizes(pairs: &mut PairsData, bytes: &[u8], mut pos: usize) -> Result<usize, SyzygyError> {
+    pairs.flags = read_u8(bytes, pos)?;
+    pos += 1;
+    if pairs.flags & FLAG_SINGLE_VALUE != 0 {
+        // the single value is stored in place of the code lengths
+        pairs.min_sym_len = usize::from(read_u8(bytes, pos)?);
+        return Ok(pos + 1);
+    }
+
+    let size = pairs.size();
+
+    pairs.block_size = 1 << read_u8(bytes, pos)?;
+    pairs.span = 1 << read_u8(bytes, pos + 1)?;
+    pairs.sparse_index_size = size.div_ceil(pairs.span as u64) as usize;
+    let padding = usize::from(read_u8(bytes, pos + 2)?);
+    pairs.blocks = read_u32_le(bytes, pos + 3)? as usize;
+    pairs.block_lengths_size = pairs.blocks + padding;
+    let max_sym_len = usize::from(read_u8(bytes, pos + 7)?);
+    pairs.min_sym_len = usize::from(read_u8(bytes, pos + 8)?);
+    if pairs.min_sym_len == 0 || pairs.min_sym_len > max_sym_len || max_sym_len > 32 {
+        return Err(SyzygyError::Corrupt("invalid symbol lengths"));
+    }
+    pos += 9;
+
+    // the canonical Huffman code assigns longer codes lower values, so the
+    // lowest code of each length, left-aligned to 64 bits, decreases with
+    // the length
+    pairs.lowest_sym = pos;
+    let lengths = max_sym_len - pairs.min_sym_len + 1;
+    pairs.base64 = vec![0; lengths];
+    for i in (0..lengths - 1).rev() {
+        let lowest = u64::from(read_u16_le(bytes, pos + 2 * i)?);
+        let next_lowest = u64::from(read_u16_le(bytes, pos + 2 * i + 2)?);
+        pairs.base64[i] = (pairs.base64[i + 1] + lowest).wrapping_sub(next_lowest) / 2;
+    }
+    for (i, base) in pairs.base64.iter_mut().enumerate() {
+        *base <<= 64 - i - pairs.min_sym_len;
+    }
+    pos += lengths * 2;
+
+    // symbols are recursively paired, with each symbol expanding to its pair
+    // of symbols or a single value
+    let symbols = usize::from(read_u16_le(bytes, pos)?);
+    pos += 2;
+    pairs.btree = pos;
+    if pos + 3 * symbols > bytes.len() {
+        return Err(SyzygyError::Corrupt("truncated symbol tree"));
+    }
+    pairs.symlen = vec![0; symbols];
+    let mut visited = vec![false; symbols];
+    for sym in 0..symbols {
+        if !visited[sym] {
+            pairs.symlen[sym] = symbol_length(bytes, pairs, &mut visited, sym)?;
+        }
+    }
+
+    Ok(pos + symbols * 3 + (symbols & 1))
+}
+
+/// symbol_length computes the number of values, minus one, a symbol expands to
+///
+/// @param: bytes - contents of the table file
+/// @param: pairs - compressed data holding the symbol
+/// @param: visited - symbols whose length is already computed
+/// @param: sym - symbol to compute the length of
+/// @return: number of values of the symbol minus one, or the tablebase error
+/// @side-effects: computes the lengths of the symbols the symbol expands to
+fn symbol_length(
+    bytes: &[u8],
+    pairs: &mut PairsData,
+    visited: &mut [bool],
+    sym: usize,
+) -> Result<u16, SyzygyError> {
+    visited[sym] = true;
+    let (left, right) = symbol_pair(bytes, pairs.btree, sym);
+    if right == 0xFFF {
+        return Ok(0);
+    }
+    if left >= visited.len() || right >= visited.len() {
+        return Err(SyzygyError::Corrupt("invalid symbol tree"));
+    }
+
+    for child in [left, right] {
+        if !visited[child] {
+            pairs.symlen[child] = symbol_length(bytes, pairs, visited, child)?;
+        }
+    }
+    Ok(pairs.symlen[left] + pairs.symlen[right] + 1)
+}
+
+/// symbol_pair returns the pair of symbols a symbol expands to, where the
+/// left symbol is the value of symbols that expand to a single value
+///
+/// @param: bytes - contents of the table file
+/// @param: btree - offset of the symbol pair tree
+/// @param: sym - symbol to expand
+/// @return: left and right symbols, with a right symbol of 0xFFF for values
+#[inline]
+fn symbol_pair(bytes: &[u8], btree: usize, sym: usize) -> (usize, usize) {
+    let pos = btree + 3 * sym;
+    let (low, middle, high) = (byte(bytes, pos), byte(bytes, pos + 1), byte(bytes, pos + 2));
+    (
+        usize::from(middle & 0xF) << 8 | usize::from(low),
+        usize::from(high) << 4 | usize::from(middle >> 4),
+    )
+}
+
+/// read_u8 reads a byte of a table file header
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the byte
+/// @return: byte at the offset, or the tablebase error if the file is too short
+#[inline]
+fn read_u8(bytes: &[u8], pos: usize) -> Result<u8, SyzygyError> {
+    bytes
+        .get(pos)
+        .copied()
+        .ok_or(SyzygyError::Corrupt("truncated table file"))
+}
+
+/// read_u16_le reads a little-endian u16 of a table file header
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset, or the tablebase error if the file is too short
+#[inline]
+fn read_u16_le(bytes: &[u8], pos: usize) -> Result<u16, SyzygyError> {
+    Ok(u16::from_le_bytes([
+        read_u8(bytes, pos)?,
+        read_u8(bytes, pos + 1)?,
+    ]))
+}
+
+/// read_u32_le reads a little-endian u32 of a table file header
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset, or the tablebase error if the file is too short
+#[inline]
+fn read_u32_le(bytes: &[u8], pos: usize) -> Result<u32, SyzygyError> {
+    Ok(u32::from(read_u16_le(bytes, pos)?) | u32::from(read_u16_le(bytes, pos + 2)?) << 16)
+}
+
+/// byte reads a byte of compressed data
+///
+/// note: compressed data is read past the end of its blocks, so bytes past
+///       the end of the file read as zero
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the byte
+/// @return: byte at the offset, or zero past the end of the file
+#[inline]
+fn byte(bytes: &[u8], pos: usize) -> u8 {
+    bytes.get(pos).copied().unwrap_or_default()
+}
+
+/// le_u16 reads a little-endian u16 of compressed data
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset
+#[inline]
+fn le_u16(bytes: &[u8], pos: usize) -> u16 {
+    u16::from_le_bytes([byte(bytes, pos), byte(bytes, pos + 1)])
+}
+
+/// le_u32 reads a little-endian u32 of compressed data
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset
+#[inline]
+fn le_u32(bytes: &[u8], pos: usize) -> u32 {
+    u32::from(le_u16(bytes, pos)) | u32::from(le_u16(bytes, pos + 2)) << 16
+}
+
+/// be_u32 reads a big-endian u32 of compressed data
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset
+#[inline]
+fn be_u32(bytes: &[u8], pos: usize) -> u32 {
+    u32::from_be_bytes([
+        byte(bytes, pos),
+        byte(bytes, pos + 1),
+        byte(bytes, pos + 2),
+        byte(bytes, pos + 3),
+    ])
+}
+
+/// be_u64 reads a big-endian u64 of compressed data
+///
+/// @param: bytes - contents of the table file
+/// @param: pos - offset of the value
+/// @return: value at the offset
+#[inline]
+fn be_u64(bytes: &[u8], pos: usize) -> u64 {
+    u64::from(be_u32(bytes, pos)) << 32 | u64::from(be_u32(bytes, pos + 4))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_files_that_do_not_match_the_table() {
+        let key = MaterialKey::from_code("KQvK").unwrap();
+        let header = |magic: [u8; 4], flags: u8| {
+            let mut bytes = magic.to_vec();
+            bytes.push(flags);
+            bytes
+        };
+
+        assert!(matches!(
+            Table::new(
+                TableKind::Wdl,
+                key,
+                header(TableKind::Dtz.magic(), HEADER_SPLIT)
+            ),
+            Err(SyzygyError::Corrupt("invalid magic bytes"))
+        ));
+        assert!(matches!(
+            Table::new(
+                TableKind::Wdl,
+                key,
+                header(TableKind::Wdl.magic(), HEADER_HAS_PAWNS)
+            ),
+            Err(SyzygyError::Corrupt("header does not match the table name"))
+        ));
+        assert!(matches!(
+            Table::new(
+                TableKind::Wdl,
+                key,
+                header(TableKind::Wdl.magic(), HEADER_SPLIT)
+            ),
+            Err(SyzygyError::Corrupt("truncated table file"))
+        ));
+    }
+}
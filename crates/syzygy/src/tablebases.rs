@@ -0,0 +1,588 @@
+use crate::table::{Board, Table, TableKind};
+use crate::{MaterialKey, SyzygyError};
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
+use chess_kit_primitives::{Castling, Move, MoveList, MoveType, Pieces};
+use chess_kit_tablebase::{EndgameTablebase, Wdl};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// PATH_SEPARATOR separates the directories of a tablebase path
+#[cfg(windows)]
+const PATH_SEPARATOR: char = ';';
+#[cfg(not(windows))]
+const PATH_SEPARATOR: char = ':';
+
+/// MAX_DTZ bounds the ranks of root moves, above any distance to zeroing
+const MAX_DTZ: i32 = 1 << 18;
+
+/// `Tablebases` is a set of Syzygy tablebases found in one or more directories
+///
+/// Table files are only read the first time a position of their material is
+/// probed. Positions are probed from the side to move's perspective and must
+/// not have castling rights, which the tables do not account for
+///
+/// @type
+#[derive(Default)]
+pub struct Tablebases {
+    entries: Vec<Entry>,
+    index: HashMap<MaterialKey, usize>,
+    max_pieces: usize,
+}
+
+/// `Entry` is the WDL and DTZ tables of one material
+///
+/// @type
+struct Entry {
+    key: MaterialKey,
+    path: PathBuf,                // path of the WDL table without its extension
+    wdl: OnceLock<Option<Table>>, // WDL table, once loaded
+    dtz: OnceLock<Option<Table>>, // DTZ table, once loaded
+}
+
+impl Entry {
+    /// table returns a table of the entry, loading it on first use
+    ///
+    /// @param: kind - kind of the table
+    /// @return: loaded table, or None if it cannot be read
+    fn table(&self, kind: TableKind) -> Option<&Table> {
+        let table = match kind {
+            TableKind::Wdl => &self.wdl,
+            TableKind::Dtz => &self.dtz,
+        };
+        table
+            .get_or_init(|| {
+                let bytes = std::fs::read(self.path.with_extension(kind.extension())).ok()?;
+                Table::new(kind, self.key, bytes).ok()
+            })
+            .as_ref()
+    }
+}
+
+/// `RankedMove` is a root move ranked by the tablebases
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RankedMove {
+    pub mv: Move,
+    /// plies to the next capture or pawn move, signed by outcome
+    pub dtz: i32,
+    /// higher ranks are better, and wins are ranked equally
+    pub rank: i32,
+}
+
+impl Tablebases {
+    /// open finds the tables in the directories of a tablebase path
+    ///
+    /// @param: paths - directories separated by `:`, or `;` on windows
+    /// @return: tablebases of the directories, or the tablebase error if a
+    ///          directory cannot be read
+    pub fn open(paths: &str) -> Result<Self, SyzygyError> {
+        let mut tablebases = Self::default();
+        for directory in paths.split(PATH_SEPARATOR).filter(|path| !path.is_empty()) {
+            tablebases.add_directory(Path::new(directory))?;
+        }
+        Ok(tablebases)
+    }
+
+    /// add_directory registers the WDL tables of a directory
+    ///
+    /// note: tables already found in an earlier directory are kept
+    ///
+    /// @param: directory - directory to search for tables
+    /// @return: Ok on success, or the tablebase error
+    /// @side-effects: registers the tables of the directory
+    fn add_directory(&mut self, directory: &Path) -> Result<(), SyzygyError> {
+        let mut paths = std::fs::read_dir(directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        paths.sort();
+
+        for path in paths {
+            if path.extension().and_then(|extension| extension.to_str())
+                != Some(TableKind::Wdl.extension())
+            {
+                continue;
+            }
+            let Some(key) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(MaterialKey::from_code)
+            else {
+                continue;
+            };
+            if self.index.contains_key(&key) {
+                continue;
+            }
+
+            self.index.insert(key, self.entries.len());
+            self.index.insert(key.flipped(), self.entries.len());
+            self.max_pieces = self.max_pieces.max(key.total());
+            self.entries.push(Entry {
+                key,
+                path: path.with_extension(""),
+                wdl: OnceLock::new(),
+                dtz: OnceLock::new(),
+            });
+        }
+        Ok(())
+    }
+
+    /// len returns the number of WDL tables found
+    ///
+    /// @return: number of tables
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// is_empty checks whether no tables were found
+    ///
+    /// @return: true if there are no tables
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// max_pieces returns the largest number of pieces, kings included, of
+    /// the tables found
+    ///
+    /// @return: largest number of pieces of a table
+    #[inline]
+    pub fn max_pieces(&self) -> usize {
+        self.max_pieces
+    }
+
+    /// probe_dtz probes the distance to the next capture or pawn move of a
+    /// position with perfect play
+    ///
+    /// note: wins and losses that are drawn by the fifty-move rule are offset
+    ///       by 100 plies
+    ///
+    /// @param: position - position to probe, which must be covered
+    /// @param: move_generator - move generator used to search moves
+    /// @return: distance in plies, positive for wins, negative for losses and
+    ///          zero for draws, or None if a required table is missing
+    pub fn probe_dtz<P, MG>(&self, position: &mut P, move_generator: &MG) -> Option<i32>
+    where
+        P: PositionView + PositionAttacks + PositionMoves,
+        MG: MoveGenerator,
+    {
+        let (wdl, zeroing) = self.search(position, move_generator, true)?;
+        if wdl == Wdl::Draw {
+            return Some(0);
+        }
+        // the tables do not store a meaningful value when the best move is a
+        // capture or a pawn move
+        if zeroing {
+            return Some(dtz_before_zeroing(wdl));
+        }
+
+        let key = MaterialKey::from_position(position);
+        let table = self.table(key, TableKind::Dtz)?;
+        if let Some(dtz) = table.dtz(&Board::from_position(position, key), wdl) {
+            let cursed = matches!(wdl, Wdl::CursedWin | Wdl::BlessedLoss);
+            return Some((dtz + if cursed { 100 } else { 0 }) * wdl.signum());
+        }
+
+        // the table stores the other side to move, so the distance is found
+        // with a search of the moves of the position
+        let mut moves = MoveList::new();
+        move_generator.generate_legal_moves(position, &mut moves);
+        let mut best = i32::MAX;
+        for &mv in moves.as_slice() {
+            let zeroing = is_zeroing(position, mv);
+            let _ = position.play_unchecked(mv);
+            // zeroing moves are counted by dtz_before_zeroing, which needs the
+            // outcome of the move
+            let dtz = if zeroing {
+                self.search(position, move_generator, false)
+                    .map(|(wdl, _)| -dtz_before_zeroing(wdl))
+            } else {
+                self.probe_dtz(position, move_generator).map(|dtz| -dtz)
+            };
+            let mates = dtz == Some(1) && is_checkmate(position, move_generator);
+            position.undo(mv);
+
+            let mut dtz = dtz?;
+            if mates {
+                best = 1;
+            }
+            if !zeroing {
+                dtz += dtz.signum();
+            }
+            if dtz < best && dtz.signum() == wdl.signum() {
+                best = dtz;
+            }
+        }
+
+        // a position without moves is checkmate
+        Some(if best == i32::MAX { -1 } else { best })
+    }
+
+    /// rank_root_moves ranks the legal moves of a position by their distance
+    /// to the next capture or pawn move
+    ///
+    /// note: wins within the fifty-move rule are ranked equally, and losses
+    ///       are ranked equally unless a fifty-move draw is in sight
+    ///
+    /// @param: position - position to rank the moves of, which must be covered
+    /// @param: move_generator - move generator used to search moves
+    /// @return: ranked legal moves, or None if a required table is missing
+    pub fn rank_root_moves<P, MG>(
+        &self,
+        position: &mut P,
+        move_generator: &MG,
+    ) -> Option<Vec<RankedMove>>
+    where
+        P: PositionView + PositionAttacks + PositionMoves,
+        MG: MoveGenerator,
+    {
+        let halfmoves = position.halfmoves() as i32;
+        let mut moves = MoveList::new();
+        move_generator.generate_legal_moves(position, &mut moves);
+
+        let mut ranked = Vec::with_capacity(moves.len());
+        for &mv in moves.as_slice() {
+            let _ = position.play_unchecked(mv);
+            let dtz = if position.halfmoves() == 0 {
+                self.probe_wdl(position, move_generator)
+                    .map(|wdl| dtz_before_zeroing(-wdl))
+            } else {
+                self.probe_dtz(position, move_generator)
+                    .map(|dtz| -dtz + (-dtz).signum())
+            };
+            let mates = dtz == Some(2) && is_checkmate(position, move_generator);
+            position.undo(mv);
+
+            let dtz = if mates { 1 } else { dtz? };
+            let rank = if dtz > 0 {
+                if dtz + halfmoves <= 99 {
+                    MAX_DTZ
+                } else {
+                    MAX_DTZ - (dtz + halfmoves)
+                }
+            } else if dtz < 0 {
+                if -dtz * 2 + halfmoves < 100 {
+                    -MAX_DTZ
+                } else {
+                    -MAX_DTZ + (-dtz + halfmoves)
+                }
+            } else {
+                0
+            };
+            ranked.push(RankedMove { mv, dtz, rank });
+        }
+        Some(ranked)
+    }
+
+    /// search probes the outcome of a position after searching its captures,
+    /// since the tables may store any value for positions where a capture is
+    /// the best move
+    ///
+    /// @param: position - position to probe
+    /// @param: move_generator - move generator used to search captures
+    /// @param: pawn_moves - whether pawn moves are searched as well
+    /// @return: outcome of the position and whether a capture or pawn move is
+    ///          the best move, or None if a required table is missing
+    fn search<P, MG>(
+        &self,
+        position: &mut P,
+        move_generator: &MG,
+        pawn_moves: bool,
+    ) -> Option<(Wdl, bool)>
+    where
+        P: PositionView + PositionAttacks + PositionMoves,
+        MG: MoveGenerator,
+    {
+        let mut moves = MoveList::new();
+        move_generator.generate_legal_moves(position, &mut moves);
+
+        let mut best = Wdl::Loss;
+        let mut searched = 0;
+        for &mv in moves.as_slice() {
+            let pawn_move = pawn_moves && position.piece_at(mv.from()) == Pieces::Pawn;
+            if !is_capture(position, mv) && !pawn_move {
+                continue;
+            }
+
+            searched += 1;
+            let _ = position.play_unchecked(mv);
+            let result = self.search(position, move_generator, false);
+            position.undo(mv);
+
+            let wdl = -result?.0;
+            if wdl > best {
+                best = wdl;
+                if wdl >= Wdl::Win {
+                    return Some((wdl, true));
+                }
+            }
+        }
+
+        // when every move was searched, the stored value is not needed and may
+        // be wrong, e.g. for positions with an en passant capture
+        let exhausted = searched > 0 && searched == moves.len();
+        let wdl = if exhausted {
+            best
+        } else {
+            self.probe_wdl_table(position)?
+        };
+
+        if best >= wdl {
+            Some((best, best > Wdl::Draw || exhausted))
+        } else {
+            Some((wdl, false))
+        }
+    }
+
+    /// probe_wdl_table looks up the outcome of a position in its WDL table
+    ///
+    /// @param: position - position to look up
+    /// @return: stored outcome, or None if the table is missing
+    fn probe_wdl_table<P: PositionView>(&self, position: &P) -> Option<Wdl> {
+        let key = MaterialKey::from_position(position);
+        if key.total() == 2 {
+            return Some(Wdl::Draw);
+        }
+        let table = self.table(key, TableKind::Wdl)?;
+        Some(table.wdl(&Board::from_position(position, key)))
+    }
+
+    /// table returns the table of a material
+    ///
+    /// @param: key - material of the table
+    /// @param: kind - kind of the table
+    /// @return: loaded table, or None if it is missing
+    #[inline]
+    fn table(&self, key: MaterialKey, kind: TableKind) -> Option<&Table> {
+        self.entries[*self.index.get(&key)?].table(kind)
+    }
+}
+
+impl EndgameTablebase for Tablebases {
+    /// covers checks whether a position may be probed, which requires few
+    /// enough pieces and no castling rights
+    ///
+    /// @impl: EndgameTablebase::covers
+    #[inline]
+    fn covers<PositionT: PositionView>(&self, position: &PositionT) -> bool {
+        position.castling() == Castling::NONE
+            && position.total_occupancy().count_ones() as usize <= self.max_pieces
+    }
+
+    /// probe_wdl probes the outcome of a position after searching the
+    /// captures its table leaves out, returning None if a required table is
+    /// missing
+    ///
+    /// @impl: EndgameTablebase::probe_wdl
+    fn probe_wdl<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Wdl>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        self.search(position, move_generator, false)
+            .map(|(wdl, _)| wdl)
+    }
+
+    /// best_root_moves returns the moves ranked best by `rank_root_moves`,
+    /// returning None if a required table is missing
+    ///
+    /// @impl: EndgameTablebase::best_root_moves
+    fn best_root_moves<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Vec<Move>>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        let ranked = self.rank_root_moves(position, move_generator)?;
+        let best = ranked.iter().map(|ranked| ranked.rank).max()?;
+        Some(
+            ranked
+                .into_iter()
+                .filter(|ranked| ranked.rank == best)
+                .map(|ranked| ranked.mv)
+                .collect(),
+        )
+    }
+}
+
+/// dtz_before_zeroing returns the distance of a position whose best move is a
+/// capture or pawn move with the given outcome
+///
+/// @param: wdl - outcome of the position
+/// @return: distance in plies, signed by the outcome
+#[inline]
+const fn dtz_before_zeroing(wdl: Wdl) -> i32 {
+    match wdl {
+        Wdl::Win => 1,
+        Wdl::CursedWin => 101,
+        Wdl::Draw => 0,
+        Wdl::BlessedLoss => -101,
+        Wdl::Loss => -1,
+    }
+}
+
+/// is_capture checks whether a move captures a piece
+///
+/// @param: position - position the move is played in
+/// @param: mv - move to check
+/// @return: true if the move is a capture
+#[inline]
+fn is_capture<P: PositionView>(position: &P, mv: Move) -> bool {
+    mv.type_of() == MoveType::EnPassant || position.piece_at(mv.to()) != Pieces::None
+}
+
+/// is_zeroing checks whether a move resets the fifty-move counter
+///
+/// @param: position - position the move is played in
+/// @param: mv - move to check
+/// @return: true if the move is a capture or a pawn move
+#[inline]
+fn is_zeroing<P: PositionView>(position: &P, mv: Move) -> bool {
+    is_capture(position, mv) || position.piece_at(mv.from()) == Pieces::Pawn
+}
+
+/// is_checkmate checks whether the side to move is checkmated
+///
+/// @param: position - position to check
+/// @param: move_generator - move generator used to find legal moves
+/// @return: true if the side to move is in check without legal moves
+fn is_checkmate<P, MG>(position: &P, move_generator: &MG) -> bool
+where
+    P: PositionView + PositionAttacks + PositionMoves,
+    MG: MoveGenerator,
+{
+    let mut moves = MoveList::new();
+    move_generator.generate_legal_moves(position, &mut moves);
+    position.checkers().not_empty() && moves.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{self, DIRECTORY, KPK_DTZ, TABLES, kpk_wdl, major_piece_wdl, placements};
+    use crate::table::wdl_from_value;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_movegen::DefaultMoveGenerator;
+    use chess_kit_primitives::Sides;
+
+    fn tablebases() -> Tablebases {
+        Tablebases::open(DIRECTORY.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn finds_the_tables_of_a_directory() {
+        let tablebases = tablebases();
+
+        assert_eq!(tablebases.len(), TABLES.len());
+        assert_eq!(tablebases.max_pieces(), 3);
+        assert!(Tablebases::open("").unwrap().is_empty());
+        assert!(Tablebases::open("/nonexistent/syzygy").is_err());
+    }
+
+    #[test]
+    fn probes_the_outcome_of_positions_of_either_color() {
+        let tablebases = tablebases();
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+        for code in TABLES {
+            for (i, (turn, pieces)) in placements(code).into_iter().enumerate() {
+                if !i.is_multiple_of(101) {
+                    continue;
+                }
+                let expected = match code {
+                    "KPvK" => kpk_wdl(turn, &pieces),
+                    "KQvK" | "KRvK" => major_piece_wdl(turn, &pieces),
+                    _ => Some(2),
+                }
+                .map(wdl_from_value)
+                .unwrap();
+
+                for flipped in [false, true] {
+                    let mut position = fixtures::position(turn, &pieces, flipped);
+                    let wdl = tablebases.probe_wdl(&mut position, &move_generator);
+                    assert_eq!(wdl, Some(expected), "{code} {turn:?} {pieces:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn probes_the_distance_to_zeroing_of_king_and_pawn_endgames() {
+        let tablebases = tablebases();
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+        for (i, (turn, pieces)) in placements("KPvK").into_iter().enumerate() {
+            if !i.is_multiple_of(37) {
+                continue;
+            }
+            let expected = KPK_DTZ.get(turn, &pieces).map_or(0, |dtz| match turn {
+                Sides::White => dtz,
+                Sides::Black => -dtz,
+            });
+            let mut position = fixtures::position(turn, &pieces, i.is_multiple_of(2));
+            let dtz = tablebases.probe_dtz(&mut position, &move_generator);
+            assert_eq!(dtz, Some(expected), "{turn:?} {pieces:?}");
+        }
+    }
+
+    #[test]
+    fn keeps_root_moves_that_win_fastest() {
+        let tablebases = tablebases();
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+        for (i, (turn, pieces)) in placements("KPvK").into_iter().enumerate() {
+            let Some(dtz) = KPK_DTZ.get(turn, &pieces).filter(|_| turn == Sides::White) else {
+                continue;
+            };
+            if !i.is_multiple_of(61) {
+                continue;
+            }
+            let mut position = fixtures::position(turn, &pieces, false);
+            let ranked = tablebases
+                .rank_root_moves(&mut position, &move_generator)
+                .unwrap();
+            let fastest = ranked
+                .iter()
+                .map(|ranked| ranked.dtz)
+                .filter(|dtz| *dtz > 0)
+                .min();
+            assert_eq!(fastest, Some(dtz), "{pieces:?}");
+
+            for mv in tablebases
+                .best_root_moves(&mut position, &move_generator)
+                .unwrap()
+            {
+                let _ = position.play_unchecked(mv);
+                let wdl = tablebases.probe_wdl(&mut position, &move_generator);
+                position.undo(mv);
+                assert_eq!(wdl, Some(Wdl::Loss), "{pieces:?} {mv:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn fails_to_probe_positions_without_tables() {
+        let tablebases = tablebases();
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+        let pieces = [
+            (Sides::White, Pieces::King, 4),
+            (Sides::White, Pieces::Queen, 3),
+            (Sides::Black, Pieces::King, 60),
+            (Sides::Black, Pieces::Rook, 56),
+        ];
+        let mut position = fixtures::position(Sides::White, &pieces, false);
+
+        assert!(!tablebases.covers(&position));
+        assert_eq!(tablebases.probe_wdl(&mut position, &move_generator), None);
+    }
+}
@@ -0,0 +1,114 @@
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_position::{DefaultPosition, Fen, Setup};
+use chess_kit_primitives::{Move, Square};
+use chess_kit_syzygy::Tablebases;
+use chess_kit_tablebase::{EndgameTablebase, Wdl};
+
+type TestPosition = DefaultPosition<DefaultAttackTable>;
+type TestMoveGenerator = DefaultMoveGenerator<DefaultAttackTable>;
+
+/// FIXTURES is the directory holding the official 3-piece Syzygy tables,
+/// downloaded unmodified from https://tablebase.lichess.ovh/tables/standard/3-4-5/
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+/// TABLES is the material of the official tables in the fixture directory,
+/// each with a `.rtbw` and a `.rtbz` file
+const TABLES: [&str; 5] = ["KQvK", "KRvK", "KPvK", "KBvK", "KNvK"];
+
+/// WDL is the known outcome of positions from the side to move's perspective
+const WDL: [(&str, Wdl); 10] = [
+    ("4k3/8/4K3/8/8/8/8/Q7 w - - 0 1", Wdl::Win),
+    ("4k3/8/4K3/8/8/8/8/Q7 b - - 0 1", Wdl::Loss),
+    // stalemate
+    ("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1", Wdl::Draw),
+    // the undefended queen is captured
+    ("8/8/8/8/8/8/1k6/Q2K4 b - - 0 1", Wdl::Draw),
+    ("4k3/8/4K3/8/8/8/8/R7 w - - 0 1", Wdl::Win),
+    ("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", Wdl::Win),
+    ("k7/4P3/8/8/8/8/8/4K3 b - - 0 1", Wdl::Loss),
+    // the defending king holds the corner of the rook pawn
+    ("7k/8/7K/7P/8/8/8/8 w - - 0 1", Wdl::Draw),
+    ("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", Wdl::Draw),
+    ("4k3/8/8/8/8/8/8/1N2K3 b - - 0 1", Wdl::Draw),
+];
+
+/// DTZ is the known distance to zeroing of positions, in plies
+const DTZ: [(&str, i32); 5] = [
+    // mate in one
+    ("4k3/8/4K3/8/8/8/8/Q7 w - - 0 1", 1),
+    ("4k3/8/4K3/8/8/8/8/R7 w - - 0 1", 1),
+    // the pawn promotes unhindered
+    ("k7/4P3/8/8/8/8/8/4K3 w - - 0 1", 1),
+    ("k7/4P3/8/8/8/8/8/4K3 b - - 0 1", -2),
+    ("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", 0),
+];
+
+fn tablebases() -> Tablebases {
+    Tablebases::open(FIXTURES).unwrap()
+}
+
+fn position(fen: &str) -> TestPosition {
+    TestPosition::try_from(Setup::from(Fen::try_from(fen).unwrap())).unwrap()
+}
+
+#[test]
+#[ignore = "run explicitly with the official tables in tests/fixtures"]
+fn finds_the_official_tables() {
+    for code in TABLES {
+        for extension in ["rtbw", "rtbz"] {
+            let path = format!("{FIXTURES}/{code}.{extension}");
+            assert!(std::path::Path::new(&path).is_file(), "missing {path}");
+        }
+    }
+
+    let tablebases = tablebases();
+    assert_eq!(tablebases.len(), TABLES.len());
+    assert_eq!(tablebases.max_pieces(), 3);
+}
+
+#[test]
+#[ignore = "run explicitly with the official tables in tests/fixtures"]
+fn probes_known_outcomes() {
+    let tablebases = tablebases();
+    let move_generator = TestMoveGenerator::new();
+
+    for (fen, expected) in WDL {
+        let mut position = position(fen);
+        assert!(tablebases.covers(&position), "{fen}");
+        assert_eq!(
+            tablebases.probe_wdl(&mut position, &move_generator),
+            Some(expected),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+#[ignore = "run explicitly with the official tables in tests/fixtures"]
+fn probes_known_distances_to_zeroing() {
+    let tablebases = tablebases();
+    let move_generator = TestMoveGenerator::new();
+
+    for (fen, expected) in DTZ {
+        let mut position = position(fen);
+        assert_eq!(
+            tablebases.probe_dtz(&mut position, &move_generator),
+            Some(expected),
+            "{fen}"
+        );
+    }
+}
+
+#[test]
+#[ignore = "run explicitly with the official tables in tests/fixtures"]
+fn keeps_the_only_drawing_root_move() {
+    let tablebases = tablebases();
+    let move_generator = TestMoveGenerator::new();
+    let mut position = position("8/8/8/8/8/8/1k6/Q2K4 b - - 0 1");
+
+    assert_eq!(
+        tablebases.best_root_moves(&mut position, &move_generator),
+        Some(vec![Move::new(Square::B2, Square::A1)])
+    );
+}
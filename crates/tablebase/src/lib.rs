@@ -0,0 +1,57 @@
+//! Endgame tablebase probing contract.
+//!
+//! [`EndgameTablebase`] is the probing contract consumed by the search, and
+//! [`Wdl`] the outcome it reports. It is shared by every tablebase backend,
+//! such as the Syzygy reader and the generated distance-to-mate tables.
+
+mod wdl;
+
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
+use chess_kit_primitives::Move;
+
+pub use wdl::Wdl;
+
+/// `EndgameTablebase` defines the contract for probing endgame tablebases
+/// during search
+///
+/// @trait
+pub trait EndgameTablebase {
+    /// covers checks whether a position may be probed
+    ///
+    /// @param: position - position to check
+    /// @return: true if the position may be probed
+    fn covers<PositionT: PositionView>(&self, position: &PositionT) -> bool;
+
+    /// probe_wdl probes the outcome of a position
+    ///
+    /// @param: position - position to probe, which must be covered
+    /// @param: move_generator - move generator used to search moves
+    /// @return: outcome from the side to move's perspective, or None if the
+    ///          position cannot be probed
+    /// @side-effects: may play and undo moves on the position
+    fn probe_wdl<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Wdl>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator;
+
+    /// best_root_moves returns the legal moves of a position that preserve
+    /// its outcome best
+    ///
+    /// @param: position - position to rank the moves of, which must be covered
+    /// @param: move_generator - move generator used to search moves
+    /// @return: best legal moves, or None if the position cannot be probed
+    /// @side-effects: may play and undo moves on the position
+    fn best_root_moves<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Vec<Move>>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator;
+}
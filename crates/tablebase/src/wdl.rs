@@ -0,0 +1,45 @@
+use std::ops::Neg;
+
+/// `Wdl` is the outcome of a position with perfect play from the side to
+/// move's perspective
+///
+/// note: cursed wins and blessed losses are wins and losses that are drawn
+///       by the fifty-move rule
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wdl {
+    Loss = -2,
+    BlessedLoss = -1,
+    Draw = 0,
+    CursedWin = 1,
+    Win = 2,
+}
+
+impl Wdl {
+    /// signum returns the sign of the outcome
+    ///
+    /// @return: 1 for wins, -1 for losses, and 0 for draws
+    #[inline]
+    pub const fn signum(self) -> i32 {
+        (self as i32).signum()
+    }
+}
+
+impl Neg for Wdl {
+    type Output = Self;
+
+    /// neg returns the outcome from the opponent's perspective
+    ///
+    /// @impl: Neg::neg
+    #[inline]
+    fn neg(self) -> Self {
+        match self {
+            Self::Loss => Self::Win,
+            Self::BlessedLoss => Self::CursedWin,
+            Self::Draw => Self::Draw,
+            Self::CursedWin => Self::BlessedLoss,
+            Self::Win => Self::Loss,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt::{self, Display};
+use std::io;
+
+/// TablegenError is returned when a table cannot be generated, read or written
+///
+/// @type
+#[derive(Debug)]
+pub enum TablegenError {
+    // a table file could not be read or written
+    Io(io::Error),
+    // a material could not be parsed or has too many pieces
+    InvalidMaterial(String),
+    // a table file is malformed
+    Corrupt(&'static str),
+    // a distance to mate is too long to be stored
+    DtmOverflow,
+}
+
+impl Display for TablegenError {
+    /// fmt writes a human-readable description of the tablebase error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to access table file: {error}"),
+            Self::InvalidMaterial(material) => write!(f, "invalid material: {material}"),
+            Self::Corrupt(reason) => write!(f, "corrupt table file: {reason}"),
+            Self::DtmOverflow => write!(f, "distance to mate too long to store"),
+        }
+    }
+}
+
+impl std::error::Error for TablegenError {}
+
+impl From<io::Error> for TablegenError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
@@ -0,0 +1,396 @@
+use crate::index::Placement;
+use crate::table::{MAX_PLIES, placements};
+use crate::{Dtm, Indexer, Material, Table, Tablebase, TablegenError};
+use chess_kit_attack_table::DefaultAttackTable;
+use chess_kit_movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit_position::{
+    DefaultPosition, Fen, PositionAttacks, PositionMoves, PositionView, Setup,
+};
+use chess_kit_primitives::{MoveList, Sides};
+
+/// generate solves the table of a material and of every material its
+/// positions can convert into, skipping the ones the tablebase already has
+///
+/// note: en passant rights are not part of a table's index, so positions are
+///       solved as if a pawn that just moved two squares could not be
+///       captured en passant
+/// note: every position of a material is held in memory while it is solved,
+///       which keeps generation practical up to four pieces
+///
+/// @param: tablebase - tablebase to add the solved tables to
+/// @param: material - material to solve, with either color holding the
+///                    stronger pieces
+/// @return: Ok on success, or the tablebase error
+/// @side-effects: adds the solved tables to the tablebase
+pub fn generate(tablebase: &mut Tablebase, material: Material) -> Result<(), TablegenError> {
+    let material = material.canonical();
+    if tablebase.get(material).is_some() {
+        return Ok(());
+    }
+    for conversion in material.conversions() {
+        generate(tablebase, conversion)?;
+    }
+    let table = solve(tablebase, material)?;
+    tablebase.insert(table);
+    Ok(())
+}
+
+/// Graph is the moves between the positions of a material, with the outcome
+/// of the moves that leave the material already known
+///
+/// @type
+struct Graph {
+    outcomes: Vec<Option<Dtm>>, // outcome of each index, solved so far
+    legal: Vec<bool>,           // whether each index is a legal position
+    children: Vec<u32>,         // number of moves to positions of the material
+    external: Vec<Option<Dtm>>, // best outcome of the moves leaving the material
+    offsets: Vec<usize>,        // start of the parents of each index
+    parents: Vec<u32>,          // indices with a move to each index
+}
+
+/// solve computes the distance to mate of every position of a material whose
+/// conversions are already in the tablebase
+///
+/// Positions are solved in rounds of increasing distance to mate: the
+/// positions solved as lost in `k` plies make their parents won in `k + 1`
+/// plies, and a parent whose moves all lead to solved wins is lost in one
+/// more ply than its slowest one. Positions never solved are draws.
+///
+/// @param: tablebase - tablebase holding the tables of the conversions
+/// @param: material - canonical material to solve
+/// @return: solved table, or the tablebase error
+fn solve(tablebase: &Tablebase, material: Material) -> Result<Table, TablegenError> {
+    let Graph {
+        mut outcomes,
+        legal,
+        mut children,
+        external,
+        offsets,
+        parents,
+    } = Graph::build(tablebase, material)?;
+
+    // rounds[k] holds the positions that may be solved in k plies
+    let mut rounds = vec![Vec::new(); usize::from(MAX_PLIES) + 1];
+    let mut longest = vec![0u8; outcomes.len()];
+    let schedule = |rounds: &mut Vec<Vec<u32>>, idx: usize, plies: usize| {
+        if plies > usize::from(MAX_PLIES) {
+            return Err(TablegenError::DtmOverflow);
+        }
+        rounds[plies].push(idx as u32);
+        Ok(())
+    };
+    for idx in 0..outcomes.len() {
+        match (outcomes[idx], external[idx]) {
+            (Some(Dtm::Loss(0)), _) => schedule(&mut rounds, idx, 0)?,
+            (Some(_), _) => {}
+            (None, Some(Dtm::Win(plies))) => schedule(&mut rounds, idx, usize::from(plies))?,
+            (None, Some(Dtm::Loss(plies))) => {
+                longest[idx] = plies;
+                if children[idx] == 0 {
+                    schedule(&mut rounds, idx, usize::from(plies))?;
+                }
+            }
+            (None, _) => {}
+        }
+    }
+
+    for plies in 0..rounds.len() {
+        for idx in std::mem::take(&mut rounds[plies]) {
+            let idx = idx as usize;
+            if plies > 0 && outcomes[idx].is_some() {
+                continue;
+            }
+            let outcome = if plies % 2 == 1 {
+                Dtm::Win(plies as u8)
+            } else {
+                Dtm::Loss(plies as u8)
+            };
+            outcomes[idx] = Some(outcome);
+
+            for &parent in &parents[offsets[idx]..offsets[idx + 1]] {
+                let parent = parent as usize;
+                if outcomes[parent].is_some() {
+                    continue;
+                }
+                match outcome {
+                    Dtm::Loss(_) => schedule(&mut rounds, parent, plies + 1)?,
+                    _ => {
+                        children[parent] -= 1;
+                        longest[parent] = longest[parent].max(plies as u8 + 1);
+                        // a parent with a move to a draw or a win outside the
+                        // material is never lost
+                        let losing = matches!(external[parent], None | Some(Dtm::Loss(_)));
+                        if children[parent] == 0 && losing {
+                            schedule(&mut rounds, parent, usize::from(longest[parent]))?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let outcomes = outcomes
+        .into_iter()
+        .zip(legal)
+        .map(|(outcome, legal)| legal.then(|| outcome.unwrap_or(Dtm::Draw)))
+        .collect::<Vec<_>>();
+    Ok(Table::new(material, &outcomes))
+}
+
+impl Graph {
+    /// build plays every legal move of every position of a material
+    ///
+    /// @param: tablebase - tablebase holding the tables of the conversions
+    /// @param: material - canonical material to build the graph of
+    /// @return: graph of the material, or the tablebase error
+    fn build(tablebase: &Tablebase, material: Material) -> Result<Self, TablegenError> {
+        let indexer = Indexer::new(material);
+        let size = indexer.size();
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+        let mut graph = Self {
+            outcomes: vec![None; size],
+            legal: vec![false; size],
+            children: vec![0; size],
+            external: vec![None; size],
+            offsets: vec![0; size + 1],
+            parents: Vec::new(),
+        };
+        let mut edges = Vec::new();
+        let mut moves = MoveList::new();
+        for idx in 0..size {
+            let Some((turn, pieces)) = indexer.decode(idx) else {
+                continue;
+            };
+            // the side that just moved cannot be left in check
+            let Some(mut position) = position(turn, &pieces) else {
+                continue;
+            };
+            graph.legal[idx] = true;
+
+            moves.clear();
+            move_generator.generate_legal_moves(&position, &mut moves);
+            if moves.is_empty() {
+                graph.outcomes[idx] = Some(if position.checkers().not_empty() {
+                    Dtm::Loss(0)
+                } else {
+                    Dtm::Draw
+                });
+                continue;
+            }
+            for &mv in moves.as_slice() {
+                let _ = position.play_unchecked(mv);
+                if Material::from_position(&position) == material {
+                    let child = indexer
+                        .index(position.turn(), &placements(&position))
+                        .ok_or(TablegenError::Corrupt("unindexed position"))?;
+                    edges.push((child as u32, idx as u32));
+                    graph.children[idx] += 1;
+                } else {
+                    let outcome = tablebase
+                        .probe(&position)
+                        .ok_or(TablegenError::Corrupt("missing conversion table"))?
+                        .before();
+                    graph.external[idx] = graph.external[idx].max(Some(outcome));
+                }
+                position.undo(mv);
+            }
+        }
+
+        // group the parents of every index together
+        for (child, _) in &edges {
+            graph.offsets[*child as usize + 1] += 1;
+        }
+        for idx in 0..size {
+            graph.offsets[idx + 1] += graph.offsets[idx];
+        }
+        let mut next = graph.offsets.clone();
+        graph.parents = vec![0; edges.len()];
+        for (child, parent) in edges {
+            graph.parents[next[child as usize]] = parent;
+            next[child as usize] += 1;
+        }
+        Ok(graph)
+    }
+}
+
+/// position sets up a decoded position, without castling or en passant rights
+///
+/// @param: turn - side to move
+/// @param: pieces - pieces of the position
+/// @return: position of the pieces, or None for an illegal position
+fn position(turn: Sides, pieces: &[Placement]) -> Option<DefaultPosition<DefaultAttackTable>> {
+    let mut board = [['.'; 8]; 8];
+    for (side, piece, square) in pieces {
+        let symbol = ['.', 'p', 'n', 'b', 'r', 'q', 'k'][piece.idx()];
+        board[square.idx() >> 3][square.idx() & 7] = if *side == Sides::White {
+            symbol.to_ascii_uppercase()
+        } else {
+            symbol
+        };
+    }
+
+    let mut fen = String::new();
+    for row in board.iter().rev() {
+        let mut empty = 0;
+        for symbol in row {
+            if *symbol == '.' {
+                empty += 1;
+                continue;
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+                empty = 0;
+            }
+            fen.push(*symbol);
+        }
+        if empty > 0 {
+            fen.push_str(&empty.to_string());
+        }
+        fen.push('/');
+    }
+    fen.pop();
+    fen.push_str(if turn == Sides::White {
+        " w - - 0 1"
+    } else {
+        " b - - 0 1"
+    });
+
+    DefaultPosition::try_from(Setup::from(
+        Fen::try_from(fen.as_str()).expect("decoded positions are valid FEN"),
+    ))
+    .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_tablebase::{EndgameTablebase, Wdl};
+    use std::sync::OnceLock;
+
+    /// tablebase solves the king and pawn endgame once for every test, which
+    /// solves the endgames of every piece it promotes to along the way
+    fn tablebase() -> &'static Tablebase {
+        static TABLEBASE: OnceLock<Tablebase> = OnceLock::new();
+        TABLEBASE.get_or_init(|| {
+            let mut tablebase = Tablebase::default();
+            generate(&mut tablebase, Material::try_from("KPvK").unwrap()).unwrap();
+            tablebase
+        })
+    }
+
+    fn table(code: &str) -> &'static Table {
+        tablebase().get(Material::try_from(code).unwrap()).unwrap()
+    }
+
+    fn probe(fen: &str) -> Option<Dtm> {
+        let position = DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+            Fen::try_from(fen).unwrap(),
+        ))
+        .unwrap();
+        tablebase().probe(&position)
+    }
+
+    #[test]
+    fn solves_the_longest_mates_of_major_pieces() {
+        // king and queen mate in at most 10 moves, king and rook in 16
+        assert_eq!(table("KQvK").longest_win(), Some(19));
+        assert_eq!(table("KRvK").longest_win(), Some(31));
+        assert_eq!(Dtm::Win(31).moves(), Some(16));
+        assert_eq!(table("KBvK").longest_win(), None);
+        assert_eq!(table("KNvK").longest_win(), None);
+        assert_eq!(tablebase().len(), 6);
+    }
+
+    #[test]
+    fn solves_positions_of_either_color() {
+        assert_eq!(probe("k7/6Q1/1K6/8/8/8/8/8 w - - 0 1"), Some(Dtm::Win(1)));
+        assert_eq!(probe("8/8/8/8/8/1k6/6q1/K7 b - - 0 1"), Some(Dtm::Win(1)));
+        assert_eq!(probe("k6Q/8/1K6/8/8/8/8/8 b - - 0 1"), Some(Dtm::Loss(0)));
+        assert_eq!(probe("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1"), Some(Dtm::Draw));
+        assert_eq!(probe("k7/1Q6/8/8/8/8/8/7K b - - 0 1"), Some(Dtm::Draw));
+        // a king in check with the other side to move is not a legal position
+        assert!(
+            Setup::from(Fen::try_from("k7/1Q6/8/8/8/8/8/7K w - - 0 1").unwrap())
+                .validate::<DefaultAttackTable>()
+                .is_err()
+        );
+        assert_eq!(probe("k7/8/8/8/8/8/8/7K w - - 0 1"), Some(Dtm::Draw));
+        assert_eq!(probe("k7/8/8/8/8/8/8/1RR4K w - - 0 1"), None);
+    }
+
+    #[test]
+    fn wins_king_and_pawn_endgames_by_promoting() {
+        // a rook pawn is drawn with the defending king in the corner, while
+        // any king on the sixth rank in front of its pawn wins
+        assert_eq!(probe("k7/8/8/8/8/8/P7/7K w - - 0 1"), Some(Dtm::Draw));
+        assert!(matches!(
+            probe("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1"),
+            Some(Dtm::Win(_))
+        ));
+        assert!(matches!(
+            probe("8/8/8/8/4p3/4k3/8/4K3 w - - 0 1"),
+            Some(Dtm::Loss(_))
+        ));
+    }
+
+    #[test]
+    fn writes_and_reads_tables() {
+        let table = table("KPvK");
+        let size = Indexer::new(table.material()).size();
+
+        let mut bytes = Vec::new();
+        table.write(&mut bytes).unwrap();
+        let read = Table::read(&mut bytes.as_slice()).unwrap();
+        assert_eq!(read.material(), table.material());
+        assert!((0..size).all(|idx| read.get(idx) == table.get(idx)));
+        assert!(bytes.len() < size * 2 / 3);
+
+        assert!(Table::read(&mut &bytes[..20]).is_err());
+        bytes[4] += 1;
+        assert!(Table::read(&mut bytes.as_slice()).is_err());
+
+        let directory =
+            std::env::temp_dir().join(format!("chess-kit-tablegen-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        tablebase().save(&directory).unwrap();
+        let opened = Tablebase::open(&directory).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(opened.len(), tablebase().len());
+        assert_eq!(
+            opened.get(table.material()).unwrap().longest_win(),
+            table.longest_win()
+        );
+    }
+
+    #[test]
+    fn probes_positions_for_the_search() {
+        let load = |fen: &str| {
+            DefaultPosition::<DefaultAttackTable>::try_from(Setup::from(
+                Fen::try_from(fen).unwrap(),
+            ))
+            .unwrap()
+        };
+        let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+
+        let mut position = load("k7/6Q1/1K6/8/8/8/8/8 w - - 0 1");
+        assert!(tablebase().covers(&position));
+        assert_eq!(
+            tablebase().probe_wdl(&mut position, &move_generator),
+            Some(Wdl::Win)
+        );
+        let best = tablebase()
+            .best_root_moves(&mut position, &move_generator)
+            .unwrap();
+        assert!(!best.is_empty());
+        for mv in best {
+            let _ = position.play_unchecked(mv);
+            assert_eq!(tablebase().probe(&position), Some(Dtm::Loss(0)));
+            position.undo(mv);
+        }
+
+        // castling rights and missing tables are left to the search
+        assert!(!tablebase().covers(&load("4k3/8/8/8/8/8/8/R3K3 w Q - 0 1")));
+        assert!(!tablebase().covers(&load("k7/8/8/8/8/8/8/1RR4K w - - 0 1")));
+    }
+}
@@ -0,0 +1,260 @@
+use crate::Material;
+use chess_kit_primitives::{Pieces, Sides, Square};
+
+/// Placement is the side, type and square of a piece on the board
+pub type Placement = (Sides, Pieces, Square);
+
+/// TRIANGLE is the squares of the a1-d1-d4 triangle, which every position
+/// without pawns can be mirrored to have the white king in
+const TRIANGLE: [usize; 10] = [0, 1, 2, 3, 9, 10, 11, 18, 19, 27];
+
+/// Indexer maps the positions of a material to the indices of its table
+///
+/// Positions are mirrored so that the white king is on files a-d, and when
+/// there are no pawns, in the a1-d1-d4 triangle. The index then encodes the
+/// side to move, the white king and the square of every other piece in the
+/// order of [`Material::pieces`], with pieces of the same type in ascending
+/// order of squares
+///
+/// @type
+#[derive(Clone, Debug)]
+pub struct Indexer {
+    pieces: Vec<(Sides, Pieces)>, // pieces in the indexed order
+    has_pawns: bool,
+    kings: usize, // number of squares the white king is indexed on
+}
+
+impl Indexer {
+    /// new creates the indexer of a material
+    ///
+    /// @param: material - material to index the positions of
+    /// @return: indexer of the material
+    pub fn new(material: Material) -> Self {
+        let has_pawns = material.has_pawns();
+        Self {
+            pieces: material.pieces(),
+            has_pawns,
+            kings: if has_pawns { 32 } else { TRIANGLE.len() },
+        }
+    }
+
+    /// size returns the number of indices of the table, including the ones of
+    /// illegal or redundant placements
+    ///
+    /// @return: number of indices
+    pub fn size(&self) -> usize {
+        Sides::TOTAL * self.kings * 64usize.pow(self.pieces.len() as u32 - 1)
+    }
+
+    /// index computes the index of a position
+    ///
+    /// @param: turn - side to move
+    /// @param: placements - pieces of the position, in any order
+    /// @return: index of the position, or None if the pieces do not match the
+    ///          material
+    pub fn index(&self, turn: Sides, placements: &[Placement]) -> Option<usize> {
+        if placements.len() != self.pieces.len() {
+            return None;
+        }
+        let white_king = placements
+            .iter()
+            .find(|(side, piece, _)| *side == Sides::White && *piece == Pieces::King)?
+            .2
+            .idx();
+
+        // mirror the board so that the white king is on files a-d, then on
+        // ranks 1-4 and below the a1-h8 diagonal when there are no pawns
+        let mirror_file = file(white_king) > 3;
+        let mirror_rank = !self.has_pawns && rank(white_king) > 3;
+        let king = transform(white_king, mirror_file, mirror_rank, false);
+        let mirror_diagonal = !self.has_pawns && rank(king) > file(king);
+        let transform =
+            |square: Square| transform(square.idx(), mirror_file, mirror_rank, mirror_diagonal);
+
+        let king = transform(Square::from_idx(white_king));
+        let mut idx = turn.idx() * self.kings
+            + if self.has_pawns {
+                rank(king) * 4 + file(king)
+            } else {
+                TRIANGLE.iter().position(|square| *square == king)?
+            };
+
+        let mut next = 1;
+        while next < self.pieces.len() {
+            let kind = self.pieces[next];
+            let mut squares = placements
+                .iter()
+                .filter(|(side, piece, _)| (*side, *piece) == kind)
+                .map(|(_, _, square)| transform(*square))
+                .collect::<Vec<_>>();
+            let count = self.pieces[next..]
+                .iter()
+                .take_while(|other| **other == kind)
+                .count();
+            if squares.len() != count {
+                return None;
+            }
+            squares.sort_unstable();
+            for square in squares {
+                idx = idx * 64 + square;
+            }
+            next += count;
+        }
+        Some(idx)
+    }
+
+    /// decode returns the position of an index
+    ///
+    /// @param: idx - index to decode
+    /// @return: side to move and pieces of the position, or None if the
+    ///          index is an illegal or redundant placement
+    pub fn decode(&self, mut idx: usize) -> Option<(Sides, Vec<Placement>)> {
+        let mut squares = vec![0; self.pieces.len()];
+        for square in squares[1..].iter_mut().rev() {
+            *square = idx % 64;
+            idx /= 64;
+        }
+        squares[0] = if self.has_pawns {
+            let king = idx % self.kings;
+            (king / 4) * 8 + king % 4
+        } else {
+            TRIANGLE[idx % self.kings]
+        };
+        let turn = Sides::from_idx(idx / self.kings);
+
+        for (i, square) in squares.iter().enumerate() {
+            if squares[..i].contains(square) {
+                return None;
+            }
+            let (side, piece) = self.pieces[i];
+            // pieces of the same type are indexed in ascending order of squares
+            if i > 0 && self.pieces[i - 1] == (side, piece) && squares[i - 1] > *square {
+                return None;
+            }
+            if piece == Pieces::Pawn && (rank(*square) == 0 || rank(*square) == 7) {
+                return None;
+            }
+        }
+        if distance(squares[0], squares[1]) <= 1 {
+            return None;
+        }
+
+        let placements = self
+            .pieces
+            .iter()
+            .zip(squares)
+            .map(|((side, piece), square)| (*side, *piece, Square::from_idx(square)))
+            .collect();
+        Some((turn, placements))
+    }
+}
+
+/// transform mirrors a square
+///
+/// @param: square - square to mirror
+/// @param: mirror_file - whether to mirror the files
+/// @param: mirror_rank - whether to mirror the ranks
+/// @param: mirror_diagonal - whether to then mirror along the a1-h8 diagonal
+/// @return: mirrored square
+#[inline]
+const fn transform(
+    square: usize,
+    mirror_file: bool,
+    mirror_rank: bool,
+    mirror_diagonal: bool,
+) -> usize {
+    let square = square ^ if mirror_file { 7 } else { 0 } ^ if mirror_rank { 56 } else { 0 };
+    if mirror_diagonal {
+        (square >> 3) | ((square & 7) << 3)
+    } else {
+        square
+    }
+}
+
+/// file returns the file of a square, from 0 for the a-file
+#[inline]
+const fn file(square: usize) -> usize {
+    square & 7
+}
+
+/// rank returns the rank of a square, from 0 for the first rank
+#[inline]
+const fn rank(square: usize) -> usize {
+    square >> 3
+}
+
+/// distance returns the number of king moves between two squares
+#[inline]
+const fn distance(from: usize, to: usize) -> usize {
+    let files = file(from).abs_diff(file(to));
+    let ranks = rank(from).abs_diff(rank(to));
+    if files > ranks { files } else { ranks }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placements(squares: [usize; 3], piece: Pieces) -> Vec<Placement> {
+        vec![
+            (Sides::White, Pieces::King, Square::from_idx(squares[0])),
+            (Sides::Black, Pieces::King, Square::from_idx(squares[1])),
+            (Sides::White, piece, Square::from_idx(squares[2])),
+        ]
+    }
+
+    #[test]
+    fn decodes_the_positions_it_indexes() {
+        let indexer = Indexer::new(Material::try_from("KRvK").unwrap());
+        let mut legal = 0;
+
+        for idx in 0..indexer.size() {
+            let Some((turn, placements)) = indexer.decode(idx) else {
+                continue;
+            };
+            legal += 1;
+            assert_eq!(indexer.index(turn, &placements), Some(idx));
+        }
+        // the black king is kept away from the white king in the corner, on
+        // the three edge squares and on the six inner squares of the triangle,
+        // and the rook is on any other square
+        assert_eq!(legal, 2 * (60 + 3 * 58 + 6 * 55) * 62);
+    }
+
+    #[test]
+    fn indexes_mirrored_positions_alike() {
+        let pawnless = Indexer::new(Material::try_from("KQvK").unwrap());
+        let with_pawns = Indexer::new(Material::try_from("KPvK").unwrap());
+
+        // king on g6, king on b8 and a piece on d2, mirrored every way
+        let squares = [46, 57, 11];
+        let mirrors = [
+            (false, false, false),
+            (true, false, false),
+            (false, true, false),
+            (true, true, false),
+            (false, false, true),
+            (true, true, true),
+        ];
+        let expected = pawnless.index(Sides::Black, &placements(squares, Pieces::Queen));
+        for (file, rank, diagonal) in mirrors {
+            let mirrored = squares.map(|square| transform(square, file, rank, diagonal));
+            let idx = pawnless.index(Sides::Black, &placements(mirrored, Pieces::Queen));
+            assert_eq!(idx, expected);
+        }
+
+        let expected = with_pawns.index(Sides::White, &placements(squares, Pieces::Pawn));
+        let mirrored = squares.map(|square| square ^ 7);
+        assert_eq!(
+            with_pawns.index(Sides::White, &placements(mirrored, Pieces::Pawn)),
+            expected
+        );
+        assert_ne!(
+            with_pawns.index(
+                Sides::White,
+                &placements(squares.map(|s| s ^ 56), Pieces::Pawn)
+            ),
+            expected
+        );
+    }
+}
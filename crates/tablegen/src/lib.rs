@@ -0,0 +1,20 @@
+//! Endgame tablebase generation by retrograde analysis.
+//!
+//! [`generate`] enumerates every position of a small [`Material`] with the
+//! position and move generator crates, solves the distance to mate of each
+//! one, and stores the results in a [`Tablebase`] together with the tables of
+//! every material the positions can convert into. Tables are written to and
+//! read from compact indexed files, and probed with [`Tablebase::probe`] or
+//! through the `EndgameTablebase` contract consumed by the search.
+
+pub mod error;
+pub mod generator;
+pub mod index;
+pub mod material;
+pub mod table;
+
+pub use error::TablegenError;
+pub use generator::generate;
+pub use index::Indexer;
+pub use material::Material;
+pub use table::{Dtm, Table, Tablebase};
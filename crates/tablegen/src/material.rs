@@ -0,0 +1,277 @@
+use crate::TablegenError;
+use chess_kit_position::PositionView;
+use chess_kit_primitives::{Black, Pieces, Sides, White};
+use std::fmt::{self, Display};
+
+/// MAX_PIECES is the largest number of pieces, kings included, of a material
+/// that can be generated
+pub const MAX_PIECES: usize = 5;
+
+/// ORDER is the order of the pieces other than the king in a material, from
+/// the most to the least valuable
+const ORDER: [Pieces; 5] = [
+    Pieces::Queen,
+    Pieces::Rook,
+    Pieces::Bishop,
+    Pieces::Knight,
+    Pieces::Pawn,
+];
+
+/// Material is the number of pieces of each type each side has, which
+/// identifies the table a position belongs to
+///
+/// @type
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct Material([[u8; Pieces::TOTAL]; Sides::TOTAL]);
+
+impl Material {
+    /// from_position counts the pieces of a position
+    ///
+    /// @param: position - position to count the pieces of
+    /// @return: material of the position
+    pub fn from_position<P: PositionView>(position: &P) -> Self {
+        let mut material = Self::default();
+        for piece in Pieces::ALL {
+            material.0[Sides::White][piece] = position.get_piece::<White>(piece).count_ones() as u8;
+            material.0[Sides::Black][piece] = position.get_piece::<Black>(piece).count_ones() as u8;
+        }
+        material
+    }
+
+    /// count returns the number of pieces of a type a side has
+    ///
+    /// @param: side - side owning the pieces
+    /// @param: piece - type of the pieces
+    /// @return: number of pieces
+    #[inline]
+    pub fn count(&self, side: Sides, piece: Pieces) -> usize {
+        usize::from(self.0[side][piece])
+    }
+
+    /// total returns the number of pieces of both sides, kings included
+    ///
+    /// @return: number of pieces
+    #[inline]
+    pub fn total(&self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .map(|count| usize::from(*count))
+            .sum()
+    }
+
+    /// has_pawns checks whether either side has a pawn
+    ///
+    /// @return: true if the material has pawns
+    #[inline]
+    pub fn has_pawns(&self) -> bool {
+        self.count(Sides::White, Pieces::Pawn) + self.count(Sides::Black, Pieces::Pawn) > 0
+    }
+
+    /// flipped returns the material with the pieces of the sides swapped
+    ///
+    /// @return: material with the colors swapped
+    #[inline]
+    pub const fn flipped(self) -> Self {
+        Self([self.0[1], self.0[0]])
+    }
+
+    /// is_canonical checks whether white has the stronger pieces, which is how
+    /// tables are generated and stored
+    ///
+    /// @return: true if white's pieces are at least as strong as black's
+    #[inline]
+    pub fn is_canonical(&self) -> bool {
+        self.strength(Sides::White) >= self.strength(Sides::Black)
+    }
+
+    /// canonical returns the material with the stronger pieces given to white
+    ///
+    /// @return: canonical material
+    #[inline]
+    pub fn canonical(self) -> Self {
+        if self.is_canonical() {
+            self
+        } else {
+            self.flipped()
+        }
+    }
+
+    /// pieces lists the pieces of the material in the order they are indexed:
+    /// the white king, the black king, then the other pieces of white and of
+    /// black from the most to the least valuable
+    ///
+    /// @return: side and type of every piece of the material
+    pub fn pieces(&self) -> Vec<(Sides, Pieces)> {
+        let mut pieces = vec![(Sides::White, Pieces::King), (Sides::Black, Pieces::King)];
+        for side in [Sides::White, Sides::Black] {
+            for piece in ORDER {
+                pieces.extend(std::iter::repeat_n((side, piece), self.count(side, piece)));
+            }
+        }
+        pieces
+    }
+
+    /// conversions returns the canonical materials a position of this material
+    /// can turn into with a single capture or promotion
+    ///
+    /// @return: distinct canonical materials reachable in one move
+    pub fn conversions(&self) -> Vec<Self> {
+        let mut conversions = Vec::new();
+        for side in [Sides::White, Sides::Black] {
+            for piece in ORDER {
+                if self.count(side, piece) == 0 {
+                    continue;
+                }
+                let mut captured = *self;
+                captured.0[side][piece] -= 1;
+                conversions.push(captured.canonical());
+
+                if piece != Pieces::Pawn {
+                    continue;
+                }
+                // a promotion may capture a piece of the other side as well
+                for promoted in [Pieces::Queen, Pieces::Rook, Pieces::Bishop, Pieces::Knight] {
+                    let mut promotion = captured;
+                    promotion.0[side][promoted] += 1;
+                    conversions.push(promotion.canonical());
+
+                    let other = side.other();
+                    for target in [Pieces::Queen, Pieces::Rook, Pieces::Bishop, Pieces::Knight] {
+                        if self.count(other, target) > 0 {
+                            let mut capture = promotion;
+                            capture.0[other][target] -= 1;
+                            conversions.push(capture.canonical());
+                        }
+                    }
+                }
+            }
+        }
+        conversions.sort_by_key(|material| material.to_string());
+        conversions.dedup();
+        conversions
+    }
+
+    /// strength returns the pieces of a side from the most to the least
+    /// valuable, compared lexicographically to find the stronger side
+    ///
+    /// @param: side - side to get the strength of
+    /// @return: number of pieces of each type other than the king
+    #[inline]
+    fn strength(&self, side: Sides) -> [u8; 5] {
+        ORDER.map(|piece| self.0[side][piece])
+    }
+}
+
+impl TryFrom<&str> for Material {
+    type Error = TablegenError;
+
+    /// try_from parses a material such as `KRPvKN`, which lists the pieces of
+    /// white followed by the pieces of black
+    ///
+    /// @param: code - material to parse
+    /// @return: parsed material, or an invalid material error
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        let invalid = || TablegenError::InvalidMaterial(code.to_owned());
+        let (white, black) = code.split_once('v').ok_or_else(invalid)?;
+
+        let mut material = Self::default();
+        for (side, pieces) in [(Sides::White, white), (Sides::Black, black)] {
+            for symbol in pieces.chars() {
+                let piece = match symbol {
+                    'P' => Pieces::Pawn,
+                    'N' => Pieces::Knight,
+                    'B' => Pieces::Bishop,
+                    'R' => Pieces::Rook,
+                    'Q' => Pieces::Queen,
+                    'K' => Pieces::King,
+                    _ => return Err(invalid()),
+                };
+                material.0[side][piece] += 1;
+            }
+            if material.count(side, Pieces::King) != 1 {
+                return Err(invalid());
+            }
+        }
+        if material.total() > MAX_PIECES {
+            return Err(invalid());
+        }
+        Ok(material)
+    }
+}
+
+impl Display for Material {
+    /// fmt writes the material as the pieces of white and black separated by
+    /// a `v`, such as `KRPvKN`
+    ///
+    /// @param: f - formatter to write the material into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for side in [Sides::White, Sides::Black] {
+            if side == Sides::Black {
+                write!(f, "v")?;
+            }
+            write!(f, "K")?;
+            for piece in ORDER {
+                let symbol = ['.', 'P', 'N', 'B', 'R', 'Q', 'K'][piece.idx()];
+                for _ in 0..self.count(side, piece) {
+                    write!(f, "{symbol}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn material(code: &str) -> Material {
+        Material::try_from(code).unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays_materials() {
+        let krpvkn = material("KRPvKN");
+
+        assert_eq!(krpvkn.to_string(), "KRPvKN");
+        assert_eq!(material("KPRvKN"), krpvkn);
+        assert_eq!(krpvkn.flipped().to_string(), "KNvKRP");
+        assert_eq!(krpvkn.total(), 5);
+        assert!(krpvkn.has_pawns());
+        assert!(Material::try_from("KRvN").is_err());
+        assert!(Material::try_from("KQRBNvK").is_err());
+        assert!(Material::try_from("KXvK").is_err());
+    }
+
+    #[test]
+    fn gives_the_stronger_pieces_to_white() {
+        assert!(material("KQvKR").is_canonical());
+        assert!(!material("KRvKQ").is_canonical());
+        assert_eq!(material("KvKP").canonical(), material("KPvK"));
+        assert!(material("KRvKR").is_canonical());
+    }
+
+    #[test]
+    fn lists_the_materials_reachable_by_a_capture_or_promotion() {
+        let conversions = |code: &str| {
+            material(code)
+                .conversions()
+                .iter()
+                .map(|material| material.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(conversions("KQvK"), ["KvK"]);
+        assert_eq!(conversions("KQvKR"), ["KQvK", "KRvK"]);
+        assert_eq!(conversions("KPvK"), ["KBvK", "KNvK", "KQvK", "KRvK", "KvK"]);
+        assert_eq!(
+            conversions("KRvKP"),
+            [
+                "KBvK", "KNvK", "KPvK", "KQvK", "KQvKR", "KRvK", "KRvKB", "KRvKN", "KRvKR"
+            ]
+        );
+        assert_eq!(material("KvK").pieces().len(), 2);
+    }
+}
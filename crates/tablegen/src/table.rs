@@ -0,0 +1,529 @@
+use crate::index::Placement;
+use crate::{Indexer, Material, TablegenError};
+use chess_kit_movegen::MoveGenerator;
+use chess_kit_position::{PositionAttacks, PositionMoves, PositionView};
+use chess_kit_primitives::{Castling, Move, MoveList, Sides, Square, White};
+use chess_kit_tablebase::{EndgameTablebase, Wdl};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// MAGIC is the bytes every table file starts with
+const MAGIC: [u8; 4] = *b"CKTB";
+
+/// VERSION is the version of the table file format
+const VERSION: u8 = 1;
+
+/// EXTENSION is the file extension of table files
+pub const EXTENSION: &str = "cktb";
+
+/// Stored values of the positions of a table
+///
+/// note: other values are the distance to mate in plies plus one, which is
+///       odd for wins and even for losses
+const DRAW: u8 = 0;
+const ILLEGAL: u8 = u8::MAX;
+
+/// REPEAT is the token of a run of one value, followed by the value and the
+/// length of the run, while other tokens are followed by their value plus one
+/// values written as they are
+const REPEAT: u8 = u8::MAX;
+
+/// MIN_RUN is the shortest run of one value that is written as a run
+const MIN_RUN: usize = 4;
+
+/// MAX_PLIES is the longest distance to mate a table can store
+pub(crate) const MAX_PLIES: u8 = ILLEGAL - 2;
+
+/// Dtm is the outcome of a position with perfect play, from the side to
+/// move's perspective, with the distance to mate in plies
+///
+/// note: outcomes are ordered from the worst to the best for the side to move,
+///       so that the fastest wins and the slowest losses are preferred
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dtm {
+    Win(u8),  // the side to move mates in this many plies
+    Loss(u8), // the side to move is mated in this many plies
+    Draw,
+}
+
+impl Dtm {
+    /// moves returns the distance to mate in moves of the winning side
+    ///
+    /// @return: number of moves until mate, or None for a draw
+    #[inline]
+    pub const fn moves(self) -> Option<u8> {
+        match self {
+            Self::Win(plies) => Some(plies.div_ceil(2)),
+            Self::Loss(plies) => Some(plies / 2),
+            Self::Draw => None,
+        }
+    }
+
+    /// before returns the outcome of the position before a move leading to
+    /// this outcome, which is seen from the other side
+    ///
+    /// @return: outcome of the move for the side making it
+    #[inline]
+    pub(crate) const fn before(self) -> Self {
+        match self {
+            Self::Win(plies) => Self::Loss(plies + 1),
+            Self::Loss(plies) => Self::Win(plies + 1),
+            Self::Draw => Self::Draw,
+        }
+    }
+
+    /// encode returns the stored value of the outcome
+    ///
+    /// @return: stored value
+    #[inline]
+    pub(crate) const fn encode(self) -> u8 {
+        match self {
+            Self::Win(plies) | Self::Loss(plies) => plies + 1,
+            Self::Draw => DRAW,
+        }
+    }
+
+    /// decode returns the outcome of a stored value
+    ///
+    /// @param: value - stored value
+    /// @return: outcome of the value, or None for an illegal position
+    #[inline]
+    const fn decode(value: u8) -> Option<Self> {
+        match value {
+            DRAW => Some(Self::Draw),
+            ILLEGAL => None,
+            value if value % 2 == 0 => Some(Self::Win(value - 1)),
+            value => Some(Self::Loss(value - 1)),
+        }
+    }
+
+    /// rank orders the outcomes from the worst to the best for the side to move
+    ///
+    /// @return: sort key of the outcome
+    #[inline]
+    const fn rank(self) -> (u8, i16) {
+        match self {
+            Self::Loss(plies) => (0, plies as i16),
+            Self::Draw => (1, 0),
+            Self::Win(plies) => (2, -(plies as i16)),
+        }
+    }
+}
+
+impl PartialOrd for Dtm {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Dtm {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+/// Table is the outcome of every position of a material, with white holding
+/// the stronger pieces
+///
+/// @type
+#[derive(Clone, Debug)]
+pub struct Table {
+    material: Material,
+    indexer: Indexer,
+    values: Vec<u8>, // stored value of each index
+}
+
+impl Table {
+    /// new creates a table from the outcomes of its positions
+    ///
+    /// @param: material - canonical material of the table
+    /// @param: outcomes - outcome of each index, or None for illegal ones
+    /// @return: table of the outcomes
+    pub(crate) fn new(material: Material, outcomes: &[Option<Dtm>]) -> Self {
+        Self {
+            material,
+            indexer: Indexer::new(material),
+            values: outcomes
+                .iter()
+                .map(|outcome| outcome.map_or(ILLEGAL, Dtm::encode))
+                .collect(),
+        }
+    }
+
+    /// material returns the material of the table
+    ///
+    /// @return: canonical material of the table
+    #[inline]
+    pub fn material(&self) -> Material {
+        self.material
+    }
+
+    /// get returns the outcome stored at an index
+    ///
+    /// @param: idx - index of the position
+    /// @return: outcome of the position, or None for an illegal position
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<Dtm> {
+        Dtm::decode(*self.values.get(idx)?)
+    }
+
+    /// longest_win returns the longest distance to mate of the table
+    ///
+    /// @return: longest win in plies, or None if no position is won
+    pub fn longest_win(&self) -> Option<u8> {
+        (0..self.values.len())
+            .filter_map(|idx| match self.get(idx) {
+                Some(Dtm::Win(plies)) => Some(plies),
+                _ => None,
+            })
+            .max()
+    }
+
+    /// probe looks up the outcome of a position of the table's material with
+    /// either color holding the stronger pieces
+    ///
+    /// @param: position - position to look up
+    /// @return: outcome of the position, or None if it is not of the table's
+    ///          material or illegal
+    pub fn probe<P: PositionView>(&self, position: &P) -> Option<Dtm> {
+        let material = Material::from_position(position);
+        // positions with black holding the pieces of the table are looked up
+        // with the colors swapped and the board flipped
+        let flip = if material == self.material {
+            false
+        } else if material.flipped() == self.material {
+            true
+        } else {
+            return None;
+        };
+
+        let flip_side = |side: Sides| {
+            if flip { side.other() } else { side }
+        };
+        let placements = placements(position)
+            .into_iter()
+            .map(|(side, piece, square)| {
+                let square = if flip {
+                    Square::from_idx(square.idx() ^ 56)
+                } else {
+                    square
+                };
+                (flip_side(side), piece, square)
+            })
+            .collect::<Vec<_>>();
+        self.get(
+            self.indexer
+                .index(flip_side(position.turn()), &placements)?,
+        )
+    }
+
+    /// write writes the table in its file format
+    ///
+    /// note: the values are run-length encoded, as illegal positions and
+    ///       draws come in long runs, with the values between runs written
+    ///       as they are
+    ///
+    /// @param: writer - destination of the table file
+    /// @return: Ok on success, or the tablebase error
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), TablegenError> {
+        let code = self.material.to_string();
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION, code.len() as u8])?;
+        writer.write_all(code.as_bytes())?;
+        writer.write_all(&(self.values.len() as u64).to_le_bytes())?;
+
+        let mut bytes = Vec::new();
+        let mut literals = Vec::new();
+        for run in self.values.chunk_by(|a, b| a == b) {
+            if run.len() < MIN_RUN {
+                literals.extend_from_slice(run);
+                continue;
+            }
+            write_literals(&mut bytes, &literals);
+            literals.clear();
+
+            bytes.extend([REPEAT, run[0]]);
+            let mut length = run.len();
+            // run lengths are written 7 bits at a time, lowest bits first
+            while length >= 0x80 {
+                bytes.push((length & 0x7F) as u8 | 0x80);
+                length >>= 7;
+            }
+            bytes.push(length as u8);
+        }
+        write_literals(&mut bytes, &literals);
+        writer.write_all(&bytes)?;
+        Ok(())
+    }
+
+    /// read reads a table written by [`Table::write`]
+    ///
+    /// @param: reader - source of the table file
+    /// @return: table, or the tablebase error
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, TablegenError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let mut bytes = bytes.into_iter();
+        let mut next = || {
+            bytes
+                .next()
+                .ok_or(TablegenError::Corrupt("truncated table file"))
+        };
+
+        if [next()?, next()?, next()?, next()?] != MAGIC {
+            return Err(TablegenError::Corrupt("invalid magic bytes"));
+        }
+        if next()? != VERSION {
+            return Err(TablegenError::Corrupt("unsupported version"));
+        }
+        let code = (0..next()?)
+            .map(|_| next())
+            .collect::<Result<Vec<_>, _>>()?;
+        let material = std::str::from_utf8(&code)
+            .ok()
+            .and_then(|code| Material::try_from(code).ok())
+            .filter(Material::is_canonical)
+            .ok_or(TablegenError::Corrupt("invalid material"))?;
+        let mut size = [0; 8];
+        for byte in &mut size {
+            *byte = next()?;
+        }
+        let size = u64::from_le_bytes(size) as usize;
+        let indexer = Indexer::new(material);
+        if size != indexer.size() {
+            return Err(TablegenError::Corrupt("size does not match the material"));
+        }
+
+        let mut values = Vec::with_capacity(size);
+        while values.len() < size {
+            let token = next()?;
+            if token != REPEAT {
+                let length = usize::from(token) + 1;
+                if values.len() + length > size {
+                    return Err(TablegenError::Corrupt("invalid run"));
+                }
+                for _ in 0..length {
+                    values.push(next()?);
+                }
+                continue;
+            }
+
+            let value = next()?;
+            let mut length = 0;
+            let mut shift = 0;
+            loop {
+                let byte = next()?;
+                length |= usize::from(byte & 0x7F) << shift;
+                shift += 7;
+                if byte & 0x80 == 0 {
+                    break;
+                }
+            }
+            if values.len() + length > size {
+                return Err(TablegenError::Corrupt("invalid run"));
+            }
+            values.extend(std::iter::repeat_n(value, length));
+        }
+        Ok(Self {
+            material,
+            indexer,
+            values,
+        })
+    }
+}
+
+/// Tablebase is a set of tables of different materials
+///
+/// @type
+#[derive(Clone, Debug, Default)]
+pub struct Tablebase {
+    tables: HashMap<Material, Table>,
+}
+
+impl Tablebase {
+    /// open reads the table files of a directory
+    ///
+    /// @param: directory - directory holding the table files
+    /// @return: tablebase of the tables, or the tablebase error
+    pub fn open(directory: impl AsRef<Path>) -> Result<Self, TablegenError> {
+        let mut tablebase = Self::default();
+        for entry in std::fs::read_dir(directory)? {
+            let path = entry?.path();
+            if path.extension().and_then(|extension| extension.to_str()) == Some(EXTENSION) {
+                tablebase.insert(Table::read(&mut std::fs::File::open(path)?)?);
+            }
+        }
+        Ok(tablebase)
+    }
+
+    /// save writes every table to a directory, in a file named after its
+    /// material
+    ///
+    /// @param: directory - directory to write the table files to
+    /// @return: Ok on success, or the tablebase error
+    pub fn save(&self, directory: impl AsRef<Path>) -> Result<(), TablegenError> {
+        for table in self.tables.values() {
+            let path = directory
+                .as_ref()
+                .join(table.material().to_string())
+                .with_extension(EXTENSION);
+            let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+            table.write(&mut writer)?;
+            writer.flush()?;
+        }
+        Ok(())
+    }
+
+    /// insert adds a table, replacing any table of the same material
+    ///
+    /// @param: table - table to add
+    /// @return: void
+    /// @side-effects: modifies the tablebase
+    pub fn insert(&mut self, table: Table) {
+        self.tables.insert(table.material(), table);
+    }
+
+    /// get returns the table of a material
+    ///
+    /// @param: material - material of the table, with either color holding
+    ///                    the stronger pieces
+    /// @return: table of the material, if any
+    #[inline]
+    pub fn get(&self, material: Material) -> Option<&Table> {
+        self.tables.get(&material.canonical())
+    }
+
+    /// len returns the number of tables
+    ///
+    /// @return: number of tables
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// is_empty checks whether the tablebase has no tables
+    ///
+    /// @return: true if there are no tables
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// probe looks up the outcome of a position in the table of its material
+    ///
+    /// @param: position - position to look up
+    /// @return: outcome of the position, or None if its table is missing
+    pub fn probe<P: PositionView>(&self, position: &P) -> Option<Dtm> {
+        self.get(Material::from_position(position))?.probe(position)
+    }
+}
+
+impl EndgameTablebase for Tablebase {
+    /// covers checks whether a position may be probed, which requires the
+    /// table of its material and no castling or en passant rights, as the
+    /// tables are solved without them
+    ///
+    /// @impl: EndgameTablebase::covers
+    #[inline]
+    fn covers<PositionT: PositionView>(&self, position: &PositionT) -> bool {
+        position.castling() == Castling::NONE
+            && position.en_passant().is_none()
+            && self.get(Material::from_position(position)).is_some()
+    }
+
+    /// probe_wdl probes the outcome of a position from its distance to mate
+    ///
+    /// note: the tables ignore the fifty-move rule, so wins that take too long
+    ///       to convert are still reported as wins
+    ///
+    /// @impl: EndgameTablebase::probe_wdl
+    #[inline]
+    fn probe_wdl<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        _move_generator: &MoveGeneratorT,
+    ) -> Option<Wdl>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        Some(match self.probe(position)? {
+            Dtm::Win(_) => Wdl::Win,
+            Dtm::Loss(_) => Wdl::Loss,
+            Dtm::Draw => Wdl::Draw,
+        })
+    }
+
+    /// best_root_moves returns the moves that mate the fastest in won
+    /// positions, hold the draw in drawn ones, and delay mate the longest in
+    /// lost ones, returning None if a required table is missing
+    ///
+    /// @impl: EndgameTablebase::best_root_moves
+    fn best_root_moves<PositionT, MoveGeneratorT>(
+        &self,
+        position: &mut PositionT,
+        move_generator: &MoveGeneratorT,
+    ) -> Option<Vec<Move>>
+    where
+        PositionT: PositionView + PositionAttacks + PositionMoves,
+        MoveGeneratorT: MoveGenerator,
+    {
+        let mut moves = MoveList::new();
+        move_generator.generate_legal_moves(position, &mut moves);
+
+        let mut outcomes = Vec::with_capacity(moves.len());
+        for &mv in moves.as_slice() {
+            let _ = position.play_unchecked(mv);
+            let outcome = self.probe(position);
+            position.undo(mv);
+            outcomes.push((mv, outcome?.before()));
+        }
+
+        let best = outcomes.iter().map(|&(_, outcome)| outcome).max()?;
+        Some(
+            outcomes
+                .into_iter()
+                .filter(|&(_, outcome)| outcome == best)
+                .map(|(mv, _)| mv)
+                .collect(),
+        )
+    }
+}
+
+/// write_literals writes values as they are, in blocks of at most as many
+/// values as there are tokens other than [`REPEAT`]
+///
+/// @param: bytes - destination of the encoded values
+/// @param: values - values to write
+/// @return: void
+/// @side-effects: appends to the bytes
+fn write_literals(bytes: &mut Vec<u8>, values: &[u8]) {
+    for block in values.chunks(usize::from(REPEAT)) {
+        bytes.push(block.len() as u8 - 1);
+        bytes.extend_from_slice(block);
+    }
+}
+
+/// placements returns the pieces of a position
+///
+/// @param: position - position to list the pieces of
+/// @return: side, type and square of every piece
+pub(crate) fn placements<P: PositionView>(position: &P) -> Vec<Placement> {
+    let white = position.occupancy::<White>();
+    position
+        .total_occupancy()
+        .into_iter()
+        .map(|square| {
+            let side = if white.has_square(square) {
+                Sides::White
+            } else {
+                Sides::Black
+            };
+            (side, position.piece_at(square), square)
+        })
+        .collect()
+}
@@ -0,0 +1,382 @@
+use crate::{NodeData, PersistentNode};
+
+/// BUCKET_SLOTS is the number of entries sharing a bucket
+///
+/// note: five slots of a compact node, such as an 8-byte search node with its
+///       key check and generation, fill a single 64-byte cache line
+pub(crate) const BUCKET_SLOTS: usize = 5;
+
+/// AGE_WEIGHT is the depth an entry is worth per search it has aged by when
+/// choosing which entry to replace
+const AGE_WEIGHT: i32 = 8;
+
+/// `Generation` identifies the search that wrote an entry
+///
+/// note: generations cycle through 1 to 255, so that a slot stamped with 0 is
+///       known to be empty
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Generation(u8);
+
+impl Generation {
+    /// FIRST is the generation of the first search on a cleared table
+    pub(crate) const FIRST: Self = Self(1);
+
+    /// EMPTY is the stamp of a slot that holds no entry
+    const EMPTY: u8 = 0;
+
+    /// next returns the generation of the following search
+    ///
+    /// @return: next generation, wrapping back to the first one
+    #[inline]
+    pub(crate) const fn next(self) -> Self {
+        match self.0 {
+            u8::MAX => Self::FIRST,
+            generation => Self(generation + 1),
+        }
+    }
+
+    /// stamp returns the stamp written on the entries of the generation
+    ///
+    /// @return: stamp of the generation
+    #[inline]
+    pub(crate) const fn stamp(self) -> u8 {
+        self.0
+    }
+
+    /// from_stamp returns the generation that writes a stamp
+    ///
+    /// @param: stamp - stamp written on entries
+    /// @return: generation of the stamp, or None for the stamp of empty slots
+    #[inline]
+    pub(crate) const fn from_stamp(stamp: u8) -> Option<Self> {
+        match stamp {
+            Self::EMPTY => None,
+            stamp => Some(Self(stamp)),
+        }
+    }
+
+    /// age returns how many searches ago an entry was written
+    ///
+    /// @param: stamp - generation stamped on the entry
+    /// @return: number of generations since the entry was written
+    #[inline]
+    const fn age(self, stamp: u8) -> u8 {
+        ((self.0 as u16 + u8::MAX as u16 - stamp as u16) % u8::MAX as u16) as u8
+    }
+}
+
+/// `Slot` is a single entry of a bucket
+///
+/// @type
+#[derive(Copy, Clone)]
+struct Slot<NodeT: NodeData> {
+    key: u16,       // low bits of the zobrist key, checked on probes
+    generation: u8, // generation that wrote the entry, or 0 if empty
+    data: NodeT,    // node data of the entry
+}
+
+impl<NodeT: NodeData> Slot<NodeT> {
+    /// empty creates a slot without an entry
+    ///
+    /// @return: empty slot
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            key: 0,
+            generation: Generation::EMPTY,
+            data: NodeT::default(),
+        }
+    }
+
+    /// is_empty checks if the slot holds no entry
+    ///
+    /// @return: true if the slot is empty, false otherwise
+    #[inline]
+    const fn is_empty(&self) -> bool {
+        self.generation == Generation::EMPTY
+    }
+
+    /// replacement_score rates how valuable the entry is to keep, where lower
+    /// scores are replaced first
+    ///
+    /// @param: generation - generation of the current search
+    /// @return: depth of the entry, discounted by its age
+    #[inline]
+    fn replacement_score(&self, generation: Generation) -> i32 {
+        if self.is_empty() {
+            return i32::MIN;
+        }
+        i32::from(self.data.depth()) - AGE_WEIGHT * i32::from(generation.age(self.generation))
+    }
+}
+
+impl<NodeT: PersistentNode> Slot<NodeT> {
+    /// SIZE is the number of bytes a slot is serialized into: the key check,
+    /// the generation and the node data
+    const SIZE: usize = 3 + NodeT::SIZE;
+
+    /// write_bytes serializes the slot
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes to write the slot into
+    /// @return: void
+    /// @side-effects: overwrites the buffer
+    fn write_bytes(&self, bytes: &mut [u8]) {
+        bytes[..2].copy_from_slice(&self.key.to_le_bytes());
+        bytes[2] = self.generation;
+        self.data.write_bytes(&mut bytes[3..]);
+    }
+
+    /// read_bytes deserializes a slot written by `write_bytes`
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes holding the slot
+    /// @return: slot, or None if the bytes do not hold valid node data
+    fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes[2] == Generation::EMPTY {
+            return Some(Self::empty());
+        }
+
+        Some(Self {
+            key: u16::from_le_bytes([bytes[0], bytes[1]]),
+            generation: bytes[2],
+            data: NodeT::read_bytes(&bytes[3..])?,
+        })
+    }
+}
+
+/// `InsertResult` describes what an insert did to its bucket
+///
+/// @type
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum InsertResult {
+    Filled,   // the entry was written to an empty slot
+    Replaced, // the entry replaced an entry of the same or another position
+    Kept,     // a deeper entry of the same position was kept
+}
+
+/// `Bucket` is a cache-line aligned group of entries that positions hashing to
+/// the same index compete for
+///
+/// @type
+#[derive(Copy, Clone)]
+#[repr(align(64))]
+pub(crate) struct Bucket<NodeT: NodeData> {
+    slots: [Slot<NodeT>; BUCKET_SLOTS],
+}
+
+impl<NodeT: NodeData> Bucket<NodeT> {
+    /// empty creates a bucket without entries
+    ///
+    /// @return: empty bucket
+    #[inline]
+    pub(crate) fn empty() -> Self {
+        Self {
+            slots: [Slot::empty(); BUCKET_SLOTS],
+        }
+    }
+
+    /// probe returns the entry stored for a key
+    ///
+    /// @param: key - key check of the position
+    /// @return: node data of the position, or None if it is not stored
+    #[inline]
+    pub(crate) fn probe(&self, key: u16) -> Option<&NodeT> {
+        self.slots
+            .iter()
+            .find(|slot| !slot.is_empty() && slot.key == key)
+            .map(|slot| &slot.data)
+    }
+
+    /// insert stores an entry, replacing the entry of the same position unless
+    /// it is a deeper one from the current search, or else the entry that is
+    /// the shallowest once discounted by its age
+    ///
+    /// @param: key - key check of the position
+    /// @param: data - node data to store
+    /// @param: generation - generation of the current search
+    /// @return: what the insert did to the bucket
+    /// @side-effects: modifies the bucket
+    #[inline]
+    pub(crate) fn insert(&mut self, key: u16, data: NodeT, generation: Generation) -> InsertResult {
+        let entry = Slot {
+            key,
+            generation: generation.0,
+            data,
+        };
+
+        if let Some(slot) = self
+            .slots
+            .iter_mut()
+            .find(|slot| !slot.is_empty() && slot.key == key)
+        {
+            if slot.generation == generation.0 && slot.data.depth() > data.depth() {
+                return InsertResult::Kept;
+            }
+            *slot = entry;
+            return InsertResult::Replaced;
+        }
+
+        let victim = self
+            .slots
+            .iter_mut()
+            .min_by_key(|slot| slot.replacement_score(generation))
+            .expect("buckets hold at least one slot");
+        let result = if victim.is_empty() {
+            InsertResult::Filled
+        } else {
+            InsertResult::Replaced
+        };
+        *victim = entry;
+        result
+    }
+
+    /// occupied counts the entries of the bucket
+    ///
+    /// @return: number of slots holding an entry
+    #[inline]
+    pub(crate) fn occupied(&self) -> usize {
+        self.slots.iter().filter(|slot| !slot.is_empty()).count()
+    }
+
+    /// count_current counts the entries written by the current search
+    ///
+    /// @param: generation - generation of the current search
+    /// @return: number of entries of the current generation
+    #[inline]
+    pub(crate) fn count_current(&self, generation: Generation) -> usize {
+        self.slots
+            .iter()
+            .filter(|slot| slot.generation == generation.0)
+            .count()
+    }
+}
+
+impl<NodeT: PersistentNode> Bucket<NodeT> {
+    /// ENTRY_SIZE is the number of bytes an entry is serialized into
+    pub(crate) const ENTRY_SIZE: usize = Slot::<NodeT>::SIZE;
+
+    /// SIZE is the number of bytes a bucket is serialized into
+    pub(crate) const SIZE: usize = BUCKET_SLOTS * Self::ENTRY_SIZE;
+
+    /// write_bytes serializes the bucket
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes to write the bucket into
+    /// @return: void
+    /// @side-effects: overwrites the buffer
+    pub(crate) fn write_bytes(&self, bytes: &mut [u8]) {
+        for (slot, bytes) in self
+            .slots
+            .iter()
+            .zip(bytes.chunks_exact_mut(Self::ENTRY_SIZE))
+        {
+            slot.write_bytes(bytes);
+        }
+    }
+
+    /// read_bytes deserializes a bucket written by `write_bytes`
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes holding the bucket
+    /// @return: bucket, or None if an entry does not hold valid node data
+    pub(crate) fn read_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut bucket = Self::empty();
+        for (slot, bytes) in bucket
+            .slots
+            .iter_mut()
+            .zip(bytes.chunks_exact(Self::ENTRY_SIZE))
+        {
+            *slot = Slot::read_bytes(bytes)?;
+        }
+        Some(bucket)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_collections::Value;
+
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    struct TestNode(i8, [u8; 7]);
+
+    impl TestNode {
+        const fn new(depth: i8) -> Self {
+            Self(depth, [0; 7])
+        }
+    }
+
+    impl Value for TestNode {
+        fn priority(&self) -> i8 {
+            self.0
+        }
+    }
+
+    impl NodeData for TestNode {
+        fn depth(&self) -> i8 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn compact_buckets_fill_a_cache_line() {
+        assert_eq!(size_of::<Bucket<TestNode>>(), 64);
+    }
+
+    #[test]
+    fn generations_skip_the_empty_stamp() {
+        let last = Generation(u8::MAX);
+
+        assert_eq!(last.next(), Generation::FIRST);
+        assert_eq!(Generation::FIRST.age(u8::MAX), 1);
+        assert_eq!(Generation(3).age(3), 0);
+        assert_eq!(Generation(3).age(1), 2);
+    }
+
+    #[test]
+    fn keeps_deeper_entries_of_the_current_search() {
+        let mut bucket = Bucket::empty();
+        let generation = Generation::FIRST;
+
+        assert_eq!(
+            bucket.insert(7, TestNode::new(5), generation),
+            InsertResult::Filled
+        );
+        assert_eq!(
+            bucket.insert(7, TestNode::new(3), generation),
+            InsertResult::Kept
+        );
+        assert_eq!(bucket.probe(7), Some(&TestNode::new(5)));
+
+        // entries of a previous search are replaced regardless of depth
+        assert_eq!(
+            bucket.insert(7, TestNode::new(3), generation.next()),
+            InsertResult::Replaced
+        );
+        assert_eq!(bucket.probe(7), Some(&TestNode::new(3)));
+    }
+
+    #[test]
+    fn replaces_stale_and_shallow_entries_first() {
+        let mut bucket = Bucket::empty();
+        let old = Generation::FIRST;
+        let current = old.next();
+
+        bucket.insert(1, TestNode::new(15), old);
+        for key in 2..=BUCKET_SLOTS as u16 {
+            bucket.insert(key, TestNode::new(10), current);
+        }
+
+        // the deep entry of the previous search is worth less than the
+        // shallower entries of the current one
+        bucket.insert(10, TestNode::new(1), current);
+        assert_eq!(bucket.probe(1), None);
+
+        // among entries of the same age, the shallowest one is replaced
+        bucket.insert(2, TestNode::new(12), current);
+        bucket.insert(11, TestNode::new(1), current);
+        assert_eq!(bucket.probe(10), None);
+        assert_eq!(bucket.probe(2), Some(&TestNode::new(12)));
+        assert_eq!(bucket.count_current(current), BUCKET_SLOTS);
+        assert_eq!(bucket.count_current(old), 0);
+    }
+}
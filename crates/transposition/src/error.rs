@@ -0,0 +1,41 @@
+use std::fmt::{self, Display};
+use std::io;
+
+/// TranspositionError is returned when a transposition table cannot be saved
+/// or loaded
+///
+/// @type
+#[derive(Debug)]
+pub enum TranspositionError {
+    // a table file could not be read or written
+    Io(io::Error),
+    // a table file was saved by an incompatible table or build
+    Incompatible(&'static str),
+    // a table file is malformed
+    Corrupt(&'static str),
+}
+
+impl Display for TranspositionError {
+    /// fmt writes a human-readable description of the transposition error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to access table file: {error}"),
+            Self::Incompatible(reason) => write!(f, "incompatible table file: {reason}"),
+            Self::Corrupt(reason) => write!(f, "corrupt table file: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for TranspositionError {}
+
+impl From<io::Error> for TranspositionError {
+    fn from(error: io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::UnexpectedEof => Self::Corrupt("truncated table file"),
+            _ => Self::Io(error),
+        }
+    }
+}
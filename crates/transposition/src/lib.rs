@@ -1,9 +1,13 @@
+mod bucket;
+pub mod error;
 pub mod transposition_table;
 
+pub use error::TranspositionError;
 pub use transposition_table::DefaultTranspositionTable;
 
 use chess_kit_collections::Value;
 use chess_kit_primitives::ZobristKey;
+use std::io::{Read, Write};
 
 /// `NodeData` is a trait that defines the data accessors for a node in the
 /// transposition table
@@ -16,6 +20,28 @@ pub trait NodeData: Value {
     fn depth(&self) -> i8;
 }
 
+/// `PersistentNode` is a trait for node data that can be saved to and loaded
+/// from a file
+///
+/// @trait
+pub trait PersistentNode: NodeData {
+    /// SIZE is the number of bytes a node is serialized into
+    const SIZE: usize;
+
+    /// write_bytes serializes the node
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes to write the node into
+    /// @return: void
+    /// @side-effects: overwrites the buffer
+    fn write_bytes(&self, bytes: &mut [u8]);
+
+    /// read_bytes deserializes a node written by `write_bytes`
+    ///
+    /// @param: bytes - buffer of `SIZE` bytes holding the node
+    /// @return: node, or None if the bytes do not hold a valid node
+    fn read_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
 /// `TranspositionTable` is a trait that defines the interface for a transposition
 /// table
 ///
@@ -41,6 +67,22 @@ pub trait TranspositionTable<NodeT: NodeData> {
     /// @return: the data if the position is found, None otherwise
     fn probe(&self, key: ZobristKey) -> Option<&NodeT>;
 
+    /// prefetch hints the cpu to load the entries of a position into the cache,
+    /// so that a later probe or insert of the position does not wait on memory
+    ///
+    /// note: this is only a hint, and does not change the transposition table
+    ///
+    /// @param: key - the key of the position that will be probed
+    /// @return: void
+    fn prefetch(&self, key: ZobristKey);
+
+    /// new_search starts a new search generation, so that the entries of
+    /// previous searches are replaced before the entries of the new one
+    ///
+    /// @return: void
+    /// @side-effects: advances the generation of the transposition table
+    fn new_search(&mut self);
+
     /// is_enabled checks if the transposition table is enabled
     ///
     /// @return: true if the transposition table is enabled, false otherwise
@@ -65,6 +107,15 @@ pub trait TranspositionTable<NodeT: NodeData> {
     /// @side-effects: clears the transposition table
     fn clear(&mut self);
 
+    /// hashfull estimates how full the transposition table is with entries of
+    /// the current search, as a value between 0 and 1000 ('permille')
+    ///
+    /// note: the estimate samples a fixed number of entries, as reported in
+    ///       the UCI `hashfull` info
+    ///
+    /// @return: permille of sampled entries written by the current search
+    fn hashfull(&self) -> u16;
+
     /// usage_permille returns the usage of the transposition table as a value
     /// between 0 and 1000 ('permille')
     ///
@@ -76,4 +127,28 @@ pub trait TranspositionTable<NodeT: NodeData> {
     ///
     /// @return: percent usage of the transposition table
     fn usage_percent(&self) -> u16;
+
+    /// save writes the entries of the transposition table to a file, after a
+    /// header describing the table they belong to
+    ///
+    /// @param: writer - destination of the table file
+    /// @return: Ok on success, or the transposition error
+    fn save<WriterT: Write>(&self, writer: &mut WriterT) -> Result<(), TranspositionError>
+    where
+        NodeT: PersistentNode;
+
+    /// load replaces the entries of the transposition table with the entries
+    /// of a file written by `save`
+    ///
+    /// note: files saved by a table of another memory size, with other node
+    ///       data, or by a build with other zobrist keys are rejected before
+    ///       any entry is replaced
+    ///
+    /// @param: reader - source of the table file
+    /// @return: Ok on success, or the transposition error
+    /// @side-effects: replaces the entries, or clears the table if the file is
+    ///                rejected after its header was accepted
+    fn load<ReaderT: Read>(&mut self, reader: &mut ReaderT) -> Result<(), TranspositionError>
+    where
+        NodeT: PersistentNode;
 }
@@ -1,31 +1,58 @@
-use crate::{NodeData, TranspositionTable};
-use chess_kit_collections::{HashFn, HashKey, Map};
-use chess_kit_primitives::ZobristKey;
+use crate::bucket::{BUCKET_SLOTS, Bucket, Generation, InsertResult};
+use crate::{NodeData, PersistentNode, TranspositionError, TranspositionTable};
+use chess_kit_collections::LargeArray;
+use chess_kit_primitives::{ZobristKey, ZobristTable};
+use std::io::{Read, Write};
 
-struct ZobristKeyHashFn;
+const MB_TO_BYTES: usize = 1024 * 1024;
 
-impl HashFn<ZobristKey> for ZobristKeyHashFn {
-    #[inline]
-    fn hash(key: &ZobristKey) -> HashKey {
-        // split the zobrist key into an index and a key
-        //
-        // index: right-shifted by 32 bits and then truncated
-        // key:   truncated to the least-significant 32 bits
-        //
-        // Note: this is an optimization that tightly enforces that the zobrist
-        //       key is an alias/wrapper around a u64 and relies on the fact that
-        //       the `as` cast truncates the superfluous upper bits
-        // TODO: make a choice as to whether or not that invariant is reasonable
-        let index = u32::from(*key >> 32u64) as usize;
-        let tag = u32::from(*key);
-        HashKey { index, tag }
-    }
-}
+/// MAGIC is the bytes every table file starts with
+const MAGIC: [u8; 4] = *b"CKTT";
+
+/// VERSION is the version of the table file format
+const VERSION: u8 = 1;
+
+/// HASHFULL_SAMPLE_BUCKETS is the number of buckets sampled to estimate how
+/// full the table is
+const HASHFULL_SAMPLE_BUCKETS: usize = 200;
 
 /// `DefaultTranspositionTable` is the default implementation of the
 /// `TranspositionTable` trait
+///
+/// note: entries live in cache-line sized buckets and are stamped with the
+///       generation of the search that wrote them, so entries of previous
+///       searches are the first to be replaced
+///
+/// note: the buckets are a `LargeArray`, which clears large tables with
+///       several threads and, with the `huge-pages` feature, backs them with
+///       transparent huge pages on linux
 pub struct DefaultTranspositionTable<NodeT: NodeData> {
-    map: Map<ZobristKey, NodeT, ZobristKeyHashFn>,
+    buckets: LargeArray<Bucket<NodeT>>, // buckets of the table, empty if disabled
+    generation: Generation,             // generation of the current search
+    len: usize,                         // number of occupied entries
+    memory_size: usize,                 // amount of memory allocated in MBs
+}
+
+impl<NodeT: NodeData> DefaultTranspositionTable<NodeT> {
+    /// split_key splits a zobrist key into the index of its bucket and the key
+    /// check stored in the bucket
+    ///
+    /// note: the upper 32 bits are scaled onto the buckets with a multiply and
+    ///       shift, and the lowest 16 bits are kept as the key check, so the
+    ///       two never overlap
+    ///
+    /// @param: key - zobrist key to split
+    /// @return: bucket index and key check, or None if the table is disabled
+    #[inline]
+    fn split_key(&self, key: ZobristKey) -> Option<(usize, u16)> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        let key = key.const_unwrap();
+        let index = ((key >> 32) * self.buckets.len() as u64) >> 32;
+        Some((index as usize, key as u16))
+    }
 }
 
 impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<NodeT> {
@@ -33,8 +60,14 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     ///
     /// @impl: TranspositionTable::new
     fn new(memory_size: usize) -> Self {
+        let buckets_per_mb = MB_TO_BYTES / size_of::<Bucket<NodeT>>();
+        let bucket_count = buckets_per_mb.saturating_mul(memory_size);
+
         Self {
-            map: Map::new(memory_size),
+            buckets: LargeArray::new(bucket_count, Bucket::empty()),
+            generation: Generation::FIRST,
+            len: 0,
+            memory_size,
         }
     }
 
@@ -43,7 +76,13 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::insert
     #[inline]
     fn insert(&mut self, zobrist_key: ZobristKey, data: NodeT) {
-        self.map.set(&zobrist_key, data);
+        let Some((index, key)) = self.split_key(zobrist_key) else {
+            return;
+        };
+
+        if self.buckets[index].insert(key, data, self.generation) == InsertResult::Filled {
+            self.len += 1;
+        }
     }
 
     /// probe probes the transposition table for an entry with the given zobrist
@@ -52,7 +91,26 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::probe
     #[inline]
     fn probe(&self, zobrist_key: ZobristKey) -> Option<&NodeT> {
-        self.map.get(&zobrist_key)
+        let (index, key) = self.split_key(zobrist_key)?;
+        self.buckets[index].probe(key)
+    }
+
+    /// prefetch loads the bucket of a key into the cache
+    ///
+    /// @impl: TranspositionTable::prefetch
+    #[inline]
+    fn prefetch(&self, zobrist_key: ZobristKey) {
+        if let Some((index, _)) = self.split_key(zobrist_key) {
+            self.buckets.prefetch(index);
+        }
+    }
+
+    /// new_search advances the generation stamped on new entries
+    ///
+    /// @impl: TranspositionTable::new_search
+    #[inline]
+    fn new_search(&mut self) {
+        self.generation = self.generation.next();
     }
 
     /// is_enabled checks if the transposition table is enabled
@@ -60,7 +118,7 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::is_enabled
     #[inline]
     fn is_enabled(&self) -> bool {
-        self.map.is_enabled()
+        !self.buckets.is_empty()
     }
 
     /// capacity returns the maximum number of entries in the transposition table
@@ -68,7 +126,7 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::capacity
     #[inline]
     fn capacity(&self) -> usize {
-        self.map.capacity()
+        self.buckets.len() * BUCKET_SLOTS
     }
 
     /// resize resizes the transposition table's underlying memory allocation to
@@ -76,7 +134,13 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     ///
     /// @impl: TranspositionTable::resize
     fn resize(&mut self, memory_size: usize) {
-        self.map.resize(memory_size);
+        // if the memory size is unchanged, just clear the table
+        if self.memory_size == memory_size {
+            self.clear();
+            return;
+        }
+
+        *self = Self::new(memory_size);
     }
 
     /// clear clears the transposition table
@@ -84,7 +148,26 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::clear
     #[inline]
     fn clear(&mut self) {
-        self.map.clear();
+        self.buckets.fill(Bucket::empty());
+        self.generation = Generation::FIRST;
+        self.len = 0;
+    }
+
+    /// hashfull estimates how full the transposition table is with entries of
+    /// the current search, as a value between 0 and 1000 ('permille')
+    ///
+    /// @impl: TranspositionTable::hashfull
+    fn hashfull(&self) -> u16 {
+        let sampled = &self.buckets[..self.buckets.len().min(HASHFULL_SAMPLE_BUCKETS)];
+        if sampled.is_empty() {
+            return 0;
+        }
+
+        let current: usize = sampled
+            .iter()
+            .map(|bucket| bucket.count_current(self.generation))
+            .sum();
+        (current * 1000 / (sampled.len() * BUCKET_SLOTS)) as u16
     }
 
     /// usage_permille returns the usage of the transposition table as a value
@@ -93,7 +176,7 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::usage_permille
     #[inline]
     fn usage_permille(&self) -> u16 {
-        self.map.usage(1000)
+        self.usage(1000)
     }
 
     /// usage_percent returns the usage of the transposition table as a value
@@ -102,6 +185,274 @@ impl<NodeT: NodeData> TranspositionTable<NodeT> for DefaultTranspositionTable<No
     /// @impl: TranspositionTable::usage_percent
     #[inline]
     fn usage_percent(&self) -> u16 {
-        self.map.usage(100)
+        self.usage(100)
+    }
+
+    /// save writes the entries of the transposition table to a file
+    ///
+    /// note: the file starts with the magic bytes and format version, followed
+    ///       by the little-endian entry size (u16), zobrist fingerprint (u64),
+    ///       memory size in MBs (u64), bucket count (u64) and generation (u8),
+    ///       and then every bucket in order
+    ///
+    /// @impl: TranspositionTable::save
+    fn save<WriterT: Write>(&self, writer: &mut WriterT) -> Result<(), TranspositionError>
+    where
+        NodeT: PersistentNode,
+    {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(Bucket::<NodeT>::ENTRY_SIZE as u16).to_le_bytes())?;
+        writer.write_all(&ZobristTable::fingerprint().to_le_bytes())?;
+        writer.write_all(&(self.memory_size as u64).to_le_bytes())?;
+        writer.write_all(&(self.buckets.len() as u64).to_le_bytes())?;
+        writer.write_all(&[self.generation.stamp()])?;
+
+        let mut bytes = vec![0; Bucket::<NodeT>::SIZE];
+        for bucket in self.buckets.iter() {
+            bucket.write_bytes(&mut bytes);
+            writer.write_all(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// load replaces the entries of the transposition table with the entries
+    /// of a file written by `save`
+    ///
+    /// @impl: TranspositionTable::load
+    fn load<ReaderT: Read>(&mut self, reader: &mut ReaderT) -> Result<(), TranspositionError>
+    where
+        NodeT: PersistentNode,
+    {
+        if read_array::<4, _>(reader)? != MAGIC {
+            return Err(TranspositionError::Corrupt("invalid magic bytes"));
+        }
+        if read_array::<1, _>(reader)? != [VERSION] {
+            return Err(TranspositionError::Incompatible("unsupported version"));
+        }
+        if u16::from_le_bytes(read_array(reader)?) as usize != Bucket::<NodeT>::ENTRY_SIZE {
+            return Err(TranspositionError::Incompatible(
+                "entry size does not match",
+            ));
+        }
+        if u64::from_le_bytes(read_array(reader)?) != ZobristTable::fingerprint() {
+            return Err(TranspositionError::Incompatible(
+                "zobrist keys do not match",
+            ));
+        }
+        if u64::from_le_bytes(read_array(reader)?) != self.memory_size as u64 {
+            return Err(TranspositionError::Incompatible(
+                "memory size does not match",
+            ));
+        }
+        if u64::from_le_bytes(read_array(reader)?) != self.buckets.len() as u64 {
+            return Err(TranspositionError::Corrupt("bucket count does not match"));
+        }
+        let [stamp] = read_array(reader)?;
+        let generation = Generation::from_stamp(stamp)
+            .ok_or(TranspositionError::Corrupt("invalid generation"))?;
+
+        // a file that breaks off or holds invalid entries leaves the table
+        // cleared rather than half replaced
+        if let Err(error) = self.load_buckets(reader) {
+            self.clear();
+            return Err(error);
+        }
+        self.generation = generation;
+        Ok(())
+    }
+}
+
+impl<NodeT: NodeData> DefaultTranspositionTable<NodeT> {
+    /// usage calculates the share of occupied entries as a ratio between 0 and
+    /// the given base
+    ///
+    /// @param: base - the base to use for the calculation
+    /// @return: usage ratio of the table
+    #[inline]
+    fn usage(&self, base: u16) -> u16 {
+        if !self.is_enabled() {
+            return 0;
+        }
+
+        (self.len * base as usize / self.capacity()) as u16
+    }
+}
+
+impl<NodeT: PersistentNode> DefaultTranspositionTable<NodeT> {
+    /// load_buckets replaces every bucket with a bucket read from a table file
+    ///
+    /// @param: reader - source of the table file, positioned after its header
+    /// @return: Ok on success, or the transposition error
+    /// @side-effects: replaces the buckets and entry count
+    fn load_buckets<ReaderT: Read>(
+        &mut self,
+        reader: &mut ReaderT,
+    ) -> Result<(), TranspositionError> {
+        let mut bytes = vec![0; Bucket::<NodeT>::SIZE];
+        self.len = 0;
+        for bucket in self.buckets.iter_mut() {
+            reader.read_exact(&mut bytes)?;
+            *bucket =
+                Bucket::read_bytes(&bytes).ok_or(TranspositionError::Corrupt("invalid entry"))?;
+            self.len += bucket.occupied();
+        }
+
+        if reader.read(&mut [0])? != 0 {
+            return Err(TranspositionError::Corrupt("trailing data"));
+        }
+        Ok(())
+    }
+}
+
+/// read_array reads a fixed number of bytes from a table file
+///
+/// @param: reader - source of the table file
+/// @return: bytes read, or the transposition error
+fn read_array<const SIZE: usize, ReaderT: Read>(
+    reader: &mut ReaderT,
+) -> Result<[u8; SIZE], TranspositionError> {
+    let mut bytes = [0; SIZE];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_collections::Value;
+
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    struct TestNode(i8);
+
+    impl Value for TestNode {
+        fn priority(&self) -> i8 {
+            self.0
+        }
+    }
+
+    impl NodeData for TestNode {
+        fn depth(&self) -> i8 {
+            self.0
+        }
+    }
+
+    impl PersistentNode for TestNode {
+        const SIZE: usize = 1;
+
+        fn write_bytes(&self, bytes: &mut [u8]) {
+            bytes[0] = self.0 as u8;
+        }
+
+        fn read_bytes(bytes: &[u8]) -> Option<Self> {
+            // negative depths stand in for invalid node data
+            Some(Self(bytes[0] as i8)).filter(|node| node.0 >= 0)
+        }
+    }
+
+    type TestTable = DefaultTranspositionTable<TestNode>;
+
+    #[test]
+    fn disabled_table_ignores_inserts_and_probes() {
+        let mut table = TestTable::new(0);
+
+        table.insert(ZobristKey::new(1), TestNode(1));
+
+        assert!(!table.is_enabled());
+        assert_eq!(table.capacity(), 0);
+        assert_eq!(table.probe(ZobristKey::new(1)), None);
+        assert_eq!(table.hashfull(), 0);
+        assert_eq!(table.usage_permille(), 0);
+    }
+
+    #[test]
+    fn hashfull_counts_only_entries_of_the_current_search() {
+        let mut table = TestTable::new(1);
+        let buckets = table.buckets.len() as u64;
+        let key = |bucket: u64, slot: u64| {
+            ZobristKey::new(((bucket << 32).div_ceil(buckets) << 32) | (slot + 1))
+        };
+
+        // one entry in every slot of every sampled bucket
+        for bucket in 0..HASHFULL_SAMPLE_BUCKETS as u64 {
+            for slot in 0..BUCKET_SLOTS as u64 {
+                table.insert(key(bucket, slot), TestNode(1));
+            }
+        }
+        assert_eq!(table.hashfull(), 1000);
+        let usage = table.usage_permille();
+
+        table.new_search();
+        assert_eq!(table.hashfull(), 0);
+        assert_eq!(table.usage_permille(), usage);
+        assert_eq!(table.probe(key(1, 0)), Some(&TestNode(1)));
+
+        table.clear();
+        assert_eq!(table.usage_permille(), 0);
+    }
+
+    #[test]
+    fn saved_tables_load_with_their_entries_and_generation() {
+        let mut table = TestTable::new(1);
+        table.insert(ZobristKey::new(1 << 40 | 1), TestNode(3));
+        table.new_search();
+        table.insert(ZobristKey::new(2 << 40 | 2), TestNode(5));
+
+        let mut file = Vec::new();
+        table.save(&mut file).unwrap();
+        let mut loaded = TestTable::new(1);
+        loaded.load(&mut file.as_slice()).unwrap();
+
+        assert_eq!(
+            loaded.probe(ZobristKey::new(1 << 40 | 1)),
+            Some(&TestNode(3))
+        );
+        assert_eq!(
+            loaded.probe(ZobristKey::new(2 << 40 | 2)),
+            Some(&TestNode(5))
+        );
+        assert_eq!(loaded.usage_permille(), table.usage_permille());
+        assert_eq!(loaded.hashfull(), table.hashfull());
+        assert_eq!(loaded.generation, table.generation);
+    }
+
+    #[test]
+    fn rejects_incompatible_and_corrupt_files() {
+        let mut table = TestTable::new(1);
+        table.insert(ZobristKey::new(1), TestNode(3));
+        let mut file = Vec::new();
+        table.save(&mut file).unwrap();
+
+        // incompatible headers are rejected before any entry is replaced
+        let mut larger = TestTable::new(2);
+        larger.insert(ZobristKey::new(1), TestNode(4));
+        assert!(matches!(
+            larger.load(&mut file.as_slice()),
+            Err(TranspositionError::Incompatible(_))
+        ));
+        assert_eq!(larger.probe(ZobristKey::new(1)), Some(&TestNode(4)));
+
+        let mut version = file.clone();
+        version[4] += 1;
+        assert!(matches!(
+            larger.load(&mut version.as_slice()),
+            Err(TranspositionError::Incompatible(_))
+        ));
+
+        let mut magic = file.clone();
+        magic[0] = b'X';
+        assert!(matches!(
+            table.load(&mut magic.as_slice()),
+            Err(TranspositionError::Corrupt(_))
+        ));
+
+        // files that break off leave the table cleared
+        let truncated = &file[..file.len() - 1];
+        assert!(matches!(
+            table.load(&mut &truncated[..]),
+            Err(TranspositionError::Corrupt(_))
+        ));
+        assert_eq!(table.probe(ZobristKey::new(1)), None);
+        assert_eq!(table.usage_permille(), 0);
     }
 }
@@ -0,0 +1,170 @@
+use chess_kit_position::{Fen, Setup};
+use std::fmt::{self, Display};
+use std::io;
+use std::path::Path;
+
+/// LabeledPosition is a position paired with the result of the game it was
+/// taken from
+///
+/// @type
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabeledPosition {
+    /// position taken from the game
+    pub setup: Setup,
+    /// game result from white's point of view, in [0, 1]
+    pub result: f64,
+}
+
+/// DatasetError is returned when a dataset of labeled positions cannot be read
+///
+/// @type
+#[derive(Debug)]
+pub enum DatasetError {
+    // the dataset file could not be read
+    Io(io::Error),
+    // a line of the dataset could not be parsed
+    Parse { line: usize, reason: String },
+}
+
+impl Display for DatasetError {
+    /// fmt writes a human-readable description of the dataset error
+    ///
+    /// @param: f - formatter to write the error into
+    /// @return: formatting result
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "unable to read dataset: {error}"),
+            Self::Parse { line, reason } => write!(f, "dataset line {line}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for DatasetError {}
+
+impl From<io::Error> for DatasetError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// load_dataset reads the labeled positions of the dataset file at the given
+/// path
+///
+/// @param: path - path of the dataset file
+/// @return: labeled positions of the dataset, or the dataset error
+pub fn load_dataset(path: impl AsRef<Path>) -> Result<Vec<LabeledPosition>, DatasetError> {
+    parse_dataset(&std::fs::read_to_string(path)?)
+}
+
+/// parse_dataset reads the labeled positions of a dataset
+///
+/// Each line holds a FEN, with or without its move clocks, followed by the
+/// game result as the last token. The result may be written as a score in
+/// [0, 1] (optionally bracketed, e.g. `[0.5]`) or as a PGN result (optionally
+/// quoted and terminated, e.g. `c9 "1-0";`). Blank lines and lines starting
+/// with `#` are ignored
+///
+/// @param: contents - contents of a dataset file
+/// @return: labeled positions of the dataset, or the dataset error
+pub fn parse_dataset(contents: &str) -> Result<Vec<LabeledPosition>, DatasetError> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, contents)| {
+            parse_labeled_position(contents).map_err(|reason| DatasetError::Parse { line, reason })
+        })
+        .collect()
+}
+
+/// parse_labeled_position parses a single line of a dataset
+///
+/// @param: line - dataset line to parse
+/// @return: labeled position of the line, or a description of the parse error
+fn parse_labeled_position(line: &str) -> Result<LabeledPosition, String> {
+    let tokens = line.split_whitespace().collect::<Vec<_>>();
+    if tokens.len() < 5 {
+        return Err(format!("expected a FEN and a result, got {line:?}"));
+    }
+
+    let result_token = tokens[tokens.len() - 1];
+    let result = parse_result(result_token)
+        .ok_or_else(|| format!("invalid game result {result_token:?}"))?;
+
+    // the move clocks are optional, so they are only taken from the line when
+    // both of them are present
+    let has_clocks = tokens.len() >= 7 && tokens[4..6].iter().all(|t| t.parse::<u32>().is_ok());
+    let fen = if has_clocks {
+        tokens[..6].join(" ")
+    } else {
+        format!("{} 0 1", tokens[..4].join(" "))
+    };
+    let fen = Fen::try_from(fen.as_str()).map_err(|error| error.to_string())?;
+
+    Ok(LabeledPosition {
+        setup: Setup::from(fen),
+        result,
+    })
+}
+
+/// parse_result parses a game result token
+///
+/// @param: token - result token to parse
+/// @return: game result from white's point of view, or None if the token is
+///          not a game result
+fn parse_result(token: &str) -> Option<f64> {
+    let token = token.trim_matches(|c| matches!(c, '[' | ']' | '"' | ';'));
+    match token {
+        "1-0" => Some(1.0),
+        "0-1" => Some(0.0),
+        "1/2-1/2" => Some(0.5),
+        _ => token
+            .parse::<f64>()
+            .ok()
+            .filter(|result| (0.0..=1.0).contains(result)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const START_POSITION: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    #[test]
+    fn parses_every_supported_result_notation() {
+        let dataset = parse_dataset(&format!(
+            "# comment\n\n\
+             {START_POSITION} [1.0]\n\
+             {START_POSITION} 0.5\n\
+             rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - c9 \"0-1\";\n\
+             rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 1/2-1/2\n"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            dataset.iter().map(|entry| entry.result).collect::<Vec<_>>(),
+            [1.0, 0.5, 0.0, 0.5]
+        );
+        assert!(
+            dataset
+                .iter()
+                .all(|entry| entry.setup == Setup::from(Fen::default()))
+        );
+    }
+
+    #[test]
+    fn reports_the_line_of_malformed_entries() {
+        for contents in [
+            format!("{START_POSITION} [1.0]\n{START_POSITION} 2-0"),
+            format!("{START_POSITION} [1.0]\n{START_POSITION} 1.5"),
+            format!("{START_POSITION} [1.0]\n8/8/8 w - - 0 1 [1.0]"),
+        ] {
+            assert!(matches!(
+                parse_dataset(&contents),
+                Err(DatasetError::Parse { line: 2, .. })
+            ));
+        }
+    }
+}
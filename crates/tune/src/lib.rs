@@ -0,0 +1,14 @@
+//! Texel tuning of the PSQT evaluation.
+//!
+//! Load labeled positions with [`load_dataset`], fit the sigmoid scaling
+//! constant and optimize the piece-square tables with a [`Tuner`], then write
+//! the tuned tables out with [`Parameters::to_constants`] in place of the eval
+//! crate's `psqt/constants.rs`.
+
+pub mod dataset;
+pub mod parameters;
+pub mod tuner;
+
+pub use dataset::{DatasetError, LabeledPosition, load_dataset, parse_dataset};
+pub use parameters::Parameters;
+pub use tuner::{Optimizer, Tuner, sigmoid};
@@ -0,0 +1,92 @@
+use chess_kit_eval::psqt::{
+    PARAMETER_COUNT, PSQTFeatures, PieceValue, current_parameters, write_constants,
+};
+
+/// Parameters is a tunable copy of the PSQT evaluation's piece-square tables
+///
+/// Each parameter is indexed like the terms of `PSQTFeatures`, so the
+/// parameters can be written back out with `write_constants` unchanged
+///
+/// @type
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameters {
+    /// middlegame value of each table entry
+    pub middlegame: [f64; PARAMETER_COUNT],
+    /// endgame value of each table entry
+    pub endgame: [f64; PARAMETER_COUNT],
+}
+
+impl Parameters {
+    /// current returns the parameters of the PSQT evaluation's current tables
+    ///
+    /// @return: current parameters
+    pub fn current() -> Self {
+        let parameters = current_parameters();
+        Self {
+            middlegame: parameters.map(|value| f64::from(value.middlegame())),
+            endgame: parameters.map(|value| f64::from(value.endgame())),
+        }
+    }
+
+    /// evaluate returns the PSQT evaluation of a position under these
+    /// parameters
+    ///
+    /// @param: features - features of the position to evaluate
+    /// @return: unrounded score from white's point of view
+    #[inline]
+    pub fn evaluate(&self, features: &PSQTFeatures) -> f64 {
+        features.evaluate(&self.middlegame, &self.endgame)
+    }
+
+    /// to_constants writes these parameters as the contents of the PSQT
+    /// evaluation's `constants.rs`
+    ///
+    /// @return: regenerated contents of `psqt/constants.rs`
+    pub fn to_constants(&self) -> String {
+        let parameters = std::array::from_fn(|index| {
+            PieceValue::new(
+                self.middlegame[index].round() as i32,
+                self.endgame[index].round() as i32,
+            )
+        });
+        write_constants(&parameters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_attack_table::DefaultAttackTable;
+    use chess_kit_eval::{EvalState, PSQTEvalState, StandaloneEvalState};
+    use chess_kit_position::{DefaultPosition, Fen, Setup};
+
+    #[test]
+    fn evaluates_positions_like_the_psqt_eval_state() {
+        let parameters = Parameters::current();
+
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "3qk3/8/8/8/8/8/8/4K3 b - - 0 1",
+        ] {
+            let setup = Setup::from(Fen::try_from(fen).unwrap());
+            let position = DefaultPosition::<DefaultAttackTable>::try_from(setup.clone()).unwrap();
+            let expected = PSQTEvalState::from_position(&position).score();
+
+            let actual = parameters.evaluate(&PSQTFeatures::from(&setup));
+            assert!(
+                (actual - f64::from(expected)).abs() <= 1.0,
+                "{fen}: expected {expected}, got {actual}"
+            );
+        }
+    }
+
+    #[test]
+    fn regenerates_the_current_constants_file() {
+        assert_eq!(
+            Parameters::current().to_constants(),
+            include_str!("../../eval/src/psqt/constants.rs")
+        );
+    }
+}
@@ -0,0 +1,298 @@
+use crate::{LabeledPosition, Parameters};
+use chess_kit_eval::psqt::{PARAMETER_COUNT, PSQTFeatures};
+use std::f64::consts::LN_10;
+
+/// SCALING_RANGE is the range searched for the sigmoid scaling constant
+const SCALING_RANGE: (f64, f64) = (0.0, 10.0);
+
+/// SCALING_ITERATIONS is the number of golden-section steps used to fit the
+/// sigmoid scaling constant
+const SCALING_ITERATIONS: usize = 100;
+
+/// ADAM_EPSILON keeps Adam's update finite for parameters without gradient
+const ADAM_EPSILON: f64 = 1e-8;
+
+/// sigmoid maps a centipawn score to an expected game result
+///
+/// @param: score - score from white's point of view
+/// @param: scaling - sigmoid scaling constant, usually called K
+/// @return: expected game result from white's point of view, in [0, 1]
+#[inline]
+pub fn sigmoid(score: f64, scaling: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-scaling * score / 400.0))
+}
+
+/// Optimizer is the update rule applied to the parameters after each epoch
+///
+/// @type
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Optimizer {
+    // plain gradient descent
+    GradientDescent {
+        learning_rate: f64,
+    },
+    // Adam with the given moment decay rates
+    Adam {
+        learning_rate: f64,
+        beta1: f64,
+        beta2: f64,
+    },
+}
+
+impl Optimizer {
+    /// adam returns an Adam optimizer with the usual moment decay rates
+    ///
+    /// @param: learning_rate - step size in centipawns
+    /// @return: Adam optimizer
+    pub const fn adam(learning_rate: f64) -> Self {
+        Self::Adam {
+            learning_rate,
+            beta1: 0.9,
+            beta2: 0.999,
+        }
+    }
+}
+
+/// TuningEntry is a labeled position prepared for evaluation by the tuner
+///
+/// @type
+#[derive(Clone, Debug, PartialEq)]
+struct TuningEntry {
+    features: PSQTFeatures, // features of the position
+    result: f64,            // game result from white's point of view
+}
+
+/// Tuner fits the PSQT parameters to a dataset of labeled positions by
+/// minimizing the mean squared error between the sigmoid-scaled evaluation of
+/// each position and the result of its game
+///
+/// @type
+#[derive(Clone, Debug)]
+pub struct Tuner {
+    entries: Vec<TuningEntry>,
+    parameters: Parameters,
+    scaling: f64,
+    optimizer: Optimizer,
+    epoch: i32,
+    // first and second moment estimates of Adam, middlegame values first
+    moments: Box<[(f64, f64); 2 * PARAMETER_COUNT]>,
+}
+
+impl Tuner {
+    /// new creates a tuner over the given dataset
+    ///
+    /// note: the scaling constant starts at 1 and should be fitted with
+    ///       fit_scaling before tuning
+    ///
+    /// @param: positions - labeled positions to tune against
+    /// @param: parameters - initial parameters
+    /// @param: optimizer - update rule applied after each epoch
+    /// @return: new tuner
+    pub fn new(
+        positions: &[LabeledPosition],
+        parameters: Parameters,
+        optimizer: Optimizer,
+    ) -> Self {
+        let entries = positions
+            .iter()
+            .map(|position| TuningEntry {
+                features: PSQTFeatures::from(&position.setup),
+                result: position.result,
+            })
+            .collect();
+
+        Self {
+            entries,
+            parameters,
+            scaling: 1.0,
+            optimizer,
+            epoch: 0,
+            moments: Box::new([(0.0, 0.0); 2 * PARAMETER_COUNT]),
+        }
+    }
+
+    /// parameters returns the current parameters
+    ///
+    /// @return: current parameters
+    pub fn parameters(&self) -> &Parameters {
+        &self.parameters
+    }
+
+    /// scaling returns the sigmoid scaling constant used by the loss
+    ///
+    /// @return: sigmoid scaling constant
+    pub fn scaling(&self) -> f64 {
+        self.scaling
+    }
+
+    /// loss returns the mean squared error of the current parameters
+    ///
+    /// @return: mean squared error over the dataset
+    pub fn loss(&self) -> f64 {
+        self.loss_with_scaling(self.scaling)
+    }
+
+    /// fit_scaling finds the sigmoid scaling constant that minimizes the loss
+    /// of the current parameters
+    ///
+    /// note: the loss is unimodal in the scaling constant, so a golden-section
+    ///       search over a generous range converges to its minimum
+    ///
+    /// @return: fitted scaling constant
+    /// @side-effects: replaces the scaling constant used by the loss
+    pub fn fit_scaling(&mut self) -> f64 {
+        let ratio = (5f64.sqrt() - 1.0) / 2.0;
+        let (mut low, mut high) = SCALING_RANGE;
+
+        for _ in 0..SCALING_ITERATIONS {
+            let left = high - ratio * (high - low);
+            let right = low + ratio * (high - low);
+            if self.loss_with_scaling(left) < self.loss_with_scaling(right) {
+                high = right;
+            } else {
+                low = left;
+            }
+        }
+
+        self.scaling = (low + high) / 2.0;
+        self.scaling
+    }
+
+    /// step runs one full-batch epoch of the optimizer
+    ///
+    /// @return: loss of the updated parameters
+    /// @side-effects: updates the parameters and the optimizer state
+    pub fn step(&mut self) -> f64 {
+        let gradient = self.gradient();
+        self.epoch += 1;
+
+        let values = self
+            .parameters
+            .middlegame
+            .iter_mut()
+            .chain(self.parameters.endgame.iter_mut());
+        for ((value, gradient), moment) in values.zip(gradient.iter()).zip(self.moments.iter_mut())
+        {
+            *value -= match self.optimizer {
+                Optimizer::GradientDescent { learning_rate } => learning_rate * gradient,
+                Optimizer::Adam {
+                    learning_rate,
+                    beta1,
+                    beta2,
+                } => {
+                    moment.0 = beta1 * moment.0 + (1.0 - beta1) * gradient;
+                    moment.1 = beta2 * moment.1 + (1.0 - beta2) * gradient * gradient;
+                    let first = moment.0 / (1.0 - beta1.powi(self.epoch));
+                    let second = moment.1 / (1.0 - beta2.powi(self.epoch));
+                    learning_rate * first / (second.sqrt() + ADAM_EPSILON)
+                }
+            };
+        }
+
+        self.loss()
+    }
+
+    /// loss_with_scaling returns the mean squared error of the current
+    /// parameters under the given scaling constant
+    ///
+    /// @param: scaling - sigmoid scaling constant
+    /// @return: mean squared error over the dataset
+    fn loss_with_scaling(&self, scaling: f64) -> f64 {
+        let total = self
+            .entries
+            .iter()
+            .map(|entry| {
+                let error =
+                    entry.result - sigmoid(self.parameters.evaluate(&entry.features), scaling);
+                error * error
+            })
+            .sum::<f64>();
+
+        total / self.entries.len().max(1) as f64
+    }
+
+    /// gradient returns the gradient of the loss with respect to every
+    /// parameter, middlegame values first
+    ///
+    /// @return: gradient of the loss
+    fn gradient(&self) -> Box<[f64; 2 * PARAMETER_COUNT]> {
+        let mut gradient = Box::new([0.0; 2 * PARAMETER_COUNT]);
+        let scale = 2.0 * LN_10 * self.scaling / 400.0 / self.entries.len().max(1) as f64;
+
+        for entry in &self.entries {
+            let expected = sigmoid(self.parameters.evaluate(&entry.features), self.scaling);
+
+            // d/ds (r - sigmoid(s))^2 = -2 (r - sigmoid(s)) sigmoid'(s)
+            let slope = -(entry.result - expected) * expected * (1.0 - expected) * scale;
+            for &(index, sign) in &entry.features.terms {
+                let index = usize::from(index);
+                let slope = slope * f64::from(sign);
+                gradient[index] += slope * entry.features.phase;
+                gradient[PARAMETER_COUNT + index] += slope * (1.0 - entry.features.phase);
+            }
+        }
+
+        gradient
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chess_kit_position::{Fen, Setup};
+
+    const POSITIONS: [&str; 6] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnb1kbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKB1R w KQkq - 0 1",
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/4p3/8/8/8/8/8/R3K3 w - - 0 1",
+    ];
+
+    fn dataset(result: impl Fn(f64) -> f64) -> Vec<LabeledPosition> {
+        let parameters = Parameters::current();
+        POSITIONS
+            .iter()
+            .map(|fen| {
+                let setup = Setup::from(Fen::try_from(*fen).unwrap());
+                let score = parameters.evaluate(&PSQTFeatures::from(&setup));
+                LabeledPosition {
+                    setup,
+                    result: result(score),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn fits_the_scaling_constant_the_results_were_drawn_with() {
+        let positions = dataset(|score| sigmoid(score, 1.3));
+        let mut tuner = Tuner::new(&positions, Parameters::current(), Optimizer::adam(1.0));
+
+        assert!((tuner.fit_scaling() - 1.3).abs() < 1e-3);
+        assert!(tuner.loss() < 1e-9);
+    }
+
+    #[test]
+    fn optimizers_reduce_the_loss() {
+        // label every position as a win for the side that is behind, so the
+        // current tables fit the results badly at the default scaling
+        let positions = dataset(|score| if score < 0.0 { 1.0 } else { 0.0 });
+
+        for optimizer in [
+            Optimizer::GradientDescent { learning_rate: 1e6 },
+            Optimizer::adam(1.0),
+        ] {
+            let mut tuner = Tuner::new(&positions, Parameters::current(), optimizer);
+
+            let initial = tuner.loss();
+            let mut loss = initial;
+            for _ in 0..10 {
+                loss = tuner.step();
+            }
+
+            assert!(loss < initial, "{optimizer:?}: {loss} >= {initial}");
+        }
+    }
+}
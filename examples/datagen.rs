@@ -0,0 +1,172 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::str::FromStr;
+
+use chess_kit::datagen::{DatagenConfig, DatagenError, generate, load_book};
+
+const USAGE: &str = "Usage: datagen [OPTIONS] (--text <FILE> | --binary <FILE>)\n\
+\n\
+Options:\n\
+  -g, --games <N>              Number of self-play games (default: 100)\n\
+  -t, --threads <N>            Number of games played at once (default: 1)\n\
+  -n, --nodes <N>              Node budget of every search (default: 5000)\n\
+  -r, --random-plies <N>       Random moves played from the opening (default: 8)\n\
+  -b, --book <FILE>            Openings to start from, one FEN per line (default: start position)\n\
+  -s, --seed <N>               Seed of the opening randomization (default: 0)\n\
+      --hash <MB>              Transposition table size of every engine (default: 16)\n\
+      --text <FILE>            Where to write positions as `<FEN> | <score> | <result>` lines\n\
+      --binary <FILE>          Where to write positions as 32-byte records\n\
+  -h, --help                   Print help";
+
+/// REPORT_INTERVAL is the number of games between progress reports
+const REPORT_INTERVAL: usize = 10;
+
+struct DatagenOptions {
+    config: DatagenConfig,
+    book: Option<String>,
+    text: Option<String>,
+    binary: Option<String>,
+}
+
+fn parse_options(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Option<DatagenOptions>, String> {
+    let mut arguments = arguments.into_iter();
+    let mut config = DatagenConfig::default();
+    let mut book = None;
+    let mut text = None;
+    let mut binary = None;
+
+    while let Some(argument) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or_else(|| format!("{argument} requires a value"))
+        };
+
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-g" | "--games" => config.games = parse_number(&value()?, "games")?,
+            "-t" | "--threads" => {
+                config.threads = parse_number(&value()?, "threads")?;
+                if config.threads == 0 {
+                    return Err("threads must be positive".to_owned());
+                }
+            }
+            "-n" | "--nodes" => config.nodes = parse_number(&value()?, "nodes")?,
+            "-r" | "--random-plies" => {
+                config.random_plies = parse_number(&value()?, "random plies")?
+            }
+            "-b" | "--book" => book = Some(value()?),
+            "-s" | "--seed" => config.seed = parse_number(&value()?, "seed")?,
+            "--hash" => config.transposition_table_size_mb = parse_number(&value()?, "hash")?,
+            "--text" => text = Some(value()?),
+            "--binary" => binary = Some(value()?),
+            _ => return Err(format!("unrecognized argument: {argument}")),
+        }
+    }
+
+    if text.is_none() && binary.is_none() {
+        return Err("a text or binary output is required".to_owned());
+    }
+    Ok(Some(DatagenOptions {
+        config,
+        book,
+        text,
+        binary,
+    }))
+}
+
+fn parse_number<T: FromStr>(value: &str, name: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{name} must be a non-negative integer (got {value:?})"))
+}
+
+fn create(path: &Option<String>) -> Result<Option<BufWriter<File>>, String> {
+    path.as_ref()
+        .map(|path| File::create(path).map(BufWriter::new))
+        .transpose()
+        .map_err(|error| error.to_string())
+}
+
+fn run() -> Result<(), String> {
+    let Some(mut options) = parse_options(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    if let Some(book) = &options.book {
+        options.config.book = load_book(book).map_err(|error| error.to_string())?;
+        eprintln!("loaded {} openings", options.config.book.len());
+    }
+    let mut text = create(&options.text)?;
+    let mut binary = create(&options.binary)?;
+
+    let mut games = 0;
+    let stats = generate(&options.config, |game| {
+        for position in &game.positions {
+            if let Some(text) = &mut text {
+                position.write_text(text)?;
+            }
+            if let Some(binary) = &mut binary {
+                position.write_binary(binary)?;
+            }
+        }
+        games += 1;
+        if games % REPORT_INTERVAL == 0 {
+            eprintln!("played {games} games");
+        }
+        Ok::<_, DatagenError>(())
+    })
+    .map_err(|error| error.to_string())?;
+
+    for writer in [&mut text, &mut binary].into_iter().flatten() {
+        writer.flush().map_err(|error| error.to_string())?;
+    }
+    eprintln!(
+        "played {} games (+{} ={} -{}) and kept {} positions",
+        stats.games, stats.white_wins, stats.draws, stats.black_wins, stats.positions
+    );
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("chess-kit datagen example: {error}");
+        eprintln!("\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_run_and_output_options() {
+        let options = parse_options(
+            [
+                "--games", "20", "-t", "4", "-n", "1000", "--text", "data.txt", "--seed", "7",
+            ]
+            .map(str::to_owned),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(options.config.games, 20);
+        assert_eq!(options.config.threads, 4);
+        assert_eq!(options.config.nodes, 1000);
+        assert_eq!(options.config.seed, 7);
+        assert_eq!(options.text.as_deref(), Some("data.txt"));
+        assert_eq!(options.binary, None);
+    }
+
+    #[test]
+    fn requires_an_output_and_valid_numbers() {
+        assert!(parse_options(["--games".to_owned(), "10".to_owned()]).is_err());
+        assert!(parse_options(["--binary", "a.bin", "-t", "0"].map(str::to_owned)).is_err());
+        assert!(parse_options(["--binary", "a.bin", "-n", "-5"].map(str::to_owned)).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::env;
+
+use chess_kit::attack_table::{Slider, find_magics, is_collision_free};
+use chess_kit::primitives::Square;
+
+/// DEFAULT_SEED is the seed of the magic search when none is given
+const DEFAULT_SEED: u64 = 0x5EED;
+
+/// MAGICS_PER_LINE is the number of magic numbers printed on each line
+const MAGICS_PER_LINE: usize = 4;
+
+const USAGE: &str = "Usage: magics [OPTIONS]\n\
+\n\
+Prints rook and bishop magic numbers as the constants of\n\
+crates/attack_table/src/magics.rs.\n\
+\n\
+Options:\n\
+  -s, --seed <SEED>  Seed of the magic search (default: 0x5eed)\n\
+  -v, --verify       Verify the magic numbers in use instead of searching\n\
+  -h, --help         Print help";
+
+#[derive(Debug, PartialEq, Eq)]
+enum MagicsCommand {
+    Find(u64),
+    Verify,
+}
+
+fn parse_seed(value: &str) -> Result<u64, String> {
+    match value.strip_prefix("0x") {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|_| format!("seed must be an unsigned 64-bit integer (got {value:?})"))
+}
+
+fn parse_options(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Option<MagicsCommand>, String> {
+    let mut arguments = arguments.into_iter();
+    let mut command = MagicsCommand::Find(DEFAULT_SEED);
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-v" | "--verify" => command = MagicsCommand::Verify,
+            "-s" | "--seed" => {
+                let value = arguments
+                    .next()
+                    .ok_or_else(|| format!("{argument} requires a value"))?;
+                command = MagicsCommand::Find(parse_seed(&value)?);
+            }
+            _ => return Err(format!("unrecognized argument: {argument}")),
+        }
+    }
+
+    Ok(Some(command))
+}
+
+/// constant formats magic numbers as the constant they are declared as
+fn constant(name: &str, magics: &[u64; Square::TOTAL]) -> String {
+    let mut source = format!(
+        "#[rustfmt::skip]\n#[allow(clippy::unreadable_literal)]\npub const {name}: [u64; Square::TOTAL] = [\n"
+    );
+    for line in magics.chunks(MAGICS_PER_LINE) {
+        let line = line
+            .iter()
+            .map(|magic| format!("{magic}u64,"))
+            .collect::<Vec<_>>()
+            .join(" ");
+        source.push_str(&format!("    {line}\n"));
+    }
+    source.push_str("];\n");
+    source
+}
+
+fn verify() -> Result<(), String> {
+    for slider in [Slider::Rook, Slider::Bishop] {
+        for (sq, &magic) in slider.magic_nums().iter().enumerate() {
+            let square = Square::from_idx(sq);
+            if !is_collision_free(slider, square, magic) {
+                return Err(format!(
+                    "{slider:?} magic {magic:#018x} collides on {square}"
+                ));
+            }
+        }
+        eprintln!("{slider:?} magics are collision-free");
+    }
+    Ok(())
+}
+
+fn run() -> Result<(), String> {
+    let Some(command) = parse_options(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    match command {
+        MagicsCommand::Verify => verify(),
+        MagicsCommand::Find(seed) => {
+            eprintln!("searching for magics with seed {seed:#x}");
+            println!(
+                "{}",
+                constant("ROOK_MAGIC_NUMS", &find_magics(Slider::Rook, seed))
+            );
+            println!(
+                "{}",
+                constant("BISHOP_MAGIC_NUMS", &find_magics(Slider::Bishop, seed))
+            );
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("chess-kit magics example: {error}");
+        eprintln!("\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_seed_in_decimal_or_hex() {
+        assert_eq!(
+            parse_options(["--seed", "42"].map(str::to_owned)),
+            Ok(Some(MagicsCommand::Find(42)))
+        );
+        assert_eq!(
+            parse_options(["-s", "0x2a"].map(str::to_owned)),
+            Ok(Some(MagicsCommand::Find(42)))
+        );
+        assert_eq!(
+            parse_options(["-v".to_owned()]),
+            Ok(Some(MagicsCommand::Verify))
+        );
+        assert!(parse_options(["--seed", "-1"].map(str::to_owned)).is_err());
+        assert!(parse_options(["--seed".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn formats_the_magics_as_constants() {
+        let source = constant("ROOK_MAGIC_NUMS", &[7; Square::TOTAL]);
+
+        assert!(source.starts_with("#[rustfmt::skip]\n"));
+        assert!(source.contains("pub const ROOK_MAGIC_NUMS: [u64; Square::TOTAL] = [\n"));
+        assert!(source.contains("\n    7u64, 7u64, 7u64, 7u64,\n"));
+        assert_eq!(
+            source.lines().count(),
+            3 + Square::TOTAL / MAGICS_PER_LINE + 1
+        );
+    }
+}
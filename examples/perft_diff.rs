@@ -0,0 +1,158 @@
+use std::env;
+
+use chess_kit::attack_table::DefaultAttackTable;
+use chess_kit::eval::{Accumulator, DefaultAccumulator, NoOpEvalState, StandaloneEvalState};
+use chess_kit::movegen::{DefaultMoveGenerator, MoveGenerator};
+use chess_kit::perft::{DivideReference, EpdReference, PerftData, UciReference, perft_diff};
+use chess_kit::position::{DefaultPosition, Fen, Setup, SetupErrors};
+use chess_kit::primitives::Depth;
+use chess_kit::transposition::{DefaultTranspositionTable, TranspositionTable};
+
+/// PERFT_TRANSPOSITION_TABLE_SIZE_MB is the transposition table size used to
+/// count the divides of the move generator
+pub const PERFT_TRANSPOSITION_TABLE_SIZE_MB: usize = 64;
+
+const USAGE: &str = "Usage: perft_diff [OPTIONS] --depth <PLIES> (--engine <PATH> | --expected <FILE>)\n\
+\n\
+Options:\n\
+  -d, --depth <PLIES>    Perft depth to diff at\n\
+  -f, --fen <FEN>        Root position (default: the start position)\n\
+  -e, --engine <PATH>    UCI engine supporting `go perft` to diff against\n\
+  -x, --expected <FILE>  File of expected divides to diff against\n\
+  -h, --help             Print help";
+
+enum ReferenceSource {
+    Engine(String),
+    Expected(String),
+}
+
+struct DiffOptions {
+    depth: Depth,
+    fen: Fen,
+    reference: ReferenceSource,
+}
+
+fn parse_options(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Option<DiffOptions>, String> {
+    let mut arguments = arguments.into_iter();
+    let mut depth = None;
+    let mut fen = None;
+    let mut reference = None;
+
+    while let Some(argument) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or_else(|| format!("{argument} requires a value"))
+        };
+
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--depth" => {
+                let value = value()?;
+                depth = Some(
+                    value
+                        .parse::<Depth>()
+                        .ok()
+                        .filter(|depth| *depth > 0)
+                        .ok_or_else(|| {
+                            format!("depth must be a positive integer (got {value:?})")
+                        })?,
+                );
+            }
+            "-f" | "--fen" => {
+                let value = value()?;
+                fen = Some(Fen::try_from(value.as_str()).map_err(|error| error.to_string())?);
+            }
+            "-e" | "--engine" => reference = Some(ReferenceSource::Engine(value()?)),
+            "-x" | "--expected" => reference = Some(ReferenceSource::Expected(value()?)),
+            _ => return Err(format!("unrecognized argument: {argument}")),
+        }
+    }
+
+    Ok(Some(DiffOptions {
+        depth: depth.ok_or("a depth is required")?,
+        fen: fen.unwrap_or_default(),
+        reference: reference.ok_or("an engine or expected divides file is required")?,
+    }))
+}
+
+fn diff<ReferenceT: DivideReference>(
+    options: &DiffOptions,
+    reference: &mut ReferenceT,
+) -> Result<(), String> {
+    let mut position: DefaultPosition<DefaultAttackTable> = Setup::from(options.fen.clone())
+        .try_into()
+        .map_err(|error: SetupErrors| error.to_string())?;
+    let move_generator = DefaultMoveGenerator::<DefaultAttackTable>::new();
+    let mut tt = DefaultTranspositionTable::<PerftData>::new(PERFT_TRANSPOSITION_TABLE_SIZE_MB);
+    let mut accumulator = DefaultAccumulator::<NoOpEvalState>::new();
+    accumulator.push(NoOpEvalState::from_position(&position));
+
+    let mismatch = perft_diff(
+        &mut position,
+        &move_generator,
+        &mut tt,
+        &mut accumulator,
+        reference,
+        options.depth,
+    )
+    .map_err(|error| error.to_string())?;
+
+    match mismatch {
+        Some(mismatch) => print!("{mismatch}"),
+        None => println!("no mismatch at depth {}", options.depth),
+    }
+    Ok(())
+}
+
+fn run() -> Result<(), String> {
+    let Some(options) = parse_options(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    match &options.reference {
+        ReferenceSource::Engine(path) => {
+            let mut reference = UciReference::spawn(path).map_err(|error| error.to_string())?;
+            diff(&options, &mut reference)
+        }
+        ReferenceSource::Expected(path) => {
+            let mut reference = EpdReference::open(path).map_err(|error| error.to_string())?;
+            diff(&options, &mut reference)
+        }
+    }
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("chess-kit perft_diff example: {error}");
+        eprintln!("\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_reference_engine_and_depth() {
+        let options = parse_options(["--depth", "3", "--engine", "stockfish"].map(str::to_owned))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(options.depth, 3);
+        assert_eq!(options.fen, Fen::default());
+        assert!(matches!(options.reference, ReferenceSource::Engine(path) if path == "stockfish"));
+    }
+
+    #[test]
+    fn requires_a_depth_and_a_reference() {
+        assert!(parse_options(["--engine".to_owned(), "stockfish".to_owned()]).is_err());
+        assert!(parse_options(["--depth".to_owned(), "2".to_owned()]).is_err());
+        assert!(parse_options(["--depth=0".to_owned()]).is_err());
+        assert!(parse_options(["--depth".to_owned(), "0".to_owned()]).is_err());
+    }
+}
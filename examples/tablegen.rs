@@ -0,0 +1,108 @@
+use std::env;
+
+use chess_kit::tablegen::{Material, Tablebase, generate};
+
+const USAGE: &str = "Usage: tablegen [OPTIONS] <MATERIAL>...\n\
+\n\
+Arguments:\n\
+  <MATERIAL>...                Materials to generate, such as KQvK or KRPvKR\n\
+\n\
+Options:\n\
+  -o, --output <DIR>           Directory to read existing tables from and write tables to (default: .)\n\
+  -h, --help                   Print help";
+
+struct TablegenOptions {
+    materials: Vec<Material>,
+    output: String,
+}
+
+fn parse_options(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Option<TablegenOptions>, String> {
+    let mut arguments = arguments.into_iter();
+    let mut materials = Vec::new();
+    let mut output = String::from(".");
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-o" | "--output" => {
+                output = arguments
+                    .next()
+                    .ok_or_else(|| format!("{argument} requires a value"))?
+            }
+            _ if argument.starts_with('-') => {
+                return Err(format!("unrecognized argument: {argument}"));
+            }
+            _ => materials
+                .push(Material::try_from(argument.as_str()).map_err(|error| error.to_string())?),
+        }
+    }
+
+    if materials.is_empty() {
+        return Err("a material is required".to_owned());
+    }
+    Ok(Some(TablegenOptions { materials, output }))
+}
+
+fn run() -> Result<(), String> {
+    let Some(options) = parse_options(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    std::fs::create_dir_all(&options.output).map_err(|error| error.to_string())?;
+    let mut tablebase = Tablebase::open(&options.output).map_err(|error| error.to_string())?;
+    eprintln!("loaded {} tables", tablebase.len());
+
+    for material in options.materials {
+        generate(&mut tablebase, material).map_err(|error| error.to_string())?;
+        let table = tablebase.get(material).expect("generated table");
+        match table.longest_win() {
+            Some(plies) => eprintln!("{}: longest mate in {plies} plies", table.material()),
+            None => eprintln!("{}: no winning positions", table.material()),
+        }
+    }
+
+    tablebase
+        .save(&options.output)
+        .map_err(|error| error.to_string())?;
+    eprintln!("wrote {} tables to {}", tablebase.len(), options.output);
+    Ok(())
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("chess-kit tablegen example: {error}");
+        eprintln!("\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_materials_and_output_directory() {
+        let options = parse_options(["KQvK", "-o", "tables", "KRPvKR"].map(str::to_owned))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(options.output, "tables");
+        assert_eq!(
+            options.materials,
+            [
+                Material::try_from("KQvK").unwrap(),
+                Material::try_from("KRPvKR").unwrap()
+            ]
+        );
+    }
+
+    #[test]
+    fn requires_valid_materials() {
+        assert!(parse_options(["-o".to_owned(), "tables".to_owned()]).is_err());
+        assert!(parse_options(["KQQQvK".to_owned()]).is_err());
+        assert!(parse_options(["KQvK", "--depth"].map(str::to_owned)).is_err());
+    }
+}
@@ -0,0 +1,167 @@
+use std::env;
+
+use chess_kit::tune::{Optimizer, Parameters, Tuner, load_dataset};
+
+const USAGE: &str = "Usage: tune [OPTIONS] --data <FILE>\n\
+\n\
+Options:\n\
+  -d, --data <FILE>            Labeled positions to tune against (FEN followed by the game result)\n\
+  -o, --output <FILE>          Where to write the regenerated psqt/constants.rs (default: stdout)\n\
+  -e, --epochs <N>             Number of full-batch epochs (default: 1000)\n\
+  -l, --learning-rate <RATE>   Optimizer step size (default: 1 for Adam, 1e6 for gradient descent)\n\
+  -g, --gradient-descent       Use plain gradient descent instead of Adam\n\
+  -h, --help                   Print help";
+
+/// DEFAULT_EPOCHS is the number of epochs run when none is given
+const DEFAULT_EPOCHS: usize = 1000;
+
+/// REPORT_INTERVAL is the number of epochs between progress reports
+const REPORT_INTERVAL: usize = 50;
+
+struct TuneOptions {
+    data: String,
+    output: Option<String>,
+    epochs: usize,
+    optimizer: Optimizer,
+}
+
+fn parse_options(
+    arguments: impl IntoIterator<Item = String>,
+) -> Result<Option<TuneOptions>, String> {
+    let mut arguments = arguments.into_iter();
+    let mut data = None;
+    let mut output = None;
+    let mut epochs = DEFAULT_EPOCHS;
+    let mut learning_rate = None;
+    let mut gradient_descent = false;
+
+    while let Some(argument) = arguments.next() {
+        let mut value = || {
+            arguments
+                .next()
+                .ok_or_else(|| format!("{argument} requires a value"))
+        };
+
+        match argument.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-d" | "--data" => data = Some(value()?),
+            "-o" | "--output" => output = Some(value()?),
+            "-e" | "--epochs" => {
+                let value = value()?;
+                epochs = value.parse().map_err(|_| {
+                    format!("epochs must be a non-negative integer (got {value:?})")
+                })?;
+            }
+            "-l" | "--learning-rate" => {
+                let value = value()?;
+                learning_rate = Some(
+                    value
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|rate| *rate > 0.0)
+                        .ok_or_else(|| {
+                            format!("learning rate must be a positive number (got {value:?})")
+                        })?,
+                );
+            }
+            "-g" | "--gradient-descent" => gradient_descent = true,
+            _ => return Err(format!("unrecognized argument: {argument}")),
+        }
+    }
+
+    let optimizer = if gradient_descent {
+        Optimizer::GradientDescent {
+            learning_rate: learning_rate.unwrap_or(1e6),
+        }
+    } else {
+        Optimizer::adam(learning_rate.unwrap_or(1.0))
+    };
+
+    Ok(Some(TuneOptions {
+        data: data.ok_or("a dataset is required")?,
+        output,
+        epochs,
+        optimizer,
+    }))
+}
+
+fn run() -> Result<(), String> {
+    let Some(options) = parse_options(env::args().skip(1))? else {
+        println!("{USAGE}");
+        return Ok(());
+    };
+
+    let positions = load_dataset(&options.data).map_err(|error| error.to_string())?;
+    eprintln!("loaded {} positions", positions.len());
+
+    let mut tuner = Tuner::new(&positions, Parameters::current(), options.optimizer);
+    let scaling = tuner.fit_scaling();
+    eprintln!(
+        "fitted K = {scaling:.4}, initial loss = {:.6}",
+        tuner.loss()
+    );
+
+    for epoch in 1..=options.epochs {
+        let loss = tuner.step();
+        if epoch % REPORT_INTERVAL == 0 || epoch == options.epochs {
+            eprintln!("epoch {epoch}: loss = {loss:.6}");
+        }
+    }
+
+    let constants = tuner.parameters().to_constants();
+    match &options.output {
+        Some(path) => std::fs::write(path, constants).map_err(|error| error.to_string()),
+        None => {
+            print!("{constants}");
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    if let Err(error) = run() {
+        eprintln!("chess-kit tune example: {error}");
+        eprintln!("\n{USAGE}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_the_dataset_and_optimizer_options() {
+        let options = parse_options(
+            [
+                "--data",
+                "positions.epd",
+                "--epochs",
+                "10",
+                "-g",
+                "-l",
+                "500",
+            ]
+            .map(str::to_owned),
+        )
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(options.data, "positions.epd");
+        assert_eq!(options.output, None);
+        assert_eq!(options.epochs, 10);
+        assert_eq!(
+            options.optimizer,
+            Optimizer::GradientDescent {
+                learning_rate: 500.0
+            }
+        );
+    }
+
+    #[test]
+    fn requires_a_dataset_and_valid_numbers() {
+        assert!(parse_options(["--epochs".to_owned(), "10".to_owned()]).is_err());
+        assert!(parse_options(["-d", "a.epd", "-e", "-1"].map(str::to_owned)).is_err());
+        assert!(parse_options(["-d", "a.epd", "-l", "0"].map(str::to_owned)).is_err());
+    }
+}
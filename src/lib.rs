@@ -1,10 +1,16 @@
 pub use chess_kit_attack_table as attack_table;
 pub use chess_kit_comm as comm;
+pub use chess_kit_datagen as datagen;
 pub use chess_kit_engine as engine;
 pub use chess_kit_eval as eval;
 pub use chess_kit_movegen as movegen;
 pub use chess_kit_perft as perft;
 pub use chess_kit_position as position;
 pub use chess_kit_primitives as primitives;
+pub use chess_kit_rng as rng;
 pub use chess_kit_search as search;
+pub use chess_kit_syzygy as syzygy;
+pub use chess_kit_tablebase as tablebase;
+pub use chess_kit_tablegen as tablegen;
 pub use chess_kit_transposition as transposition;
+pub use chess_kit_tune as tune;